@@ -1,5 +1,8 @@
 // Modul: api/routes — REST API Endpoints (Tauri Commands + HTTP)
 
+use crate::history::MixerCommand;
+use crate::{execute, AppState};
+
 /// System-Informationen abrufen (CPU, RAM, Audio-Geräte)
 pub fn get_system_info() -> Result<String, String> {
     // TODO: System-Infos sammeln und als JSON zurückgeben
@@ -17,3 +20,18 @@ pub fn set_volume(_channel_id: &str, _volume_db: f32) -> Result<(), String> {
     // TODO: Volume über MixerState ändern
     todo!("set_volume")
 }
+
+/// Makro-Regler setzen (0.0-1.0)
+///
+/// Läuft wie `set_macro_value` über das Journal; unbekannte Makros und
+/// fehlgeschlagene Ziele liefern einen Fehler.
+pub fn set_macro(state: &AppState, macro_id: &str, value: f32) -> Result<(), String> {
+    execute(
+        state,
+        MixerCommand::MacroValue {
+            macro_id: macro_id.to_string(),
+            value,
+            targets: None,
+        },
+    )
+}
@@ -1,4 +1,12 @@
-// Modul: audio/bus — Output-Busse (A1/A2 physisch, B1/B2 virtuell)
+// Modul: audio/bus — Output-Busse (A-Busse physisch, B-Busse virtuell)
+//
+// Die Bus-Registry: vier Standard-Busse (A1/A2/B1/B2) plus frei angelegte
+// Busse. Die Bus-ID ist stabil (PipeWire-Node "inoX-Bus-<ID>", Routing,
+// Scenes) — Umbenennen ändert nur den Anzeige-Namen.
+use super::delay::validate_delay_ms;
+use super::mix_minus::MixMinus;
+use crate::fx::bus_chain::BusFxChain;
+use crate::fx::{FxModuleInfo, FxModuleType};
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -7,9 +15,25 @@ use std::collections::HashMap;
 const MIN_VOLUME_DB: f32 = -50.0;
 /// Maximale Lautstärke in dB
 const MAX_VOLUME_DB: f32 = 10.0;
+/// Maximale Anzahl an Bussen
+const MAX_BUSES: usize = 16;
+/// Maximale Kanalzahl eines Bus
+const MAX_CHANNELS: u16 = 8;
+/// Maximale Länge eines Bus-Namens (Zeichen)
+const MAX_NAME_LEN: usize = 32;
+/// Maximale Länge einer Bus-ID (Teil des PipeWire-Node-Namens)
+const MAX_ID_LEN: usize = 16;
+
+/// Standard-Busse (ID, Name, Typ) — Ausgangsstand einer neuen Installation
+pub const DEFAULT_BUSES: [(&str, &str, BusType); 4] = [
+    ("A1", "SPEAKERS", BusType::Physical),
+    ("A2", "HEADSET", BusType::Physical),
+    ("B1", "STREAM", BusType::Virtual),
+    ("B2", "VOIP", BusType::Virtual),
+];
 
 /// Typ eines Output-Bus
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum BusType {
     /// Physischer Ausgang (Speakers, Headset)
     Physical,
@@ -17,53 +41,81 @@ pub enum BusType {
     Virtual,
 }
 
+impl BusType {
+    /// ID-Präfix neuer Busse (A = physisch, B = virtuell)
+    pub fn id_prefix(&self) -> char {
+        match self {
+            Self::Physical => 'A',
+            Self::Virtual => 'B',
+        }
+    }
+}
+
 /// Ein Output-Bus
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct OutputBus {
-    /// Bus-ID (A1, A2, B1, B2)
+    /// Bus-ID (z.B. A1, B3)
     pub id: String,
-    /// Anzeige-Name (SPEAKERS, HEADSET, STREAM, VOIP)
+    /// Anzeige-Name (z.B. SPEAKERS, STREAM)
     pub name: String,
     /// Bus-Typ (Physical oder Virtual)
     pub bus_type: BusType,
+    /// Kanalzahl (ältere Sessions/Scenes: Stereo)
+    #[serde(default = "default_channels")]
+    pub channels: u16,
     /// Zugeordnete PipeWire-Device-ID (falls vorhanden)
     pub device_id: Option<u32>,
+    /// Gebundenes Ausgabe-Device physischer Busse (PipeWire node.name,
+    /// bleibt über Neustarts stabil; None = Standard-Ausgabe)
+    #[serde(default)]
+    pub device_name: Option<String>,
     /// Lautstärke in dB (-50.0 bis +10.0)
     pub volume_db: f32,
     /// Stummschaltung aktiv
     pub muted: bool,
     /// Recording aktiv
     pub recording: bool,
+    /// Ausgangs-Delay in ms (0-1000, Bild/Ton-Synchronisation)
+    #[serde(default)]
+    pub delay_ms: f32,
+    /// Mix-Minus: Routing wird vom Referenz-Bus abgeleitet (None = manuell)
+    #[serde(default)]
+    pub mix_minus: Option<MixMinus>,
+    /// Insert-FX nach der Summierung (ältere Sessions/Scenes: alle im Bypass)
+    #[serde(default = "BusFxChain::default_modules")]
+    pub inserts: Vec<FxModuleInfo>,
 }
 
 impl OutputBus {
-    /// Neuen physischen Bus erstellen
-    pub fn new_physical(id: &str, name: &str) -> Self {
+    /// Neuen Stereo-Bus eines Typs erstellen
+    pub fn new(id: &str, name: &str, bus_type: BusType) -> Self {
         Self {
             id: id.to_string(),
             name: name.to_string(),
-            bus_type: BusType::Physical,
+            bus_type,
+            channels: default_channels(),
             device_id: None,
+            device_name: None,
             volume_db: 0.0,
             muted: false,
             recording: false,
+            delay_ms: 0.0,
+            mix_minus: None,
+            inserts: BusFxChain::default_modules(),
         }
     }
 
-    /// Neuen virtuellen Bus erstellen
-    pub fn new_virtual(id: &str, name: &str) -> Self {
-        Self {
-            id: id.to_string(),
-            name: name.to_string(),
-            bus_type: BusType::Virtual,
-            device_id: None,
-            volume_db: 0.0,
-            muted: false,
-            recording: false,
-        }
+    /// Physischer Ausgang (Wiedergabe über Hardware)
+    pub fn is_physical(&self) -> bool {
+        self.bus_type == BusType::Physical
     }
 }
 
+/// Standard-Kanalzahl (Stereo)
+fn default_channels() -> u16 {
+    2
+}
+
 /// Bus-Manager verwaltet alle Output-Busse
 #[derive(Debug)]
 pub struct BusManager {
@@ -79,28 +131,150 @@ impl BusManager {
     /// B1: STREAM (Virtual, Orange)
     /// B2: VOIP (Virtual, Orange)
     pub fn new() -> Self {
-        let mut buses = HashMap::new();
+        let buses: HashMap<String, OutputBus> = DEFAULT_BUSES
+            .iter()
+            .map(|(id, name, bus_type)| (id.to_string(), OutputBus::new(id, name, *bus_type)))
+            .collect();
 
-        // A-Busse (Physical, Cyan)
-        buses.insert("A1".to_string(), OutputBus::new_physical("A1", "SPEAKERS"));
-        buses.insert("A2".to_string(), OutputBus::new_physical("A2", "HEADSET"));
+        info!("BusManager erstellt mit {} Standard-Bussen", buses.len());
 
-        // B-Busse (Virtual, Orange)
-        buses.insert("B1".to_string(), OutputBus::new_virtual("B1", "STREAM"));
-        buses.insert("B2".to_string(), OutputBus::new_virtual("B2", "VOIP"));
+        Self { buses }
+    }
 
-        info!("BusManager erstellt mit 4 Standard-Bussen");
+    /// Bus-Manager aus gespeicherten Bussen wiederherstellen (Session/Scene)
+    pub fn from_buses(buses: Vec<OutputBus>) -> Result<Self, String> {
+        if buses.is_empty() {
+            return Err("Keine Busse vorhanden".to_string());
+        }
+        if buses.len() > MAX_BUSES {
+            return Err(format!(
+                "Zu viele Busse: {} (max {})",
+                buses.len(),
+                MAX_BUSES
+            ));
+        }
 
-        Self { buses }
+        let mut map = HashMap::new();
+        for bus in buses {
+            let bus = checked_bus(bus)?;
+            if map.contains_key(&bus.id) {
+                return Err(format!("Bus-ID doppelt: {}", bus.id));
+            }
+            map.insert(bus.id.clone(), bus);
+        }
+
+        let manager = Self { buses: map };
+        for bus in manager.buses.values() {
+            if let Some(mm) = &bus.mix_minus {
+                manager.check_mix_minus(&bus.id, mm)?;
+            }
+        }
+
+        info!(
+            "BusManager wiederhergestellt mit {} Bussen",
+            manager.buses.len()
+        );
+        Ok(manager)
     }
 
-    /// Alle Busse als sortierte Liste zurückgeben (A1, A2, B1, B2)
+    /// Alle Busse als sortierte Liste zurückgeben (A1, A2, …, A10, B1, …)
     pub fn get_buses(&self) -> Vec<OutputBus> {
         let mut buses: Vec<OutputBus> = self.buses.values().cloned().collect();
-        buses.sort_by_key(|b| b.id.clone());
+        buses.sort_by_key(|b| sort_key(&b.id));
         buses
     }
 
+    /// Alle Bus-IDs in Anzeige-Reihenfolge
+    pub fn bus_ids(&self) -> Vec<String> {
+        self.get_buses().into_iter().map(|b| b.id).collect()
+    }
+
+    /// Erster Bus eines Typs (Standard-Ziel z.B. für Soundboard-Sounds)
+    pub fn first_of_type(&self, bus_type: BusType) -> Option<OutputBus> {
+        self.get_buses()
+            .into_iter()
+            .find(|b| b.bus_type == bus_type)
+    }
+
+    /// Neuen Bus anlegen
+    ///
+    /// Die ID wird aus dem Typ-Präfix und der kleinsten freien Nummer
+    /// gebildet (z.B. A3 oder B3) und bleibt danach unverändert.
+    pub fn create_bus(
+        &mut self,
+        name: &str,
+        bus_type: BusType,
+        channels: u16,
+    ) -> Result<OutputBus, String> {
+        if self.buses.len() >= MAX_BUSES {
+            return Err(format!("Maximale Anzahl von {} Bussen erreicht", MAX_BUSES));
+        }
+        let name = validate_name(name)?;
+        validate_channels(channels)?;
+
+        let id = (1..=MAX_BUSES)
+            .map(|n| format!("{}{}", bus_type.id_prefix(), n))
+            .find(|id| !self.buses.contains_key(id))
+            .ok_or_else(|| "Keine freie Bus-ID".to_string())?;
+        let mut bus = OutputBus::new(&id, &name, bus_type);
+        bus.channels = channels;
+        self.buses.insert(id.clone(), bus.clone());
+
+        info!(
+            "Bus {} angelegt: {} ({:?}, {} Kanäle)",
+            id, name, bus_type, channels
+        );
+        Ok(bus)
+    }
+
+    /// Gelöschten Bus mit seiner ID wiederherstellen (Undo)
+    pub fn restore_bus(&mut self, bus: OutputBus) -> Result<OutputBus, String> {
+        if self.buses.len() >= MAX_BUSES {
+            return Err(format!("Maximale Anzahl von {} Bussen erreicht", MAX_BUSES));
+        }
+        let bus = checked_bus(bus)?;
+        if self.buses.contains_key(&bus.id) {
+            return Err(format!("Bus '{}' existiert bereits", bus.id));
+        }
+        if let Some(mm) = &bus.mix_minus {
+            self.check_mix_minus(&bus.id, mm)?;
+        }
+        self.buses.insert(bus.id.clone(), bus.clone());
+        info!("Bus {} wiederhergestellt", bus.id);
+        Ok(bus)
+    }
+
+    /// Bus umbenennen (nur Anzeige-Name, die ID bleibt)
+    pub fn rename_bus(&mut self, bus_id: &str, name: &str) -> Result<(), String> {
+        let name = validate_name(name)?;
+        let bus = self
+            .buses
+            .get_mut(bus_id)
+            .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))?;
+
+        bus.name = name;
+        Ok(())
+    }
+
+    /// Bus löschen
+    ///
+    /// Der letzte Bus und Busse mit laufender Aufnahme bleiben bestehen.
+    pub fn delete_bus(&mut self, bus_id: &str) -> Result<OutputBus, String> {
+        if self.get_bus(bus_id).is_some_and(|b| b.recording) {
+            return Err(format!("Bus '{}' wird gerade aufgenommen", bus_id));
+        }
+        if self.buses.len() == 1 && self.buses.contains_key(bus_id) {
+            return Err("Der letzte Bus kann nicht gelöscht werden".to_string());
+        }
+
+        let bus = self
+            .buses
+            .remove(bus_id)
+            .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))?;
+        info!("Bus {} gelöscht", bus_id);
+        Ok(bus)
+    }
+
     /// Einen Bus anhand der ID abfragen
     pub fn get_bus(&self, bus_id: &str) -> Option<&OutputBus> {
         self.buses.get(bus_id)
@@ -139,12 +313,271 @@ impl BusManager {
         Ok(())
     }
 
+    /// Ausgabe-Device an einen physischen Bus binden
+    ///
+    /// Die Node-ID wird getrennt aufgelöst (`set_pw_device`) — fehlt das
+    /// Device, bleibt die Bindung bestehen und der Bus spielt über die
+    /// Standard-Ausgabe.
+    pub fn bind_output_device(&mut self, bus_id: &str, device_name: &str) -> Result<(), String> {
+        if device_name.trim().is_empty() {
+            return Err("Device-Name darf nicht leer sein".to_string());
+        }
+        let bus = self
+            .buses
+            .get_mut(bus_id)
+            .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))?;
+        if !bus.is_physical() {
+            return Err(format!(
+                "Bus '{}' ist virtuell — nur physische Busse haben ein Ausgabe-Device",
+                bus_id
+            ));
+        }
+
+        bus.device_name = Some(device_name.to_string());
+        bus.device_id = None;
+        info!("Bus {} an Device '{}' gebunden", bus_id, device_name);
+        Ok(())
+    }
+
+    /// Device-Bindung eines Bus lösen (zurück zur Standard-Ausgabe)
+    pub fn unbind_output_device(&mut self, bus_id: &str) -> Result<(), String> {
+        let bus = self
+            .buses
+            .get_mut(bus_id)
+            .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))?;
+
+        bus.device_name = None;
+        bus.device_id = None;
+        info!("Device-Bindung von Bus {} gelöst", bus_id);
+        Ok(())
+    }
+
+    /// Aufgelöste PipeWire-Node-ID des Ausgabe-Devices setzen (None = fehlt)
+    pub fn set_pw_device(&mut self, bus_id: &str, device_id: Option<u32>) -> Result<(), String> {
+        let bus = self
+            .buses
+            .get_mut(bus_id)
+            .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))?;
+
+        bus.device_id = device_id;
+        Ok(())
+    }
+
+    /// Alle Busse mit gebundenem Ausgabe-Device (Bus-ID → Device-Name)
+    pub fn output_bindings(&self) -> HashMap<String, String> {
+        self.buses
+            .values()
+            .filter_map(|b| b.device_name.clone().map(|d| (b.id.clone(), d)))
+            .collect()
+    }
+
+    /// Mix-Minus eines Bus setzen (None = Routing wieder manuell)
+    ///
+    /// Der Referenz-Bus muss existieren und darf selbst kein Mix-Minus sein.
+    pub fn set_mix_minus(
+        &mut self,
+        bus_id: &str,
+        mix_minus: Option<MixMinus>,
+    ) -> Result<(), String> {
+        if let Some(mm) = &mix_minus {
+            self.check_mix_minus(bus_id, mm)?;
+            if self.buses.values().any(|b| {
+                b.mix_minus
+                    .as_ref()
+                    .is_some_and(|other| other.reference_bus == bus_id)
+            }) {
+                return Err(format!(
+                    "Bus {} ist Referenz eines Mix-Minus-Busses und kann selbst keiner sein",
+                    bus_id
+                ));
+            }
+        }
+        let bus = self
+            .buses
+            .get_mut(bus_id)
+            .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))?;
+
+        match &mix_minus {
+            Some(mm) => info!(
+                "Bus {}: Mix-Minus von {} ohne {}",
+                bus_id,
+                mm.reference_bus,
+                mm.excluded.join(", ")
+            ),
+            None => info!("Bus {}: Mix-Minus aus", bus_id),
+        }
+        bus.mix_minus = mix_minus;
+        Ok(())
+    }
+
+    /// Alle Mix-Minus-Busse als (Bus-ID, Einstellung)
+    pub fn mix_minus_buses(&self) -> Vec<(String, MixMinus)> {
+        self.get_buses()
+            .into_iter()
+            .filter_map(|b| b.mix_minus.map(|mm| (b.id, mm)))
+            .collect()
+    }
+
+    /// Mix-Minus-Einstellung gegen die Registry prüfen
+    fn check_mix_minus(&self, bus_id: &str, mm: &MixMinus) -> Result<(), String> {
+        mm.validate(bus_id)?;
+        match self.buses.get(&mm.reference_bus) {
+            None => Err(format!(
+                "Referenz-Bus '{}' nicht gefunden",
+                mm.reference_bus
+            )),
+            Some(reference) if reference.mix_minus.is_some() => Err(format!(
+                "Referenz-Bus {} ist selbst ein Mix-Minus-Bus",
+                mm.reference_bus
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Ausgangs-Delay eines Bus setzen (in ms, 0-1000)
+    pub fn set_delay(&mut self, bus_id: &str, delay_ms: f32) -> Result<(), String> {
+        validate_delay_ms(delay_ms)?;
+        let bus = self
+            .buses
+            .get_mut(bus_id)
+            .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))?;
+
+        bus.delay_ms = delay_ms;
+        Ok(())
+    }
+
+    /// Parameter eines Bus-Inserts setzen
+    pub fn set_insert_param(
+        &mut self,
+        bus_id: &str,
+        module_type: FxModuleType,
+        param_name: &str,
+        value: f32,
+    ) -> Result<(), String> {
+        self.update_inserts(bus_id, |chain| {
+            chain.set_param(module_type, param_name, value)
+        })
+    }
+
+    /// Bus-Insert bypassen / aktivieren
+    pub fn set_insert_bypass(
+        &mut self,
+        bus_id: &str,
+        module_type: FxModuleType,
+        bypass: bool,
+    ) -> Result<(), String> {
+        self.update_inserts(bus_id, |chain| chain.set_bypass(module_type, bypass))
+    }
+
+    /// Alle Inserts eines Busses ersetzen (Scene-Recall)
+    pub fn set_inserts(&mut self, bus_id: &str, inserts: &[FxModuleInfo]) -> Result<(), String> {
+        self.update_inserts(bus_id, |chain| chain.apply_modules(inserts))
+    }
+
+    /// Änderung an einer Kopie der Chain prüfen und erst bei Erfolg speichern
+    fn update_inserts(
+        &mut self,
+        bus_id: &str,
+        change: impl FnOnce(&mut BusFxChain) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let bus = self
+            .buses
+            .get_mut(bus_id)
+            .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))?;
+
+        let mut chain = BusFxChain::new();
+        chain.apply_modules(&bus.inserts)?;
+        change(&mut chain)?;
+        bus.inserts = chain.get_all_modules();
+        Ok(())
+    }
+
     /// Anzahl der Busse
     pub fn bus_count(&self) -> usize {
         self.buses.len()
     }
 }
 
+/// Gespeicherte Inserts prüfen und vollständig zurückgeben
+fn checked_inserts(inserts: &[FxModuleInfo]) -> Result<Vec<FxModuleInfo>, String> {
+    let mut chain = BusFxChain::new();
+    chain.apply_modules(inserts)?;
+    Ok(chain.get_all_modules())
+}
+
+/// Bus-ID prüfen (Teil von PipeWire-Node- und Port-Namen)
+///
+/// Erlaubt sind 1 bis 16 Zeichen aus Buchstaben, Ziffern, '-' und '_'.
+pub fn validate_bus_id(bus_id: &str) -> Result<(), String> {
+    let valid = !bus_id.is_empty()
+        && bus_id.len() <= MAX_ID_LEN
+        && bus_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Ungültige Bus-ID: {}", bus_id));
+    }
+    Ok(())
+}
+
+/// Bus-Namen prüfen und bereinigt zurückgeben
+fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!(
+            "Ungültiger Bus-Name (1 bis {} Zeichen erlaubt)",
+            MAX_NAME_LEN
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Kanalzahl prüfen
+fn validate_channels(channels: u16) -> Result<(), String> {
+    if !(1..=MAX_CHANNELS).contains(&channels) {
+        return Err(format!(
+            "Ungültige Kanalzahl: {} (1-{})",
+            channels, MAX_CHANNELS
+        ));
+    }
+    Ok(())
+}
+
+/// Sortier-Schlüssel: Präfix, dann Nummer (A2 vor A10)
+fn sort_key(bus_id: &str) -> (String, u32, String) {
+    let split = bus_id
+        .find(|c: char| c.is_ascii_digit())
+        .unwrap_or(bus_id.len());
+    let (prefix, number) = bus_id.split_at(split);
+    (
+        prefix.to_string(),
+        number.parse().unwrap_or(u32::MAX),
+        bus_id.to_string(),
+    )
+}
+
+/// Gespeicherten Bus prüfen und Laufzeit-Felder zurücksetzen
+fn checked_bus(mut bus: OutputBus) -> Result<OutputBus, String> {
+    validate_bus_id(&bus.id)?;
+    validate_channels(bus.channels)?;
+    if !(MIN_VOLUME_DB..=MAX_VOLUME_DB).contains(&bus.volume_db) {
+        return Err(format!(
+            "Bus '{}': Lautstärke außerhalb des Bereichs",
+            bus.id
+        ));
+    }
+    validate_delay_ms(bus.delay_ms).map_err(|e| format!("Bus '{}': {}", bus.id, e))?;
+    if !bus.is_physical() {
+        bus.device_name = None;
+    }
+    // Node-IDs sind nur zur Laufzeit gültig — werden neu aufgelöst
+    bus.device_id = None;
+    bus.inserts = checked_inserts(&bus.inserts).map_err(|e| format!("Bus '{}': {}", bus.id, e))?;
+    // Aufnahme läuft nach einem Neustart nicht weiter
+    bus.recording = false;
+    Ok(bus)
+}
+
 /// dB-Wert in linearen Faktor umrechnen
 pub fn db_to_linear(db: f32) -> f32 {
     10.0_f32.powf(db / 20.0)
@@ -231,7 +664,7 @@ mod tests {
 
     #[test]
     fn test_physical_bus_defaults() {
-        let bus = OutputBus::new_physical("A1", "SPEAKERS");
+        let bus = OutputBus::new("A1", "SPEAKERS", BusType::Physical);
         assert_eq!(bus.bus_type, BusType::Physical);
         assert_eq!(bus.volume_db, 0.0);
         assert!(!bus.muted);
@@ -240,13 +673,13 @@ mod tests {
 
     #[test]
     fn test_virtual_bus_defaults() {
-        let bus = OutputBus::new_virtual("B1", "STREAM");
+        let bus = OutputBus::new("B1", "STREAM", BusType::Virtual);
         assert_eq!(bus.bus_type, BusType::Virtual);
     }
 
     #[test]
     fn test_bus_serialize() {
-        let bus = OutputBus::new_physical("A1", "SPEAKERS");
+        let bus = OutputBus::new("A1", "SPEAKERS", BusType::Physical);
         let json = serde_json::to_string(&bus);
         assert!(json.is_ok());
         let json_str = json.unwrap();
@@ -265,4 +698,205 @@ mod tests {
         assert!((linear_to_db(1.0) - 0.0).abs() < 0.001);
         assert_eq!(linear_to_db(0.0), MIN_VOLUME_DB);
     }
+
+    #[test]
+    fn test_from_buses() {
+        let mut manager = BusManager::new();
+        manager.set_volume("B1", -6.0).unwrap();
+        manager.set_recording("B1", true).unwrap();
+
+        let restored = BusManager::from_buses(manager.get_buses()).unwrap();
+        let b1 = restored.get_bus("B1").unwrap();
+        assert_eq!(b1.volume_db, -6.0);
+        assert!(!b1.recording);
+
+        let mut buses = manager.get_buses();
+        buses[0].volume_db = f32::INFINITY;
+        assert!(BusManager::from_buses(buses).is_err());
+    }
+
+    #[test]
+    fn test_create_rename_delete() {
+        let mut manager = BusManager::new();
+        let bus = manager
+            .create_bus(" MONITOR ", BusType::Physical, 2)
+            .unwrap();
+        assert_eq!(bus.id, "A3");
+        assert_eq!(bus.name, "MONITOR");
+        let bus = manager.create_bus("RECORD", BusType::Virtual, 8).unwrap();
+        assert_eq!(bus.id, "B3");
+        assert_eq!(bus.channels, 8);
+        assert!(manager.create_bus("X", BusType::Virtual, 0).is_err());
+        assert!(manager.create_bus("", BusType::Virtual, 2).is_err());
+
+        manager.rename_bus("A3", "WEDGE").unwrap();
+        assert_eq!(manager.get_bus("A3").unwrap().name, "WEDGE");
+
+        manager.set_recording("B3", true).unwrap();
+        assert!(manager.delete_bus("B3").is_err());
+        manager.set_recording("B3", false).unwrap();
+        manager.delete_bus("B3").unwrap();
+        assert!(manager.get_bus("B3").is_none());
+        assert!(manager.delete_bus("B3").is_err());
+
+        // Freie Nummer wird wiederverwendet
+        manager.delete_bus("A1").unwrap();
+        assert_eq!(
+            manager.create_bus("NEU", BusType::Physical, 2).unwrap().id,
+            "A1"
+        );
+    }
+
+    #[test]
+    fn test_last_bus_stays() {
+        let mut manager =
+            BusManager::from_buses(vec![OutputBus::new("A1", "MAIN", BusType::Physical)]).unwrap();
+        assert!(manager.delete_bus("A1").is_err());
+    }
+
+    #[test]
+    fn test_restore_bus() {
+        let mut manager = BusManager::new();
+        manager.set_volume("B2", -6.0).unwrap();
+        let bus = manager.delete_bus("B2").unwrap();
+
+        let restored = manager.restore_bus(bus.clone()).unwrap();
+        assert_eq!(restored.id, "B2");
+        assert_eq!(manager.get_bus("B2").unwrap().volume_db, -6.0);
+        // ID ist wieder belegt
+        assert!(manager.restore_bus(bus).is_err());
+    }
+
+    #[test]
+    fn test_sort_numeric() {
+        let mut manager = BusManager::new();
+        for _ in 0..8 {
+            manager.create_bus("EXTRA", BusType::Physical, 2).unwrap();
+        }
+        let ids = manager.bus_ids();
+        assert_eq!(ids[1], "A2");
+        assert_eq!(ids[9], "A10");
+        assert_eq!(ids[10], "B1");
+    }
+
+    #[test]
+    fn test_validate_bus_id() {
+        assert!(validate_bus_id("A1").is_ok());
+        assert!(validate_bus_id("stream_2").is_ok());
+        assert!(validate_bus_id("").is_err());
+        assert!(validate_bus_id("A1:input_FL").is_err());
+        assert!(validate_bus_id("Bus mit Leerzeichen").is_err());
+    }
+
+    #[test]
+    fn test_old_bus_json_migrates() {
+        // Sessions/Scenes vor der Bus-Registry kennen keine Kanalzahl
+        let json = r#"{"id":"B1","name":"STREAM","bus_type":"Virtual","device_id":null,
+            "volume_db":-3.0,"muted":false,"recording":false}"#;
+        let bus: OutputBus = serde_json::from_str(json).unwrap();
+        assert_eq!(bus.channels, 2);
+        assert!(!bus.is_physical());
+        assert!(bus.inserts.iter().all(|m| !m.enabled));
+        assert_eq!(bus.delay_ms, 0.0);
+        assert!(bus.device_name.is_none());
+    }
+
+    #[test]
+    fn test_bind_output_device() {
+        let mut manager = BusManager::new();
+        manager
+            .bind_output_device("A2", "alsa_output.usb-headset.analog-stereo")
+            .unwrap();
+        manager.set_pw_device("A2", Some(57)).unwrap();
+        let bus = manager.get_bus("A2").unwrap();
+        assert_eq!(
+            bus.device_name.as_deref(),
+            Some("alsa_output.usb-headset.analog-stereo")
+        );
+        assert_eq!(bus.device_id, Some(57));
+        assert_eq!(manager.output_bindings().len(), 1);
+
+        // Virtuelle Busse haben kein Ausgabe-Device
+        assert!(manager.bind_output_device("B1", "alsa_output.x").is_err());
+        assert!(manager.bind_output_device("A1", " ").is_err());
+
+        // Stabile Identität: nach Neustart bleibt der Name, die Node-ID nicht
+        let restored = BusManager::from_buses(manager.get_buses()).unwrap();
+        let bus = restored.get_bus("A2").unwrap();
+        assert!(bus.device_name.is_some());
+        assert_eq!(bus.device_id, None);
+
+        manager.unbind_output_device("A2").unwrap();
+        assert!(manager.output_bindings().is_empty());
+    }
+
+    #[test]
+    fn test_set_mix_minus() {
+        let mut manager = BusManager::new();
+        let minus = |reference: &str| MixMinus {
+            reference_bus: reference.to_string(),
+            excluded: vec!["app-discord".to_string()],
+        };
+        manager.set_mix_minus("B2", Some(minus("B1"))).unwrap();
+        assert_eq!(manager.mix_minus_buses().len(), 1);
+
+        // Keine Ketten: Referenz darf kein Mix-Minus sein und umgekehrt
+        assert!(manager.set_mix_minus("A2", Some(minus("B2"))).is_err());
+        assert!(manager.set_mix_minus("B1", Some(minus("A1"))).is_err());
+        assert!(manager.set_mix_minus("A2", Some(minus("X9"))).is_err());
+
+        // Gespeicherte Referenz auf fehlenden Bus wird beim Laden abgelehnt
+        let mut buses = manager.get_buses();
+        buses.retain(|b| b.id != "B1");
+        assert!(BusManager::from_buses(buses).is_err());
+
+        manager.set_mix_minus("B2", None).unwrap();
+        assert!(manager.mix_minus_buses().is_empty());
+    }
+
+    #[test]
+    fn test_set_delay() {
+        let mut manager = BusManager::new();
+        manager.set_delay("B1", 120.0).unwrap();
+        assert_eq!(manager.get_bus("B1").unwrap().delay_ms, 120.0);
+        assert!(manager.set_delay("B1", 1500.0).is_err());
+        assert!(manager.set_delay("B1", -5.0).is_err());
+        assert_eq!(manager.get_bus("B1").unwrap().delay_ms, 120.0);
+        assert!(manager.set_delay("X9", 10.0).is_err());
+    }
+
+    #[test]
+    fn test_insert_params() {
+        let mut manager = BusManager::new();
+        manager
+            .set_insert_param("B1", FxModuleType::Limiter, "ceiling", -1.0)
+            .unwrap();
+        manager
+            .set_insert_bypass("B1", FxModuleType::Limiter, false)
+            .unwrap();
+
+        let limiter = manager
+            .get_bus("B1")
+            .unwrap()
+            .inserts
+            .iter()
+            .find(|m| m.module_type == FxModuleType::Limiter)
+            .cloned()
+            .unwrap();
+        assert!(limiter.enabled);
+        assert!(limiter.params.contains(&("ceiling".to_string(), -1.0)));
+
+        // Ungültige Werte lassen den Bus unverändert
+        let before = manager.get_bus("B1").unwrap().inserts.clone();
+        assert!(manager
+            .set_insert_param("B1", FxModuleType::Limiter, "ceiling", 5.0)
+            .is_err());
+        assert!(manager
+            .set_insert_bypass("B1", FxModuleType::Gate, false)
+            .is_err());
+        assert_eq!(manager.get_bus("B1").unwrap().inserts, before);
+        assert!(manager
+            .set_insert_bypass("X9", FxModuleType::Eq, false)
+            .is_err());
+    }
 }
@@ -0,0 +1,348 @@
+// Modul: audio/bus_output — PipeWire-Pfad der Busse durch die Mix-Engine
+//
+// Im PipeWire-Modus enden die Routing-Links im Summen-Node `inoX-Sum-<ID>`
+// statt direkt im Bus-Node. Pro Bus laufen zwei Prozesse:
+// - Rückkehr: `pw-record` schneidet die Monitor-Ports des Summen-Nodes mit,
+//             die Mix-Engine addiert den Block vor den Inserts in den Bus.
+// - Ausgang:  `pw-play` spielt den fertigen Bus-Block (Inserts, Fader, Delay,
+//             Submixe, Master-Stufe) in den Bus-Node `inoX-Bus-<ID>`, der ihn
+//             an das Ausgabe-Device bzw. die konsumierende Anwendung gibt.
+// Endet einer der Prozesse, wird der Bus getrennt und mit Backoff neu gestartet.
+use super::bus_tap::pump_raw_f32;
+use super::cpal_capture::reconnect_delay;
+use super::mix_engine::MixEngine;
+use super::tap::{ring, RingReader, CAPTURE_RING_SAMPLES};
+use log::{info, warn};
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Samples pro Schreibvorgang in pw-play (Stereo interleaved)
+const PLAYBACK_CHUNK_SAMPLES: usize = 1024;
+/// Wartezeit des Wiedergabe-Threads wenn die Engine nichts geliefert hat (ms)
+const PLAYBACK_IDLE_MS: u64 = 2;
+
+/// Laufender PipeWire-Pfad eines Busses
+struct BusLink {
+    /// pw-record auf den Monitor-Ports des Summen-Nodes
+    record: Child,
+    /// pw-play in den Bus-Node
+    play: Child,
+    /// Von einem der Pump-Threads gesetzt wenn sein Prozess endet
+    ended: Arc<AtomicBool>,
+}
+
+impl BusLink {
+    /// Beide Prozesse beenden (die Pump-Threads enden mit ihren Pipes)
+    fn stop(&mut self) {
+        for child in [&mut self.record, &mut self.play] {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+/// Fehlgeschlagener Start (wartet auf nächsten Versuch)
+struct OutputRetry {
+    /// Fehlgeschlagene Versuche
+    attempts: u32,
+    /// Frühester Zeitpunkt für den nächsten Versuch
+    next_retry: Instant,
+}
+
+/// Verwaltet Rückkehr und Ausgang aller Busse
+pub struct BusOutputManager {
+    /// Pfade aktiv (nur im PipeWire-Modus)
+    active: bool,
+    /// Sample-Rate der laufenden Prozesse (Hz)
+    sample_rate: u32,
+    /// Laufende Pfade (Key: Bus-ID)
+    links: HashMap<String, BusLink>,
+    /// Busse deren Start fehlgeschlagen ist (Backoff)
+    retries: HashMap<String, OutputRetry>,
+}
+
+impl BusOutputManager {
+    /// Neuen, inaktiven Manager erstellen
+    pub fn new() -> Self {
+        Self {
+            active: false,
+            sample_rate: 0,
+            links: HashMap::new(),
+            retries: HashMap::new(),
+        }
+    }
+
+    /// Pfade ein-/ausschalten — ausgeschaltet werden alle Busse getrennt
+    pub fn set_active(&mut self, active: bool, engine: &mut MixEngine) {
+        if self.active == active {
+            return;
+        }
+        info!("Bus-Ausgang über die Mix-Engine: {}", active);
+        self.active = active;
+        if !active {
+            self.stop_all(engine);
+        }
+    }
+
+    /// Pfade an die Busse der Registry anpassen
+    ///
+    /// Beendete Prozesse und entfernte Busse werden getrennt; ändert sich die
+    /// Sample-Rate der Engine, starten alle Pfade in der neuen Rate neu.
+    pub fn sync(&mut self, bus_ids: &[String], engine: &mut MixEngine) {
+        if !self.active {
+            return;
+        }
+        if engine.sample_rate() != self.sample_rate {
+            self.stop_all(engine);
+            self.sample_rate = engine.sample_rate();
+        }
+
+        let stale: Vec<String> = self
+            .links
+            .iter()
+            .filter(|(id, link)| !bus_ids.contains(id) || link.ended.load(Ordering::Relaxed))
+            .map(|(id, _)| id.clone())
+            .collect();
+        for bus_id in stale {
+            self.stop_link(&bus_id, engine);
+        }
+        self.retries.retain(|id, _| bus_ids.contains(id));
+
+        let now = Instant::now();
+        for bus_id in bus_ids {
+            if self.links.contains_key(bus_id) {
+                continue;
+            }
+            if let Some(retry) = self.retries.get(bus_id) {
+                if now < retry.next_retry {
+                    continue;
+                }
+            }
+
+            match spawn_link(bus_id, self.sample_rate, engine) {
+                Ok(link) => {
+                    info!("🔈 Bus-Ausgang gestartet: {}", bus_id);
+                    self.retries.remove(bus_id);
+                    self.links.insert(bus_id.clone(), link);
+                }
+                Err(e) => {
+                    let retry = self.retries.entry(bus_id.clone()).or_insert(OutputRetry {
+                        attempts: 0,
+                        next_retry: now,
+                    });
+                    retry.attempts += 1;
+                    retry.next_retry = now + reconnect_delay(retry.attempts);
+                    warn!(
+                        "⚠️  Bus-Ausgang {} fehlgeschlagen (Versuch {}): {}",
+                        bus_id, retry.attempts, e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Pfad eines Busses trennen und die Prozesse beenden
+    fn stop_link(&mut self, bus_id: &str, engine: &mut MixEngine) {
+        engine.detach_bus_output(bus_id);
+        if let Some(mut link) = self.links.remove(bus_id) {
+            link.stop();
+            info!("Bus-Ausgang gestoppt: {}", bus_id);
+        }
+    }
+
+    /// Alle Pfade trennen
+    pub fn stop_all(&mut self, engine: &mut MixEngine) {
+        let ids: Vec<String> = self.links.keys().cloned().collect();
+        for bus_id in ids {
+            self.stop_link(&bus_id, engine);
+        }
+        self.retries.clear();
+    }
+}
+
+impl Default for BusOutputManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Argumente für den Mitschnitt des Summen-Nodes eines Busses
+///
+/// Wie beim Bus-Monitor: `stream.capture.sink` greift die Monitor-Ports ab.
+pub fn return_command_args(bus_id: &str, sample_rate: u32) -> Vec<String> {
+    vec![
+        "--target".to_string(),
+        format!("inoX-Sum-{}", bus_id),
+        "-P".to_string(),
+        "{ stream.capture.sink = true }".to_string(),
+        "--rate".to_string(),
+        sample_rate.to_string(),
+        "--channels".to_string(),
+        "2".to_string(),
+        "--format".to_string(),
+        "f32".to_string(),
+        "--raw".to_string(),
+        "-".to_string(),
+    ]
+}
+
+/// Argumente für die Wiedergabe in den Bus-Node (Raw-f32 Stereo von Stdin)
+pub fn playback_command_args(bus_id: &str, sample_rate: u32) -> Vec<String> {
+    vec![
+        "--target".to_string(),
+        format!("inoX-Bus-{}", bus_id),
+        "--rate".to_string(),
+        sample_rate.to_string(),
+        "--channels".to_string(),
+        "2".to_string(),
+        "--format".to_string(),
+        "f32".to_string(),
+        "--raw".to_string(),
+        "-".to_string(),
+    ]
+}
+
+/// pw-record und pw-play eines Busses starten und an die Engine anschließen
+fn spawn_link(bus_id: &str, sample_rate: u32, engine: &mut MixEngine) -> Result<BusLink, String> {
+    let mut record = Command::new("pw-record")
+        .args(return_command_args(bus_id, sample_rate))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("pw-record konnte nicht gestartet werden: {}", e))?;
+    let play = match Command::new("pw-play")
+        .args(playback_command_args(bus_id, sample_rate))
+        .stdin(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(play) => play,
+        Err(e) => {
+            let _ = record.kill();
+            let _ = record.wait();
+            return Err(format!("pw-play konnte nicht gestartet werden: {}", e));
+        }
+    };
+
+    let mut link = BusLink {
+        record,
+        play,
+        ended: Arc::new(AtomicBool::new(false)),
+    };
+    // Ohne beide Pipes liefe einer der Prozesse ins Leere
+    let stdout = link.record.stdout.take();
+    let stdin = link.play.stdin.take();
+    let (stdout, stdin) = match (stdout, stdin) {
+        (Some(stdout), Some(stdin)) => (stdout, stdin),
+        _ => {
+            link.stop();
+            return Err("pw-record/pw-play liefern keine Pipes".to_string());
+        }
+    };
+
+    let (ret_writer, ret_reader) = ring(CAPTURE_RING_SAMPLES);
+    let (play_writer, play_reader) = ring(CAPTURE_RING_SAMPLES);
+
+    let ended = Arc::clone(&link.ended);
+    let spawned = thread::Builder::new()
+        .name(format!("bus-return-{}", bus_id))
+        .spawn(move || {
+            pump_raw_f32(stdout, ret_writer);
+            ended.store(true, Ordering::Relaxed);
+        })
+        .and_then(|_| {
+            let ended = Arc::clone(&link.ended);
+            thread::Builder::new()
+                .name(format!("bus-output-{}", bus_id))
+                .spawn(move || {
+                    feed_raw_f32(play_reader, stdin);
+                    ended.store(true, Ordering::Relaxed);
+                })
+        });
+    if let Err(e) = spawned {
+        link.stop();
+        return Err(format!(
+            "Bus-Ausgang-Thread konnte nicht gestartet werden: {}",
+            e
+        ));
+    }
+
+    engine.attach_bus_output(bus_id, ret_reader, play_writer);
+    Ok(link)
+}
+
+/// Samples aus dem Ring als Raw-f32 (Little Endian) in einen Stream schreiben
+///
+/// Endet wenn der Stream nicht mehr schreibbar ist oder die Engine den Bus
+/// getrennt hat (Schreibseite des Rings verworfen).
+fn feed_raw_f32(mut reader: RingReader, mut output: impl Write) {
+    let mut samples = vec![0.0_f32; PLAYBACK_CHUNK_SAMPLES];
+    let mut bytes = Vec::with_capacity(PLAYBACK_CHUNK_SAMPLES * 4);
+
+    loop {
+        let read = reader.read(&mut samples);
+        if read == 0 {
+            if !reader.is_connected() {
+                break;
+            }
+            thread::sleep(Duration::from_millis(PLAYBACK_IDLE_MS));
+            continue;
+        }
+        encode_f32le(&samples[..read], &mut bytes);
+        if output.write_all(&bytes).is_err() {
+            break;
+        }
+    }
+}
+
+/// Samples in Raw-Bytes (f32 Little Endian) umwandeln
+fn encode_f32le(samples: &[f32], out: &mut Vec<u8>) {
+    out.clear();
+    out.extend(samples.iter().flat_map(|s| s.to_le_bytes()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_args() {
+        let ret = return_command_args("B1", 48000);
+        assert_eq!(ret[1], "inoX-Sum-B1");
+        assert!(ret.contains(&"{ stream.capture.sink = true }".to_string()));
+
+        let play = playback_command_args("B1", 44100);
+        assert_eq!(play[1], "inoX-Bus-B1");
+        assert!(play.contains(&"44100".to_string()));
+        assert_eq!(play.last().unwrap(), "-");
+    }
+
+    #[test]
+    fn test_feed_raw_f32_until_disconnect() {
+        let (mut writer, reader) = ring(16);
+        writer.write(&[0.5, -1.0]);
+        drop(writer);
+
+        let mut out = Vec::new();
+        feed_raw_f32(reader, &mut out);
+        let expected: Vec<u8> = [0.5f32, -1.0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_inactive_manager_starts_nothing() {
+        let mut manager = BusOutputManager::new();
+        let mut engine = MixEngine::new();
+        manager.sync(&["B1".to_string()], &mut engine);
+        assert!(manager.links.is_empty());
+        assert!(manager.retries.is_empty());
+    }
+}
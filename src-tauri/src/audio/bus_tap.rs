@@ -0,0 +1,343 @@
+// Modul: audio/bus_tap — Monitor-Taps der Output-Busse
+//
+// Macht das Summensignal jedes inoX-Busses als Tap-Quelle (Source-ID = Bus-ID)
+// im TapHub verfügbar — für Aufnahme, Bus-Metering und STT/Bleeper.
+//
+// Zwei Quellen, je nachdem wo gemischt wird:
+// - Engine:   Die In-Process Mix-Engine verteilt ihre Bus-Blöcke direkt.
+// - PipeWire: Die Monitor-Ports der Bus-Nodes (inoX-Bus-XX) werden per
+//             `pw-record` als Raw-f32 mitgeschnitten und in einen Ring geschrieben.
+use super::cpal_capture::reconnect_delay;
+use super::tap::{ring, RingReader, RingWriter, TapHub, CAPTURE_RING_SAMPLES};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Read;
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Instant;
+
+/// Sample-Rate der Monitor-Aufnahme (Pipeline-Rate)
+const MONITOR_SAMPLE_RATE: u32 = 48000;
+/// Lesegröße vom pw-record-Stdout in Bytes
+const MONITOR_READ_BYTES: usize = 4096;
+/// Samples pro Verteil-Durchgang (Stereo interleaved)
+const DISTRIBUTE_CHUNK_SAMPLES: usize = 1024;
+
+/// Herkunft der Bus-Monitor-Signale
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BusTapSource {
+    /// In-Process Mix-Engine (Bus-Ausgang direkt)
+    Engine,
+    /// Monitor-Ports der PipeWire Bus-Nodes
+    PipeWire,
+}
+
+/// Laufender Mitschnitt eines Bus-Monitors via pw-record
+struct PwMonitor {
+    /// pw-record Prozess
+    child: Child,
+    /// Leseseite des Monitor-Rings (Schreibseite gehört dem Leser-Thread)
+    reader: RingReader,
+    /// Vom Leser-Thread gesetzt wenn der Prozess endet
+    ended: Arc<AtomicBool>,
+}
+
+/// Fehlgeschlagener Monitor-Start (wartet auf nächsten Versuch)
+struct MonitorRetry {
+    /// Fehlgeschlagene Versuche
+    attempts: u32,
+    /// Frühester Zeitpunkt für den nächsten Versuch
+    next_retry: Instant,
+}
+
+/// Verwaltet die Monitor-Taps aller Busse
+pub struct BusMonitorManager {
+    /// Aktive Quelle
+    source: BusTapSource,
+    /// Laufende PipeWire-Mitschnitte (Key: Bus-ID)
+    monitors: HashMap<String, PwMonitor>,
+    /// Busse deren Mitschnitt fehlgeschlagen ist (Backoff)
+    retries: HashMap<String, MonitorRetry>,
+    /// Lese-Puffer für die Verteilung
+    scratch: Vec<f32>,
+}
+
+impl BusMonitorManager {
+    /// Neuen Manager mit der gegebenen Quelle erstellen
+    pub fn new(source: BusTapSource) -> Self {
+        info!("Bus-Monitor erstellt (Quelle: {:?})", source);
+        Self {
+            source,
+            monitors: HashMap::new(),
+            retries: HashMap::new(),
+            scratch: vec![0.0; DISTRIBUTE_CHUNK_SAMPLES],
+        }
+    }
+
+    /// Aktive Quelle abfragen
+    pub fn source(&self) -> BusTapSource {
+        self.source
+    }
+
+    /// Quelle wechseln — beim Wechsel auf die Engine enden alle Mitschnitte
+    pub fn set_source(&mut self, source: BusTapSource) {
+        if self.source == source {
+            return;
+        }
+        info!("Bus-Monitor Quelle: {:?} → {:?}", self.source, source);
+        self.source = source;
+        if source == BusTapSource::Engine {
+            self.stop_all();
+        }
+    }
+
+    /// Mitschnitte an die abonnierten Busse anpassen
+    ///
+    /// Im PipeWire-Modus läuft ein `pw-record` pro Bus der mindestens einen
+    /// Consumer-Tap hat. Beendete Mitschnitte werden mit Backoff neu gestartet.
+    pub fn sync(&mut self, bus_ids: &[String], hub: &TapHub) {
+        if self.source != BusTapSource::PipeWire {
+            return;
+        }
+
+        // Beendete oder nicht mehr benötigte Mitschnitte stoppen
+        let stale: Vec<String> = self
+            .monitors
+            .iter()
+            .filter(|(id, monitor)| {
+                !bus_ids.contains(id) || !hub.has_taps(id) || monitor.ended.load(Ordering::Relaxed)
+            })
+            .map(|(id, _)| id.clone())
+            .collect();
+        for bus_id in stale {
+            self.stop_monitor(&bus_id);
+        }
+        self.retries.retain(|id, _| bus_ids.contains(id));
+
+        let now = Instant::now();
+        for bus_id in bus_ids {
+            if self.monitors.contains_key(bus_id) || !hub.has_taps(bus_id) {
+                continue;
+            }
+            if let Some(retry) = self.retries.get(bus_id) {
+                if now < retry.next_retry {
+                    continue;
+                }
+            }
+
+            match spawn_monitor(bus_id) {
+                Ok(monitor) => {
+                    info!("🎧 Bus-Monitor gestartet: {}", bus_id);
+                    self.retries.remove(bus_id);
+                    self.monitors.insert(bus_id.clone(), monitor);
+                }
+                Err(e) => {
+                    let retry = self.retries.entry(bus_id.clone()).or_insert(MonitorRetry {
+                        attempts: 0,
+                        next_retry: now,
+                    });
+                    retry.attempts += 1;
+                    retry.next_retry = now + reconnect_delay(retry.attempts);
+                    warn!(
+                        "⚠️  Bus-Monitor {} fehlgeschlagen (Versuch {}): {}",
+                        bus_id, retry.attempts, e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Mitgeschnittene Samples an die Bus-Taps verteilen
+    pub fn distribute(&mut self, hub: &mut TapHub) {
+        for (bus_id, monitor) in self.monitors.iter_mut() {
+            hub.set_sample_rate(bus_id, MONITOR_SAMPLE_RATE);
+            loop {
+                let read = monitor.reader.read(&mut self.scratch);
+                if read == 0 {
+                    break;
+                }
+                hub.distribute(bus_id, &self.scratch[..read]);
+            }
+        }
+    }
+
+    /// Einen Mitschnitt beenden
+    fn stop_monitor(&mut self, bus_id: &str) {
+        if let Some(mut monitor) = self.monitors.remove(bus_id) {
+            let _ = monitor.child.kill();
+            let _ = monitor.child.wait();
+            info!("Bus-Monitor gestoppt: {}", bus_id);
+        }
+    }
+
+    /// Alle Mitschnitte beenden
+    pub fn stop_all(&mut self) {
+        let ids: Vec<String> = self.monitors.keys().cloned().collect();
+        for bus_id in ids {
+            self.stop_monitor(&bus_id);
+        }
+        self.retries.clear();
+    }
+}
+
+impl Drop for BusMonitorManager {
+    fn drop(&mut self) {
+        self.stop_all();
+    }
+}
+
+/// Argumente für den Mitschnitt der Monitor-Ports eines Bus-Nodes
+///
+/// `stream.capture.sink` lässt pw-record die Monitor-Ports statt der Eingänge
+/// abgreifen; Raw-f32 Stereo auf Stdout.
+pub fn monitor_command_args(bus_id: &str, sample_rate: u32) -> Vec<String> {
+    vec![
+        "--target".to_string(),
+        format!("inoX-Bus-{}", bus_id),
+        "-P".to_string(),
+        "{ stream.capture.sink = true }".to_string(),
+        "--rate".to_string(),
+        sample_rate.to_string(),
+        "--channels".to_string(),
+        "2".to_string(),
+        "--format".to_string(),
+        "f32".to_string(),
+        "--raw".to_string(),
+        "-".to_string(),
+    ]
+}
+
+/// pw-record für einen Bus starten und Stdout in einen Ring leiten
+fn spawn_monitor(bus_id: &str) -> Result<PwMonitor, String> {
+    let mut child = Command::new("pw-record")
+        .args(monitor_command_args(bus_id, MONITOR_SAMPLE_RATE))
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("pw-record konnte nicht gestartet werden: {}", e))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "pw-record liefert kein Stdout".to_string())?;
+
+    let (writer, reader) = ring(CAPTURE_RING_SAMPLES);
+    let ended = Arc::new(AtomicBool::new(false));
+    let ended_thread = Arc::clone(&ended);
+
+    thread::Builder::new()
+        .name(format!("bus-monitor-{}", bus_id))
+        .spawn(move || {
+            pump_raw_f32(stdout, writer);
+            ended_thread.store(true, Ordering::Relaxed);
+        })
+        .map_err(|e| format!("Monitor-Thread konnte nicht gestartet werden: {}", e))?;
+
+    Ok(PwMonitor {
+        child,
+        reader,
+        ended,
+    })
+}
+
+/// Raw-f32 (Little Endian) aus einem Stream lesen bis EOF und in den Ring schreiben
+pub fn pump_raw_f32(mut input: impl Read, mut writer: RingWriter) {
+    let mut bytes = [0u8; MONITOR_READ_BYTES];
+    let mut samples = Vec::with_capacity(MONITOR_READ_BYTES / 4);
+    // Angefangenes Sample vom letzten Read (max. 3 Bytes)
+    let mut pending = Vec::with_capacity(4);
+
+    loop {
+        let read = match input.read(&mut bytes) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.extend_from_slice(&bytes[..read]);
+        let complete = pending.len() - pending.len() % 4;
+        decode_f32le(&pending[..complete], &mut samples);
+        pending.drain(..complete);
+        writer.write(&samples);
+    }
+}
+
+/// Raw-Bytes (f32 Little Endian) in Samples umwandeln
+fn decode_f32le(bytes: &[u8], out: &mut Vec<f32>) {
+    out.clear();
+    out.extend(
+        bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::tap::TapKind;
+
+    #[test]
+    fn test_monitor_command_args() {
+        let args = monitor_command_args("B1", 48000);
+        assert_eq!(args[1], "inoX-Bus-B1");
+        assert!(args.contains(&"{ stream.capture.sink = true }".to_string()));
+        assert_eq!(args.last().unwrap(), "-");
+    }
+
+    #[test]
+    fn test_decode_f32le() {
+        let bytes: Vec<u8> = [0.5f32, -1.0]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let mut out = Vec::new();
+        decode_f32le(&bytes, &mut out);
+        assert_eq!(out, vec![0.5, -1.0]);
+    }
+
+    #[test]
+    fn test_pump_raw_f32_split_samples() {
+        // Reader liefert Samples über Read-Grenzen hinweg zerteilt
+        struct Chunked(Vec<Vec<u8>>);
+        impl Read for Chunked {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                if self.0.is_empty() {
+                    return Ok(0);
+                }
+                let chunk = self.0.remove(0);
+                buf[..chunk.len()].copy_from_slice(&chunk);
+                Ok(chunk.len())
+            }
+        }
+
+        let bytes: Vec<u8> = [0.25f32, 0.75]
+            .iter()
+            .flat_map(|s| s.to_le_bytes())
+            .collect();
+        let input = Chunked(vec![bytes[..3].to_vec(), bytes[3..].to_vec()]);
+        let (writer, mut reader) = ring(16);
+        pump_raw_f32(input, writer);
+
+        let mut out = [0.0; 4];
+        assert_eq!(reader.read(&mut out), 2);
+        assert_eq!(&out[..2], &[0.25, 0.75]);
+    }
+
+    #[test]
+    fn test_engine_source_starts_no_monitors() {
+        let mut manager = BusMonitorManager::new(BusTapSource::Engine);
+        let mut hub = TapHub::new();
+        let _tap = hub.subscribe("B1", TapKind::Stt);
+        manager.sync(&["B1".to_string()], &hub);
+        assert!(manager.monitors.is_empty());
+    }
+
+    #[test]
+    fn test_source_serialize() {
+        let json = serde_json::to_string(&BusTapSource::PipeWire).unwrap();
+        assert_eq!(json, "\"pipewire\"");
+    }
+}
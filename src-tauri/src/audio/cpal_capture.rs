@@ -1,35 +1,116 @@
 // Modul: audio/cpal_capture — Echtes Audio-Capture via CPAL
 //
 // Phase 2d: CPAL-Integration für Production-Ready Audio
+use super::tap::{ring, RingReader, RingWriter, TapHub, CAPTURE_RING_SAMPLES};
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
-use cpal::{Device, SampleFormat, Stream, StreamConfig};
-use log::{error, info};
-use std::collections::{HashMap, VecDeque};
+use cpal::{Device, SampleFormat, Stream, StreamConfig, StreamError};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Samples pro Verteil-Durchgang (Stereo interleaved)
+const DISTRIBUTE_CHUNK_SAMPLES: usize = 1024;
+/// Erste Wartezeit vor einem Reconnect-Versuch (ms)
+const RECONNECT_BASE_DELAY_MS: u64 = 500;
+/// Maximale Wartezeit zwischen zwei Reconnect-Versuchen (ms)
+const RECONNECT_MAX_DELAY_MS: u64 = 30_000;
+/// Liefert ein Stream so lange keine Daten, gilt er als abgerissen (ms)
+const STREAM_STALL_TIMEOUT_MS: u64 = 2_000;
+
+/// Verbindungszustand eines gebundenen Capture-Streams
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CaptureState {
+    /// Stream läuft und liefert Daten
+    Connected,
+    /// Stream ist abgerissen, Device wird erneut geöffnet
+    Reconnecting,
+    /// Gebundenes Device ist nicht (mehr) vorhanden
+    Missing,
+}
+
+/// Zustand eines Strips mit gebundenem Capture-Device (fürs Frontend)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptureStatus {
+    /// Strip-ID
+    pub strip_id: String,
+    /// Gebundener Device-Name
+    pub device_name: String,
+    /// Verbindungszustand
+    pub state: CaptureState,
+    /// Fehlgeschlagene Reconnect-Versuche seit dem Abriss
+    pub attempts: u32,
+}
+
+/// Gebundener Strip ohne laufenden Stream (wartet auf nächsten Versuch)
+struct PendingCapture {
+    /// Gebundener Device-Name
+    binding: String,
+    /// Reconnecting oder Missing
+    state: CaptureState,
+    /// Fehlgeschlagene Versuche
+    attempts: u32,
+    /// Frühester Zeitpunkt für den nächsten Versuch
+    next_retry: Instant,
+}
 
-/// Audio-Sample (32-bit float, Stereo)
-#[derive(Debug, Clone, Copy, Default)]
-pub struct AudioSample {
-    pub left: f32,
-    pub right: f32,
+/// Wartezeit vor dem nächsten Versuch (exponentiell, gedeckelt)
+pub fn reconnect_delay(attempts: u32) -> Duration {
+    let factor = 1u64 << attempts.min(16);
+    Duration::from_millis((RECONNECT_BASE_DELAY_MS * factor).min(RECONNECT_MAX_DELAY_MS))
 }
 
 /// CPAL Audio-Capture Manager
 ///
 /// Verwaltet echte Audio-Input-Streams von Hardware-Devices via CPAL
 pub struct CpalCaptureManager {
-    /// Aktive Streams (Device-Name → Stream-Handle)
+    /// Aktive Streams (Strip-ID → Stream-Handle)
     streams: Arc<Mutex<HashMap<String, CpalStreamHandle>>>,
+    /// Gebundene Strips ohne laufenden Stream (Reconnect-Supervisor)
+    pending: HashMap<String, PendingCapture>,
+    /// Arbeits-Puffer für das Verteilen an die Taps
+    scratch: Vec<f32>,
     /// CPAL Host (Audio-System)
     host: cpal::Host,
 }
 
+/// Overflow-Statistik eines Capture-Streams
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CaptureStats {
+    /// Strip-ID des Streams
+    pub strip_id: String,
+    /// Name des geöffneten Devices
+    pub device_name: String,
+    /// Im Echtzeit-Callback verworfene Samples (Capture-Ring voll)
+    pub overflow_samples: u64,
+}
+
+/// Ergebnis eines Abgleichs zwischen Strip-Bindungen und laufenden Streams
+#[derive(Default)]
+pub struct CaptureChanges {
+    /// Neu gestartete Streams (Strip-ID)
+    pub started: Vec<String>,
+    /// Gestoppte Streams (Strip-ID)
+    pub stopped: Vec<String>,
+}
+
 /// Handle für einen CPAL-Stream
 struct CpalStreamHandle {
     /// Device-Name
     device_name: String,
-    /// Ring-Buffer für Audio-Daten (VecDeque ist einfacher als ringbuf)
-    buffer: Arc<Mutex<VecDeque<AudioSample>>>,
+    /// Gebundener Device-Name aus dem Strip (Suchbegriff)
+    binding: String,
+    /// Leseseite des Capture-Rings (Schreibseite gehört dem Callback)
+    reader: RingReader,
+    /// Sample-Rate des Devices (Hz)
+    sample_rate: u32,
+    /// Vom Error-Callback gesetzt wenn das Device verschwindet
+    failed: Arc<AtomicBool>,
+    /// Zeitpunkt der letzten gelesenen Daten (Stall-Erkennung)
+    last_data: Instant,
     /// CPAL Stream (muss am Leben bleiben!)
     _stream: Stream,
 }
@@ -46,6 +127,8 @@ impl CpalCaptureManager {
 
         Ok(Self {
             streams: Arc::new(Mutex::new(HashMap::new())),
+            pending: HashMap::new(),
+            scratch: vec![0.0; DISTRIBUTE_CHUNK_SAMPLES],
             host,
         })
     }
@@ -94,13 +177,9 @@ impl CpalCaptureManager {
     /// * `device` - CPAL Input-Device
     /// * `stream_id` - Logische Stream-ID (z.B. "hw-mic-1")
     ///
-    /// # Returns
-    /// Ring-Buffer Arc für Audio-Daten
-    pub fn start_capture(
-        &mut self,
-        device: Device,
-        stream_id: &str,
-    ) -> Result<Arc<Mutex<VecDeque<AudioSample>>>, String> {
+    /// Der Callback schreibt Stereo-Samples in einen eigenen SPSC-Ring,
+    /// `distribute()` verteilt sie anschließend an die Consumer-Taps.
+    pub fn start_capture(&mut self, device: Device, stream_id: &str) -> Result<(), String> {
         let device_name = device.name().unwrap_or_else(|_| "Unknown".to_string());
 
         info!(
@@ -128,17 +207,20 @@ impl CpalCaptureManager {
             config.channels()
         );
 
-        // Ring-Buffer erstellen (2048 Samples ≈ 42ms @ 48kHz)
-        let buffer = Arc::new(Mutex::new(VecDeque::with_capacity(2048)));
-        let buffer_clone = Arc::clone(&buffer);
+        let sample_rate = config.sample_rate().0;
+
+        // Lock-freier SPSC-Ring (Callback → Verteiler)
+        let (writer, reader) = ring(CAPTURE_RING_SAMPLES);
+        let failed = Arc::new(AtomicBool::new(false));
+        let failed_cb = Arc::clone(&failed);
 
         // Audio-Stream basierend auf Sample-Format erstellen
         let stream = match config.sample_format() {
             SampleFormat::F32 => {
-                self.build_input_stream_f32(&device, config.into(), buffer_clone)?
+                self.build_input_stream_f32(&device, config.into(), writer, failed_cb)?
             }
             SampleFormat::I16 => {
-                self.build_input_stream_i16(&device, config.into(), buffer_clone)?
+                self.build_input_stream_i16(&device, config.into(), writer, failed_cb)?
             }
             SampleFormat::U16 => {
                 return Err("U16 Sample-Format nicht unterstützt".to_string());
@@ -161,7 +243,11 @@ impl CpalCaptureManager {
         // Handle speichern
         let handle = CpalStreamHandle {
             device_name: device_name.clone(),
-            buffer: Arc::clone(&buffer),
+            binding: device_name.clone(),
+            reader,
+            sample_rate,
+            failed,
+            last_data: Instant::now(),
             _stream: stream,
         };
 
@@ -170,7 +256,7 @@ impl CpalCaptureManager {
             .unwrap()
             .insert(stream_id.to_string(), handle);
 
-        Ok(buffer)
+        Ok(())
     }
 
     /// Input-Stream für F32-Samples erstellen
@@ -178,7 +264,8 @@ impl CpalCaptureManager {
         &self,
         device: &Device,
         config: StreamConfig,
-        buffer: Arc<Mutex<VecDeque<AudioSample>>>,
+        mut writer: RingWriter,
+        failed: Arc<AtomicBool>,
     ) -> Result<Stream, String> {
         let channels = config.channels as usize;
 
@@ -186,33 +273,23 @@ impl CpalCaptureManager {
             .build_input_stream(
                 &config,
                 move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    let mut buf = buffer.lock().unwrap();
-
-                    // Audio-Daten in Ring-Buffer schreiben
                     // CPAL liefert interleaved samples: L, R, L, R, ...
-                    for chunk in data.chunks_exact(channels) {
-                        let sample = if channels >= 2 {
-                            AudioSample {
-                                left: chunk[0],
-                                right: chunk[1],
-                            }
-                        } else {
-                            // Mono → Beide Kanäle gleich
-                            AudioSample {
-                                left: chunk[0],
-                                right: chunk[0],
-                            }
-                        };
-
-                        // VecDeque als Ring-Buffer: Alte Samples entfernen wenn voll
-                        if buf.len() >= 2048 {
-                            buf.pop_front();
+                    if channels == 2 {
+                        // Stereo: ein einziger Schreibvorgang, keine Kopie
+                        writer.write(data);
+                    } else {
+                        for chunk in data.chunks_exact(channels) {
+                            // Mono → Beide Kanäle gleich, >2 Kanäle → die ersten beiden
+                            let right = if channels >= 2 { chunk[1] } else { chunk[0] };
+                            writer.write(&[chunk[0], right]);
                         }
-                        buf.push_back(sample);
                     }
                 },
-                |err| {
+                move |err| {
                     error!("CPAL Stream-Fehler: {}", err);
+                    if matches!(err, StreamError::DeviceNotAvailable) {
+                        failed.store(true, Ordering::Relaxed);
+                    }
                 },
                 None, // Timeout
             )
@@ -224,7 +301,8 @@ impl CpalCaptureManager {
         &self,
         device: &Device,
         config: StreamConfig,
-        buffer: Arc<Mutex<VecDeque<AudioSample>>>,
+        mut writer: RingWriter,
+        failed: Arc<AtomicBool>,
     ) -> Result<Stream, String> {
         let channels = config.channels as usize;
 
@@ -232,37 +310,200 @@ impl CpalCaptureManager {
             .build_input_stream(
                 &config,
                 move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    let mut buf = buffer.lock().unwrap();
-
-                    // I16 → F32 Konvertierung
+                    // I16 → F32 Konvertierung, Frame für Frame ohne Allokation
                     for chunk in data.chunks_exact(channels) {
-                        let sample = if channels >= 2 {
-                            AudioSample {
-                                left: i16_to_f32(chunk[0]),
-                                right: i16_to_f32(chunk[1]),
-                            }
+                        let left = i16_to_f32(chunk[0]);
+                        let right = if channels >= 2 {
+                            i16_to_f32(chunk[1])
                         } else {
-                            let mono = i16_to_f32(chunk[0]);
-                            AudioSample {
-                                left: mono,
-                                right: mono,
-                            }
+                            left
                         };
-
-                        if buf.len() >= 2048 {
-                            buf.pop_front();
-                        }
-                        buf.push_back(sample);
+                        writer.write(&[left, right]);
                     }
                 },
-                |err| {
+                move |err| {
                     error!("CPAL Stream-Fehler: {}", err);
+                    if matches!(err, StreamError::DeviceNotAvailable) {
+                        failed.store(true, Ordering::Relaxed);
+                    }
                 },
                 None,
             )
             .map_err(|e| format!("Fehler beim Erstellen des Streams: {}", e))
     }
 
+    /// Gecapturete Samples aller Streams an die Consumer-Taps verteilen
+    ///
+    /// Läuft im Capture-Thread (nicht im Echtzeit-Callback).
+    pub fn distribute(&mut self, hub: &mut TapHub) {
+        let mut streams = self.streams.lock().unwrap();
+        for (strip_id, handle) in streams.iter_mut() {
+            hub.set_sample_rate(strip_id, handle.sample_rate);
+            loop {
+                let read = handle.reader.read(&mut self.scratch);
+                if read == 0 {
+                    break;
+                }
+                handle.last_data = Instant::now();
+                hub.distribute(strip_id, &self.scratch[..read]);
+            }
+        }
+    }
+
+    /// Overflow-Statistik aller Capture-Streams (sortiert nach Strip-ID)
+    pub fn capture_stats(&self) -> Vec<CaptureStats> {
+        let streams = self.streams.lock().unwrap();
+        let mut stats: Vec<CaptureStats> = streams
+            .iter()
+            .map(|(strip_id, handle)| CaptureStats {
+                strip_id: strip_id.clone(),
+                device_name: handle.device_name.clone(),
+                overflow_samples: handle.reader.overflow_count(),
+            })
+            .collect();
+        stats.sort_by(|a, b| a.strip_id.cmp(&b.strip_id));
+        stats
+    }
+
+    /// Streams an die Strip-Bindungen anpassen (Strip-ID → Device-Name)
+    ///
+    /// Stoppt Streams von Strips die nicht mehr (oder an ein anderes Device)
+    /// gebunden sind und startet Streams für neu gebundene Strips.
+    ///
+    /// Zugleich Reconnect-Supervisor: abgerissene Streams (Device entfernt oder
+    /// keine Daten mehr) werden geschlossen und mit exponentiellem Backoff
+    /// erneut auf demselben gebundenen Device geöffnet.
+    pub fn sync_bindings(&mut self, bindings: &HashMap<String, String>) -> CaptureChanges {
+        let mut changes = CaptureChanges::default();
+        let now = Instant::now();
+        let stall_timeout = Duration::from_millis(STREAM_STALL_TIMEOUT_MS);
+
+        // 1. Veraltete und abgerissene Streams stoppen
+        let (stale, dead): (Vec<String>, Vec<String>) = {
+            let streams = self.streams.lock().unwrap();
+            let stale = streams
+                .iter()
+                .filter(|(id, handle)| bindings.get(*id) != Some(&handle.binding))
+                .map(|(id, _)| id.clone())
+                .collect();
+            let dead = streams
+                .iter()
+                .filter(|(id, handle)| {
+                    bindings.get(*id) == Some(&handle.binding)
+                        && (handle.failed.load(Ordering::Relaxed)
+                            || now.duration_since(handle.last_data) > stall_timeout)
+                })
+                .map(|(id, _)| id.clone())
+                .collect();
+            (stale, dead)
+        };
+        for strip_id in stale {
+            if self.stop_capture(&strip_id).is_ok() {
+                changes.stopped.push(strip_id);
+            }
+        }
+        for strip_id in dead {
+            warn!(
+                "⚠️  Capture-Stream abgerissen: {} — starte Reconnect",
+                strip_id
+            );
+            if self.stop_capture(&strip_id).is_ok() {
+                changes.stopped.push(strip_id.clone());
+            }
+            self.pending.insert(
+                strip_id.clone(),
+                PendingCapture {
+                    binding: bindings[&strip_id].clone(),
+                    state: CaptureState::Reconnecting,
+                    attempts: 0,
+                    next_retry: now + reconnect_delay(0),
+                },
+            );
+        }
+
+        // Wartende Versuche verwerfen sobald sich die Bindung ändert
+        self.pending
+            .retain(|id, p| bindings.get(id) == Some(&p.binding));
+
+        // 2. Fehlende Streams starten (neue Bindungen sofort, sonst nach Backoff)
+        for (strip_id, device_name) in bindings {
+            let running = self.streams.lock().unwrap().contains_key(strip_id);
+            let waiting = self
+                .pending
+                .get(strip_id)
+                .map(|p| now < p.next_retry)
+                .unwrap_or(false);
+            if running || waiting {
+                continue;
+            }
+
+            let device = self.find_input_device(device_name);
+            let device_found = device.is_ok();
+            let result = device.and_then(|device| self.start_capture(device, strip_id));
+
+            match result {
+                Ok(()) => {
+                    if let Some(handle) = self.streams.lock().unwrap().get_mut(strip_id) {
+                        handle.binding = device_name.clone();
+                    }
+                    if self.pending.remove(strip_id).is_some() {
+                        info!("✅ Capture wiederhergestellt: {}", strip_id);
+                    }
+                    changes.started.push(strip_id.clone());
+                }
+                Err(e) => {
+                    let pending =
+                        self.pending
+                            .entry(strip_id.clone())
+                            .or_insert_with(|| PendingCapture {
+                                binding: device_name.clone(),
+                                state: CaptureState::Reconnecting,
+                                attempts: 0,
+                                next_retry: now,
+                            });
+                    pending.state = if device_found {
+                        CaptureState::Reconnecting
+                    } else {
+                        CaptureState::Missing
+                    };
+                    pending.attempts += 1;
+                    pending.next_retry = now + reconnect_delay(pending.attempts);
+                    warn!(
+                        "⚠️  Capture für Strip {} fehlgeschlagen (Versuch {}, nächster in {:?}): {}",
+                        strip_id,
+                        pending.attempts,
+                        reconnect_delay(pending.attempts),
+                        e
+                    );
+                }
+            }
+        }
+
+        changes
+    }
+
+    /// Verbindungszustand aller gebundenen Strips (sortiert nach Strip-ID)
+    pub fn capture_status(&self) -> Vec<CaptureStatus> {
+        let streams = self.streams.lock().unwrap();
+        let mut status: Vec<CaptureStatus> = streams
+            .iter()
+            .map(|(strip_id, handle)| CaptureStatus {
+                strip_id: strip_id.clone(),
+                device_name: handle.binding.clone(),
+                state: CaptureState::Connected,
+                attempts: 0,
+            })
+            .chain(self.pending.iter().map(|(strip_id, p)| CaptureStatus {
+                strip_id: strip_id.clone(),
+                device_name: p.binding.clone(),
+                state: p.state,
+                attempts: p.attempts,
+            }))
+            .collect();
+        status.sort_by(|a, b| a.strip_id.cmp(&b.strip_id));
+        status
+    }
+
     /// Audio-Capture stoppen
     pub fn stop_capture(&mut self, stream_id: &str) -> Result<(), String> {
         let mut streams = self.streams.lock().unwrap();
@@ -317,10 +558,44 @@ mod tests {
     }
 
     #[test]
-    fn test_audio_sample_default() {
-        let sample = AudioSample::default();
-        assert_eq!(sample.left, 0.0);
-        assert_eq!(sample.right, 0.0);
+    fn test_sync_bindings_unknown_device() {
+        let mut manager = CpalCaptureManager::new().unwrap();
+        let mut bindings = HashMap::new();
+        bindings.insert("hw-mic".to_string(), "inox-gibt-es-nicht".to_string());
+
+        let changes = manager.sync_bindings(&bindings);
+        assert!(changes.started.is_empty());
+        assert!(manager.list_active_streams().is_empty());
+
+        // Device fehlt → Supervisor meldet "missing" und wartet den Backoff ab
+        let status = manager.capture_status();
+        assert_eq!(status.len(), 1);
+        assert_eq!(status[0].state, CaptureState::Missing);
+        assert_eq!(status[0].attempts, 1);
+
+        manager.sync_bindings(&bindings);
+        assert_eq!(manager.capture_status()[0].attempts, 1);
+
+        // Bindung gelöst → kein Zustand mehr
+        let changes = manager.sync_bindings(&HashMap::new());
+        assert!(changes.stopped.is_empty());
+        assert!(manager.capture_status().is_empty());
+    }
+
+    #[test]
+    fn test_reconnect_delay_backoff() {
+        assert_eq!(reconnect_delay(0), Duration::from_millis(500));
+        assert_eq!(reconnect_delay(1), Duration::from_millis(1000));
+        assert_eq!(reconnect_delay(3), Duration::from_millis(4000));
+        // Gedeckelt auf RECONNECT_MAX_DELAY_MS
+        assert_eq!(reconnect_delay(10), Duration::from_millis(30_000));
+        assert_eq!(reconnect_delay(u32::MAX), Duration::from_millis(30_000));
+    }
+
+    #[test]
+    fn test_capture_state_serialize() {
+        let json = serde_json::to_string(&CaptureState::Reconnecting).unwrap();
+        assert_eq!(json, "\"reconnecting\"");
     }
 
     #[test]
@@ -0,0 +1,258 @@
+// Modul: audio/delay — Ausgangs-Delay pro Bus (Bild/Ton-Synchronisation)
+//
+// Stereo-Ringpuffer (interleaved) für bis zu MAX_DELAY_MS. Der Puffer läuft
+// auch ohne Delay mit, damit schon die erste Änderung Vorgeschichte hat.
+// Eine Änderung der Delay-Zeit springt nicht hart auf die neue Leseposition,
+// sondern blendet alte und neue Position über DELAY_FADE_MS linear über
+// (klickfrei, keine Tonhöhen-Verschiebung wie bei einer Rampe).
+// Kommt eine Änderung während einer laufenden Überblendung (Slider-Drag),
+// bleibt die aktuelle Mischung der Startpunkt: jede bisherige Position blendet
+// von ihrem momentanen Gewicht aus weiter auf null, das neue Ziel übernimmt
+// den Rest — der Ausgang springt dabei nicht.
+// Hörbar ist das Delay über den Bus-Ausgang der Mix-Engine (siehe bus_output).
+
+/// Maximale Delay-Zeit pro Bus (ms)
+pub const MAX_DELAY_MS: f32 = 1000.0;
+/// Überblendzeit beim Ändern der Delay-Zeit (ms)
+const DELAY_FADE_MS: f32 = 20.0;
+/// Maximale Zahl gleichzeitig ausblendender Lese-Positionen
+const MAX_FADE_SOURCES: usize = 16;
+
+/// Delay-Zeit prüfen (0 bis MAX_DELAY_MS)
+pub fn validate_delay_ms(delay_ms: f32) -> Result<(), String> {
+    if !delay_ms.is_finite() || !(0.0..=MAX_DELAY_MS).contains(&delay_ms) {
+        return Err(format!(
+            "Delay außerhalb des Bereichs: {} ms (erlaubt: 0 bis {} ms)",
+            delay_ms, MAX_DELAY_MS
+        ));
+    }
+    Ok(())
+}
+
+/// Ausblendende Lese-Position einer Überblendung
+#[derive(Debug, Clone, Copy)]
+struct FadeSource {
+    /// Delay-Zeit (Frames)
+    delay: usize,
+    /// Gewicht beim Start des Ausblendens
+    start_weight: f32,
+    /// Verbleibende Frames bis Gewicht 0
+    remaining: usize,
+}
+
+/// Stereo-Delay-Line mit klickfreier Umschaltung
+pub struct DelayLine {
+    /// Ringpuffer (Stereo interleaved)
+    buffer: Vec<f32>,
+    /// Schreib-Position (Frames)
+    write: usize,
+    /// Aktuelle Delay-Zeit (Frames)
+    delay: usize,
+    /// Ausblendende frühere Positionen (leer wenn keine Überblendung läuft)
+    fade_from: Vec<FadeSource>,
+    /// Länge der Überblendung (Frames)
+    fade_len: usize,
+    /// Sample-Rate (Hz)
+    sample_rate: u32,
+}
+
+impl DelayLine {
+    /// Neue Delay-Line ohne Verzögerung
+    pub fn new(sample_rate: u32) -> Self {
+        let frames = ms_to_frames(MAX_DELAY_MS, sample_rate) + 1;
+        Self {
+            buffer: vec![0.0; frames * 2],
+            write: 0,
+            delay: 0,
+            fade_from: Vec::with_capacity(MAX_FADE_SOURCES),
+            fade_len: ms_to_frames(DELAY_FADE_MS, sample_rate).max(1),
+            sample_rate,
+        }
+    }
+
+    /// Delay-Zeit setzen (wird bei Bedarf übergeblendet)
+    pub fn set_delay_ms(&mut self, delay_ms: f32) {
+        let delay = ms_to_frames(delay_ms.clamp(0.0, MAX_DELAY_MS), self.sample_rate);
+        if delay == self.delay {
+            return;
+        }
+        // Das bisherige Ziel blendet mit seinem aktuellen Gewicht aus; ältere
+        // Positionen laufen unverändert weiter. Nur bei sehr schnellen
+        // Änderungen (mehr als MAX_FADE_SOURCES pro Überblendung) entfällt die
+        // älteste — sie ist dann fast ausgeblendet.
+        let weight = self.target_weight();
+        if weight > 0.0 {
+            if self.fade_from.len() == MAX_FADE_SOURCES {
+                self.fade_from.remove(0);
+            }
+            self.fade_from.push(FadeSource {
+                delay: self.delay,
+                start_weight: weight,
+                remaining: self.fade_len,
+            });
+        }
+        self.delay = delay;
+    }
+
+    /// Aktuelle Delay-Zeit (ms)
+    pub fn delay_ms(&self) -> f32 {
+        self.delay as f32 * 1000.0 / self.sample_rate as f32
+    }
+
+    /// Block verzögern (Stereo interleaved, in-place)
+    pub fn process(&mut self, block: &mut [f32]) {
+        let frames = self.buffer.len() / 2;
+
+        for frame in block.chunks_exact_mut(2) {
+            self.buffer[self.write * 2] = frame[0];
+            self.buffer[self.write * 2 + 1] = frame[1];
+
+            let (l, r) = self.read(self.delay, frames);
+            if self.fade_from.is_empty() {
+                frame[0] = l;
+                frame[1] = r;
+            } else {
+                let target = self.target_weight();
+                frame[0] = l * target;
+                frame[1] = r * target;
+                for source in &self.fade_from {
+                    let weight = self.weight(source);
+                    let (old_l, old_r) = self.read(source.delay, frames);
+                    frame[0] += old_l * weight;
+                    frame[1] += old_r * weight;
+                }
+                for source in self.fade_from.iter_mut() {
+                    source.remaining -= 1;
+                }
+                self.fade_from.retain(|source| source.remaining > 0);
+            }
+
+            self.write = (self.write + 1) % frames;
+        }
+    }
+
+    /// Aktuelles Gewicht einer ausblendenden Position
+    fn weight(&self, source: &FadeSource) -> f32 {
+        source.start_weight * source.remaining as f32 / self.fade_len as f32
+    }
+
+    /// Aktuelles Gewicht der Ziel-Position (Rest zu 1)
+    fn target_weight(&self) -> f32 {
+        let faded: f32 = self.fade_from.iter().map(|s| self.weight(s)).sum();
+        (1.0 - faded).max(0.0)
+    }
+
+    /// Frame `delay` Frames vor der Schreib-Position lesen
+    fn read(&self, delay: usize, frames: usize) -> (f32, f32) {
+        let pos = (self.write + frames - delay) % frames;
+        (self.buffer[pos * 2], self.buffer[pos * 2 + 1])
+    }
+}
+
+/// Millisekunden in Frames umrechnen
+fn ms_to_frames(ms: f32, sample_rate: u32) -> usize {
+    (ms / 1000.0 * sample_rate as f32).round() as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Impuls im ersten Frame eines Blocks
+    fn impulse(frames: usize) -> Vec<f32> {
+        let mut block = vec![0.0; frames * 2];
+        block[0] = 1.0;
+        block[1] = 1.0;
+        block
+    }
+
+    #[test]
+    fn test_zero_delay_passthrough() {
+        let mut line = DelayLine::new(48000);
+        let mut block = vec![0.3; 512];
+        line.process(&mut block);
+        assert!(block.iter().all(|&s| s == 0.3));
+    }
+
+    #[test]
+    fn test_impulse_delayed() {
+        let mut line = DelayLine::new(48000);
+        line.set_delay_ms(10.0);
+        // Überblendung aus der Stille abwarten
+        let mut silence = vec![0.0; 48000 / 1000 * 40 * 2];
+        line.process(&mut silence);
+
+        let mut block = impulse(1024);
+        line.process(&mut block);
+        let peak = block.iter().position(|&s| s == 1.0).unwrap();
+        assert_eq!(peak / 2, 480);
+    }
+
+    #[test]
+    fn test_change_is_click_free() {
+        let mut line = DelayLine::new(48000);
+        // Stetiges Signal: der Sprung der Leseposition darf keinen Sprung
+        // im Ausgang erzeugen
+        let ramp: Vec<f32> = (0..48000)
+            .flat_map(|i| {
+                let s = (i as f32 * 0.001).sin();
+                [s, s]
+            })
+            .collect();
+        let mut out = Vec::new();
+        for (n, chunk) in ramp.chunks(512).enumerate() {
+            if n == 20 {
+                line.set_delay_ms(120.0);
+            }
+            let mut block = chunk.to_vec();
+            line.process(&mut block);
+            out.extend(block);
+        }
+        let max_step = out
+            .chunks_exact(2)
+            .zip(out.chunks_exact(2).skip(1))
+            .map(|(a, b)| (b[0] - a[0]).abs())
+            .fold(0.0, f32::max);
+        assert!(max_step < 0.01, "Sprung im Ausgang: {}", max_step);
+    }
+
+    #[test]
+    fn test_changes_within_fade_are_click_free() {
+        let mut line = DelayLine::new(48000);
+        // Slider-Drag: alle 64 Frames (≈ 1.3ms) eine neue Delay-Zeit, also
+        // viele Änderungen innerhalb eines Überblend-Fensters (20ms)
+        let signal: Vec<f32> = (0..48000)
+            .flat_map(|i| {
+                let s = (i as f32 * 0.001).sin();
+                [s, s]
+            })
+            .collect();
+        let mut out = Vec::new();
+        for (n, chunk) in signal.chunks(128).enumerate() {
+            if (100..160).contains(&n) {
+                line.set_delay_ms((n - 100) as f32 * 3.0);
+            }
+            let mut block = chunk.to_vec();
+            line.process(&mut block);
+            out.extend(block);
+        }
+        let max_step = out
+            .chunks_exact(2)
+            .zip(out.chunks_exact(2).skip(1))
+            .map(|(a, b)| (b[0] - a[0]).abs())
+            .fold(0.0, f32::max);
+        assert!(max_step < 0.01, "Sprung im Ausgang: {}", max_step);
+        // Nach dem Drag endet die Überblendung auf der letzten Position
+        assert!(line.fade_from.is_empty());
+        assert!((line.delay_ms() - 177.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_validate_delay() {
+        assert!(validate_delay_ms(0.0).is_ok());
+        assert!(validate_delay_ms(1000.0).is_ok());
+        assert!(validate_delay_ms(-1.0).is_err());
+        assert!(validate_delay_ms(1000.5).is_err());
+        assert!(validate_delay_ms(f32::NAN).is_err());
+    }
+}
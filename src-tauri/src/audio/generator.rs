@@ -0,0 +1,542 @@
+// Modul: audio/generator — Signal-Generator für Line-Checks (Sinus, Sweep, Rauschen)
+//
+// Der Generator bespielt einen Virtual-Strip: Fader, Mute, Bus-Routing und
+// Metering kommen vom Strip, der Generator liefert nur das Signal. Solange
+// er läuft, ist ein eventuell gebundener Capture-Stream des Strips pausiert
+// (audio/metering_service). Jeder Start braucht ein Timeout — ein vergessener
+// Testton stoppt von selbst.
+// Hörbar ist der Testton im PipeWire-Modus über den Bus-Ausgang der
+// Mix-Engine (siehe bus_output): die Engine mischt den Strip in die Busse und
+// spielt sie in die Bus-Nodes.
+
+use super::mixer::db_to_linear;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Frequenz des Referenztons (Hz)
+pub const REFERENCE_FREQ_HZ: f32 = 1000.0;
+/// Pegel des Referenztons (dBFS)
+pub const REFERENCE_LEVEL_DB: f32 = -18.0;
+/// Standard-Timeout bis zum automatischen Stopp (s)
+const DEFAULT_GENERATOR_TIMEOUT_S: u32 = 30;
+/// Maximales Timeout (s)
+const MAX_TIMEOUT_S: u32 = 600;
+/// Frequenzbereich (Hz)
+const MIN_FREQ_HZ: f32 = 20.0;
+const MAX_FREQ_HZ: f32 = 20000.0;
+/// Pegelbereich (dBFS)
+const MIN_LEVEL_DB: f32 = -60.0;
+const MAX_LEVEL_DB: f32 = 0.0;
+/// Sweep-Dauer (s)
+const MIN_SWEEP_S: f32 = 1.0;
+const MAX_SWEEP_S: f32 = 60.0;
+/// Maximal auf einmal nachgerechnete Zeit (ms) — wer länger hängt springt
+/// vor statt einen Block nachzuliefern
+const MAX_CATCH_UP_MS: u64 = 100;
+/// RMS des ungewichteten Pink-Filters bei gleichverteiltem Weißrauschen
+const PINK_RMS: f32 = 1.82;
+
+/// Testsignal
+///
+/// Pegel in dBFS: bei Sinus und Sweep der Spitzenwert, bei Rauschen der RMS.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GeneratorSignal {
+    /// Sinus mit fester Frequenz
+    Sine { freq_hz: f32, level_db: f32 },
+    /// Logarithmischer Sweep, wiederholt sich nach `duration_s`
+    Sweep {
+        start_hz: f32,
+        end_hz: f32,
+        duration_s: f32,
+        level_db: f32,
+    },
+    /// Rosa Rauschen (-3 dB/Oktave)
+    PinkNoise { level_db: f32 },
+    /// Weißes Rauschen
+    WhiteNoise { level_db: f32 },
+    /// Referenzton 1 kHz bei -18 dBFS
+    Reference,
+}
+
+impl GeneratorSignal {
+    /// Wertebereiche prüfen
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            Self::Sine { freq_hz, level_db } => {
+                validate_freq(freq_hz)?;
+                validate_level(level_db)
+            }
+            Self::Sweep {
+                start_hz,
+                end_hz,
+                duration_s,
+                level_db,
+            } => {
+                validate_freq(start_hz)?;
+                validate_freq(end_hz)?;
+                if !duration_s.is_finite() || !(MIN_SWEEP_S..=MAX_SWEEP_S).contains(&duration_s) {
+                    return Err(format!(
+                        "Sweep-Dauer außerhalb des Bereichs: {} s (erlaubt: {} bis {} s)",
+                        duration_s, MIN_SWEEP_S, MAX_SWEEP_S
+                    ));
+                }
+                validate_level(level_db)
+            }
+            Self::PinkNoise { level_db } | Self::WhiteNoise { level_db } => {
+                validate_level(level_db)
+            }
+            Self::Reference => Ok(()),
+        }
+    }
+}
+
+/// Frequenz prüfen
+fn validate_freq(freq_hz: f32) -> Result<(), String> {
+    if !freq_hz.is_finite() || !(MIN_FREQ_HZ..=MAX_FREQ_HZ).contains(&freq_hz) {
+        return Err(format!(
+            "Frequenz außerhalb des Bereichs: {} Hz (erlaubt: {} bis {} Hz)",
+            freq_hz, MIN_FREQ_HZ, MAX_FREQ_HZ
+        ));
+    }
+    Ok(())
+}
+
+/// Pegel prüfen
+fn validate_level(level_db: f32) -> Result<(), String> {
+    if !level_db.is_finite() || !(MIN_LEVEL_DB..=MAX_LEVEL_DB).contains(&level_db) {
+        return Err(format!(
+            "Pegel außerhalb des Bereichs: {} dBFS (erlaubt: {} bis {} dBFS)",
+            level_db, MIN_LEVEL_DB, MAX_LEVEL_DB
+        ));
+    }
+    Ok(())
+}
+
+/// Generator-Einstellungen für einen Start
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneratorSettings {
+    /// Testsignal
+    pub signal: GeneratorSignal,
+    /// Virtual-Strip der bespielt wird
+    pub target_strip: String,
+    /// Automatischer Stopp nach (s, 1 bis 600, fehlt = 30 s)
+    #[serde(default = "default_timeout_s")]
+    pub timeout_s: u32,
+}
+
+/// Standard-Timeout für Starts ohne Angabe
+fn default_timeout_s() -> u32 {
+    DEFAULT_GENERATOR_TIMEOUT_S
+}
+
+impl GeneratorSettings {
+    /// Wertebereiche prüfen (ob der Strip existiert prüft der Aufrufer)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.target_strip.trim().is_empty() {
+            return Err("Generator-Ziel-Strip darf nicht leer sein".to_string());
+        }
+        if !(1..=MAX_TIMEOUT_S).contains(&self.timeout_s) {
+            return Err(format!(
+                "Generator-Timeout außerhalb des Bereichs (1 bis {} s)",
+                MAX_TIMEOUT_S
+            ));
+        }
+        self.signal.validate()
+    }
+}
+
+/// Zustand für das Frontend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GeneratorStatus {
+    /// Generator läuft
+    pub running: bool,
+    /// Einstellungen des laufenden Generators
+    pub settings: Option<GeneratorSettings>,
+    /// Verbleibende Zeit bis zum Auto-Stopp (s)
+    pub remaining_s: u32,
+}
+
+/// Generator-Manager (Start/Stopp und Auto-Stopp, ohne Signalverarbeitung)
+#[derive(Debug, Default)]
+pub struct GeneratorManager {
+    /// Einstellungen des laufenden Generators
+    settings: Option<GeneratorSettings>,
+    /// Startzeitpunkt (für Auto-Stopp)
+    since: Option<Instant>,
+}
+
+impl GeneratorManager {
+    /// Neuer Manager (Generator aus)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generator starten (ersetzt einen laufenden, Timeout beginnt neu)
+    pub fn start(&mut self, settings: GeneratorSettings, now: Instant) -> Result<(), String> {
+        settings.validate()?;
+        self.settings = Some(settings);
+        self.since = Some(now);
+        Ok(())
+    }
+
+    /// Generator stoppen (false wenn er nicht lief)
+    pub fn stop(&mut self) -> bool {
+        self.since = None;
+        self.settings.take().is_some()
+    }
+
+    /// Einstellungen des laufenden Generators
+    pub fn active(&self) -> Option<&GeneratorSettings> {
+        self.settings.as_ref()
+    }
+
+    /// Verbleibende Laufzeit (None wenn aus)
+    fn remaining(&self, now: Instant) -> Option<Duration> {
+        let settings = self.settings.as_ref()?;
+        let elapsed = now.saturating_duration_since(self.since?);
+        Some(Duration::from_secs(settings.timeout_s as u64).saturating_sub(elapsed))
+    }
+
+    /// Ist das Timeout abgelaufen?
+    pub fn expired(&self, now: Instant) -> bool {
+        self.remaining(now).is_some_and(|r| r.is_zero())
+    }
+
+    /// Zustand für das Frontend
+    pub fn status(&self, now: Instant) -> GeneratorStatus {
+        let remaining = self.remaining(now);
+        GeneratorStatus {
+            running: remaining.is_some(),
+            settings: self.settings.clone(),
+            remaining_s: remaining.map_or(0, |r| r.as_secs_f32().ceil() as u32),
+        }
+    }
+}
+
+/// Sinus-Oszillator mit Phasen-Akkumulator
+#[derive(Debug, Clone)]
+struct SineOscillator {
+    /// Sample-Rate (Hz)
+    sample_rate: f32,
+    /// Phase (0..2π)
+    phase: f32,
+}
+
+impl SineOscillator {
+    /// Neuer Oszillator mit Phase 0
+    fn new(sample_rate: f32) -> Self {
+        Self {
+            sample_rate,
+            phase: 0.0,
+        }
+    }
+
+    /// Nächstes Sample (Amplitude 1) bei `freq_hz` — Frequenzwechsel bleiben
+    /// phasenstetig
+    fn next_sample(&mut self, freq_hz: f32) -> f32 {
+        let sample = self.phase.sin();
+        self.phase += 2.0 * std::f32::consts::PI * freq_hz / self.sample_rate;
+
+        // Phase normalisieren (0..2π)
+        if self.phase >= 2.0 * std::f32::consts::PI {
+            self.phase -= 2.0 * std::f32::consts::PI;
+        }
+        sample
+    }
+}
+
+/// Rosa-Rauschen-Filter (Paul Kellet, "refined") über Weißrauschen
+#[derive(Debug, Clone, Default)]
+struct PinkFilter {
+    b: [f32; 7],
+}
+
+impl PinkFilter {
+    /// Weißes Sample (-1..1) filtern
+    fn next_sample(&mut self, white: f32) -> f32 {
+        let b = &mut self.b;
+        b[0] = 0.99886 * b[0] + white * 0.0555179;
+        b[1] = 0.99332 * b[1] + white * 0.0750759;
+        b[2] = 0.96900 * b[2] + white * 0.153852;
+        b[3] = 0.86650 * b[3] + white * 0.3104856;
+        b[4] = 0.55000 * b[4] + white * 0.5329522;
+        b[5] = -0.7616 * b[5] - white * 0.0168980;
+        let pink = b.iter().sum::<f32>() + white * 0.5362;
+        b[6] = white * 0.115926;
+        pink
+    }
+}
+
+/// Signal-Erzeugung (läuft im Capture-Thread)
+#[derive(Debug, Clone)]
+pub struct SignalGenerator {
+    /// Testsignal
+    signal: GeneratorSignal,
+    /// Oszillator für Sinus, Sweep und Referenz
+    osc: SineOscillator,
+    /// Filter-Zustand für rosa Rauschen
+    pink: PinkFilter,
+    /// Seit dem Start erzeugte Frames (Sweep-Position, Takt)
+    frames: u64,
+    /// Sample-Rate der Mix-Engine (Hz)
+    sample_rate: u32,
+}
+
+impl SignalGenerator {
+    /// Neuer Generator für ein Signal in der Rate der Mix-Engine
+    pub fn new(signal: GeneratorSignal, sample_rate: u32) -> Self {
+        Self {
+            signal,
+            osc: SineOscillator::new(sample_rate as f32),
+            pink: PinkFilter::default(),
+            frames: 0,
+            sample_rate,
+        }
+    }
+
+    /// Sample-Rate des erzeugten Signals (Hz)
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Frames bis zur Laufzeit `elapsed` erzeugen (Stereo interleaved)
+    ///
+    /// `out` wird ersetzt; liegt der Aufrufer mehr als 100 ms zurück, wird
+    /// der Rückstand übersprungen.
+    pub fn render_until(&mut self, elapsed: Duration, out: &mut Vec<f32>) {
+        let due = (elapsed.as_secs_f64() * self.sample_rate as f64) as u64;
+        let max_catch_up = self.sample_rate as u64 * MAX_CATCH_UP_MS / 1000;
+        let behind = due.saturating_sub(self.frames);
+        if behind > max_catch_up {
+            self.frames = due - max_catch_up;
+        }
+        let frames = due.saturating_sub(self.frames) as usize;
+        out.clear();
+        out.resize(frames * 2, 0.0);
+        self.render(out);
+    }
+
+    /// Block füllen (Stereo interleaved, beide Kanäle gleich)
+    pub fn render(&mut self, out: &mut [f32]) {
+        use rand::Rng;
+        let mut rng = rand::thread_rng();
+
+        for frame in out.chunks_exact_mut(2) {
+            let sample = match self.signal {
+                GeneratorSignal::Sine { freq_hz, level_db } => {
+                    db_to_linear(level_db) * self.osc.next_sample(freq_hz)
+                }
+                GeneratorSignal::Sweep {
+                    start_hz,
+                    end_hz,
+                    duration_s,
+                    level_db,
+                } => {
+                    let t = self.frames as f32 / self.sample_rate as f32;
+                    let pos = (t % duration_s) / duration_s;
+                    let freq = start_hz * (end_hz / start_hz).powf(pos);
+                    db_to_linear(level_db) * self.osc.next_sample(freq)
+                }
+                GeneratorSignal::PinkNoise { level_db } => {
+                    let white: f32 = rng.gen_range(-1.0..=1.0);
+                    let pink = self.pink.next_sample(white) / PINK_RMS;
+                    (db_to_linear(level_db) * pink).clamp(-1.0, 1.0)
+                }
+                GeneratorSignal::WhiteNoise { level_db } => {
+                    // Gleichverteilt: RMS = Amplitude / √3
+                    let white: f32 = rng.gen_range(-1.0..=1.0);
+                    (db_to_linear(level_db) * 3.0_f32.sqrt() * white).clamp(-1.0, 1.0)
+                }
+                GeneratorSignal::Reference => {
+                    db_to_linear(REFERENCE_LEVEL_DB) * self.osc.next_sample(REFERENCE_FREQ_HZ)
+                }
+            };
+            frame[0] = sample;
+            frame[1] = sample;
+            self.frames += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Peak und RMS des linken Kanals
+    fn peak_rms(block: &[f32]) -> (f32, f32) {
+        let left: Vec<f32> = block.iter().step_by(2).copied().collect();
+        let peak = left.iter().fold(0.0_f32, |p, s| p.max(s.abs()));
+        let rms = (left.iter().map(|s| s * s).sum::<f32>() / left.len() as f32).sqrt();
+        (peak, rms)
+    }
+
+    /// Nulldurchgänge pro Sekunde / 2 ≈ Frequenz
+    fn zero_crossings(block: &[f32]) -> usize {
+        let left: Vec<f32> = block.iter().step_by(2).copied().collect();
+        left.windows(2)
+            .filter(|w| (w[0] < 0.0) != (w[1] < 0.0))
+            .count()
+    }
+
+    fn settings(signal: GeneratorSignal) -> GeneratorSettings {
+        GeneratorSettings {
+            signal,
+            target_strip: "virt-1".to_string(),
+            timeout_s: DEFAULT_GENERATOR_TIMEOUT_S,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(settings(GeneratorSignal::Reference).validate().is_ok());
+        let sine = |freq_hz, level_db| settings(GeneratorSignal::Sine { freq_hz, level_db });
+        assert!(sine(440.0, -12.0).validate().is_ok());
+        assert!(sine(10.0, -12.0).validate().is_err());
+        assert!(sine(440.0, 3.0).validate().is_err());
+        assert!(sine(f32::NAN, -12.0).validate().is_err());
+
+        let mut s = settings(GeneratorSignal::Reference);
+        s.timeout_s = 0;
+        assert!(s.validate().is_err());
+        s.timeout_s = 30;
+        s.target_strip = " ".to_string();
+        assert!(s.validate().is_err());
+    }
+
+    #[test]
+    fn test_reference_tone() {
+        let mut gen = SignalGenerator::new(GeneratorSignal::Reference, 48000);
+        let mut block = vec![0.0; 48000 * 2];
+        gen.render(&mut block);
+
+        let (peak, _) = peak_rms(&block);
+        assert!((peak - db_to_linear(-18.0)).abs() < 0.001);
+        // 1 kHz: 2000 Nulldurchgänge pro Sekunde
+        assert!((zero_crossings(&block) as i32 - 2000).abs() <= 2);
+        // Beide Kanäle identisch
+        assert!(block.chunks_exact(2).all(|f| f[0] == f[1]));
+    }
+
+    #[test]
+    fn test_reference_tone_at_engine_rate() {
+        // 44.1 kHz: eine Sekunde sind 44100 Frames, der Ton bleibt 1 kHz
+        let mut gen = SignalGenerator::new(GeneratorSignal::Reference, 44100);
+        let mut out = Vec::new();
+        gen.render_until(Duration::from_secs(1), &mut out);
+        assert_eq!(out.len(), 4410 * 2);
+        let mut block = vec![0.0; 44100 * 2];
+        gen.render(&mut block);
+        assert!((zero_crossings(&block) as i32 - 2000).abs() <= 2);
+    }
+
+    #[test]
+    fn test_sweep_rises_and_repeats() {
+        let mut gen = SignalGenerator::new(
+            GeneratorSignal::Sweep {
+                start_hz: 100.0,
+                end_hz: 10000.0,
+                duration_s: 1.0,
+                level_db: -6.0,
+            },
+            48000,
+        );
+        let mut block = vec![0.0; 4800 * 2];
+        gen.render(&mut block);
+        let low = zero_crossings(&block);
+        // Ans Ende des ersten Durchlaufs springen
+        let mut skip = vec![0.0; 38400 * 2];
+        gen.render(&mut skip);
+        gen.render(&mut block);
+        let high = zero_crossings(&block);
+        assert!(high > low * 10, "Sweep steigt nicht: {} → {}", low, high);
+        // Nächster Durchlauf beginnt wieder unten
+        gen.render(&mut block);
+        assert!(zero_crossings(&block) < low * 2);
+    }
+
+    #[test]
+    fn test_noise_levels() {
+        let mut block = vec![0.0; 96000 * 2];
+        for signal in [
+            GeneratorSignal::WhiteNoise { level_db: -20.0 },
+            GeneratorSignal::PinkNoise { level_db: -20.0 },
+        ] {
+            let mut gen = SignalGenerator::new(signal.clone(), 48000);
+            gen.render(&mut block);
+            let (_, rms) = peak_rms(&block);
+            let rms_db = 20.0 * rms.log10();
+            assert!((rms_db + 20.0).abs() < 1.0, "{:?}: {} dB", signal, rms_db);
+        }
+    }
+
+    #[test]
+    fn test_render_until_follows_clock() {
+        let mut gen = SignalGenerator::new(GeneratorSignal::Reference, 48000);
+        let mut out = Vec::new();
+        gen.render_until(Duration::from_millis(10), &mut out);
+        assert_eq!(out.len(), 480 * 2);
+        gen.render_until(Duration::from_millis(15), &mut out);
+        assert_eq!(out.len(), 240 * 2);
+
+        // Langer Hänger: höchstens 100 ms nachliefern
+        gen.render_until(Duration::from_secs(5), &mut out);
+        assert_eq!(out.len(), 4800 * 2);
+    }
+
+    #[test]
+    fn test_manager_auto_stop() {
+        let mut manager = GeneratorManager::new();
+        let now = Instant::now();
+        assert!(!manager.status(now).running);
+
+        let mut s = settings(GeneratorSignal::Reference);
+        s.timeout_s = 10;
+        manager.start(s, now).unwrap();
+        let status = manager.status(now + Duration::from_millis(2500));
+        assert!(status.running);
+        assert_eq!(status.remaining_s, 8);
+        assert!(!manager.expired(now + Duration::from_secs(9)));
+        assert!(manager.expired(now + Duration::from_secs(10)));
+
+        assert!(manager.stop());
+        assert!(!manager.stop());
+        assert!(!manager.expired(now + Duration::from_secs(20)));
+        assert!(manager.active().is_none());
+    }
+
+    #[test]
+    fn test_invalid_start_keeps_state() {
+        let mut manager = GeneratorManager::new();
+        let now = Instant::now();
+        manager
+            .start(settings(GeneratorSignal::Reference), now)
+            .unwrap();
+        let invalid = settings(GeneratorSignal::WhiteNoise { level_db: 6.0 });
+        assert!(manager.start(invalid, now).is_err());
+        assert_eq!(
+            manager.active().map(|s| &s.signal),
+            Some(&GeneratorSignal::Reference)
+        );
+    }
+
+    #[test]
+    fn test_signal_serde() {
+        let json = r#"{"type":"sine","freq_hz":440.0,"level_db":-12.0}"#;
+        let signal: GeneratorSignal = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            signal,
+            GeneratorSignal::Sine {
+                freq_hz: 440.0,
+                level_db: -12.0
+            }
+        );
+        let json = serde_json::to_string(&GeneratorSignal::Reference).unwrap();
+        assert_eq!(json, r#"{"type":"reference"}"#);
+
+        // Ohne Timeout-Angabe greift der Standard
+        let json = r#"{"signal":{"type":"pink_noise","level_db":-20.0},"target_strip":"virt-1"}"#;
+        let settings: GeneratorSettings = serde_json::from_str(json).unwrap();
+        assert_eq!(settings.timeout_s, DEFAULT_GENERATOR_TIMEOUT_S);
+    }
+}
@@ -1,12 +1,86 @@
 // Modul: master — Master-Sektion (Master Volume, Limiter, DIM, MONO, Talkback)
 //
 // Verwaltet die Master-Ausgangsstufe mit globalem Volume, Limiter und Spezialfunktionen
+// Die Signalverarbeitung dazu liegt in audio/master_stage (Mix-Engine).
+// Talkback speist einen Quell-Strip in die Ziel-Busse ein und dimmt dort den
+// restlichen Mix (Auto-DIM). Die Taste arbeitet rastend (Latch) oder nur
+// solange gedrückt (Momentary); optional löst sie nach einer Zeit selbst aus.
 // SPEC: 12-master
 
+use crate::audio::bus::DEFAULT_BUSES;
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Absenkung bei aktivem DIM (dB)
+pub const DIM_DB: f32 = -20.0;
+/// Standard-Quelle für Talkback (Haupt-Mikrofon)
+pub const DEFAULT_TALKBACK_SOURCE: &str = "hw-mic";
+/// Standard-Absenkung der Ziel-Busse während Talkback (dB)
+pub const DEFAULT_TALKBACK_DIM_DB: f32 = -12.0;
+/// Stärkste Absenkung der Ziel-Busse während Talkback (dB)
+const MIN_TALKBACK_DIM_DB: f32 = -40.0;
+/// Maximale Zeit bis zum automatischen Lösen (s)
+const MAX_AUTO_RELEASE_S: u32 = 600;
+
+/// Schaltverhalten der Talkback-Taste
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TalkbackMode {
+    /// Jeder Druck schaltet um
+    #[default]
+    Latch,
+    /// Aktiv solange gedrückt
+    Momentary,
+}
+
+/// Talkback-Einstellungen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TalkbackSettings {
+    /// Schaltverhalten der Taste
+    pub mode: TalkbackMode,
+    /// Strip der eingespeist wird (vor Fader und Mute)
+    pub source_strip: String,
+    /// Absenkung des restlichen Mix auf den Ziel-Bussen (dB, -40 bis 0; 0 = aus)
+    pub dim_db: f32,
+    /// Automatisch lösen nach (s, 0 = nie)
+    pub auto_release_s: u32,
+}
+
+impl Default for TalkbackSettings {
+    fn default() -> Self {
+        Self {
+            mode: TalkbackMode::Latch,
+            source_strip: DEFAULT_TALKBACK_SOURCE.to_string(),
+            dim_db: DEFAULT_TALKBACK_DIM_DB,
+            auto_release_s: 0,
+        }
+    }
+}
+
+impl TalkbackSettings {
+    /// Wertebereiche prüfen
+    pub fn validate(&self) -> Result<(), String> {
+        if self.source_strip.trim().is_empty() {
+            return Err("Talkback-Quelle darf nicht leer sein".to_string());
+        }
+        if !self.dim_db.is_finite() || !(MIN_TALKBACK_DIM_DB..=0.0).contains(&self.dim_db) {
+            return Err(format!(
+                "Talkback-DIM außerhalb des Bereichs: {} dB (erlaubt: {} bis 0 dB)",
+                self.dim_db, MIN_TALKBACK_DIM_DB
+            ));
+        }
+        if self.auto_release_s > MAX_AUTO_RELEASE_S {
+            return Err(format!(
+                "Talkback-Auto-Release außerhalb des Bereichs (0 bis {} s)",
+                MAX_AUTO_RELEASE_S
+            ));
+        }
+        Ok(())
+    }
+}
 
 /// Master-Sektion Zustand
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct MasterState {
     /// Master Volume in dB (-∞ bis +12 dB, Standard: 0 dB)
     pub volume_db: f32,
@@ -20,6 +94,9 @@ pub struct MasterState {
     pub talkback: bool,
     /// Talkback Ziel-Busse (z.B. ["A1", "B1"])
     pub talkback_buses: Vec<String>,
+    /// Talkback-Modus, Quelle, Auto-DIM und Auto-Release (ältere Sessions: Standard)
+    #[serde(default)]
+    pub talkback_settings: TalkbackSettings,
 }
 
 impl Default for MasterState {
@@ -31,6 +108,7 @@ impl Default for MasterState {
             mono: false,
             talkback: false,
             talkback_buses: vec![],
+            talkback_settings: TalkbackSettings::default(),
         }
     }
 }
@@ -38,6 +116,10 @@ impl Default for MasterState {
 /// Master-Manager verwaltet die Master-Sektion
 pub struct MasterManager {
     state: MasterState,
+    /// Bekannte Bus-IDs (Abbild der Bus-Registry, für Talkback-Ziele)
+    bus_ids: Vec<String>,
+    /// Zeitpunkt des Einschaltens (für Auto-Release)
+    talkback_since: Option<Instant>,
 }
 
 impl MasterManager {
@@ -46,9 +128,41 @@ impl MasterManager {
         log::info!("MasterManager::new() — Erstelle Master-Sektion");
         Self {
             state: MasterState::default(),
+            bus_ids: DEFAULT_BUSES
+                .iter()
+                .map(|(id, _, _)| id.to_string())
+                .collect(),
+            talkback_since: None,
         }
     }
 
+    /// Master-Sektion aus gespeichertem State wiederherstellen (Session/Scene)
+    ///
+    /// Läuft über die Setter, damit dieselben Bereichsprüfungen greifen;
+    /// Talkback-Ziele werden gegen `bus_ids` geprüft.
+    pub fn from_state(state: MasterState, bus_ids: &[String]) -> Result<Self, String> {
+        if !state.volume_db.is_finite() || !state.limiter_ceiling_db.is_finite() {
+            return Err("Master-State enthält ungültige Werte".to_string());
+        }
+        let mut manager = Self::new();
+        manager.bus_ids = bus_ids.to_vec();
+        manager.set_volume(state.volume_db)?;
+        manager.set_limiter(state.limiter_ceiling_db)?;
+        manager.set_dim(state.dim)?;
+        manager.set_mono(state.mono)?;
+        manager.set_talkback_settings(state.talkback_settings)?;
+        manager.set_talkback(state.talkback, state.talkback_buses)?;
+        Ok(manager)
+    }
+
+    /// Bekannte Busse aktualisieren (nach Anlegen/Löschen in der Bus-Registry)
+    ///
+    /// Talkback-Ziele auf entfernte Busse fallen weg.
+    pub fn set_bus_ids(&mut self, bus_ids: &[String]) {
+        self.bus_ids = bus_ids.to_vec();
+        self.state.talkback_buses.retain(|id| bus_ids.contains(id));
+    }
+
     /// Master-State abrufen
     pub fn get_state(&self) -> MasterState {
         self.state.clone()
@@ -116,13 +230,18 @@ impl MasterManager {
     /// * `active` - Talkback aktiv (true) oder inaktiv (false)
     /// * `target_buses` - Ziel-Busse für Talkback (z.B. ["A1", "B1"])
     pub fn set_talkback(&mut self, active: bool, target_buses: Vec<String>) -> Result<(), String> {
-        // Validierung: Bus-IDs müssen A1, A2, B1 oder B2 sein
+        // Validierung: Ziel-Busse müssen in der Registry existieren
         for bus_id in &target_buses {
-            if !["A1", "A2", "B1", "B2"].contains(&bus_id.as_str()) {
+            if !self.bus_ids.contains(bus_id) {
                 return Err(format!("Ungültige Bus-ID für Talkback: {}", bus_id));
             }
         }
 
+        if active && !self.state.talkback {
+            self.talkback_since = Some(Instant::now());
+        } else if !active {
+            self.talkback_since = None;
+        }
         self.state.talkback = active;
         self.state.talkback_buses = target_buses.clone();
         log::info!(
@@ -133,12 +252,56 @@ impl MasterManager {
         Ok(())
     }
 
+    /// Talkback-Einstellungen setzen (Modus, Quelle, Auto-DIM, Auto-Release)
+    ///
+    /// Ob der Quell-Strip existiert, prüft der Aufrufer (Mixer).
+    pub fn set_talkback_settings(&mut self, settings: TalkbackSettings) -> Result<(), String> {
+        settings.validate()?;
+        log::info!(
+            "Talkback: {:?}, Quelle {}, DIM {} dB, Auto-Release {} s",
+            settings.mode,
+            settings.source_strip,
+            settings.dim_db,
+            settings.auto_release_s
+        );
+        self.state.talkback_settings = settings;
+        Ok(())
+    }
+
+    /// Neuer Talkback-Zustand beim Drücken der Taste (None = unverändert)
+    pub fn talkback_on_press(&self) -> Option<bool> {
+        match self.state.talkback_settings.mode {
+            TalkbackMode::Latch => Some(!self.state.talkback),
+            TalkbackMode::Momentary if !self.state.talkback => Some(true),
+            TalkbackMode::Momentary => None,
+        }
+    }
+
+    /// Neuer Talkback-Zustand beim Loslassen der Taste (None = unverändert)
+    pub fn talkback_on_release(&self) -> Option<bool> {
+        match self.state.talkback_settings.mode {
+            TalkbackMode::Momentary if self.state.talkback => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Ist die Auto-Release-Zeit abgelaufen?
+    pub fn talkback_expired(&self, now: Instant) -> bool {
+        let timeout = self.state.talkback_settings.auto_release_s;
+        match self.talkback_since {
+            Some(since) if self.state.talkback && timeout > 0 => {
+                now.saturating_duration_since(since) >= Duration::from_secs(timeout as u64)
+            }
+            _ => false,
+        }
+    }
+
     /// Effektive Master-Lautstärke berechnen (mit DIM)
     ///
     /// Gibt die tatsächliche Lautstärke zurück unter Berücksichtigung von DIM
     pub fn get_effective_volume_db(&self) -> f32 {
         if self.state.dim {
-            self.state.volume_db - 20.0 // DIM: -20 dB
+            self.state.volume_db + DIM_DB
         } else {
             self.state.volume_db
         }
@@ -312,6 +475,7 @@ mod tests {
             mono: false,
             talkback: true,
             talkback_buses: vec!["A1".to_string(), "B1".to_string()],
+            talkback_settings: TalkbackSettings::default(),
         };
 
         let json = serde_json::to_string(&state);
@@ -322,4 +486,116 @@ mod tests {
         assert!(json_str.contains("true"));
         assert!(json_str.contains("A1"));
     }
+
+    #[test]
+    fn test_from_state() {
+        let state = MasterState {
+            volume_db: -6.0,
+            mono: true,
+            talkback_buses: vec!["B1".to_string()],
+            ..MasterState::default()
+        };
+        let manager = MasterManager::from_state(state, &["B1".to_string()]).unwrap();
+        assert_eq!(manager.get_state().volume_db, -6.0);
+        assert!(manager.get_state().mono);
+
+        let invalid = MasterState {
+            volume_db: f32::NAN,
+            ..MasterState::default()
+        };
+        assert!(MasterManager::from_state(invalid, &[]).is_err());
+
+        // Talkback-Ziel ohne Bus in der Registry
+        let unknown = MasterState {
+            talkback_buses: vec!["B3".to_string()],
+            ..MasterState::default()
+        };
+        assert!(MasterManager::from_state(unknown, &["B1".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_talkback_press_release() {
+        let mut manager = MasterManager::new();
+        // Latch: jeder Druck schaltet um, Loslassen ändert nichts
+        assert_eq!(manager.talkback_on_press(), Some(true));
+        manager.set_talkback(true, vec!["A2".to_string()]).unwrap();
+        assert_eq!(manager.talkback_on_release(), None);
+        assert_eq!(manager.talkback_on_press(), Some(false));
+
+        manager
+            .set_talkback_settings(TalkbackSettings {
+                mode: TalkbackMode::Momentary,
+                ..TalkbackSettings::default()
+            })
+            .unwrap();
+        assert_eq!(manager.talkback_on_press(), None);
+        assert_eq!(manager.talkback_on_release(), Some(false));
+        manager.set_talkback(false, vec!["A2".to_string()]).unwrap();
+        assert_eq!(manager.talkback_on_press(), Some(true));
+        assert_eq!(manager.talkback_on_release(), None);
+    }
+
+    #[test]
+    fn test_talkback_auto_release() {
+        let mut manager = MasterManager::new();
+        manager
+            .set_talkback_settings(TalkbackSettings {
+                auto_release_s: 30,
+                ..TalkbackSettings::default()
+            })
+            .unwrap();
+        manager.set_talkback(true, vec!["A2".to_string()]).unwrap();
+        let now = Instant::now();
+        assert!(!manager.talkback_expired(now));
+        assert!(manager.talkback_expired(now + Duration::from_secs(31)));
+
+        // Ziel-Busse ändern startet die Zeit nicht neu, Ausschalten beendet sie
+        manager.set_talkback(true, vec!["A1".to_string()]).unwrap();
+        assert!(manager.talkback_expired(now + Duration::from_secs(31)));
+        manager.set_talkback(false, vec![]).unwrap();
+        assert!(!manager.talkback_expired(now + Duration::from_secs(31)));
+    }
+
+    #[test]
+    fn test_talkback_settings_invalid() {
+        let mut manager = MasterManager::new();
+        let invalid = [
+            TalkbackSettings {
+                dim_db: -50.0,
+                ..TalkbackSettings::default()
+            },
+            TalkbackSettings {
+                dim_db: 3.0,
+                ..TalkbackSettings::default()
+            },
+            TalkbackSettings {
+                source_strip: " ".to_string(),
+                ..TalkbackSettings::default()
+            },
+            TalkbackSettings {
+                auto_release_s: 3600,
+                ..TalkbackSettings::default()
+            },
+        ];
+        for settings in invalid {
+            assert!(manager.set_talkback_settings(settings).is_err());
+        }
+        assert_eq!(
+            manager.get_state().talkback_settings,
+            TalkbackSettings::default()
+        );
+    }
+
+    #[test]
+    fn test_set_bus_ids() {
+        let mut manager = MasterManager::new();
+        manager.set_bus_ids(&["A1".to_string(), "B3".to_string()]);
+        manager
+            .set_talkback(true, vec!["A1".to_string(), "B3".to_string()])
+            .unwrap();
+
+        manager.set_bus_ids(&["A1".to_string()]);
+        assert_eq!(manager.get_state().talkback_buses, vec!["A1".to_string()]);
+        assert!(manager.set_talkback(true, vec!["B3".to_string()]).is_err());
+    }
 }
@@ -0,0 +1,310 @@
+// Modul: audio/master_stage — Master-Stufe pro Bus-Ausgang
+//
+// Läuft in der Mix-Engine am Ende jedes Bus-Pfads (nach Fader und Delay,
+// nach dem Abgriff für Submixe — ein Submix durchläuft die Master-Stufe nur
+// einmal, im Ziel-Bus):
+// Master-Volume inkl. DIM → Talkback-Einspeisung → MONO → Limiter
+// Talkback liegt hinter DIM, damit die Ansage auch bei gedimmtem Ausgang
+// verständlich bleibt; der Limiter steht zuletzt und fängt sie mit ab.
+// Auf Talkback-Ziel-Bussen wird der restliche Mix zusätzlich um die
+// Talkback-Absenkung gedimmt (Auto-DIM, gleiche Rampe wie Master/DIM).
+// Jeder Bus hat einen eigenen Limiter (LimiterModule mit Look-Ahead) in der
+// Sample-Rate der Engine.
+// Hörbar ist die Stufe nur auf Bussen, deren Ausgang die Mix-Engine in den
+// Bus-Node spielt (PipeWire-Modus, siehe bus_output); sonst wirkt sie nur auf
+// die Monitor-Taps der Engine.
+
+use super::master::{MasterState, DIM_DB};
+use super::mixer::db_to_linear;
+use crate::fx::limiter::LimiterModule;
+use crate::fx::AudioProcessor;
+use log::warn;
+use std::collections::HashMap;
+
+/// Laufzeit-Zustand der Master-Stufe eines Busses
+struct BusMaster {
+    /// Brickwall-Limiter des Busses
+    limiter: LimiterModule,
+    /// Zuletzt angewendeter Master-Faktor (Start der nächsten Rampe)
+    gain: f32,
+}
+
+/// Master-Stufe für die Bus-Ausgänge der Mix-Engine
+pub struct MasterStage {
+    /// Master-Faktor inkl. DIM (linear)
+    gain: f32,
+    /// DIM aktiv
+    dim: bool,
+    /// Mono-Summe aktiv
+    mono: bool,
+    /// Limiter-Ceiling (dB)
+    ceiling_db: f32,
+    /// Talkback aktiv
+    talkback: bool,
+    /// Ziel-Busse für Talkback
+    talkback_buses: Vec<String>,
+    /// Strip der bei Talkback eingespeist wird
+    talkback_source: String,
+    /// Auto-DIM der Ziel-Busse während Talkback (linear)
+    talkback_dim: f32,
+    /// Zustand pro Bus (Key: Bus-ID)
+    buses: HashMap<String, BusMaster>,
+    /// Sample-Rate der Limiter (Hz)
+    sample_rate: u32,
+    /// Arbeits-Puffer für den Limiter (deinterleaved)
+    buf_l: Vec<f32>,
+    buf_r: Vec<f32>,
+}
+
+impl MasterStage {
+    /// Neue Master-Stufe aus dem Master-State in der Rate der Engine
+    pub fn new(state: &MasterState, sample_rate: u32) -> Self {
+        let mut stage = Self {
+            gain: 1.0,
+            dim: false,
+            mono: false,
+            ceiling_db: state.limiter_ceiling_db,
+            talkback: false,
+            talkback_buses: Vec::new(),
+            talkback_source: String::new(),
+            talkback_dim: 1.0,
+            buses: HashMap::new(),
+            sample_rate,
+            buf_l: Vec::new(),
+            buf_r: Vec::new(),
+        };
+        stage.update(state);
+        stage
+    }
+
+    /// Master-State übernehmen (Limiter behalten ihre Hüllkurven)
+    pub fn update(&mut self, state: &MasterState) {
+        self.dim = state.dim;
+        self.set_volume(state.volume_db);
+        self.mono = state.mono;
+        self.talkback = state.talkback;
+        self.talkback_buses = state.talkback_buses.clone();
+        self.talkback_source = state.talkback_settings.source_strip.clone();
+        self.talkback_dim = db_to_linear(state.talkback_settings.dim_db);
+        if self.ceiling_db != state.limiter_ceiling_db {
+            self.ceiling_db = state.limiter_ceiling_db;
+            for (bus_id, bus) in self.buses.iter_mut() {
+                if let Err(e) = bus.limiter.set_ceiling(self.ceiling_db) {
+                    warn!("Bus {}: Master-Limiter nicht gesetzt: {}", bus_id, e);
+                }
+            }
+        }
+    }
+
+    /// Nur die Master-Lautstärke setzen (DIM bleibt erhalten)
+    pub fn set_volume(&mut self, volume_db: f32) {
+        let dim_db = if self.dim { DIM_DB } else { 0.0 };
+        self.gain = db_to_linear(volume_db + dim_db);
+    }
+
+    /// Sample-Rate wechseln — die Limiter entstehen beim nächsten Block neu
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        if sample_rate != self.sample_rate {
+            self.sample_rate = sample_rate;
+            self.buses.clear();
+        }
+    }
+
+    /// Zustand entfernter Busse verwerfen
+    pub fn retain_buses(&mut self, keep: impl Fn(&str) -> bool) {
+        self.buses.retain(|id, _| keep(id));
+    }
+
+    /// Bekommt der Bus gerade Talkback?
+    pub fn talkback_to(&self, bus_id: &str) -> bool {
+        self.talkback && self.talkback_buses.iter().any(|id| id == bus_id)
+    }
+
+    /// Strip der gerade eingespeist wird (None wenn Talkback aus)
+    pub fn talkback_source(&self) -> Option<&str> {
+        if self.talkback {
+            Some(&self.talkback_source)
+        } else {
+            None
+        }
+    }
+
+    /// Bus-Block bearbeiten (Stereo interleaved, in-place)
+    ///
+    /// `talkback` ist der Block der Talkback-Quelle (gleiche Länge).
+    pub fn process(&mut self, bus_id: &str, block: &mut [f32], talkback: &[f32]) {
+        let inject = self.talkback_to(bus_id);
+        let gain = if inject {
+            self.gain * self.talkback_dim
+        } else {
+            self.gain
+        };
+        let ceiling_db = self.ceiling_db;
+        // Zustand nur beim ersten Block eines Busses anlegen (keine
+        // String-Allokation pro Block)
+        if !self.buses.contains_key(bus_id) {
+            let mut limiter = LimiterModule::new(self.sample_rate as f32);
+            if let Err(e) = limiter.set_ceiling(ceiling_db) {
+                warn!("Bus {}: Master-Limiter nicht gesetzt: {}", bus_id, e);
+            }
+            self.buses
+                .insert(bus_id.to_string(), BusMaster { limiter, gain });
+        }
+        let bus = match self.buses.get_mut(bus_id) {
+            Some(bus) => bus,
+            None => return,
+        };
+
+        // Master-Volume/DIM/Auto-DIM als Rampe über den Block (klickfrei)
+        let frames = block.len() / 2;
+        let step = (gain - bus.gain) / frames.max(1) as f32;
+        for (i, frame) in block.chunks_exact_mut(2).enumerate() {
+            let g = bus.gain + step * (i + 1) as f32;
+            frame[0] *= g;
+            frame[1] *= g;
+        }
+        bus.gain = gain;
+
+        if inject {
+            block.iter_mut().zip(talkback).for_each(|(s, t)| *s += t);
+        }
+
+        if self.mono {
+            for frame in block.chunks_exact_mut(2) {
+                let sum = (frame[0] + frame[1]) * 0.5;
+                frame[0] = sum;
+                frame[1] = sum;
+            }
+        }
+
+        self.buf_l.resize(frames, 0.0);
+        self.buf_r.resize(frames, 0.0);
+        for (i, frame) in block.chunks_exact(2).enumerate() {
+            self.buf_l[i] = frame[0];
+            self.buf_r[i] = frame[1];
+        }
+        bus.limiter.process(&mut self.buf_l, &mut self.buf_r);
+        for (i, frame) in block.chunks_exact_mut(2).enumerate() {
+            frame[0] = self.buf_l[i];
+            frame[1] = self.buf_r[i];
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mehrere Blöcke verarbeiten und den letzten zurückgeben
+    /// (der Limiter-Look-Ahead verzögert um 5 ms)
+    fn run(stage: &mut MasterStage, bus_id: &str, l: f32, r: f32, talkback: f32) -> Vec<f32> {
+        let frames = 256;
+        let mut block = Vec::new();
+        for _ in 0..8 {
+            block = [l, r].repeat(frames);
+            stage.process(bus_id, &mut block, &vec![talkback; frames * 2]);
+        }
+        block
+    }
+
+    /// Frames bis zum ersten Sample nach einem Sprung aus der Stille
+    fn look_ahead(stage: &mut MasterStage) -> usize {
+        let frames = 2048;
+        let mut block = [0.5, 0.5].repeat(frames);
+        stage.process("A1", &mut block, &vec![0.0; frames * 2]);
+        block.iter().step_by(2).take_while(|&&s| s == 0.0).count()
+    }
+
+    #[test]
+    fn test_limiter_follows_sample_rate() {
+        let state = MasterState::default();
+        let at_48k = look_ahead(&mut MasterStage::new(&state, 48000));
+        assert!(at_48k > 0);
+
+        // Gleiche Look-Ahead-Zeit bei doppelter Rate: doppelt so viele Frames
+        let mut stage = MasterStage::new(&state, 48000);
+        stage.set_sample_rate(96000);
+        assert_eq!(look_ahead(&mut stage), 2 * at_48k);
+    }
+
+    #[test]
+    fn test_dim_attenuates() {
+        let mut state = MasterState::default();
+        let mut stage = MasterStage::new(&state, 48000);
+        assert!((run(&mut stage, "A1", 0.5, 0.5, 0.0)[0] - 0.5).abs() < 0.001);
+
+        state.dim = true;
+        stage.update(&state);
+        let out = run(&mut stage, "A1", 0.5, 0.5, 0.0);
+        assert!((out[0] - 0.05).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_mono_sum() {
+        let state = MasterState {
+            mono: true,
+            ..MasterState::default()
+        };
+        let mut stage = MasterStage::new(&state, 48000);
+        let out = run(&mut stage, "A1", 0.6, 0.0, 0.0);
+        assert!((out[0] - 0.3).abs() < 0.001);
+        assert!((out[1] - 0.3).abs() < 0.001);
+
+        // Gegenphasiges Signal löscht sich in Mono aus
+        let out = run(&mut stage, "A1", 0.5, -0.5, 0.0);
+        assert!(out.iter().all(|s| s.abs() < 0.001));
+    }
+
+    #[test]
+    fn test_limiter_holds_ceiling() {
+        let state = MasterState {
+            volume_db: 12.0,
+            limiter_ceiling_db: -6.0,
+            ..MasterState::default()
+        };
+        let mut stage = MasterStage::new(&state, 48000);
+        let out = run(&mut stage, "A1", 0.5, 0.5, 0.0);
+        let ceiling = db_to_linear(-6.0);
+        assert!(out.iter().all(|s| s.abs() <= ceiling + 1e-3));
+    }
+
+    #[test]
+    fn test_talkback_only_on_targets_and_not_dimmed() {
+        let state = MasterState {
+            dim: true,
+            talkback: true,
+            talkback_buses: vec!["A2".to_string()],
+            ..MasterState::default()
+        };
+        let mut stage = MasterStage::new(&state, 48000);
+        let a2 = run(&mut stage, "A2", 0.0, 0.0, 0.25);
+        assert!((a2[0] - 0.25).abs() < 0.001);
+        let a1 = run(&mut stage, "A1", 0.0, 0.0, 0.25);
+        assert_eq!(a1[0], 0.0);
+    }
+
+    #[test]
+    fn test_talkback_auto_dim() {
+        let mut state = MasterState {
+            talkback: true,
+            talkback_buses: vec!["A2".to_string()],
+            ..MasterState::default()
+        };
+        state.talkback_settings.dim_db = -20.0;
+        let mut stage = MasterStage::new(&state, 48000);
+        assert_eq!(stage.talkback_source(), Some("hw-mic"));
+
+        // Restlicher Mix auf A2 um 20 dB gesenkt, A1 unverändert
+        let a2 = run(&mut stage, "A2", 0.5, 0.5, 0.25);
+        assert!((a2[0] - (0.05 + 0.25)).abs() < 0.001);
+        let a1 = run(&mut stage, "A1", 0.5, 0.5, 0.25);
+        assert!((a1[0] - 0.5).abs() < 0.001);
+
+        // Talkback aus: A2 wieder auf vollem Pegel
+        state.talkback = false;
+        stage.update(&state);
+        assert_eq!(stage.talkback_source(), None);
+        let a2 = run(&mut stage, "A2", 0.5, 0.5, 0.25);
+        assert!((a2[0] - 0.5).abs() < 0.001);
+    }
+}
@@ -1,4 +1,10 @@
 // Modul: audio/metering — VU-Meter, Peak- und RMS-Berechnung
+//
+// Neben Peak/RMS werden für Stereo-Signale Korrelation, Stereo-Breite und
+// Balance gemessen (gleitend gemittelt über die Metering-Ticks). Bus-Meter
+// sehen das Signal hinter der Master-Stufe, zeigen also den Ausgang inkl.
+// MONO/Limiter — Korrelation nahe -1 auf B1 heißt: in Mono löscht sich der
+// Stream aus.
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -8,6 +14,10 @@ const MIN_DB: f32 = -60.0;
 const PEAK_HOLD_FRAMES: u32 = 90;
 /// Peak-Fallrate pro Frame in dB
 const PEAK_FALL_RATE: f32 = 0.5;
+/// Glättung der Stereo-Messung pro Block (Anteil des alten Werts)
+const STEREO_SMOOTHING: f32 = 0.8;
+/// Korrelation ab der die UI vor Mono-Auslöschung warnt
+pub const PHASE_WARN_CORRELATION: f32 = -0.3;
 
 /// Stereo-Messwerte für einen Strip
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -24,6 +34,14 @@ pub struct StripLevels {
     pub rms_r: f32,
     /// Clipping erkannt
     pub clipping: bool,
+    /// Phasen-Korrelation L/R (-1 gegenphasig, 0 unkorreliert, +1 mono)
+    pub correlation: f32,
+    /// Stereo-Breite (0 mono, 0.5 unkorreliert, 1 nur Seitensignal)
+    pub width: f32,
+    /// Balance (-1 nur links, 0 mittig, +1 nur rechts)
+    pub balance: f32,
+    /// Korrelation unter der Warnschwelle (Mono-Auslöschung droht)
+    pub phase_warning: bool,
 }
 
 /// Interner Meter-State pro Strip
@@ -47,6 +65,12 @@ struct MeterState {
     held_peak_r: f32,
     /// Clipping erkannt
     clipping: bool,
+    /// Gemittelte Energie links (Mittelwert L²)
+    energy_l: f32,
+    /// Gemittelte Energie rechts (Mittelwert R²)
+    energy_r: f32,
+    /// Gemitteltes Kreuzprodukt (Mittelwert L·R)
+    cross: f32,
 }
 
 impl MeterState {
@@ -62,6 +86,56 @@ impl MeterState {
             held_peak_l: 0.0,
             held_peak_r: 0.0,
             clipping: false,
+            energy_l: 0.0,
+            energy_r: 0.0,
+            cross: 0.0,
+        }
+    }
+
+    /// Stereo-Produkte eines Blocks einrechnen (gleitender Mittelwert)
+    fn update_stereo(&mut self, energy_l: f32, energy_r: f32, cross: f32) {
+        let keep = STEREO_SMOOTHING;
+        self.energy_l = self.energy_l * keep + energy_l * (1.0 - keep);
+        self.energy_r = self.energy_r * keep + energy_r * (1.0 - keep);
+        self.cross = self.cross * keep + cross * (1.0 - keep);
+    }
+
+    /// Korrelation, Breite und Balance aus den gemittelten Produkten
+    ///
+    /// Bei Stille liefern alle drei 0 (neutral, keine Warnung).
+    fn stereo(&self) -> (f32, f32, f32) {
+        let total = self.energy_l + self.energy_r;
+        let floor = db_to_linear(MIN_DB);
+        if total < floor * floor {
+            return (0.0, 0.0, 0.0);
+        }
+        let norm = (self.energy_l * self.energy_r).sqrt();
+        let correlation = if norm > 0.0 {
+            (self.cross / norm).clamp(-1.0, 1.0)
+        } else {
+            // Nur ein Kanal führt Signal
+            0.0
+        };
+        // Seitenenergie / Gesamtenergie mit M = (L+R)/2, S = (L-R)/2
+        let width = ((total - 2.0 * self.cross) / (2.0 * total)).clamp(0.0, 1.0);
+        let balance = ((self.energy_r - self.energy_l) / total).clamp(-1.0, 1.0);
+        (correlation, width, balance)
+    }
+
+    /// Messwerte für die Ausgabe (dB)
+    fn levels(&self, strip_id: &str) -> StripLevels {
+        let (correlation, width, balance) = self.stereo();
+        StripLevels {
+            strip_id: strip_id.to_string(),
+            peak_l: linear_to_db(self.held_peak_l),
+            peak_r: linear_to_db(self.held_peak_r),
+            rms_l: linear_to_db(self.rms_l),
+            rms_r: linear_to_db(self.rms_r),
+            clipping: self.clipping,
+            correlation,
+            width,
+            balance,
+            phase_warning: correlation < PHASE_WARN_CORRELATION,
         }
     }
 }
@@ -112,10 +186,14 @@ impl MeteringEngine {
             let (peak_l, rms_l) = calculate_peak_rms_channel(samples, 0, 2);
             let (peak_r, rms_r) = calculate_peak_rms_channel(samples, 1, 2);
             update_meter(meter, peak_l, peak_r, rms_l, rms_r);
+            let cross = calculate_cross(samples, channels as usize);
+            meter.update_stereo(rms_l * rms_l, rms_r * rms_r, cross);
         } else {
             // Mono: gleicher Wert für beide Kanäle
             let (peak, rms) = calculate_peak_rms(samples);
             update_meter(meter, peak, peak, rms, rms);
+            let energy = rms * rms;
+            meter.update_stereo(energy, energy, energy);
         }
     }
 
@@ -123,27 +201,15 @@ impl MeteringEngine {
     pub fn get_levels(&self) -> Vec<StripLevels> {
         self.meters
             .iter()
-            .map(|(id, meter)| StripLevels {
-                strip_id: id.clone(),
-                peak_l: linear_to_db(meter.held_peak_l),
-                peak_r: linear_to_db(meter.held_peak_r),
-                rms_l: linear_to_db(meter.rms_l),
-                rms_r: linear_to_db(meter.rms_r),
-                clipping: meter.clipping,
-            })
+            .map(|(id, meter)| meter.levels(id))
             .collect()
     }
 
     /// Messwerte für einen bestimmten Strip abrufen
     pub fn get_strip_levels(&self, strip_id: &str) -> Option<StripLevels> {
-        self.meters.get(strip_id).map(|meter| StripLevels {
-            strip_id: strip_id.to_string(),
-            peak_l: linear_to_db(meter.held_peak_l),
-            peak_r: linear_to_db(meter.held_peak_r),
-            rms_l: linear_to_db(meter.rms_l),
-            rms_r: linear_to_db(meter.rms_r),
-            clipping: meter.clipping,
-        })
+        self.meters
+            .get(strip_id)
+            .map(|meter| meter.levels(strip_id))
     }
 
     /// Clipping-Flag für einen Strip zurücksetzen
@@ -189,6 +255,19 @@ fn calculate_peak_rms_channel(
     (peak, rms)
 }
 
+/// Mittelwert des Kreuzprodukts L·R aus interleaved Samples
+fn calculate_cross(samples: &[f32], channel_count: usize) -> f32 {
+    let frames = samples.len() / channel_count;
+    if frames == 0 {
+        return 0.0;
+    }
+    let sum: f32 = samples
+        .chunks_exact(channel_count)
+        .map(|frame| frame[0] * frame[1])
+        .sum();
+    sum / frames as f32
+}
+
 /// Peak und RMS für einen Mono-Buffer berechnen
 fn calculate_peak_rms(samples: &[f32]) -> (f32, f32) {
     if samples.is_empty() {
@@ -437,9 +516,92 @@ mod tests {
             rms_l: -10.0,
             rms_r: -16.0,
             clipping: false,
+            correlation: -0.5,
+            width: 0.75,
+            balance: 0.0,
+            phase_warning: true,
         };
         let json = serde_json::to_string(&levels);
         assert!(json.is_ok());
-        assert!(json.unwrap().contains("test"));
+        let json = json.unwrap();
+        assert!(json.contains("test"));
+        assert!(json.contains("\"phase_warning\":true"));
+    }
+
+    /// Stereo-Block mehrfach einspeisen bis die Glättung eingeschwungen ist
+    fn feed_stereo(engine: &mut MeteringEngine, l: impl Fn(f32) -> f32, r: impl Fn(f32) -> f32) {
+        let block: Vec<f32> = (0..256)
+            .flat_map(|i| {
+                let t = i as f32 / 48000.0;
+                [l(t), r(t)]
+            })
+            .collect();
+        for _ in 0..50 {
+            engine.process_buffer("test", &block, 2);
+        }
+    }
+
+    fn sine(freq: f32) -> impl Fn(f32) -> f32 {
+        move |t| 0.5 * (2.0 * std::f32::consts::PI * freq * t).sin()
+    }
+
+    #[test]
+    fn test_correlation_and_width() {
+        let mut engine = MeteringEngine::new();
+
+        // Identische Kanäle: mono
+        feed_stereo(&mut engine, sine(375.0), sine(375.0));
+        let levels = engine.get_strip_levels("test").unwrap();
+        assert!((levels.correlation - 1.0).abs() < 0.01);
+        assert!(levels.width < 0.01);
+        assert!(levels.balance.abs() < 0.01);
+        assert!(!levels.phase_warning);
+
+        // Gegenphasig: löscht sich in Mono aus
+        let inverted = |t| -sine(375.0)(t);
+        feed_stereo(&mut engine, sine(375.0), inverted);
+        let levels = engine.get_strip_levels("test").unwrap();
+        assert!((levels.correlation + 1.0).abs() < 0.01);
+        assert!((levels.width - 1.0).abs() < 0.01);
+        assert!(levels.phase_warning);
+
+        // Unterschiedliche Frequenzen: unkorreliert
+        feed_stereo(&mut engine, sine(375.0), sine(750.0));
+        let levels = engine.get_strip_levels("test").unwrap();
+        assert!(levels.correlation.abs() < 0.05);
+        assert!((levels.width - 0.5).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_balance() {
+        let mut engine = MeteringEngine::new();
+        feed_stereo(&mut engine, |_| 0.0, sine(375.0));
+        let levels = engine.get_strip_levels("test").unwrap();
+        assert!((levels.balance - 1.0).abs() < 0.01);
+        assert_eq!(levels.correlation, 0.0);
+        assert!(!levels.phase_warning);
+
+        feed_stereo(&mut engine, sine(375.0), |_| 0.0);
+        let levels = engine.get_strip_levels("test").unwrap();
+        assert!((levels.balance + 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_stereo_silence_and_mono_neutral() {
+        let mut engine = MeteringEngine::new();
+        engine.process_buffer("test", &[0.0; 512], 2);
+        let levels = engine.get_strip_levels("test").unwrap();
+        assert_eq!(levels.correlation, 0.0);
+        assert_eq!(levels.width, 0.0);
+        assert!(!levels.phase_warning);
+
+        // Mono-Quelle: voll korreliert, mittig
+        let signal: Vec<f32> = (0..256).map(|i| sine(375.0)(i as f32 / 48000.0)).collect();
+        for _ in 0..50 {
+            engine.process_buffer("test", &signal, 1);
+        }
+        let levels = engine.get_strip_levels("test").unwrap();
+        assert!((levels.correlation - 1.0).abs() < 0.01);
+        assert!(levels.balance.abs() < 0.01);
     }
 }
@@ -1,24 +1,51 @@
 // Modul: audio/metering_service — Echtzeit-Metering Service mit Tauri Events
-use super::cpal_capture::{AudioSample, CpalCaptureManager};
+use super::bus_output::BusOutputManager;
+use super::bus_tap::{BusMonitorManager, BusTapSource};
+use super::cpal_capture::{CaptureStats, CaptureStatus, CpalCaptureManager};
+use super::generator::{GeneratorSettings, SignalGenerator};
 use super::metering::MeteringEngine;
+use super::mix_engine::{MixEngine, MIX_SAMPLE_RATE};
 use super::pipewire;
+use super::tap::{AudioTap, SharedTapHub, TapHub, TapKind, TAP_RING_SAMPLES};
 use log::{error, info, warn};
-use std::collections::VecDeque;
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tauri::{AppHandle, Emitter};
 
 /// Metering-Update-Intervall (16ms ≈ 60fps)
 const METERING_INTERVAL_MS: u64 = 16;
+/// Intervall für den Abgleich der Capture-Bindungen (ms)
+const CAPTURE_SYNC_INTERVAL_MS: u64 = 100;
+/// Intervall des Verteilers (Capture-Ringe → Consumer-Taps) in ms
+const FANOUT_INTERVAL_MS: u64 = 5;
 
 /// Metering-Service verwaltet Echtzeit-Metering und sendet Updates an Frontend
+///
+/// Werden mehrere Locks gleichzeitig gehalten, dann immer in der Reihenfolge
+/// `engine` → `tap_hub` → `metering_taps` → Mix-Engine (sonst Deadlock zwischen
+/// Metering-Loop, Capture-Thread und Tauri-Commands).
 pub struct MeteringService {
     /// Metering-Engine (Thread-sicher)
     engine: Arc<Mutex<MeteringEngine>>,
-    /// Audio-Buffer für jeden Strip (von CPAL gefüllt)
-    audio_buffers: Arc<Mutex<std::collections::HashMap<String, Arc<Mutex<VecDeque<AudioSample>>>>>>,
+    /// Metering-Taps für jeden gecaptureten Strip
+    metering_taps: Arc<Mutex<HashMap<String, AudioTap>>>,
+    /// Verteiler für alle Consumer-Taps (Metering, Mix-Engine, STT, Recording)
+    tap_hub: SharedTapHub,
+    /// Overflow-Statistik der Capture-Streams (vom Capture-Thread aktualisiert)
+    capture_stats: Arc<Mutex<Vec<CaptureStats>>>,
+    /// Verbindungszustand der gebundenen Strips (vom Capture-Thread aktualisiert)
+    capture_status: Arc<Mutex<Vec<CaptureStatus>>>,
+    /// Gewünschte Capture-Bindungen (Strip-ID → Device-Name)
+    capture_bindings: Arc<Mutex<HashMap<String, String>>>,
+    /// Busse mit Monitor-Tap (Bus-Metering, Aufnahme, STT)
+    monitored_buses: Arc<Mutex<Vec<String>>>,
+    /// Herkunft der Bus-Monitor-Signale (Mix-Engine oder PipeWire)
+    bus_tap_source: Arc<Mutex<BusTapSource>>,
+    /// Gewünschter Signal-Generator (None = aus)
+    generator: Arc<Mutex<Option<GeneratorSettings>>>,
     /// Flag ob Service läuft
     running: Arc<AtomicBool>,
     /// Thread-Handle für Metering-Loop
@@ -31,16 +58,32 @@ pub struct MeteringService {
 
 impl MeteringService {
     /// Neuen Metering-Service erstellen und starten
-    pub fn start(app_handle: AppHandle) -> Self {
+    ///
+    /// Gecapturete Strip-Signale werden zusätzlich in die Mix-Engine eingespeist.
+    pub fn start(app_handle: AppHandle, mix_engine: Arc<Mutex<MixEngine>>) -> Self {
         let engine = Arc::new(Mutex::new(MeteringEngine::new()));
         let running = Arc::new(AtomicBool::new(true));
         let use_real_audio = Arc::new(AtomicBool::new(true)); // Versuche CPAL, falle zurück auf Simulation
-        let audio_buffers = Arc::new(Mutex::new(std::collections::HashMap::new()));
+        let metering_taps = Arc::new(Mutex::new(HashMap::new()));
+        let tap_hub: SharedTapHub = Arc::new(Mutex::new(TapHub::new()));
+        let capture_stats = Arc::new(Mutex::new(Vec::new()));
+        let capture_status = Arc::new(Mutex::new(Vec::new()));
+        let capture_bindings = Arc::new(Mutex::new(HashMap::new()));
+        let monitored_buses = Arc::new(Mutex::new(Vec::new()));
+        let generator: Arc<Mutex<Option<GeneratorSettings>>> = Arc::new(Mutex::new(None));
+        // Bus-Signale aus PipeWire abgreifen wenn PipeWire läuft, sonst aus der Mix-Engine
+        let bus_tap_source = Arc::new(Mutex::new(
+            if pipewire::check_pipewire_available().is_ok() {
+                BusTapSource::PipeWire
+            } else {
+                BusTapSource::Engine
+            },
+        ));
 
         let engine_clone = Arc::clone(&engine);
         let running_clone = Arc::clone(&running);
         let use_real_audio_clone = Arc::clone(&use_real_audio);
-        let audio_buffers_clone = Arc::clone(&audio_buffers);
+        let metering_taps_clone = Arc::clone(&metering_taps);
 
         // ═══════════════════════════════════════════════════════════════
         // Phase 2c: Dynamische Strip-Registrierung via PipeWire Discovery
@@ -125,10 +168,19 @@ impl MeteringService {
 
         // CPAL Audio-Capture in separatem Thread starten (da Streams nicht Send sind)
         let cpal_thread_handle = if use_real_audio.load(Ordering::Relaxed) {
-            let audio_buffers_cpal = Arc::clone(&audio_buffers);
+            let metering_taps_cpal = Arc::clone(&metering_taps);
+            let tap_hub_cpal = Arc::clone(&tap_hub);
+            let capture_stats_cpal = Arc::clone(&capture_stats);
+            let capture_status_cpal = Arc::clone(&capture_status);
+            let app_handle_cpal = app_handle.clone();
             let use_real_audio_cpal = Arc::clone(&use_real_audio);
             let running_cpal = Arc::clone(&running);
             let engine_cpal = Arc::clone(&engine);
+            let bindings_cpal = Arc::clone(&capture_bindings);
+            let mix_engine_cpal = Arc::clone(&mix_engine);
+            let buses_cpal = Arc::clone(&monitored_buses);
+            let bus_source_cpal = Arc::clone(&bus_tap_source);
+            let generator_cpal = Arc::clone(&generator);
 
             thread::Builder::new()
                 .name("cpal-capture".to_string())
@@ -136,48 +188,184 @@ impl MeteringService {
                     info!("🎤 CPAL Capture-Thread gestartet");
 
                     // CPAL Manager in diesem Thread erstellen (Streams bleiben hier!)
+                    // Ohne CPAL läuft der Thread weiter für die Bus-Monitore.
                     let mut cpal_manager = match CpalCaptureManager::new() {
                         Ok(manager) => {
                             info!("✅ CPAL initialisiert in Capture-Thread");
-                            manager
+                            Some(manager)
                         }
                         Err(e) => {
                             warn!("⚠️  CPAL-Init fehlgeschlagen: {} - Nutze Simulation", e);
                             use_real_audio_cpal.store(false, Ordering::Relaxed);
-                            return;
+                            None
                         }
                     };
+                    let mut bus_monitor = BusMonitorManager::new(
+                        bus_source_cpal
+                            .lock()
+                            .map(|s| *s)
+                            .unwrap_or(BusTapSource::Engine),
+                    );
+                    let mut bus_output = BusOutputManager::new();
+
+                    // Ein Stream pro Strip mit gebundenem Device — Bindungen werden
+                    // laufend abgeglichen, damit Bind/Unbind/Remove sofort wirken.
+                    // Dazwischen verteilt der Thread die Capture-Ringe an die Taps
+                    // und lässt die Mix-Engine die vollständigen Blöcke mischen.
+                    // Die Bus-Summen landen als Monitor-Taps im selben Verteiler.
+                    // Im PipeWire-Modus laufen die Busse über den Summen-Node
+                    // durch die Mix-Engine zurück in ihre Bus-Nodes.
+                    // Der Signal-Generator speist wie ein Capture-Stream unter der
+                    // ID seines Ziel-Strips ein; dessen Capture pausiert solange.
+                    let sync_every = (CAPTURE_SYNC_INTERVAL_MS / FANOUT_INTERVAL_MS).max(1);
+                    let mut tick: u64 = 0;
+                    let mut playing: Option<(GeneratorSettings, SignalGenerator, Instant)> = None;
+                    let mut generator_block = Vec::new();
 
-                    // Default Input-Device starten
-                    if let Ok(device) = cpal_manager.get_default_input_device() {
-                        match cpal_manager.start_capture(device, "default-input") {
-                            Ok(buffer) => {
-                                audio_buffers_cpal
-                                    .lock()
-                                    .unwrap()
-                                    .insert("default-input".to_string(), buffer);
-                                info!("🎤 Default Input-Device Capture gestartet");
-
-                                // Strip für Default-Input registrieren
-                                if let Ok(mut eng) = engine_cpal.lock() {
-                                    eng.register_strip("default-input");
+                    while running_cpal.load(Ordering::Relaxed) {
+                        if tick % sync_every == 0 {
+                            // Bus-Monitore: Quelle anwenden, Mitschnitte abgleichen
+                            if let Ok(source) = bus_source_cpal.lock() {
+                                bus_monitor.set_source(*source);
+                            }
+                            let bus_ids = buses_cpal.lock().map(|b| b.clone()).unwrap_or_default();
+                            if let Ok(mut mix) = mix_engine_cpal.lock() {
+                                let pipewire = bus_monitor.source() == BusTapSource::PipeWire;
+                                mix.set_publish_outputs(!pipewire);
+                                bus_output.set_active(pipewire, &mut mix);
+                                bus_output.sync(&bus_ids, &mut mix);
+                            }
+                            if let Ok(hub) = tap_hub_cpal.lock() {
+                                bus_monitor.sync(&bus_ids, &hub);
+                            }
+
+                            // Generator: alten Ziel-Strip vor dem Capture-Abgleich
+                            // freigeben, neuen erst danach anschließen
+                            let wanted = generator_cpal.lock().map(|g| g.clone()).unwrap_or(None);
+                            let playing_target =
+                                playing.as_ref().map(|(s, _, _)| s.target_strip.clone());
+                            let wanted_target = wanted.as_ref().map(|s| s.target_strip.clone());
+                            if playing_target != wanted_target {
+                                if let Some(strip_id) = &playing_target {
+                                    detach_source(
+                                        strip_id,
+                                        &tap_hub_cpal,
+                                        &metering_taps_cpal,
+                                        &mix_engine_cpal,
+                                        &engine_cpal,
+                                    );
+                                    info!("🔊 Generator gestoppt: {}", strip_id);
                                 }
                             }
-                            Err(e) => {
-                                warn!("⚠️  Default-Input Capture fehlgeschlagen: {}", e);
-                                use_real_audio_cpal.store(false, Ordering::Relaxed);
+
+                            if let Some(cpal_manager) = cpal_manager.as_mut() {
+                                let mut desired =
+                                    bindings_cpal.lock().map(|b| b.clone()).unwrap_or_default();
+                                if let Some(strip_id) = &wanted_target {
+                                    desired.remove(strip_id);
+                                }
+                                let changes = cpal_manager.sync_bindings(&desired);
+
+                                for strip_id in changes.stopped {
+                                    detach_source(
+                                        &strip_id,
+                                        &tap_hub_cpal,
+                                        &metering_taps_cpal,
+                                        &mix_engine_cpal,
+                                        &engine_cpal,
+                                    );
+                                }
+
+                                for strip_id in changes.started {
+                                    attach_source(
+                                        &strip_id,
+                                        &tap_hub_cpal,
+                                        &metering_taps_cpal,
+                                        &mix_engine_cpal,
+                                        &engine_cpal,
+                                    );
+                                    info!("🎤 Strip-Capture gestartet: {}", strip_id);
+                                }
+
+                                if let Ok(mut stats) = capture_stats_cpal.lock() {
+                                    *stats = cpal_manager.capture_stats();
+                                }
+
+                                // Zustandswechsel (connected/reconnecting/missing) ans Frontend
+                                let status = cpal_manager.capture_status();
+                                if let Ok(mut current) = capture_status_cpal.lock() {
+                                    if *current != status {
+                                        if let Err(e) =
+                                            app_handle_cpal.emit("capture-state", &status)
+                                        {
+                                            warn!("capture-state Event fehlgeschlagen: {}", e);
+                                        }
+                                        *current = status;
+                                    }
+                                }
+                            }
+
+                            if playing_target != wanted_target {
+                                if let Some(strip_id) = &wanted_target {
+                                    attach_source(
+                                        strip_id,
+                                        &tap_hub_cpal,
+                                        &metering_taps_cpal,
+                                        &mix_engine_cpal,
+                                        &engine_cpal,
+                                    );
+                                    info!("🔊 Generator gestartet: {}", strip_id);
+                                }
+                            }
+                            // Neues Signal, neuer Start oder neue Engine-Rate:
+                            // Generator von vorn
+                            let rate = mix_engine_cpal
+                                .lock()
+                                .map(|mix| mix.sample_rate())
+                                .unwrap_or(MIX_SAMPLE_RATE);
+                            let restart = match (&playing, &wanted) {
+                                (Some((s, g, _)), Some(w)) => s != w || g.sample_rate() != rate,
+                                (None, None) => false,
+                                _ => true,
+                            };
+                            if restart {
+                                playing = wanted.map(|s| {
+                                    let generator = SignalGenerator::new(s.signal.clone(), rate);
+                                    (s, generator, Instant::now())
+                                });
                             }
                         }
-                    }
 
-                    // CPAL-Thread läuft weiter solange MeteringService aktiv ist
-                    // Streams müssen am Leben bleiben!
-                    while running_cpal.load(Ordering::Relaxed) {
-                        thread::sleep(Duration::from_millis(100));
+                        if let Ok(mut hub) = tap_hub_cpal.lock() {
+                            if let Some(cpal_manager) = cpal_manager.as_mut() {
+                                cpal_manager.distribute(&mut hub);
+                            }
+                            if let Some((settings, generator, started)) = playing.as_mut() {
+                                generator.render_until(started.elapsed(), &mut generator_block);
+                                hub.set_sample_rate(
+                                    &settings.target_strip,
+                                    generator.sample_rate(),
+                                );
+                                hub.distribute(&settings.target_strip, &generator_block);
+                            }
+                            if let Ok(mut mix) = mix_engine_cpal.lock() {
+                                mix.process(&mut hub);
+                            }
+                            bus_monitor.distribute(&mut hub);
+                        }
+
+                        tick = tick.wrapping_add(1);
+                        thread::sleep(Duration::from_millis(FANOUT_INTERVAL_MS));
                     }
 
                     info!("🎤 CPAL Capture-Thread beendet");
-                    cpal_manager.shutdown();
+                    bus_monitor.stop_all();
+                    if let Ok(mut mix) = mix_engine_cpal.lock() {
+                        bus_output.stop_all(&mut mix);
+                    }
+                    if let Some(mut cpal_manager) = cpal_manager {
+                        cpal_manager.shutdown();
+                    }
                 })
                 .ok()
         } else {
@@ -192,7 +380,7 @@ impl MeteringService {
                     engine_clone,
                     running_clone,
                     use_real_audio_clone,
-                    audio_buffers_clone,
+                    metering_taps_clone,
                     app_handle,
                 );
             })
@@ -202,7 +390,14 @@ impl MeteringService {
 
         Self {
             engine,
-            audio_buffers,
+            metering_taps,
+            tap_hub,
+            capture_stats,
+            capture_status,
+            capture_bindings,
+            monitored_buses,
+            bus_tap_source,
+            generator,
             running,
             thread_handle,
             cpal_thread_handle,
@@ -215,11 +410,12 @@ impl MeteringService {
         engine: Arc<Mutex<MeteringEngine>>,
         running: Arc<AtomicBool>,
         use_real_audio: Arc<AtomicBool>,
-        audio_buffers: Arc<
-            Mutex<std::collections::HashMap<String, Arc<Mutex<VecDeque<AudioSample>>>>>,
-        >,
+        metering_taps: Arc<Mutex<HashMap<String, AudioTap>>>,
         app_handle: AppHandle,
     ) {
+        // Wiederverwendeter Lese-Puffer (keine Allokation pro Tick)
+        let mut samples = vec![0.0_f32; TAP_RING_SAMPLES];
+
         while running.load(Ordering::Relaxed) {
             // Metering-Daten sammeln
             let levels = if let Ok(eng) = engine.lock() {
@@ -234,26 +430,15 @@ impl MeteringService {
             // ═══════════════════════════════════════════════════════════════
 
             if use_real_audio.load(Ordering::Relaxed) {
-                // ✅ ECHTES AUDIO von CPAL
+                // ✅ ECHTES AUDIO von CPAL (über den eigenen Metering-Tap)
                 if let Ok(mut eng) = engine.lock() {
-                    let buffers = audio_buffers.lock().unwrap();
-
-                    for (strip_id, buffer_arc) in buffers.iter() {
-                        let mut buffer = buffer_arc.lock().unwrap();
-
-                        // Samples aus Buffer lesen (max 256 Samples ≈ 5.3ms @ 48kHz)
-                        let sample_count = buffer.len().min(256);
-                        let mut samples = Vec::with_capacity(sample_count * 2); // Stereo
-
-                        for _ in 0..sample_count {
-                            if let Some(sample) = buffer.pop_front() {
-                                samples.push(sample.left);
-                                samples.push(sample.right);
-                            }
-                        }
+                    let mut taps = metering_taps.lock().unwrap();
 
-                        if !samples.is_empty() {
-                            eng.process_buffer(strip_id, &samples, 2);
+                    for (strip_id, tap) in taps.iter_mut() {
+                        // Alle seit dem letzten Tick gecaptureten Samples lesen
+                        let read = tap.read(&mut samples);
+                        if read > 0 {
+                            eng.process_buffer(strip_id, &samples[..read], 2);
                         }
                     }
                 }
@@ -292,6 +477,91 @@ impl MeteringService {
         info!("Metering-Loop beendet");
     }
 
+    /// Capture-Bindungen setzen (Strip-ID → Device-Name)
+    ///
+    /// Der Capture-Thread startet/stoppt die Streams beim nächsten Abgleich.
+    pub fn set_capture_bindings(&self, bindings: HashMap<String, String>) {
+        if let Ok(mut current) = self.capture_bindings.lock() {
+            *current = bindings;
+        }
+    }
+
+    /// Busse mit Monitor-Tap festlegen
+    ///
+    /// Jeder Bus bekommt einen Metering-Tap (Pegel, Korrelation und Breite
+    /// hinter der Master-Stufe erscheinen unter der Bus-ID im
+    /// "metering-update" Event) und steht als Tap-Quelle für Aufnahme und
+    /// STT bereit.
+    pub fn set_monitored_buses(&self, bus_ids: Vec<String>) {
+        let previous = match self.monitored_buses.lock() {
+            Ok(mut current) => std::mem::replace(&mut *current, bus_ids.clone()),
+            Err(_) => return,
+        };
+
+        // Lock-Reihenfolge: engine → tap_hub → metering_taps
+        if let (Ok(mut engine), Ok(mut hub), Ok(mut taps)) = (
+            self.engine.lock(),
+            self.tap_hub.lock(),
+            self.metering_taps.lock(),
+        ) {
+            for bus_id in previous.iter().filter(|id| !bus_ids.contains(id)) {
+                hub.unsubscribe(bus_id, TapKind::Metering);
+                taps.remove(bus_id);
+                engine.unregister_strip(bus_id);
+            }
+            for bus_id in bus_ids.iter().filter(|id| !previous.contains(id)) {
+                taps.insert(bus_id.clone(), hub.subscribe(bus_id, TapKind::Metering));
+                engine.register_strip(bus_id);
+            }
+        }
+    }
+
+    /// Signal-Generator setzen (None = aus)
+    ///
+    /// Der Ziel-Strip wird ab dem nächsten Abgleich vom Generator bespielt;
+    /// ein gebundener Capture-Stream des Strips pausiert solange.
+    pub fn set_generator(&self, settings: Option<GeneratorSettings>) {
+        if let Ok(mut current) = self.generator.lock() {
+            *current = settings;
+        }
+    }
+
+    /// Herkunft der Bus-Monitor-Signale setzen
+    pub fn set_bus_tap_source(&self, source: BusTapSource) {
+        if let Ok(mut current) = self.bus_tap_source.lock() {
+            *current = source;
+        }
+    }
+
+    /// Herkunft der Bus-Monitor-Signale abfragen
+    pub fn bus_tap_source(&self) -> BusTapSource {
+        self.bus_tap_source
+            .lock()
+            .map(|s| *s)
+            .unwrap_or(BusTapSource::Engine)
+    }
+
+    /// Geteilten Tap-Verteiler abfragen (für STT- und Recording-Taps)
+    pub fn tap_hub(&self) -> SharedTapHub {
+        Arc::clone(&self.tap_hub)
+    }
+
+    /// Verbindungszustand der gebundenen Capture-Devices abfragen
+    pub fn capture_status(&self) -> Vec<CaptureStatus> {
+        self.capture_status
+            .lock()
+            .map(|s| s.clone())
+            .unwrap_or_default()
+    }
+
+    /// Overflow-Statistik der Capture-Streams abfragen
+    pub fn capture_stats(&self) -> Vec<CaptureStats> {
+        self.capture_stats
+            .lock()
+            .map(|s| s.clone())
+            .unwrap_or_default()
+    }
+
     /// Strip für Metering registrieren
     pub fn register_strip(&self, strip_id: &str) {
         if let Ok(mut engine) = self.engine.lock() {
@@ -340,6 +610,51 @@ impl Drop for MeteringService {
     }
 }
 
+/// Quelle an Metering und Mix-Engine anschließen (Capture-Stream oder Generator)
+fn attach_source(
+    strip_id: &str,
+    tap_hub: &SharedTapHub,
+    metering_taps: &Mutex<HashMap<String, AudioTap>>,
+    mix_engine: &Mutex<MixEngine>,
+    engine: &Mutex<MeteringEngine>,
+) {
+    if let Ok(mut eng) = engine.lock() {
+        eng.register_strip(strip_id);
+    }
+    if let Ok(mut hub) = tap_hub.lock() {
+        let meter_tap = hub.subscribe(strip_id, TapKind::Metering);
+        let mix_tap = hub.subscribe(strip_id, TapKind::MixEngine);
+        metering_taps
+            .lock()
+            .unwrap()
+            .insert(strip_id.to_string(), meter_tap);
+        if let Ok(mut mix) = mix_engine.lock() {
+            mix.attach_input(mix_tap);
+        }
+    }
+}
+
+/// Quelle von Metering und Mix-Engine trennen
+fn detach_source(
+    strip_id: &str,
+    tap_hub: &SharedTapHub,
+    metering_taps: &Mutex<HashMap<String, AudioTap>>,
+    mix_engine: &Mutex<MixEngine>,
+    engine: &Mutex<MeteringEngine>,
+) {
+    if let Ok(mut hub) = tap_hub.lock() {
+        hub.unsubscribe(strip_id, TapKind::Metering);
+        hub.unsubscribe(strip_id, TapKind::MixEngine);
+    }
+    metering_taps.lock().unwrap().remove(strip_id);
+    if let Ok(mut mix) = mix_engine.lock() {
+        mix.remove_input(strip_id);
+    }
+    if let Ok(mut eng) = engine.lock() {
+        eng.unregister_strip(strip_id);
+    }
+}
+
 /// App-Namen aus PipeWire-Node-Namen extrahieren
 fn extract_app_name(node_name: &str) -> String {
     // Beispiele:
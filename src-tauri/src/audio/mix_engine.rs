@@ -0,0 +1,1000 @@
+// Modul: audio/mix_engine — In-Process Mix-Engine (Input-Strips → Output-Busse)
+//
+// Summiert die gecaptureten Strip-Signale blockweise in die Output-Busse.
+// Die Parameter (Volume, Gain, Pan, Mute, Solo, Routing) werden als
+// vorberechnete Snapshots aus MixerState/BusManager übernommen.
+// Optional werden die Bus-Blöcke als Monitor-Taps (Source-ID = Bus-ID) verteilt.
+// Im PipeWire-Modus kommen die App-Links als Rückkehr der Summen-Nodes
+// (inoX-Sum-<ID>) in den Bus, und der fertige Bus-Block geht in den Bus-Node
+// (inoX-Bus-<ID>) — nur so sind Inserts, Delay und Master-Stufe hörbar
+// (siehe bus_output).
+// Talk-Gates (PTT/PTM/Toggle) blenden Strips pro Block mit einer Gain-Rampe.
+// Nach der Summierung läuft jeder Bus durch seine Insert-Chain (vor dem Fader).
+// Submixe (Bus → Bus) werden in topologischer Reihenfolge verarbeitet: ein
+// fertiger Quell-Bus wird post-Fader mit dem Kreuzpunkt-Gain in seine
+// Ziel-Busse addiert.
+// Am Ende des Bus-Pfads liegt das Ausgangs-Delay (A/V-Sync, vor den Submixen).
+// Danach läuft jeder Bus-Block der Engine durch die Master-Stufe (DIM,
+// Talkback, MONO, Limiter) — sobald ein Master-State übergeben wurde.
+// Die Automation läuft im Takt der Engine: zu Beginn jedes Blocks werden die
+// Lane-Werte an der Frame-Position gelesen und direkt auf die Mix-Parameter
+// angewendet; die Kommandos gehen zusätzlich an die Manager (siehe
+// `take_automation_commands`).
+use super::bus::OutputBus;
+use super::delay::DelayLine;
+use super::master::MasterState;
+use super::master_stage::MasterStage;
+use super::mixer::{db_to_linear, InputStrip};
+use super::talk::{TalkGate, TalkMode, TalkStatus};
+use super::tap::{AudioTap, RingReader, RingWriter, TapHub};
+use crate::automation::AutomationEngine;
+use crate::fx::bus_chain::BusFxChain;
+use crate::history::MixerCommand;
+use log::{info, warn};
+use std::collections::HashMap;
+
+/// Block-Größe der Mix-Engine in Frames (≈ 5.3ms @ 48kHz)
+pub const MIX_BLOCK_FRAMES: usize = 256;
+/// Standard-Sample-Rate bis die konfigurierte Rate gesetzt ist (Hz)
+pub const MIX_SAMPLE_RATE: u32 = 48000;
+
+/// Vorberechnete Mix-Parameter eines Strips
+#[derive(Debug, Clone)]
+struct StripMix {
+    /// Linearer Faktor links (Gain × Volume × Pan)
+    gain_l: f32,
+    /// Linearer Faktor rechts (Gain × Volume × Pan)
+    gain_r: f32,
+    /// Linearer Eingangs-Gain (für Talkback, unabhängig von Fader und Mute)
+    trim: f32,
+    /// Fader (dB)
+    volume_db: f32,
+    /// Eingangs-Gain (dB)
+    gain_db: f32,
+    /// Balance (-1.0 bis 1.0)
+    pan: f32,
+    /// Strip stumm
+    muted: bool,
+    /// Strip im Solo
+    solo: bool,
+    /// Ziel-Busse
+    buses: Vec<String>,
+}
+
+impl StripMix {
+    /// Mix-Parameter aus einem Input-Strip ableiten
+    fn from_strip(strip: &InputStrip) -> Self {
+        let mut mix = Self {
+            gain_l: 1.0,
+            gain_r: 1.0,
+            trim: 1.0,
+            volume_db: strip.volume_db,
+            gain_db: strip.gain_db,
+            pan: strip.pan,
+            muted: strip.muted,
+            solo: strip.solo,
+            buses: strip.bus_routing.clone(),
+        };
+        mix.update_levels();
+        mix
+    }
+
+    /// Lineare Faktoren aus Fader, Gain und Pan neu berechnen
+    fn update_levels(&mut self) {
+        let level = db_to_linear(self.gain_db + self.volume_db);
+        // Balance-Pan: Mitte = beide Seiten unverändert
+        let pan = self.pan.clamp(-1.0, 1.0);
+        self.gain_l = level * (1.0 - pan).min(1.0);
+        self.gain_r = level * (1.0 + pan).min(1.0);
+        self.trim = db_to_linear(self.gain_db);
+    }
+}
+
+/// Vorberechnete Mix-Parameter eines Busses
+#[derive(Debug, Clone)]
+struct BusMix {
+    /// Linearer Bus-Faktor
+    gain: f32,
+    /// Bus stumm
+    muted: bool,
+}
+
+/// Mix-Engine: summiert Strip-Eingänge in Output-Busse
+pub struct MixEngine {
+    /// Mix-Parameter pro Strip (Key: Strip-ID)
+    strips: HashMap<String, StripMix>,
+    /// Mix-Parameter pro Bus (Key: Bus-ID)
+    buses: HashMap<String, BusMix>,
+    /// Insert-Chains pro Bus (Key: Bus-ID)
+    bus_fx: HashMap<String, BusFxChain>,
+    /// Ausgangs-Delays pro Bus (Key: Bus-ID)
+    delays: HashMap<String, DelayLine>,
+    /// Master-Stufe aller Bus-Ausgänge (None bis zum ersten Master-State)
+    master: Option<MasterStage>,
+    /// Block der Talkback-Quelle (Stereo interleaved)
+    talkback: Vec<f32>,
+    /// Verarbeitungs-Reihenfolge der Busse (Quell-Busse vor Ziel-Bussen)
+    bus_order: Vec<String>,
+    /// Submix-Ziele pro Quell-Bus mit linearem Kreuzpunkt-Gain (Key: Bus-ID)
+    bus_routes: HashMap<String, Vec<(String, f32)>>,
+    /// Talk-Gates der Strips mit PTT/PTM/Toggle (Key: Strip-ID)
+    talk: HashMap<String, TalkGate>,
+    /// Eingangs-Taps pro Strip (Stereo interleaved)
+    inputs: HashMap<String, AudioTap>,
+    /// Rückkehr der Summen-Nodes pro Bus (Stereo interleaved, Key: Bus-ID)
+    returns: HashMap<String, RingReader>,
+    /// Wiedergabe der fertigen Bus-Blöcke in die Bus-Nodes (Key: Bus-ID)
+    playback: HashMap<String, RingWriter>,
+    /// Zuletzt berechneter Block pro Bus (Stereo interleaved)
+    outputs: HashMap<String, Vec<f32>>,
+    /// Arbeits-Puffer für einen Eingangs-Block
+    scratch: Vec<f32>,
+    /// Arbeits-Puffer für die Bus-Inserts (deinterleaved)
+    fx_l: Vec<f32>,
+    fx_r: Vec<f32>,
+    /// Anzahl verarbeiteter Frames seit Start
+    frames_processed: u64,
+    /// Sample-Rate der Eingänge und Busse (Hz) für Nachlauf, Fades und Delays
+    sample_rate: u32,
+    /// Bus-Blöcke an die Bus-Taps verteilen (Monitor-Quelle "Engine")
+    publish_outputs: bool,
+    /// Automation-Transport im Takt der Engine
+    automation: AutomationEngine,
+    /// Angewendete Automation-Kommandos für die Manager
+    automation_out: Vec<MixerCommand>,
+}
+
+impl MixEngine {
+    /// Neue, leere Mix-Engine erstellen
+    pub fn new() -> Self {
+        info!("Mix-Engine erstellt (Block: {} Frames)", MIX_BLOCK_FRAMES);
+        Self {
+            strips: HashMap::new(),
+            buses: HashMap::new(),
+            bus_fx: HashMap::new(),
+            delays: HashMap::new(),
+            master: None,
+            talkback: vec![0.0; MIX_BLOCK_FRAMES * 2],
+            bus_order: Vec::new(),
+            bus_routes: HashMap::new(),
+            talk: HashMap::new(),
+            inputs: HashMap::new(),
+            returns: HashMap::new(),
+            playback: HashMap::new(),
+            outputs: HashMap::new(),
+            scratch: vec![0.0; MIX_BLOCK_FRAMES * 2],
+            fx_l: vec![0.0; MIX_BLOCK_FRAMES],
+            fx_r: vec![0.0; MIX_BLOCK_FRAMES],
+            frames_processed: 0,
+            sample_rate: MIX_SAMPLE_RATE,
+            publish_outputs: true,
+            automation: AutomationEngine::new(),
+            automation_out: Vec::new(),
+        }
+    }
+
+    /// Strip-Parameter aus dem Mixer-State übernehmen
+    ///
+    /// Talk-Gates behalten ihren Laufzeit-Zustand (gedrückt, Nachlauf, Fade).
+    pub fn update_strips(&mut self, strips: &[InputStrip]) {
+        self.strips = strips
+            .iter()
+            .map(|s| (s.id.clone(), StripMix::from_strip(s)))
+            .collect();
+
+        self.talk.retain(|id, _| {
+            strips
+                .iter()
+                .any(|s| &s.id == id && s.talk.mode != TalkMode::AlwaysOn)
+        });
+        for strip in strips.iter().filter(|s| s.talk.mode != TalkMode::AlwaysOn) {
+            match self.talk.get_mut(&strip.id) {
+                Some(gate) => gate.set_settings(strip.talk.clone()),
+                None => {
+                    self.talk
+                        .insert(strip.id.clone(), TalkGate::new(strip.talk.clone()));
+                }
+            }
+        }
+
+        // Eingänge entfernter Strips verwerfen
+        let strips = &self.strips;
+        self.inputs.retain(|id, _| strips.contains_key(id));
+    }
+
+    /// Bus-Parameter aus dem Bus-Manager übernehmen
+    ///
+    /// Bestehende Insert-Chains behalten ihren Laufzeit-Zustand (Hüllkurven).
+    pub fn update_buses(&mut self, buses: &[OutputBus]) {
+        self.buses = buses
+            .iter()
+            .map(|b| {
+                (
+                    b.id.clone(),
+                    BusMix {
+                        gain: db_to_linear(b.volume_db),
+                        muted: b.muted,
+                    },
+                )
+            })
+            .collect();
+        let live = &self.buses;
+        self.outputs.retain(|id, _| live.contains_key(id));
+        self.bus_fx.retain(|id, _| live.contains_key(id));
+        self.delays.retain(|id, _| live.contains_key(id));
+        if let Some(master) = self.master.as_mut() {
+            master.retain_buses(|id| live.contains_key(id));
+        }
+
+        for bus in buses {
+            self.delays
+                .entry(bus.id.clone())
+                .or_insert_with(|| DelayLine::new(self.sample_rate))
+                .set_delay_ms(bus.delay_ms);
+            let sample_rate = self.sample_rate as f32;
+            let chain = self
+                .bus_fx
+                .entry(bus.id.clone())
+                .or_insert_with(|| BusFxChain::with_sample_rate(sample_rate));
+            if let Err(e) = chain.apply_modules(&bus.inserts) {
+                warn!("Bus {}: Inserts nicht übernommen: {}", bus.id, e);
+            }
+        }
+        self.complete_bus_order();
+    }
+
+    /// Nur die Lautstärke eines Busses setzen (Inserts und Delay bleiben unberührt)
+    pub fn set_bus_volume(&mut self, bus_id: &str, volume_db: f32) {
+        if let Some(bus) = self.buses.get_mut(bus_id) {
+            bus.gain = db_to_linear(volume_db);
+        }
+    }
+
+    /// Master-State übernehmen (aktiviert die Master-Stufe)
+    pub fn update_master(&mut self, state: &MasterState) {
+        match self.master.as_mut() {
+            Some(master) => master.update(state),
+            None => self.master = Some(MasterStage::new(state, self.sample_rate)),
+        }
+    }
+
+    /// Bus → Bus Routings übernehmen (Reihenfolge aus dem Routing-Manager)
+    ///
+    /// `order` muss topologisch sortiert sein; Busse die darin fehlen, werden
+    /// danach in beliebiger Reihenfolge verarbeitet. `routes` enthält
+    /// (Quell-Bus, Ziel-Bus, Kreuzpunkt-Gain in dB).
+    pub fn update_bus_routes(&mut self, order: &[String], routes: &[(String, String, f32)]) {
+        self.bus_order = order.to_vec();
+        self.bus_routes.clear();
+        for (from, to, gain_db) in routes {
+            self.bus_routes
+                .entry(from.clone())
+                .or_default()
+                .push((to.clone(), db_to_linear(*gain_db)));
+        }
+        self.complete_bus_order();
+    }
+
+    /// Reihenfolge auf die aktuellen Busse abgleichen (fehlende anhängen)
+    fn complete_bus_order(&mut self) {
+        let buses = &self.buses;
+        self.bus_order.retain(|id| buses.contains_key(id));
+        let mut missing: Vec<String> = buses
+            .keys()
+            .filter(|id| !self.bus_order.contains(id))
+            .cloned()
+            .collect();
+        missing.sort();
+        self.bus_order.extend(missing);
+    }
+
+    /// Eingangs-Tap eines Strips anschließen (ersetzt einen bestehenden)
+    pub fn attach_input(&mut self, tap: AudioTap) {
+        self.inputs.insert(tap.source_id.clone(), tap);
+    }
+
+    /// Eingang eines Strips entfernen (z.B. wenn der Capture-Stream stoppt)
+    pub fn remove_input(&mut self, strip_id: &str) {
+        self.inputs.remove(strip_id);
+    }
+
+    /// PipeWire-Pfad eines Busses anschließen (ersetzt einen bestehenden)
+    ///
+    /// `ret` liefert die Summe der App-Links, sie wird vor den Inserts in den
+    /// Bus addiert; der fertige Bus-Block geht in `playback`.
+    pub fn attach_bus_output(&mut self, bus_id: &str, ret: RingReader, playback: RingWriter) {
+        self.returns.insert(bus_id.to_string(), ret);
+        self.playback.insert(bus_id.to_string(), playback);
+    }
+
+    /// PipeWire-Pfad eines Busses trennen
+    pub fn detach_bus_output(&mut self, bus_id: &str) {
+        self.returns.remove(bus_id);
+        self.playback.remove(bus_id);
+    }
+
+    /// Talk-Taste eines Strips gedrückt
+    pub fn talk_press(&mut self, strip_id: &str) -> Result<(), String> {
+        self.talk_gate(strip_id)?.press();
+        Ok(())
+    }
+
+    /// Talk-Taste eines Strips losgelassen (startet den Nachlauf)
+    pub fn talk_release(&mut self, strip_id: &str) -> Result<(), String> {
+        let sample_rate = self.sample_rate;
+        self.talk_gate(strip_id)?.release(sample_rate);
+        Ok(())
+    }
+
+    /// Zustand aller Talk-Gates (sortiert nach Strip-ID)
+    pub fn talk_status(&self) -> Vec<TalkStatus> {
+        let mut status: Vec<TalkStatus> =
+            self.talk.iter().map(|(id, gate)| gate.status(id)).collect();
+        status.sort_by(|a, b| a.strip_id.cmp(&b.strip_id));
+        status
+    }
+
+    /// Talk-Gate eines Strips (Fehler wenn der Strip keinen Talk-Modus hat)
+    fn talk_gate(&mut self, strip_id: &str) -> Result<&mut TalkGate, String> {
+        self.talk
+            .get_mut(strip_id)
+            .ok_or_else(|| format!("Strip '{}' hat keinen Talk-Modus", strip_id))
+    }
+
+    /// Sample-Rate des Audio-Graphen setzen (Hz)
+    ///
+    /// Talk-Nachlauf und -Fades rechnen ab sofort in der neuen Rate; die
+    /// Bus-Delays werden mit ihrer bisherigen Zeit, die Bus-Inserts mit ihren
+    /// Einstellungen neu angelegt. Die Master-Limiter entstehen neu.
+    pub fn set_sample_rate(&mut self, sample_rate: u32) {
+        if sample_rate == 0 || sample_rate == self.sample_rate {
+            return;
+        }
+        info!("Mix-Engine Sample-Rate: {} Hz", sample_rate);
+        self.sample_rate = sample_rate;
+        self.automation
+            .set_engine_rate(sample_rate, self.frames_processed);
+        for delay in self.delays.values_mut() {
+            let delay_ms = delay.delay_ms();
+            *delay = DelayLine::new(sample_rate);
+            delay.set_delay_ms(delay_ms);
+        }
+        for (bus_id, chain) in self.bus_fx.iter_mut() {
+            let modules = chain.get_all_modules();
+            *chain = BusFxChain::with_sample_rate(sample_rate as f32);
+            if let Err(e) = chain.apply_modules(&modules) {
+                warn!("Bus {}: Inserts nicht übernommen: {}", bus_id, e);
+            }
+        }
+        if let Some(master) = self.master.as_mut() {
+            master.set_sample_rate(sample_rate);
+        }
+    }
+
+    /// Sample-Rate des Audio-Graphen (Hz)
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Bus-Ausgänge als Monitor-Taps veröffentlichen (an/aus)
+    pub fn set_publish_outputs(&mut self, publish: bool) {
+        self.publish_outputs = publish;
+    }
+
+    /// Alle vollständigen Blöcke verarbeiten
+    ///
+    /// Ein Block wird gemischt sobald mindestens ein Eingang oder eine
+    /// Bus-Rückkehr einen vollen Block bereithält; Quellen mit weniger Daten
+    /// werden mit Stille aufgefüllt. Jeder fertige Bus-Block geht an die Taps
+    /// des Busses im `hub` und an die Wiedergabe in den Bus-Node.
+    ///
+    /// # Returns
+    /// Anzahl verarbeiteter Blöcke
+    pub fn process(&mut self, hub: &mut TapHub) -> usize {
+        let mut blocks = 0;
+        while self
+            .inputs
+            .values()
+            .any(|tap| tap.available() >= MIX_BLOCK_FRAMES * 2)
+            || self
+                .returns
+                .values()
+                .any(|ret| ret.available() >= MIX_BLOCK_FRAMES * 2)
+        {
+            self.process_block();
+            if self.publish_outputs {
+                for (bus_id, out) in &self.outputs {
+                    hub.set_sample_rate(bus_id, self.sample_rate);
+                    hub.distribute(bus_id, out);
+                }
+            }
+            for (bus_id, writer) in self.playback.iter_mut() {
+                if let Some(out) = self.outputs.get(bus_id) {
+                    writer.write(out);
+                }
+            }
+            blocks += 1;
+        }
+        blocks
+    }
+
+    /// Automation-Transport (Position = `frames_processed`)
+    pub fn automation_mut(&mut self) -> &mut AutomationEngine {
+        &mut self.automation
+    }
+
+    /// Seit der letzten Abfrage angewendete Automation-Kommandos abholen
+    ///
+    /// Die Engine hat sie bereits blockgenau übernommen; die Manager
+    /// spiegeln sie für Anzeige, Sessions und die übrigen Ziele (FX).
+    pub fn take_automation_commands(&mut self) -> Vec<MixerCommand> {
+        std::mem::take(&mut self.automation_out)
+    }
+
+    /// Lane-Werte an der Block-Grenze lesen und auf die Mix-Parameter anwenden
+    fn run_automation(&mut self) {
+        let commands = self.automation.tick(self.frames_processed);
+        for command in &commands {
+            match command {
+                MixerCommand::StripVolume {
+                    strip_id,
+                    volume_db,
+                } => {
+                    if let Some(mix) = self.strips.get_mut(strip_id) {
+                        mix.volume_db = *volume_db;
+                        mix.update_levels();
+                    }
+                }
+                MixerCommand::StripGain { strip_id, gain_db } => {
+                    if let Some(mix) = self.strips.get_mut(strip_id) {
+                        mix.gain_db = *gain_db;
+                        mix.update_levels();
+                    }
+                }
+                MixerCommand::StripMute { strip_id, muted } => {
+                    if let Some(mix) = self.strips.get_mut(strip_id) {
+                        mix.muted = *muted;
+                    }
+                }
+                MixerCommand::BusVolume { bus_id, volume_db } => {
+                    self.set_bus_volume(bus_id, *volume_db);
+                }
+                MixerCommand::BusMute { bus_id, muted } => {
+                    if let Some(bus) = self.buses.get_mut(bus_id) {
+                        bus.muted = *muted;
+                    }
+                }
+                MixerCommand::MasterVolume { volume_db } => {
+                    if let Some(master) = self.master.as_mut() {
+                        master.set_volume(*volume_db);
+                    }
+                }
+                // FX-Ziele laufen nicht in der Engine (nur über die Manager)
+                _ => {}
+            }
+        }
+        self.automation_out.extend(commands);
+    }
+
+    /// Einen Block aller Eingänge in die Busse mischen
+    fn process_block(&mut self) {
+        let block_len = MIX_BLOCK_FRAMES * 2;
+        self.run_automation();
+
+        for bus_id in self.buses.keys() {
+            let out = self.outputs.entry(bus_id.clone()).or_default();
+            out.clear();
+            out.resize(block_len, 0.0);
+        }
+
+        // Rückkehr der Summen-Nodes (App-Links) vor den Inserts addieren
+        for (bus_id, ret) in self.returns.iter_mut() {
+            let read = ret.read(&mut self.scratch[..block_len]);
+            if let Some(out) = self.outputs.get_mut(bus_id) {
+                out.iter_mut()
+                    .zip(&self.scratch[..read])
+                    .for_each(|(o, s)| *o += s);
+            }
+        }
+
+        let any_solo = self.strips.values().any(|s| s.solo);
+
+        for gate in self.talk.values_mut() {
+            gate.advance(MIX_BLOCK_FRAMES, self.sample_rate);
+        }
+
+        self.talkback.fill(0.0);
+        let talkback_source = self.master.as_ref().and_then(|m| m.talkback_source());
+
+        for (strip_id, tap) in self.inputs.iter_mut() {
+            let read = tap.read(&mut self.scratch[..block_len]);
+            self.scratch[read..].fill(0.0);
+
+            // Talkback-Quelle vor Mute/Solo abgreifen
+            if talkback_source == Some(strip_id.as_str()) {
+                let trim = self.strips.get(strip_id).map_or(1.0, |mix| mix.trim);
+                for (t, s) in self.talkback.iter_mut().zip(&self.scratch) {
+                    *t = s * trim;
+                }
+            }
+
+            let mix = match self.strips.get(strip_id) {
+                Some(mix) if !mix.muted && (!any_solo || mix.solo) => mix,
+                _ => continue,
+            };
+
+            let gate = self.talk.get(strip_id);
+
+            for bus_id in &mix.buses {
+                let out = match self.outputs.get_mut(bus_id) {
+                    Some(out) => out,
+                    None => continue,
+                };
+                match gate.filter(|g| g.settings().applies_to(bus_id)) {
+                    Some(gate) => {
+                        let (start, end) = gate.ramp();
+                        if start == 0.0 && end == 0.0 {
+                            continue;
+                        }
+                        let step = (end - start) / MIX_BLOCK_FRAMES as f32;
+                        for (i, (frame, input)) in out
+                            .chunks_exact_mut(2)
+                            .zip(self.scratch.chunks_exact(2))
+                            .enumerate()
+                        {
+                            let g = start + step * i as f32;
+                            frame[0] += input[0] * mix.gain_l * g;
+                            frame[1] += input[1] * mix.gain_r * g;
+                        }
+                    }
+                    None => {
+                        for (frame, input) in
+                            out.chunks_exact_mut(2).zip(self.scratch.chunks_exact(2))
+                        {
+                            frame[0] += input[0] * mix.gain_l;
+                            frame[1] += input[1] * mix.gain_r;
+                        }
+                    }
+                }
+            }
+        }
+
+        for bus_id in &self.bus_order {
+            let mut out = match self.outputs.get_mut(bus_id) {
+                Some(out) => std::mem::take(out),
+                None => continue,
+            };
+
+            if let Some(chain) = self.bus_fx.get_mut(bus_id).filter(|c| c.is_active()) {
+                for (i, frame) in out.chunks_exact(2).enumerate() {
+                    self.fx_l[i] = frame[0];
+                    self.fx_r[i] = frame[1];
+                }
+                chain.process(&mut self.fx_l, &mut self.fx_r);
+                for (i, frame) in out.chunks_exact_mut(2).enumerate() {
+                    frame[0] = self.fx_l[i];
+                    frame[1] = self.fx_r[i];
+                }
+            }
+
+            let gain = match self.buses.get(bus_id) {
+                Some(bus) if !bus.muted => bus.gain,
+                _ => 0.0,
+            };
+            out.iter_mut().for_each(|s| *s *= gain);
+
+            if let Some(delay) = self.delays.get_mut(bus_id) {
+                delay.process(&mut out);
+            }
+
+            // Submix: fertigen Bus in die Ziel-Busse addieren
+            for (target, gain) in self.bus_routes.get(bus_id).into_iter().flatten() {
+                if let Some(dest) = self.outputs.get_mut(target) {
+                    dest.iter_mut().zip(&out).for_each(|(d, s)| *d += s * gain);
+                }
+            }
+
+            if let Some(master) = self.master.as_mut() {
+                master.process(bus_id, &mut out, &self.talkback);
+            }
+            if let Some(slot) = self.outputs.get_mut(bus_id) {
+                *slot = out;
+            }
+        }
+
+        self.frames_processed += MIX_BLOCK_FRAMES as u64;
+    }
+
+    /// Zuletzt berechneten Block eines Busses abfragen (Stereo interleaved)
+    pub fn bus_output(&self, bus_id: &str) -> Option<&[f32]> {
+        self.outputs.get(bus_id).map(|b| b.as_slice())
+    }
+
+    /// Anzahl verarbeiteter Frames seit Start
+    pub fn frames_processed(&self) -> u64 {
+        self.frames_processed
+    }
+}
+
+impl Default for MixEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::bus::BusManager;
+    use crate::audio::master::DEFAULT_TALKBACK_DIM_DB;
+    use crate::audio::mixer::MixerState;
+    use crate::audio::tap::{ring, TapHub, TapKind, TAP_RING_SAMPLES};
+    use crate::automation::{AutomationClip, AutomationLane, AutomationTarget};
+    use crate::fx::FxModuleType;
+
+    /// Engine mit Standard-Strips, -Bussen und Taps für alle Strips
+    fn setup() -> (MixEngine, TapHub, MixerState, BusManager) {
+        let mixer = MixerState::new();
+        let buses = BusManager::new();
+        let mut hub = TapHub::new();
+        let mut engine = MixEngine::new();
+        engine.update_strips(&mixer.get_strips());
+        engine.update_buses(&buses.get_buses());
+        for strip in mixer.get_strips() {
+            engine.attach_input(hub.subscribe(&strip.id, TapKind::MixEngine));
+        }
+        (engine, hub, mixer, buses)
+    }
+
+    /// Konstantes Stereo-Signal für einen Block
+    fn block(value: f32) -> Vec<f32> {
+        vec![value; MIX_BLOCK_FRAMES * 2]
+    }
+
+    #[test]
+    fn test_no_processing_without_full_block() {
+        let (mut engine, mut hub, _, _) = setup();
+        hub.distribute("hw-mic", &[0.5; 10]);
+        assert_eq!(engine.process(&mut hub), 0);
+        assert_eq!(engine.frames_processed(), 0);
+    }
+
+    #[test]
+    fn test_strip_routed_to_bus() {
+        let (mut engine, mut hub, _, _) = setup();
+        // hw-mic ist standardmäßig auf A1 geroutet
+        hub.distribute("hw-mic", &block(0.5));
+        assert_eq!(engine.process(&mut hub), 1);
+
+        let a1 = engine.bus_output("A1").unwrap();
+        assert!((a1[0] - 0.5).abs() < 0.001);
+        let b1 = engine.bus_output("B1").unwrap();
+        assert_eq!(b1[0], 0.0);
+    }
+
+    #[test]
+    fn test_strips_are_summed() {
+        let (mut engine, mut hub, _, _) = setup();
+        hub.distribute("hw-mic", &block(0.25));
+        hub.distribute("hw-headset", &block(0.25));
+        engine.process(&mut hub);
+
+        let a1 = engine.bus_output("A1").unwrap();
+        assert!((a1[0] - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_strip_mute_and_volume() {
+        let (mut engine, mut hub, mut mixer, _) = setup();
+        mixer.set_mute("hw-mic", true).unwrap();
+        mixer.set_volume("hw-headset", -6.0).unwrap();
+        engine.update_strips(&mixer.get_strips());
+
+        hub.distribute("hw-mic", &block(0.5));
+        hub.distribute("hw-headset", &block(0.5));
+        engine.process(&mut hub);
+
+        let a1 = engine.bus_output("A1").unwrap();
+        assert!((a1[0] - 0.25).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_solo_silences_other_strips() {
+        let (mut engine, mut hub, mut mixer, _) = setup();
+        mixer.set_solo("hw-headset", true).unwrap();
+        engine.update_strips(&mixer.get_strips());
+
+        hub.distribute("hw-mic", &block(0.5));
+        hub.distribute("hw-headset", &block(0.1));
+        engine.process(&mut hub);
+
+        let a1 = engine.bus_output("A1").unwrap();
+        assert!((a1[0] - 0.1).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_pan_hard_left() {
+        let (mut engine, mut hub, mut mixer, _) = setup();
+        mixer.set_pan("hw-mic", -1.0).unwrap();
+        engine.update_strips(&mixer.get_strips());
+
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+
+        let a1 = engine.bus_output("A1").unwrap();
+        assert!((a1[0] - 0.5).abs() < 0.001);
+        assert_eq!(a1[1], 0.0);
+    }
+
+    #[test]
+    fn test_bus_mute() {
+        let (mut engine, mut hub, _, mut buses) = setup();
+        buses.set_mute("A1", true).unwrap();
+        engine.update_buses(&buses.get_buses());
+
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+        assert_eq!(engine.bus_output("A1").unwrap()[0], 0.0);
+    }
+
+    #[test]
+    fn test_submix_post_fader() {
+        let (mut engine, mut hub, mut mixer, mut buses) = setup();
+        mixer.set_bus_routing("hw-mic", "B2", true).unwrap();
+        engine.update_strips(&mixer.get_strips());
+        buses.set_volume("B2", -6.0).unwrap();
+        engine.update_buses(&buses.get_buses());
+        // B2 (VoIP-Mix) speist A2; Reihenfolge absichtlich Ziel vor Quelle
+        // in der Registry — die Engine folgt der übergebenen Ordnung
+        let order: Vec<String> = ["A1", "B1", "B2", "A2"].map(String::from).to_vec();
+        engine.update_bus_routes(&order, &[("B2".to_string(), "A2".to_string(), 0.0)]);
+
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+
+        let b2 = engine.bus_output("B2").unwrap()[0];
+        let a2 = engine.bus_output("A2").unwrap()[0];
+        assert!((b2 - 0.25).abs() < 0.01);
+        assert!((a2 - b2).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_submix_crosspoint_gain() {
+        let (mut engine, mut hub, mut mixer, _) = setup();
+        mixer.set_bus_routing("hw-mic", "B2", true).unwrap();
+        engine.update_strips(&mixer.get_strips());
+        let order: Vec<String> = ["B2", "A1", "B1", "A2"].map(String::from).to_vec();
+        engine.update_bus_routes(&order, &[("B2".to_string(), "B1".to_string(), -6.0)]);
+
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+
+        let b2 = engine.bus_output("B2").unwrap()[0];
+        let b1 = engine.bus_output("B1").unwrap()[0];
+        assert!((b1 - b2 * 0.501).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_bus_insert_limiter() {
+        let (mut engine, mut hub, _, mut buses) = setup();
+        buses
+            .set_insert_param("A1", FxModuleType::Limiter, "ceiling", -12.0)
+            .unwrap();
+        buses
+            .set_insert_bypass("A1", FxModuleType::Limiter, false)
+            .unwrap();
+        engine.update_buses(&buses.get_buses());
+
+        for _ in 0..8 {
+            hub.distribute("hw-mic", &block(0.9));
+            engine.process(&mut hub);
+        }
+        let ceiling = db_to_linear(-12.0);
+        assert!(engine
+            .bus_output("A1")
+            .unwrap()
+            .iter()
+            .all(|s| s.abs() <= ceiling + 1e-3));
+    }
+
+    #[test]
+    fn test_bus_return_played_through_inserts() {
+        let (mut engine, mut hub, _, mut buses) = setup();
+        buses
+            .set_insert_param("A1", FxModuleType::Limiter, "ceiling", -12.0)
+            .unwrap();
+        buses
+            .set_insert_bypass("A1", FxModuleType::Limiter, false)
+            .unwrap();
+        engine.update_buses(&buses.get_buses());
+
+        let (mut ret_writer, ret_reader) = ring(TAP_RING_SAMPLES);
+        let (play_writer, mut play_reader) = ring(TAP_RING_SAMPLES);
+        engine.attach_bus_output("A1", ret_reader, play_writer);
+
+        // Nur die Rückkehr liefert Daten — sie allein taktet die Engine
+        for _ in 0..8 {
+            ret_writer.write(&block(0.9));
+            assert_eq!(engine.process(&mut hub), 1);
+        }
+        let mut played = vec![0.0; MIX_BLOCK_FRAMES * 2 * 8];
+        assert_eq!(play_reader.read(&mut played), played.len());
+        let ceiling = db_to_linear(-12.0);
+        assert!(played.iter().all(|s| s.abs() <= ceiling + 1e-3));
+        assert!(played.iter().any(|s| s.abs() > 0.1));
+
+        engine.detach_bus_output("A1");
+        ret_writer.write(&block(0.9));
+        assert_eq!(engine.process(&mut hub), 0);
+    }
+
+    #[test]
+    fn test_input_backlog_is_bounded() {
+        let (mut engine, mut hub, _, _) = setup();
+        hub.distribute("hw-mic", &vec![0.1; TAP_RING_SAMPLES * 2]);
+        // Maximal eine Tap-Kapazität wird gepuffert, der Rest zählt als Overflow
+        assert_eq!(
+            engine.process(&mut hub),
+            TAP_RING_SAMPLES / (MIX_BLOCK_FRAMES * 2)
+        );
+        let mic = hub
+            .stats()
+            .into_iter()
+            .find(|t| t.source_id == "hw-mic")
+            .unwrap();
+        assert_eq!(mic.overflow_samples, TAP_RING_SAMPLES as u64);
+    }
+
+    #[test]
+    fn test_bus_output_published_to_taps() {
+        let (mut engine, mut hub, _, _) = setup();
+        let mut monitor = hub.subscribe("A1", TapKind::Recording);
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+
+        let mut out = block(0.0);
+        assert_eq!(monitor.read(&mut out), MIX_BLOCK_FRAMES * 2);
+        assert!((out[0] - 0.5).abs() < 0.001);
+
+        // Monitor-Quelle PipeWire: Engine verteilt nichts
+        engine.set_publish_outputs(false);
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+        assert_eq!(monitor.available(), 0);
+    }
+
+    #[test]
+    fn test_push_to_talk_gates_scoped_bus() {
+        use crate::audio::talk::TalkSettings;
+
+        let (mut engine, mut hub, mut mixer, _) = setup();
+        mixer.set_bus_routing("hw-mic", "B1", true).unwrap();
+        mixer.set_bus_routing("hw-mic", "B2", true).unwrap();
+        let talk = TalkSettings {
+            mode: TalkMode::PushToTalk,
+            release_tail_ms: 0,
+            fade_ms: 0,
+            bus_ids: vec!["B2".to_string()],
+        };
+        mixer.set_talk("hw-mic", talk).unwrap();
+        engine.update_strips(&mixer.get_strips());
+        assert!(engine.talk_press("hw-headset").is_err());
+
+        // Nicht gedrückt: B2 stumm, B1 live
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+        assert!(engine.bus_output("B2").unwrap().iter().all(|&s| s == 0.0));
+        assert!((engine.bus_output("B1").unwrap()[0] - 0.5).abs() < 1e-6);
+
+        // Gedrückt: B2 offen
+        engine.talk_press("hw-mic").unwrap();
+        assert!(engine.talk_status()[0].open);
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+        assert!((engine.bus_output("B2").unwrap()[0] - 0.5).abs() < 1e-6);
+
+        // Losgelassen ohne Nachlauf: wieder stumm
+        engine.talk_release("hw-mic").unwrap();
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+        assert!(engine.bus_output("B2").unwrap().iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_talk_tail_uses_configured_rate() {
+        use crate::audio::talk::TalkSettings;
+
+        let (mut engine, mut hub, mut mixer, _) = setup();
+        let talk = TalkSettings {
+            mode: TalkMode::PushToTalk,
+            release_tail_ms: 10,
+            fade_ms: 0,
+            bus_ids: vec![],
+        };
+        mixer.set_talk("hw-mic", talk).unwrap();
+        engine.update_strips(&mixer.get_strips());
+        engine.set_sample_rate(96000);
+
+        // 10ms @ 96kHz = 960 Frames — nach 3 Blöcken (768 Frames) noch offen
+        engine.talk_press("hw-mic").unwrap();
+        engine.talk_release("hw-mic").unwrap();
+        for _ in 0..3 {
+            hub.distribute("hw-mic", &block(0.5));
+            engine.process(&mut hub);
+        }
+        assert!(engine.talk_status()[0].open);
+
+        for _ in 0..2 {
+            hub.distribute("hw-mic", &block(0.5));
+            engine.process(&mut hub);
+        }
+        assert!(!engine.talk_status()[0].open);
+    }
+
+    #[test]
+    fn test_master_stage_talkback() {
+        let (mut engine, mut hub, mut mixer, _) = setup();
+        // Mikro ist stumm — Talkback greift trotzdem vor dem Mute ab
+        mixer.set_mute("hw-mic", true).unwrap();
+        mixer.set_bus_routing("hw-headset", "A2", true).unwrap();
+        engine.update_strips(&mixer.get_strips());
+        engine.update_master(&MasterState {
+            dim: true,
+            talkback: true,
+            talkback_buses: vec!["A2".to_string()],
+            ..MasterState::default()
+        });
+
+        // Look-Ahead des Master-Limiters: erst der zweite Block ist eingeschwungen
+        for _ in 0..2 {
+            hub.distribute("hw-mic", &block(0.25));
+            hub.distribute("hw-headset", &block(0.5));
+            engine.process(&mut hub);
+        }
+        let a2 = *engine.bus_output("A2").unwrap().last().unwrap();
+        let a1 = *engine.bus_output("A1").unwrap().last().unwrap();
+        // A2: Headset gedimmt (DIM + Talkback-Auto-DIM) + Talkback ungedimmt
+        let dimmed = 0.05 * db_to_linear(DEFAULT_TALKBACK_DIM_DB);
+        assert!((a2 - (dimmed + 0.25)).abs() < 0.001);
+        // A1: nur das Headset, gedimmt — kein Talkback
+        assert!((a1 - 0.05).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_removed_strip_input_dropped() {
+        let (mut engine, mut hub, mut mixer, _) = setup();
+        hub.distribute("virt-1", &block(0.5));
+        mixer.remove_virtual_strip("virt-1").unwrap();
+        engine.update_strips(&mixer.get_strips());
+        assert_eq!(engine.process(&mut hub), 0);
+    }
+
+    #[test]
+    fn test_automation_applied_at_block_boundary() {
+        let (mut engine, mut hub, _, _) = setup();
+        let mut lane = AutomationLane::new(AutomationTarget::BusMute {
+            bus_id: "A1".to_string(),
+        });
+        lane.write(0, 0.0);
+        lane.write(MIX_BLOCK_FRAMES as u64, 1.0);
+        let mut clip = AutomationClip::new("clip_1", "Mute");
+        clip.lanes.push(lane);
+        engine.automation_mut().load(clip);
+        engine.automation_mut().play(0).unwrap();
+
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+        assert!((engine.bus_output("A1").unwrap()[0] - 0.5).abs() < 0.001);
+
+        // Zweiter Block beginnt genau am Mute-Punkt
+        hub.distribute("hw-mic", &block(0.5));
+        engine.process(&mut hub);
+        assert_eq!(engine.bus_output("A1").unwrap()[0], 0.0);
+
+        let commands = engine.take_automation_commands();
+        assert_eq!(commands.len(), 2);
+        assert!(engine.take_automation_commands().is_empty());
+        // Clip-Ende erreicht: Transport steht
+        assert!(engine.automation_mut().take_ended());
+    }
+}
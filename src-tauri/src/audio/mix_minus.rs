@@ -0,0 +1,200 @@
+// Modul: audio/mix_minus — Mix-Minus-Busse (z.B. B2 für VoIP-Gäste)
+//
+// Ein Mix-Minus-Bus übernimmt das Routing eines Referenz-Busses ohne die
+// ausgeschlossenen Quellen — typischerweise der Rückweg der VoIP-App, damit
+// Gäste sich nicht selbst hören. Das Routing wird nicht von Hand gepflegt,
+// sondern nach jeder Änderung neu abgeleitet (Strips, Apps, Submixe).
+
+use super::mixer::InputStrip;
+use super::routing::RoutingEntry;
+use serde::{Deserialize, Serialize};
+
+/// Maximale Anzahl ausgeschlossener Quellen
+const MAX_EXCLUDED: usize = 16;
+
+/// Mix-Minus-Einstellung eines Busses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MixMinus {
+    /// Bus dessen Routing übernommen wird (z.B. B1)
+    pub reference_bus: String,
+    /// Quellen die fehlen (Strip-, App- oder Bus-IDs, z.B. "app-discord")
+    pub excluded: Vec<String>,
+}
+
+impl MixMinus {
+    /// Einstellung prüfen (`bus_id` = der Mix-Minus-Bus selbst)
+    pub fn validate(&self, bus_id: &str) -> Result<(), String> {
+        if self.reference_bus == bus_id {
+            return Err(format!(
+                "Bus {} kann nicht sein eigener Referenz-Bus sein",
+                bus_id
+            ));
+        }
+        if self.excluded.is_empty() {
+            return Err("Mix-Minus braucht mindestens eine ausgeschlossene Quelle".to_string());
+        }
+        if self.excluded.len() > MAX_EXCLUDED {
+            return Err(format!(
+                "Zu viele ausgeschlossene Quellen: {} (max {})",
+                self.excluded.len(),
+                MAX_EXCLUDED
+            ));
+        }
+        if self.excluded.iter().any(|s| s.trim().is_empty()) {
+            return Err("Source-ID darf nicht leer sein".to_string());
+        }
+        Ok(())
+    }
+
+    /// Gehört eine Quelle in den Mix-Minus-Bus?
+    fn includes(&self, source_id: &str, bus_id: &str) -> bool {
+        source_id != bus_id && !self.excluded.iter().any(|s| s == source_id)
+    }
+}
+
+/// Nötige Strip-Routing-Änderungen (Strip-ID, Bus-ID, aktiv)
+///
+/// `mix_minus` enthält alle Mix-Minus-Busse als (Bus-ID, Einstellung).
+pub fn strip_changes(
+    strips: &[InputStrip],
+    mix_minus: &[(String, MixMinus)],
+) -> Vec<(String, String, bool)> {
+    let mut changes = Vec::new();
+    for (bus_id, mm) in mix_minus {
+        for strip in strips {
+            let wanted =
+                mm.includes(&strip.id, bus_id) && strip.bus_routing.contains(&mm.reference_bus);
+            if strip.bus_routing.contains(bus_id) != wanted {
+                changes.push((strip.id.clone(), bus_id.clone(), wanted));
+            }
+        }
+    }
+    changes
+}
+
+/// Nötige Änderungen der Routing-Matrix (Apps, Submixe)
+///
+/// Der Kreuzpunkt-Gain wird vom Referenz-Bus übernommen.
+pub fn matrix_changes(
+    entries: &[RoutingEntry],
+    mix_minus: &[(String, MixMinus)],
+) -> Vec<RoutingEntry> {
+    let find = |source_id: &str, bus_id: &str| {
+        entries
+            .iter()
+            .find(|e| e.active && e.source_id == source_id && e.bus_id == bus_id)
+    };
+
+    let mut changes = Vec::new();
+    for (bus_id, mm) in mix_minus {
+        // Quellen des Referenz-Busses hinzufügen
+        for entry in entries
+            .iter()
+            .filter(|e| e.active && e.bus_id == mm.reference_bus)
+        {
+            let current = find(&entry.source_id, bus_id).map(|e| e.gain_db);
+            if mm.includes(&entry.source_id, bus_id) && current != Some(entry.gain_db) {
+                changes.push(RoutingEntry {
+                    bus_id: bus_id.clone(),
+                    ..entry.clone()
+                });
+            }
+        }
+        // Quellen entfernen die nicht (mehr) dazugehören
+        for entry in entries.iter().filter(|e| e.active && &e.bus_id == bus_id) {
+            let wanted = mm.includes(&entry.source_id, bus_id)
+                && find(&entry.source_id, &mm.reference_bus).is_some();
+            if !wanted {
+                changes.push(RoutingEntry {
+                    active: false,
+                    gain_db: 0.0,
+                    ..entry.clone()
+                });
+            }
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::mixer::MixerState;
+
+    fn voip_minus() -> Vec<(String, MixMinus)> {
+        vec![(
+            "B2".to_string(),
+            MixMinus {
+                reference_bus: "B1".to_string(),
+                excluded: vec!["app-discord".to_string(), "virt-1".to_string()],
+            },
+        )]
+    }
+
+    fn entry(source_id: &str, bus_id: &str) -> RoutingEntry {
+        RoutingEntry {
+            source_id: source_id.to_string(),
+            bus_id: bus_id.to_string(),
+            active: true,
+            gain_db: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_validate() {
+        let mm = &voip_minus()[0].1;
+        assert!(mm.validate("B2").is_ok());
+        assert!(mm.validate("B1").is_err());
+        let empty = MixMinus {
+            reference_bus: "B1".to_string(),
+            excluded: Vec::new(),
+        };
+        assert!(empty.validate("B2").is_err());
+    }
+
+    #[test]
+    fn test_strip_changes_follow_reference() {
+        let mut mixer = MixerState::new();
+        mixer.set_bus_routing("hw-mic", "B1", true).unwrap();
+        mixer.set_bus_routing("virt-1", "B1", true).unwrap();
+        mixer.set_bus_routing("hw-headset", "B2", true).unwrap();
+
+        let changes = strip_changes(&mixer.get_strips(), &voip_minus());
+        // Mikro kommt dazu, ausgeschlossener Strip nicht, fremder Strip fliegt raus
+        assert!(changes.contains(&("hw-mic".to_string(), "B2".to_string(), true)));
+        assert!(changes.contains(&("hw-headset".to_string(), "B2".to_string(), false)));
+        assert!(!changes.iter().any(|(id, _, _)| id == "virt-1"));
+
+        for (strip_id, bus_id, active) in &changes {
+            mixer.set_bus_routing(strip_id, bus_id, *active).unwrap();
+        }
+        assert!(strip_changes(&mixer.get_strips(), &voip_minus()).is_empty());
+    }
+
+    #[test]
+    fn test_matrix_changes() {
+        let quiet = |source_id: &str, bus_id: &str| RoutingEntry {
+            gain_db: -6.0,
+            ..entry(source_id, bus_id)
+        };
+        let entries = vec![
+            entry("app-discord", "B1"),
+            quiet("app-spotify", "B1"),
+            entry("app-game", "B2"),
+        ];
+        let changes = matrix_changes(&entries, &voip_minus());
+        assert_eq!(changes.len(), 2);
+        assert!(changes.contains(&quiet("app-spotify", "B2")));
+        assert!(changes.contains(&RoutingEntry {
+            active: false,
+            ..entry("app-game", "B2")
+        }));
+
+        // Geänderter Gain am Referenz-Bus wird nachgezogen
+        let entries = vec![quiet("app-spotify", "B1"), entry("app-spotify", "B2")];
+        assert_eq!(
+            matrix_changes(&entries, &voip_minus()),
+            vec![quiet("app-spotify", "B2")]
+        );
+    }
+}
@@ -1,4 +1,6 @@
 // Modul: audio/mixer — Input-Strips, Lautstärke-Kontrolle und Bus-Routing
+use super::bus::{BusType, DEFAULT_BUSES};
+use super::talk::TalkSettings;
 use log::info;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -13,6 +15,10 @@ const MAX_VOLUME_DB: f32 = 10.0;
 const MIN_GAIN_DB: f32 = -20.0;
 /// Maximaler Gain in dB
 const MAX_GAIN_DB: f32 = 20.0;
+/// Maximale Länge eines Strip-Namens (Zeichen)
+const MAX_LABEL_LEN: usize = 24;
+/// Maximale Länge eines Icons (Zeichen, Emoji können mehrere belegen)
+const MAX_ICON_LEN: usize = 8;
 
 /// Typ eines Input-Strips
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -24,7 +30,7 @@ pub enum StripType {
 }
 
 /// Ein Input-Strip im Mixer
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct InputStrip {
     /// Eindeutige Strip-ID
     pub id: String,
@@ -34,6 +40,9 @@ pub struct InputStrip {
     pub strip_type: StripType,
     /// Zugeordnete PipeWire-Device-ID (falls vorhanden)
     pub device_id: Option<u32>,
+    /// Gebundenes Capture-Device (Name, bleibt über Neustarts stabil)
+    #[serde(default)]
+    pub device_name: Option<String>,
     /// Lautstärke in dB (-50.0 bis +10.0)
     pub volume_db: f32,
     /// Gain in dB (-20.0 bis +20.0)
@@ -50,8 +59,14 @@ pub struct InputStrip {
     pub bus_routing: Vec<String>,
     /// Icon-Emoji für die Anzeige
     pub icon: String,
+    /// Akzentfarbe für die Anzeige ("#RRGGBB", None = Standardfarbe)
+    #[serde(default)]
+    pub color: Option<String>,
     /// Sortier-Reihenfolge
     pub order: u32,
+    /// Talk-Modus (PTT/PTM/Toggle) inkl. Nachlauf, Fade und Bus-Auswahl
+    #[serde(default)]
+    pub talk: TalkSettings,
 }
 
 impl InputStrip {
@@ -62,6 +77,7 @@ impl InputStrip {
             label: label.to_string(),
             strip_type: StripType::Hardware,
             device_id: None,
+            device_name: None,
             volume_db: 0.0,
             gain_db: 0.0,
             muted: false,
@@ -70,7 +86,9 @@ impl InputStrip {
             fx_enabled: false,
             bus_routing: vec!["A1".to_string()],
             icon: icon.to_string(),
+            color: None,
             order,
+            talk: TalkSettings::default(),
         }
     }
 
@@ -81,15 +99,18 @@ impl InputStrip {
             label: label.to_string(),
             strip_type: StripType::Virtual,
             device_id: None,
+            device_name: None,
             volume_db: 0.0,
             gain_db: 0.0,
             muted: false,
             solo: false,
             pan: 0.0,
             fx_enabled: false,
-            bus_routing: vec!["B1".to_string()],
+            bus_routing: Vec::new(),
             icon: icon.to_string(),
+            color: None,
             order,
+            talk: TalkSettings::default(),
         }
     }
 }
@@ -101,10 +122,12 @@ pub struct MixerState {
     strips: HashMap<String, InputStrip>,
     /// Zähler für nächste Virtual-Strip-ID
     next_virtual_id: u32,
+    /// Bekannte Bus-IDs (Abbild der Bus-Registry)
+    bus_ids: Vec<String>,
 }
 
 impl MixerState {
-    /// Neuen Mixer-State mit Standard-Strips erstellen
+    /// Neuen Mixer-State mit Standard-Strips erstellen (kennt die Standard-Busse)
     ///
     /// Standard: 3 Hardware-Strips (USB MIC, HEADSET, LINE IN)
     /// und 2 Virtual-Strips (VIRTUAL 1, VIRTUAL 2)
@@ -134,10 +157,88 @@ impl MixerState {
 
         info!("MixerState erstellt mit {} Strips", strips.len());
 
-        Self {
+        let mut state = Self {
             strips,
             next_virtual_id: 3,
+            bus_ids: default_bus_ids(),
+        };
+        if let Some(bus_id) = state.default_virtual_bus() {
+            for strip in state.strips.values_mut() {
+                if strip.strip_type == StripType::Virtual {
+                    strip.bus_routing.push(bus_id.clone());
+                }
+            }
+        }
+        state
+    }
+
+    /// Mixer-State aus gespeicherten Strips wiederherstellen (Session/Scene)
+    ///
+    /// Prüft IDs, Anzahl und Wertebereiche — ungültige Daten werden abgelehnt
+    /// statt still korrigiert, damit der Aufrufer auf Defaults zurückfallen kann.
+    pub fn from_strips(strips: Vec<InputStrip>) -> Result<Self, String> {
+        if strips.is_empty() || strips.len() > MAX_STRIPS {
+            return Err(format!(
+                "Ungültige Strip-Anzahl: {} (erlaubt: 1 bis {})",
+                strips.len(),
+                MAX_STRIPS
+            ));
+        }
+
+        let mut map = HashMap::new();
+        for strip in strips {
+            if strip.id.is_empty() {
+                return Err("Strip-ID darf nicht leer sein".to_string());
+            }
+            if !(MIN_VOLUME_DB..=MAX_VOLUME_DB).contains(&strip.volume_db)
+                || !(MIN_GAIN_DB..=MAX_GAIN_DB).contains(&strip.gain_db)
+                || !(-1.0..=1.0).contains(&strip.pan)
+            {
+                return Err(format!("Strip '{}': Wert außerhalb des Bereichs", strip.id));
+            }
+            if let Some(color) = &strip.color {
+                validate_color(color)?;
+            }
+            strip.talk.validate()?;
+            if map.contains_key(&strip.id) {
+                return Err(format!("Strip-ID doppelt: {}", strip.id));
+            }
+            map.insert(strip.id.clone(), strip);
+        }
+
+        // Nächste freie Virtual-ID hinter der höchsten vorhandenen
+        let next_virtual_id = map
+            .keys()
+            .filter_map(|id| id.strip_prefix("virt-")?.parse::<u32>().ok())
+            .max()
+            .map_or(1, |max| max + 1);
+
+        info!("MixerState wiederhergestellt mit {} Strips", map.len());
+
+        Ok(Self {
+            strips: map,
+            next_virtual_id,
+            bus_ids: default_bus_ids(),
+        })
+    }
+
+    /// Bekannte Busse aktualisieren (nach Anlegen/Löschen in der Bus-Registry)
+    ///
+    /// Strip-Zuweisungen auf entfernte Busse fallen weg.
+    pub fn set_bus_ids(&mut self, bus_ids: &[String]) {
+        for strip in self.strips.values_mut() {
+            strip.bus_routing.retain(|id| bus_ids.contains(id));
         }
+        self.bus_ids = bus_ids.to_vec();
+    }
+
+    /// Standard-Ziel neuer Virtual-Strips: erster virtueller Bus der Registry
+    fn default_virtual_bus(&self) -> Option<String> {
+        let prefix = BusType::Virtual.id_prefix();
+        self.bus_ids
+            .iter()
+            .find(|id| id.starts_with(prefix))
+            .cloned()
     }
 
     /// Alle Strips als sortierte Liste zurückgeben
@@ -203,6 +304,9 @@ impl MixerState {
         bus_id: &str,
         active: bool,
     ) -> Result<(), String> {
+        if active && !self.bus_ids.iter().any(|id| id == bus_id) {
+            return Err(format!("Ungültige Bus-ID: {}", bus_id));
+        }
         let strip = self
             .strips
             .get_mut(strip_id)
@@ -240,6 +344,156 @@ impl MixerState {
         Ok(())
     }
 
+    /// Strip umbenennen (Leerzeichen am Rand werden entfernt)
+    pub fn set_label(&mut self, strip_id: &str, label: &str) -> Result<(), String> {
+        let label = validate_label(label)?;
+        let strip = self
+            .strips
+            .get_mut(strip_id)
+            .ok_or_else(|| format!("Strip '{}' nicht gefunden", strip_id))?;
+
+        info!(
+            "Strip '{}' umbenannt: '{}' → '{}'",
+            strip_id, strip.label, label
+        );
+        strip.label = label;
+        Ok(())
+    }
+
+    /// Icon eines Strips setzen
+    pub fn set_icon(&mut self, strip_id: &str, icon: &str) -> Result<(), String> {
+        let icon = icon.trim();
+        if icon.is_empty() || icon.chars().count() > MAX_ICON_LEN {
+            return Err(format!(
+                "Ungültiges Icon (1 bis {} Zeichen erlaubt)",
+                MAX_ICON_LEN
+            ));
+        }
+
+        let strip = self
+            .strips
+            .get_mut(strip_id)
+            .ok_or_else(|| format!("Strip '{}' nicht gefunden", strip_id))?;
+
+        strip.icon = icon.to_string();
+        Ok(())
+    }
+
+    /// Akzentfarbe eines Strips setzen (None = Standardfarbe)
+    pub fn set_color(&mut self, strip_id: &str, color: Option<&str>) -> Result<(), String> {
+        if let Some(color) = color {
+            validate_color(color)?;
+        }
+
+        let strip = self
+            .strips
+            .get_mut(strip_id)
+            .ok_or_else(|| format!("Strip '{}' nicht gefunden", strip_id))?;
+
+        strip.color = color.map(|c| c.to_ascii_uppercase());
+        Ok(())
+    }
+
+    /// Strips neu anordnen
+    ///
+    /// `strip_ids` muss jeden Strip genau einmal enthalten; `order` wird
+    /// lückenlos ab 0 neu vergeben. Bei ungültiger Liste bleibt alles unverändert.
+    pub fn reorder(&mut self, strip_ids: &[String]) -> Result<(), String> {
+        if strip_ids.len() != self.strips.len() {
+            return Err(format!(
+                "Reihenfolge muss alle {} Strips enthalten (erhalten: {})",
+                self.strips.len(),
+                strip_ids.len()
+            ));
+        }
+        for (i, id) in strip_ids.iter().enumerate() {
+            if !self.strips.contains_key(id) {
+                return Err(format!("Strip '{}' nicht gefunden", id));
+            }
+            if strip_ids[..i].contains(id) {
+                return Err(format!("Strip '{}' doppelt in der Reihenfolge", id));
+            }
+        }
+
+        for (order, id) in strip_ids.iter().enumerate() {
+            if let Some(strip) = self.strips.get_mut(id) {
+                strip.order = order as u32;
+            }
+        }
+        info!("Strips neu angeordnet: {:?}", strip_ids);
+        Ok(())
+    }
+
+    /// Aktuelle Reihenfolge als Liste von Strip-IDs
+    pub fn strip_order(&self) -> Vec<String> {
+        self.get_strips().into_iter().map(|s| s.id).collect()
+    }
+
+    /// PipeWire-Device (Node-ID) an einen Strip binden (None = lösen)
+    pub fn set_pw_device(&mut self, strip_id: &str, device_id: Option<u32>) -> Result<(), String> {
+        let strip = self
+            .strips
+            .get_mut(strip_id)
+            .ok_or_else(|| format!("Strip '{}' nicht gefunden", strip_id))?;
+
+        strip.device_id = device_id;
+        match device_id {
+            Some(id) => info!("Strip '{}' an PipeWire-Node {} gebunden", strip_id, id),
+            None => info!("PipeWire-Bindung von Strip '{}' gelöst", strip_id),
+        }
+        Ok(())
+    }
+
+    /// Talk-Modus eines Strips setzen
+    pub fn set_talk(&mut self, strip_id: &str, talk: TalkSettings) -> Result<(), String> {
+        talk.validate()?;
+        let strip = self
+            .strips
+            .get_mut(strip_id)
+            .ok_or_else(|| format!("Strip '{}' nicht gefunden", strip_id))?;
+
+        info!("Strip '{}': Talk-Modus {:?}", strip_id, talk.mode);
+        strip.talk = talk;
+        Ok(())
+    }
+
+    /// Capture-Device an einen Strip binden
+    pub fn bind_device(&mut self, strip_id: &str, device_name: &str) -> Result<(), String> {
+        if device_name.trim().is_empty() {
+            return Err("Device-Name darf nicht leer sein".to_string());
+        }
+
+        let strip = self
+            .strips
+            .get_mut(strip_id)
+            .ok_or_else(|| format!("Strip '{}' nicht gefunden", strip_id))?;
+
+        strip.device_name = Some(device_name.to_string());
+        info!("Strip '{}' an Device '{}' gebunden", strip_id, device_name);
+        Ok(())
+    }
+
+    /// Device-Bindung eines Strips lösen
+    pub fn unbind_device(&mut self, strip_id: &str) -> Result<(), String> {
+        let strip = self
+            .strips
+            .get_mut(strip_id)
+            .ok_or_else(|| format!("Strip '{}' nicht gefunden", strip_id))?;
+
+        strip.device_name = None;
+        strip.device_id = None;
+        info!("Device-Bindung von Strip '{}' gelöst", strip_id);
+        Ok(())
+    }
+
+    /// Alle Strips mit gebundenem Device (Strip-ID → Device-Name)
+    pub fn capture_bindings(&self) -> HashMap<String, String> {
+        self.strips
+            .values()
+            .filter_map(|s| s.device_name.clone().map(|d| (s.id.clone(), d)))
+            .collect()
+    }
+
     /// Neuen Virtual-Strip hinzufügen (max 10 Strips gesamt)
     pub fn add_virtual_strip(&mut self) -> Result<InputStrip, String> {
         if self.strips.len() >= MAX_STRIPS {
@@ -252,7 +506,8 @@ impl MixerState {
         let id = format!("virt-{}", self.next_virtual_id);
         let label = format!("VIRTUAL {}", self.next_virtual_id);
         let order = self.strips.len() as u32;
-        let strip = InputStrip::new_virtual(&id, &label, "◇", order);
+        let mut strip = InputStrip::new_virtual(&id, &label, "◇", order);
+        strip.bus_routing.extend(self.default_virtual_bus());
 
         self.strips.insert(id, strip.clone());
         self.next_virtual_id += 1;
@@ -283,12 +538,63 @@ impl MixerState {
         Ok(())
     }
 
+    /// Entfernten Strip unverändert wieder einfügen (Undo/Redo)
+    ///
+    /// Zuweisungen auf inzwischen gelöschte Busse fallen weg.
+    pub fn restore_strip(&mut self, mut strip: InputStrip) -> Result<(), String> {
+        if self.strips.contains_key(&strip.id) {
+            return Err(format!("Strip '{}' existiert bereits", strip.id));
+        }
+        if self.strips.len() >= MAX_STRIPS {
+            return Err(format!(
+                "Maximale Anzahl von {} Strips erreicht",
+                MAX_STRIPS
+            ));
+        }
+
+        if let Some(n) = strip
+            .id
+            .strip_prefix("virt-")
+            .and_then(|n| n.parse::<u32>().ok())
+        {
+            self.next_virtual_id = self.next_virtual_id.max(n + 1);
+        }
+        let bus_ids = &self.bus_ids;
+        strip.bus_routing.retain(|id| bus_ids.contains(id));
+        info!("Strip '{}' wiederhergestellt", strip.id);
+        self.strips.insert(strip.id.clone(), strip);
+        Ok(())
+    }
+
     /// Anzahl der aktuellen Strips
     pub fn strip_count(&self) -> usize {
         self.strips.len()
     }
 }
 
+/// Strip-Namen prüfen und bereinigt zurückgeben
+pub fn validate_label(label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() || label.chars().count() > MAX_LABEL_LEN {
+        return Err(format!(
+            "Ungültiger Strip-Name (1 bis {} Zeichen erlaubt)",
+            MAX_LABEL_LEN
+        ));
+    }
+    Ok(label.to_string())
+}
+
+/// Farbwert im Format "#RRGGBB" prüfen
+fn validate_color(color: &str) -> Result<(), String> {
+    let valid = color.len() == 7
+        && color.starts_with('#')
+        && color[1..].chars().all(|c| c.is_ascii_hexdigit());
+    if !valid {
+        return Err(format!("Ungültige Farbe '{}' (erwartet: #RRGGBB)", color));
+    }
+    Ok(())
+}
+
 /// dB-Wert in linearen Faktor umrechnen
 pub fn db_to_linear(db: f32) -> f32 {
     10.0_f32.powf(db / 20.0)
@@ -303,6 +609,14 @@ pub fn linear_to_db(linear: f32) -> f32 {
     }
 }
 
+/// Bus-IDs der Standard-Busse (bis die Registry übergeben wird)
+fn default_bus_ids() -> Vec<String> {
+    DEFAULT_BUSES
+        .iter()
+        .map(|(id, _, _)| id.to_string())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -478,6 +792,19 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_restore_strip() {
+        let mut state = MixerState::new();
+        let strip = state.add_virtual_strip().unwrap();
+        state.remove_virtual_strip(&strip.id).unwrap();
+        state.restore_strip(strip.clone()).unwrap();
+        assert_eq!(state.get_strip(&strip.id), Some(&strip));
+        // Doppelt einfügen schlägt fehl
+        assert!(state.restore_strip(strip).is_err());
+        // Nächste Virtual-ID kollidiert nicht mit dem wiederhergestellten Strip
+        assert_eq!(state.add_virtual_strip().unwrap().id, "virt-4");
+    }
+
     #[test]
     fn test_hardware_strip_defaults() {
         let strip = InputStrip::new_hardware("test", "TEST", "🎙️", 0);
@@ -495,7 +822,23 @@ mod tests {
     fn test_virtual_strip_defaults() {
         let strip = InputStrip::new_virtual("test", "TEST", "◆", 0);
         assert_eq!(strip.strip_type, StripType::Virtual);
-        assert_eq!(strip.bus_routing, vec!["B1".to_string()]);
+        assert!(strip.bus_routing.is_empty());
+
+        // Im Mixer: erster virtueller Bus der Registry, sonst ohne Ziel
+        let mut state = MixerState::new();
+        assert_eq!(state.add_virtual_strip().unwrap().bus_routing, vec!["B1"]);
+        state.set_bus_ids(&["A1".to_string()]);
+        assert!(state.add_virtual_strip().unwrap().bus_routing.is_empty());
+        assert!(state.get_strip("virt-1").unwrap().bus_routing.is_empty());
+    }
+
+    #[test]
+    fn test_bus_routing_rejects_unknown_bus() {
+        let mut state = MixerState::new();
+        let result = state.set_bus_routing("hw-mic", "B7", true);
+        assert!(result.unwrap_err().contains("Ungültige Bus-ID"));
+        // Trennen geht immer
+        state.set_bus_routing("hw-mic", "B7", false).unwrap();
     }
 
     #[test]
@@ -508,6 +851,111 @@ mod tests {
         assert!(json_str.contains("Hardware"));
     }
 
+    #[test]
+    fn test_bind_device() {
+        let mut mixer = MixerState::new();
+        assert!(mixer.capture_bindings().is_empty());
+
+        mixer
+            .bind_device("hw-mic", "Yeti Stereo Microphone")
+            .unwrap();
+        let bindings = mixer.capture_bindings();
+        assert_eq!(bindings.len(), 1);
+        assert_eq!(bindings.get("hw-mic").unwrap(), "Yeti Stereo Microphone");
+    }
+
+    #[test]
+    fn test_bind_device_invalid() {
+        let mut mixer = MixerState::new();
+        assert!(mixer.bind_device("hw-mic", "  ").is_err());
+        assert!(mixer.bind_device("nonexistent", "Yeti").is_err());
+    }
+
+    #[test]
+    fn test_unbind_device() {
+        let mut mixer = MixerState::new();
+        mixer.bind_device("hw-mic", "Yeti").unwrap();
+        mixer.unbind_device("hw-mic").unwrap();
+        assert!(mixer.capture_bindings().is_empty());
+        assert!(mixer.get_strip("hw-mic").unwrap().device_name.is_none());
+    }
+
+    #[test]
+    fn test_set_label_and_icon() {
+        let mut state = MixerState::new();
+        state.set_label("hw-mic", "  Host Mic ").unwrap();
+        assert_eq!(state.get_strip("hw-mic").unwrap().label, "Host Mic");
+        assert!(state.set_label("hw-mic", "   ").is_err());
+        assert!(state.set_label("hw-mic", &"X".repeat(25)).is_err());
+        assert!(state.set_label("nonexistent", "Test").is_err());
+
+        state.set_icon("hw-mic", "🎤").unwrap();
+        assert_eq!(state.get_strip("hw-mic").unwrap().icon, "🎤");
+        assert!(state.set_icon("hw-mic", "").is_err());
+    }
+
+    #[test]
+    fn test_set_color() {
+        let mut state = MixerState::new();
+        state.set_color("virt-1", Some("#ff8800")).unwrap();
+        assert_eq!(
+            state.get_strip("virt-1").unwrap().color.as_deref(),
+            Some("#FF8800")
+        );
+        assert!(state.set_color("virt-1", Some("ff8800")).is_err());
+        assert!(state.set_color("virt-1", Some("#GG0000")).is_err());
+        state.set_color("virt-1", None).unwrap();
+        assert!(state.get_strip("virt-1").unwrap().color.is_none());
+    }
+
+    #[test]
+    fn test_reorder() {
+        let mut state = MixerState::new();
+        let order: Vec<String> = ["virt-2", "hw-mic", "virt-1", "hw-line", "hw-headset"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        state.reorder(&order).unwrap();
+        assert_eq!(state.strip_order(), order);
+        assert_eq!(state.get_strip("virt-2").unwrap().order, 0);
+
+        // Unvollständige oder doppelte Listen ändern nichts
+        assert!(state.reorder(&order[..4]).is_err());
+        let mut dup = order.clone();
+        dup[4] = "hw-mic".to_string();
+        assert!(state.reorder(&dup).is_err());
+        assert_eq!(state.strip_order(), order);
+    }
+
+    #[test]
+    fn test_set_pw_device() {
+        let mut state = MixerState::new();
+        state.set_pw_device("hw-mic", Some(42)).unwrap();
+        assert_eq!(state.get_strip("hw-mic").unwrap().device_id, Some(42));
+        state.set_pw_device("hw-mic", None).unwrap();
+        assert!(state.get_strip("hw-mic").unwrap().device_id.is_none());
+    }
+
+    #[test]
+    fn test_set_talk() {
+        use crate::audio::talk::TalkMode;
+
+        let mut state = MixerState::new();
+        let talk = TalkSettings {
+            mode: TalkMode::PushToTalk,
+            bus_ids: vec!["B2".to_string()],
+            ..TalkSettings::default()
+        };
+        state.set_talk("hw-mic", talk.clone()).unwrap();
+        assert_eq!(state.get_strip("hw-mic").unwrap().talk, talk);
+
+        let invalid = TalkSettings {
+            fade_ms: 10_000,
+            ..TalkSettings::default()
+        };
+        assert!(state.set_talk("hw-mic", invalid).is_err());
+    }
+
     #[test]
     fn test_db_to_linear() {
         // 0 dB = 1.0 linear
@@ -527,4 +975,32 @@ mod tests {
         // Negative Werte = MIN_VOLUME_DB
         assert_eq!(linear_to_db(-0.5), MIN_VOLUME_DB);
     }
+
+    #[test]
+    fn test_from_strips_roundtrip() {
+        let mut mixer = MixerState::new();
+        mixer.set_volume("hw-mic", -12.0).unwrap();
+        mixer.add_virtual_strip().unwrap();
+
+        let restored = MixerState::from_strips(mixer.get_strips()).unwrap();
+        assert_eq!(restored.strip_count(), 6);
+        assert_eq!(restored.get_strip("hw-mic").unwrap().volume_db, -12.0);
+
+        // Nächste Virtual-ID läuft hinter virt-3 weiter
+        let mut restored = restored;
+        assert_eq!(restored.add_virtual_strip().unwrap().id, "virt-4");
+    }
+
+    #[test]
+    fn test_from_strips_rejects_invalid() {
+        let mut strips = MixerState::new().get_strips();
+        strips[0].volume_db = f32::NAN;
+        assert!(MixerState::from_strips(strips).is_err());
+
+        let mut strips = MixerState::new().get_strips();
+        strips[1].id = strips[0].id.clone();
+        assert!(MixerState::from_strips(strips).is_err());
+
+        assert!(MixerState::from_strips(vec![]).is_err());
+    }
 }
@@ -1,14 +1,24 @@
 // Modul: audio — Audio-Engine Verwaltung (PipeWire, Mixer, Metering)
 
 pub mod bus;
+pub mod bus_output;
+pub mod bus_tap;
 pub mod capture;
 pub mod cpal_capture;
+pub mod delay;
+pub mod generator;
 pub mod master;
+pub mod master_stage;
 pub mod metering;
 pub mod metering_service;
+pub mod mix_engine;
+pub mod mix_minus;
 pub mod mixer;
+pub mod mute_groups;
 pub mod pipewire;
 pub mod routing;
+pub mod talk;
+pub mod tap;
 
 use self::pipewire::{PipeWireSession, PipeWireStatus};
 use log::{error, info};
@@ -0,0 +1,378 @@
+// Modul: audio/mute_groups — Benannte Mute-Gruppen (z.B. "BRB", "Private")
+//
+// Eine Gruppe schaltet mehrere Strips gemeinsam stumm — entweder auf allen
+// Bussen oder nur auf ausgewählten (z.B. Mics nur auf B1, B2 bleibt offen).
+// Gruppen-Mutes überlagern den Strip-Mute, statt ihn zu verändern: Wird die
+// Gruppe gelöst, gilt wieder der vorherige Strip-Zustand.
+// Exklusive Gruppen lösen beim Aktivieren alle anderen exklusiven Gruppen.
+use super::mixer::InputStrip;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maximale Anzahl an Mute-Gruppen
+const MAX_GROUPS: usize = 16;
+/// Maximale Länge eines Gruppen-Namens (Zeichen)
+const MAX_NAME_LEN: usize = 32;
+
+/// Eine Mute-Gruppe
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MuteGroup {
+    /// Eindeutige Gruppen-ID (z.B. "mg-1")
+    pub id: String,
+    /// Anzeige-Name
+    pub name: String,
+    /// Betroffene Strips
+    pub strip_ids: Vec<String>,
+    /// Betroffene Busse (leer = alle Busse)
+    pub bus_ids: Vec<String>,
+    /// Exklusiv: Aktivieren löst alle anderen exklusiven Gruppen
+    pub exclusive: bool,
+    /// Gruppe aktiv (Strips stumm)
+    pub active: bool,
+}
+
+/// Verwaltet alle Mute-Gruppen
+#[derive(Debug)]
+pub struct MuteGroupManager {
+    /// Alle Gruppen (Key: Gruppen-ID)
+    groups: HashMap<String, MuteGroup>,
+    /// Zähler für die nächste Gruppen-ID
+    next_id: u32,
+}
+
+impl MuteGroupManager {
+    /// Neuen Manager ohne Gruppen erstellen
+    pub fn new() -> Self {
+        Self {
+            groups: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Gruppen aus gespeichertem State wiederherstellen (Session/Scene)
+    pub fn from_groups(groups: Vec<MuteGroup>) -> Result<Self, String> {
+        if groups.len() > MAX_GROUPS {
+            return Err(format!(
+                "Zu viele Mute-Gruppen: {} (max {})",
+                groups.len(),
+                MAX_GROUPS
+            ));
+        }
+
+        let mut map = HashMap::new();
+        for mut group in groups {
+            if group.id.is_empty() {
+                return Err("Gruppen-ID darf nicht leer sein".to_string());
+            }
+            group.name = validate_name(&group.name)?;
+            if map.contains_key(&group.id) {
+                return Err(format!("Gruppen-ID doppelt: {}", group.id));
+            }
+            map.insert(group.id.clone(), group);
+        }
+
+        let next_id = map
+            .keys()
+            .filter_map(|id| id.strip_prefix("mg-")?.parse::<u32>().ok())
+            .max()
+            .map_or(1, |max| max + 1);
+
+        Ok(Self {
+            groups: map,
+            next_id,
+        })
+    }
+
+    /// Alle Gruppen als sortierte Liste (nach Name)
+    pub fn get_groups(&self) -> Vec<MuteGroup> {
+        let mut groups: Vec<MuteGroup> = self.groups.values().cloned().collect();
+        groups.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+        groups
+    }
+
+    /// Neue Gruppe anlegen (inaktiv)
+    pub fn create_group(
+        &mut self,
+        name: &str,
+        strip_ids: Vec<String>,
+        bus_ids: Vec<String>,
+        exclusive: bool,
+    ) -> Result<MuteGroup, String> {
+        if self.groups.len() >= MAX_GROUPS {
+            return Err(format!(
+                "Maximale Anzahl von {} Mute-Gruppen erreicht",
+                MAX_GROUPS
+            ));
+        }
+
+        let group = MuteGroup {
+            id: format!("mg-{}", self.next_id),
+            name: validate_name(name)?,
+            strip_ids: dedup(strip_ids),
+            bus_ids: dedup(bus_ids),
+            exclusive,
+            active: false,
+        };
+        self.next_id += 1;
+        self.groups.insert(group.id.clone(), group.clone());
+
+        info!("Mute-Gruppe '{}' angelegt ({})", group.name, group.id);
+        Ok(group)
+    }
+
+    /// Gruppe ändern (Name, Mitglieder, Busse, Exklusiv — Aktiv-Status bleibt)
+    pub fn update_group(
+        &mut self,
+        group_id: &str,
+        name: &str,
+        strip_ids: Vec<String>,
+        bus_ids: Vec<String>,
+        exclusive: bool,
+    ) -> Result<(), String> {
+        let name = validate_name(name)?;
+        let group = self
+            .groups
+            .get_mut(group_id)
+            .ok_or_else(|| format!("Mute-Gruppe '{}' nicht gefunden", group_id))?;
+
+        group.name = name;
+        group.strip_ids = dedup(strip_ids);
+        group.bus_ids = dedup(bus_ids);
+        group.exclusive = exclusive;
+        Ok(())
+    }
+
+    /// Gruppe löschen
+    pub fn delete_group(&mut self, group_id: &str) -> Result<(), String> {
+        self.groups
+            .remove(group_id)
+            .ok_or_else(|| format!("Mute-Gruppe '{}' nicht gefunden", group_id))?;
+        info!("Mute-Gruppe {} gelöscht", group_id);
+        Ok(())
+    }
+
+    /// Gruppe aktivieren/lösen
+    ///
+    /// Eine exklusive Gruppe löst beim Aktivieren alle anderen exklusiven Gruppen.
+    pub fn set_active(&mut self, group_id: &str, active: bool) -> Result<(), String> {
+        let exclusive = self
+            .groups
+            .get(group_id)
+            .ok_or_else(|| format!("Mute-Gruppe '{}' nicht gefunden", group_id))?
+            .exclusive;
+
+        if active && exclusive {
+            for group in self.groups.values_mut() {
+                if group.exclusive && group.id != group_id {
+                    group.active = false;
+                }
+            }
+        }
+        if let Some(group) = self.groups.get_mut(group_id) {
+            group.active = active;
+        }
+        Ok(())
+    }
+
+    /// Gruppe umschalten
+    ///
+    /// # Returns
+    /// Neuer Aktiv-Status
+    pub fn toggle(&mut self, group_id: &str) -> Result<bool, String> {
+        let active = !self
+            .groups
+            .get(group_id)
+            .ok_or_else(|| format!("Mute-Gruppe '{}' nicht gefunden", group_id))?
+            .active;
+        self.set_active(group_id, active)?;
+        Ok(active)
+    }
+
+    /// IDs aller aktiven Gruppen (sortiert)
+    pub fn active_groups(&self) -> Vec<String> {
+        let mut ids: Vec<String> = self
+            .groups
+            .values()
+            .filter(|g| g.active)
+            .map(|g| g.id.clone())
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    /// Genau die angegebenen Gruppen aktiv setzen (Undo/Redo, Szenen)
+    ///
+    /// Keine Exklusiv-Logik — der Zustand wird unverändert übernommen.
+    pub fn set_active_groups(&mut self, group_ids: &[String]) -> Result<(), String> {
+        if let Some(unknown) = group_ids.iter().find(|id| !self.groups.contains_key(*id)) {
+            return Err(format!("Mute-Gruppe '{}' nicht gefunden", unknown));
+        }
+        for group in self.groups.values_mut() {
+            group.active = group_ids.contains(&group.id);
+        }
+        Ok(())
+    }
+
+    /// Effektive Strips für die Mix-Engine berechnen
+    ///
+    /// Aktive Gruppen ohne Bus-Auswahl schalten die Strips komplett stumm,
+    /// Gruppen mit Bus-Auswahl entfernen nur die betroffenen Bus-Zuweisungen.
+    pub fn apply(&self, strips: &[InputStrip]) -> Vec<InputStrip> {
+        let mut strips = strips.to_vec();
+        for group in self.groups.values().filter(|g| g.active) {
+            for strip in strips
+                .iter_mut()
+                .filter(|s| group.strip_ids.contains(&s.id))
+            {
+                if group.bus_ids.is_empty() {
+                    strip.muted = true;
+                } else {
+                    strip.bus_routing.retain(|b| !group.bus_ids.contains(b));
+                }
+            }
+        }
+        strips
+    }
+}
+
+impl Default for MuteGroupManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Gruppen-Namen prüfen und bereinigt zurückgeben
+fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!(
+            "Ungültiger Gruppen-Name (1 bis {} Zeichen erlaubt)",
+            MAX_NAME_LEN
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Doppelte Einträge entfernen (Reihenfolge bleibt erhalten)
+fn dedup(ids: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(ids.len());
+    for id in ids {
+        if !out.contains(&id) {
+            out.push(id);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::mixer::MixerState;
+
+    fn ids(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_create_and_delete() {
+        let mut manager = MuteGroupManager::new();
+        let group = manager
+            .create_group(" BRB ", ids(&["hw-mic", "hw-mic"]), vec![], false)
+            .unwrap();
+        assert_eq!(group.id, "mg-1");
+        assert_eq!(group.name, "BRB");
+        assert_eq!(group.strip_ids, ids(&["hw-mic"]));
+        assert!(!group.active);
+
+        assert!(manager.create_group("", vec![], vec![], false).is_err());
+        manager.delete_group("mg-1").unwrap();
+        assert!(manager.get_groups().is_empty());
+        assert!(manager.delete_group("mg-1").is_err());
+    }
+
+    #[test]
+    fn test_apply_full_mute() {
+        let mixer = MixerState::new();
+        let mut manager = MuteGroupManager::new();
+        manager
+            .create_group("BRB", ids(&["hw-mic", "hw-headset"]), vec![], false)
+            .unwrap();
+
+        // Inaktiv: keine Wirkung
+        assert_eq!(manager.apply(&mixer.get_strips()), mixer.get_strips());
+
+        manager.set_active("mg-1", true).unwrap();
+        let strips = manager.apply(&mixer.get_strips());
+        let muted: Vec<&str> = strips
+            .iter()
+            .filter(|s| s.muted)
+            .map(|s| s.id.as_str())
+            .collect();
+        assert_eq!(muted, vec!["hw-mic", "hw-headset"]);
+        // Strip-Mute im Mixer bleibt unverändert
+        assert!(!mixer.get_strip("hw-mic").unwrap().muted);
+    }
+
+    #[test]
+    fn test_apply_bus_scoped() {
+        let mut mixer = MixerState::new();
+        mixer.set_bus_routing("hw-mic", "B1", true).unwrap();
+        mixer.set_bus_routing("hw-mic", "B2", true).unwrap();
+
+        let mut manager = MuteGroupManager::new();
+        manager
+            .create_group("Private", ids(&["hw-mic"]), ids(&["B1"]), false)
+            .unwrap();
+        manager.set_active("mg-1", true).unwrap();
+
+        let strips = manager.apply(&mixer.get_strips());
+        let mic = strips.iter().find(|s| s.id == "hw-mic").unwrap();
+        assert!(!mic.muted);
+        assert_eq!(mic.bus_routing, ids(&["A1", "B2"]));
+    }
+
+    #[test]
+    fn test_exclusive_releases_others() {
+        let mut manager = MuteGroupManager::new();
+        manager
+            .create_group("BRB", ids(&["hw-mic"]), vec![], true)
+            .unwrap();
+        manager
+            .create_group("Private", ids(&["hw-mic"]), ids(&["B1"]), true)
+            .unwrap();
+        manager
+            .create_group("Cough", ids(&["hw-mic"]), vec![], false)
+            .unwrap();
+
+        manager.set_active("mg-1", true).unwrap();
+        manager.set_active("mg-3", true).unwrap();
+        manager.set_active("mg-2", true).unwrap();
+        // mg-1 wurde von mg-2 gelöst, nicht-exklusive mg-3 bleibt aktiv
+        assert_eq!(manager.active_groups(), ids(&["mg-2", "mg-3"]));
+
+        assert!(!manager.toggle("mg-2").unwrap());
+        assert_eq!(manager.active_groups(), ids(&["mg-3"]));
+    }
+
+    #[test]
+    fn test_set_active_groups_and_restore() {
+        let mut manager = MuteGroupManager::new();
+        manager.create_group("A", vec![], vec![], false).unwrap();
+        manager.create_group("B", vec![], vec![], false).unwrap();
+        manager.set_active_groups(&ids(&["mg-2"])).unwrap();
+        assert_eq!(manager.active_groups(), ids(&["mg-2"]));
+        assert!(manager.set_active_groups(&ids(&["mg-9"])).is_err());
+
+        let restored = MuteGroupManager::from_groups(manager.get_groups()).unwrap();
+        assert_eq!(restored.active_groups(), ids(&["mg-2"]));
+        let mut restored = restored;
+        assert_eq!(
+            restored
+                .create_group("C", vec![], vec![], false)
+                .unwrap()
+                .id,
+            "mg-3"
+        );
+    }
+}
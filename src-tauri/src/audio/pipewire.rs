@@ -1,4 +1,6 @@
 // Modul: audio/pipewire — PipeWire-Session und Node-Verwaltung
+use crate::audio::bus::{validate_bus_id, OutputBus};
+use crate::audio::mix_engine::MIX_BLOCK_FRAMES;
 use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::sync::{
@@ -317,6 +319,405 @@ pub fn check_pipewire_available() -> Result<(), String> {
     Ok(())
 }
 
+// --- Clock-/Latenz-Steuerung (Quantum + Sample-Rate) ---
+
+/// Von PipeWire unterstützte Sample-Raten für clock.force-rate (Hz)
+pub const SUPPORTED_SAMPLE_RATES: [u32; 5] = [44100, 48000, 88200, 96000, 192000];
+/// Kleinstes erlaubtes Quantum (Samples)
+pub const MIN_QUANTUM: u32 = 32;
+/// Größtes erlaubtes Quantum (Samples)
+pub const MAX_QUANTUM: u32 = 8192;
+
+/// Clock-Einstellungen aus den PipeWire settings-Metadaten
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClockSettings {
+    /// Aktuelle Graph-Sample-Rate (clock.rate)
+    pub rate: u32,
+    /// Standard-Quantum des Graphen (clock.quantum)
+    pub quantum: u32,
+    /// Erzwungene Sample-Rate (clock.force-rate, 0 = nicht erzwungen)
+    pub force_rate: u32,
+    /// Erzwungenes Quantum (clock.force-quantum, 0 = nicht erzwungen)
+    pub force_quantum: u32,
+}
+
+impl ClockSettings {
+    /// Tatsächlich wirksame Sample-Rate (force-rate hat Vorrang)
+    pub fn effective_rate(&self) -> u32 {
+        if self.force_rate > 0 {
+            self.force_rate
+        } else if self.rate > 0 {
+            self.rate
+        } else {
+            DEFAULT_SAMPLE_RATE
+        }
+    }
+
+    /// Tatsächlich wirksames Quantum (force-quantum hat Vorrang)
+    pub fn effective_quantum(&self) -> u32 {
+        if self.force_quantum > 0 {
+            self.force_quantum
+        } else if self.quantum > 0 {
+            self.quantum
+        } else {
+            DEFAULT_BUFFER_SIZE
+        }
+    }
+}
+
+/// Latenz eines einzelnen Signalpfads (Eingang → Bus → Ausgang)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PathLatency {
+    /// Bus-ID des Pfads (A1, B1, ...)
+    pub bus_id: String,
+    /// Lesbare Pfad-Beschreibung
+    pub path: String,
+    /// Vom Graphen ausgehandeltes Quantum des Bus-Nodes (Samples)
+    pub quantum: u32,
+    /// Sample-Rate des Bus-Nodes (Hz)
+    pub rate: u32,
+    /// Gesamtlatenz des Pfads in Samples
+    pub latency_samples: u32,
+    /// Gesamtlatenz des Pfads in Millisekunden
+    pub latency_ms: f32,
+}
+
+/// Latenz-Report: angefordert vs. tatsächlich ausgehandelt
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatencyReport {
+    /// Angefordertes Quantum aus der AppConfig
+    pub requested_quantum: u32,
+    /// Angeforderte Sample-Rate aus der AppConfig
+    pub requested_rate: u32,
+    /// Ausgehandeltes Graph-Quantum
+    pub negotiated_quantum: u32,
+    /// Ausgehandelte Graph-Sample-Rate
+    pub negotiated_rate: u32,
+    /// Dauer eines Quantums in Millisekunden
+    pub quantum_ms: f32,
+    /// Latenz je Signalpfad
+    pub paths: Vec<PathLatency>,
+}
+
+/// Quantum/Sample-Rate-Anforderung validieren
+///
+/// Quantum muss eine Zweierpotenz zwischen MIN_QUANTUM und MAX_QUANTUM sein.
+pub fn validate_clock_request(quantum: u32, rate: u32) -> Result<(), String> {
+    if !(MIN_QUANTUM..=MAX_QUANTUM).contains(&quantum) || !quantum.is_power_of_two() {
+        return Err(format!(
+            "Ungültige Buffer-Größe: {} (Zweierpotenz zwischen {} und {} erwartet)",
+            quantum, MIN_QUANTUM, MAX_QUANTUM
+        ));
+    }
+    if !SUPPORTED_SAMPLE_RATES.contains(&rate) {
+        return Err(format!("Ungültige Sample-Rate: {} Hz", rate));
+    }
+    Ok(())
+}
+
+/// Einen Schlüssel in den PipeWire settings-Metadaten setzen
+fn set_settings_metadata(key: &str, value: u32) -> Result<(), String> {
+    let output = std::process::Command::new("pw-metadata")
+        .arg("-n")
+        .arg("settings")
+        .arg("0")
+        .arg(key)
+        .arg(value.to_string())
+        .output()
+        .map_err(|e| format!("pw-metadata konnte nicht ausgeführt werden: {}", e))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "pw-metadata {} fehlgeschlagen: {}",
+            key,
+            stderr.trim()
+        ));
+    }
+    Ok(())
+}
+
+/// Quantum und Sample-Rate beim PipeWire-Graphen anfordern
+///
+/// Setzt clock.force-rate und clock.force-quantum in den settings-Metadaten.
+/// Der Graph wechselt sofort auf die neuen Werte (sofern die Hardware sie kann).
+fn apply_clock_settings(quantum: u32, rate: u32) -> Result<(), String> {
+    validate_clock_request(quantum, rate)?;
+
+    set_settings_metadata("clock.force-rate", rate)?;
+    set_settings_metadata("clock.force-quantum", quantum)?;
+
+    info!(
+        "PipeWire-Clock angefordert: {} Samples @ {} Hz",
+        quantum, rate
+    );
+    Ok(())
+}
+
+/// Von der App gesetzte Clock-Anforderung
+///
+/// Merkt sich, welche force-Werte die App geschrieben hat und welche vorher
+/// aktiv waren. Beim Freigeben wird nur zurückgesetzt, was noch unser Wert ist —
+/// hat ein anderes Programm (oder der User) die Clock inzwischen geändert,
+/// bleibt dessen Einstellung unangetastet.
+#[derive(Debug, Clone, Default)]
+pub struct ClockClaim {
+    /// Von uns geschriebene Werte (Quantum, Rate); None = nichts angefordert
+    written: Option<(u32, u32)>,
+    /// force-Werte vor unserer ersten Anforderung (Quantum, Rate)
+    previous: (u32, u32),
+}
+
+impl ClockClaim {
+    /// Neue, leere Anforderung
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Quantum und Sample-Rate anfordern und als eigene Werte merken
+    pub fn apply(&mut self, quantum: u32, rate: u32) -> Result<(), String> {
+        validate_clock_request(quantum, rate)?;
+        if self.written.is_none() {
+            let current = read_clock_settings();
+            self.previous = (current.force_quantum, current.force_rate);
+        }
+        apply_clock_settings(quantum, rate)?;
+        self.written = Some((quantum, rate));
+        Ok(())
+    }
+
+    /// Eigene Clock-Anforderung freigeben
+    ///
+    /// Setzt nur Schlüssel zurück, die noch den von uns geschriebenen Wert haben,
+    /// und stellt dort den Wert von vor unserer Anforderung wieder her.
+    pub fn release(&mut self) -> Result<(), String> {
+        let written = match self.written.take() {
+            Some(w) => w,
+            None => return Ok(()),
+        };
+
+        let current = read_clock_settings();
+        let plan = clock_release_plan(&current, written, self.previous);
+        for (key, value) in &plan {
+            set_settings_metadata(key, *value)?;
+        }
+        if plan.is_empty() {
+            info!("PipeWire-Clock wurde extern geändert — nichts freizugeben");
+        } else {
+            info!("PipeWire-Clock-Anforderung freigegeben");
+        }
+        Ok(())
+    }
+}
+
+/// Schlüssel bestimmen, die beim Freigeben zurückgesetzt werden
+///
+/// `written` und `previous` sind jeweils (Quantum, Rate).
+fn clock_release_plan(
+    current: &ClockSettings,
+    written: (u32, u32),
+    previous: (u32, u32),
+) -> Vec<(&'static str, u32)> {
+    let mut plan = Vec::new();
+    if current.force_rate == written.1 {
+        plan.push(("clock.force-rate", previous.1));
+    }
+    if current.force_quantum == written.0 {
+        plan.push(("clock.force-quantum", previous.0));
+    }
+    plan
+}
+
+/// Aktuelle Clock-Einstellungen aus den settings-Metadaten lesen
+pub fn read_clock_settings() -> ClockSettings {
+    let output = std::process::Command::new("pw-metadata")
+        .arg("-n")
+        .arg("settings")
+        .arg("0")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .unwrap_or_default();
+
+    parse_clock_settings(&output)
+}
+
+/// pw-metadata-Ausgabe parsen
+///
+/// Format: `update: id:0 key:'clock.force-quantum' value:'256' type:''`
+fn parse_clock_settings(output: &str) -> ClockSettings {
+    let mut settings = ClockSettings::default();
+
+    for line in output.lines() {
+        let key = match quoted_field(line, "key:") {
+            Some(k) => k,
+            None => continue,
+        };
+        let value = quoted_field(line, "value:")
+            .and_then(|v| v.parse::<u32>().ok())
+            .unwrap_or(0);
+
+        match key {
+            "clock.rate" => settings.rate = value,
+            "clock.quantum" => settings.quantum = value,
+            "clock.force-rate" => settings.force_rate = value,
+            "clock.force-quantum" => settings.force_quantum = value,
+            _ => {}
+        }
+    }
+
+    settings
+}
+
+/// Wert eines Feldes im Format `name:'wert'` extrahieren
+fn quoted_field<'a>(line: &'a str, name: &str) -> Option<&'a str> {
+    let start = line.find(name)? + name.len();
+    let rest = line[start..].strip_prefix('\'')?;
+    let end = rest.find('\'')?;
+    Some(&rest[..end])
+}
+
+/// Pro Node ausgehandeltes Quantum und Sample-Rate über pw-top abfragen
+///
+/// Liefert Node-Name → (Quantum, Rate). Follower-Nodes übernehmen die Werte
+/// ihres Treibers, wenn sie selbst keine melden.
+pub fn read_node_clocks() -> std::collections::HashMap<String, (u32, u32)> {
+    let output = std::process::Command::new("pw-top")
+        .arg("-b")
+        .arg("-n")
+        .arg("2")
+        .output()
+        .ok()
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .unwrap_or_default();
+
+    parse_pw_top(&output)
+}
+
+/// pw-top Batch-Ausgabe parsen
+///
+/// Spalten: `S ID QUANT RATE WAIT BUSY W/Q B/Q ERR FORMAT... NAME`,
+/// Follower-Nodes sind mit `+` vor dem Namen markiert.
+fn parse_pw_top(output: &str) -> std::collections::HashMap<String, (u32, u32)> {
+    let mut clocks = std::collections::HashMap::new();
+    let mut driver = (0u32, 0u32);
+
+    for line in output.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        if tokens.len() < 5 || tokens[1].parse::<u32>().is_err() {
+            continue; // Kopfzeile oder leere Zeile
+        }
+
+        let quantum = tokens[2].parse::<u32>().unwrap_or(0);
+        let rate = tokens[3].parse::<u32>().unwrap_or(0);
+        let is_follower = tokens[tokens.len() - 2] == "+";
+        let name = tokens[tokens.len() - 1].to_string();
+
+        let clock = if is_follower {
+            (
+                if quantum > 0 { quantum } else { driver.0 },
+                if rate > 0 { rate } else { driver.1 },
+            )
+        } else {
+            driver = (quantum, rate);
+            driver
+        };
+
+        clocks.insert(name, clock);
+    }
+
+    clocks
+}
+
+/// Latenz eines Pfads berechnen
+///
+/// Ein Pfad durchläuft Capture (1 Quantum), den Summen-Node (1 Quantum), die
+/// Mix-Engine (1 Block), den Bus-Loopback (1 Quantum) und — bei physischen
+/// Ausgängen — die Wiedergabe (1 Quantum). Virtuelle Busse enden beim
+/// konsumierenden Programm (OBS, Discord), dessen Puffer nicht zählt.
+pub fn path_latency(bus_id: &str, physical: bool, quantum: u32, rate: u32) -> PathLatency {
+    let hops = if physical { 4 } else { 3 };
+    let latency_samples = quantum * hops + MIX_BLOCK_FRAMES as u32;
+    let latency_ms = if rate > 0 {
+        latency_samples as f32 / rate as f32 * 1000.0
+    } else {
+        0.0
+    };
+    let path = if physical {
+        format!(
+            "Eingang → inoX-Sum-{0} → Mix-Engine → inoX-Bus-{0} → Hardware-Ausgang",
+            bus_id
+        )
+    } else {
+        format!(
+            "Eingang → inoX-Sum-{0} → Mix-Engine → inoX-Bus-{0} → Anwendung",
+            bus_id
+        )
+    };
+
+    PathLatency {
+        bus_id: bus_id.to_string(),
+        path,
+        quantum,
+        rate,
+        latency_samples,
+        latency_ms,
+    }
+}
+
+/// Latenz-Report für alle Bus-Pfade erstellen
+///
+/// Vergleicht die angeforderten Werte mit den tatsächlich vom Graphen
+/// ausgehandelten. Kennt pw-top einen Bus-Node nicht, gelten die Graph-Werte.
+pub fn get_latency_report(
+    requested_quantum: u32,
+    requested_rate: u32,
+    buses: &[OutputBus],
+) -> LatencyReport {
+    let settings = read_clock_settings();
+    let node_clocks = read_node_clocks();
+    build_latency_report(
+        requested_quantum,
+        requested_rate,
+        &settings,
+        &node_clocks,
+        buses,
+    )
+}
+
+/// Report aus bereits gelesenen Clock-Daten zusammensetzen
+fn build_latency_report(
+    requested_quantum: u32,
+    requested_rate: u32,
+    settings: &ClockSettings,
+    node_clocks: &std::collections::HashMap<String, (u32, u32)>,
+    buses: &[OutputBus],
+) -> LatencyReport {
+    let negotiated_quantum = settings.effective_quantum();
+    let negotiated_rate = settings.effective_rate();
+
+    let paths = buses
+        .iter()
+        .map(|bus| {
+            let node_name = format!("inoX-Bus-{}", bus.id);
+            let (quantum, rate) = match node_clocks.get(&node_name) {
+                Some(&(q, r)) if q > 0 && r > 0 => (q, r),
+                _ => (negotiated_quantum, negotiated_rate),
+            };
+            path_latency(&bus.id, bus.is_physical(), quantum, rate)
+        })
+        .collect();
+
+    LatencyReport {
+        requested_quantum,
+        requested_rate,
+        negotiated_quantum,
+        negotiated_rate,
+        quantum_ms: negotiated_quantum as f32 / negotiated_rate as f32 * 1000.0,
+        paths,
+    }
+}
+
 // --- Audio-Routing Link-Management ---
 
 /// Audio-Link erstellen (Source → Bus Verbindung)
@@ -326,7 +727,7 @@ pub fn check_pipewire_available() -> Result<(), String> {
 ///
 /// # Argumente
 /// * `source_id` - Logische Source-ID (z.B. "mic-1", "app-browser")
-/// * `bus_id` - Bus-ID (A1, A2, B1, B2)
+/// * `bus_id` - Bus-ID aus der Bus-Registry (z.B. A1, B3)
 ///
 /// # Phase 2b TODO
 /// - Node-Discovery: Source/Bus IDs zu PipeWire Node/Port-Namen mappen
@@ -669,35 +1070,28 @@ fn map_source_to_port(source_id: &str) -> Result<String, String> {
     Ok(port.to_string())
 }
 
-/// Virtual Bus Nodes erstellen (inoX-Bus-A1 bis B2)
+/// Virtual Bus Nodes erstellen (inoX-Sum-<ID> und inoX-Bus-<ID> für jeden Bus der Registry)
 ///
 /// Phase 2b: Erstellt virtuelle Loopback-Nodes für jeden Bus
-/// Diese können dann als Routing-Ziele verwendet werden
-pub fn create_virtual_bus_nodes() -> Result<(), String> {
-    info!("Erstelle Virtual Bus Nodes...");
-
-    let buses = vec!["A1", "A2", "B1", "B2"];
-
-    for bus_id in buses {
-        // pw-loopback nutzen um virtuelle Nodes zu erstellen
-        // Format: pw-loopback -n "inoX-Bus-A1" -c 2
-        let bus_name = format!("inoX-Bus-{}", bus_id);
-
-        let output = std::process::Command::new("pw-loopback")
-            .arg("-n")
-            .arg(&bus_name)
-            .arg("-c")
-            .arg("2") // Stereo
-            .arg("--latency")
-            .arg("256/48000") // 256 Samples @ 48kHz
-            .spawn();
-
-        match output {
-            Ok(_) => info!("Virtual Bus Node erstellt: {}", bus_name),
-            Err(e) => {
-                error!("Fehler beim Erstellen von {}: {}", bus_name, e);
-                // Nicht abbrechen, weiter mit nächstem Bus
-            }
+/// Die Summen-Nodes sind die Routing-Ziele, die Bus-Nodes die Ausgänge
+///
+/// `buffer_size`/`sample_rate` stammen aus der AppConfig und werden als
+/// node.latency-Hinweis an jeden Bus-Node übergeben.
+pub fn create_virtual_bus_nodes(
+    buses: &[OutputBus],
+    buffer_size: u32,
+    sample_rate: u32,
+) -> Result<(), String> {
+    validate_clock_request(buffer_size, sample_rate)?;
+    info!(
+        "Erstelle Virtual Bus Nodes ({} Samples @ {} Hz)...",
+        buffer_size, sample_rate
+    );
+
+    for bus in buses {
+        if let Err(e) = create_bus_node(bus, buffer_size, sample_rate) {
+            // Nicht abbrechen, weiter mit nächstem Bus
+            error!("{}", e);
         }
     }
 
@@ -705,14 +1099,118 @@ pub fn create_virtual_bus_nodes() -> Result<(), String> {
     Ok(())
 }
 
+/// Loopback-Nodes eines einzelnen Bus erstellen (Kanalzahl aus der Registry)
+///
+/// Der Summen-Node `inoX-Sum-<ID>` nimmt die Routing-Links auf; sein Ausgang
+/// bleibt unverbunden, die Mix-Engine schneidet seine Monitor-Ports mit und
+/// spielt den fertigen Bus in den Bus-Node `inoX-Bus-<ID>` (siehe bus_output).
+/// Physische Busse mit gebundenem Ausgabe-Device geben die Wiedergabe-Seite
+/// des Bus-Nodes per `target.object` an dieses Device. Fehlt es, verbindet der
+/// Session-Manager den Bus mit der Standard-Ausgabe.
+pub fn create_bus_node(bus: &OutputBus, buffer_size: u32, sample_rate: u32) -> Result<(), String> {
+    validate_bus_id(&bus.id)?;
+    let sum_name = format!("inoX-Sum-{}", bus.id);
+    let bus_name = format!("inoX-Bus-{}", bus.id);
+
+    std::process::Command::new("pw-loopback")
+        .args(sum_node_args(bus, buffer_size, sample_rate))
+        .spawn()
+        .map_err(|e| format!("Fehler beim Erstellen von {}: {}", sum_name, e))?;
+
+    std::process::Command::new("pw-loopback")
+        .args(bus_node_args(bus, buffer_size, sample_rate))
+        .spawn()
+        .map_err(|e| format!("Fehler beim Erstellen von {}: {}", bus_name, e))?;
+
+    info!(
+        "Virtual Bus Node erstellt: {} ({} Kanäle)",
+        bus_name, bus.channels
+    );
+    Ok(())
+}
+
+/// Argumente für pw-loopback eines Bus-Nodes
+///
+/// Format: pw-loopback -n "inoX-Bus-A1" -c 2 --latency 256/48000
+/// [-P "target.object=<device>"]
+fn bus_node_args(bus: &OutputBus, buffer_size: u32, sample_rate: u32) -> Vec<String> {
+    let mut args = vec![
+        "-n".to_string(),
+        format!("inoX-Bus-{}", bus.id),
+        "-c".to_string(),
+        bus.channels.to_string(),
+        "--latency".to_string(),
+        format!("{}/{}", buffer_size, sample_rate),
+    ];
+    if let Some(device) = bus.device_name.as_ref().filter(|_| bus.is_physical()) {
+        args.push("-P".to_string());
+        args.push(format!("target.object={}", device));
+    }
+    args
+}
+
+/// Argumente für pw-loopback eines Summen-Nodes
+///
+/// Format: pw-loopback -n "inoX-Sum-A1" -c 2 --latency 256/48000
+/// -C "media.class=Audio/Sink" -P "node.autoconnect=false"
+fn sum_node_args(bus: &OutputBus, buffer_size: u32, sample_rate: u32) -> Vec<String> {
+    vec![
+        "-n".to_string(),
+        format!("inoX-Sum-{}", bus.id),
+        "-c".to_string(),
+        bus.channels.to_string(),
+        "--latency".to_string(),
+        format!("{}/{}", buffer_size, sample_rate),
+        "-C".to_string(),
+        "media.class=Audio/Sink".to_string(),
+        "-P".to_string(),
+        "node.autoconnect=false".to_string(),
+    ]
+}
+
+/// Bus-Node neu starten (z.B. nach Wechsel des Ausgabe-Devices)
+///
+/// Summen- und Bus-Node starten neu; Links in den Bus gehen dabei verloren — der Aufrufer stellt sie über den
+/// Routing-Manager wieder her.
+pub fn restart_bus_node(bus: &OutputBus, buffer_size: u32, sample_rate: u32) -> Result<(), String> {
+    destroy_bus_node(&bus.id)?;
+    create_bus_node(bus, buffer_size, sample_rate)
+}
+
+/// Ausgabe-Device anhand des stabilen Node-Namens suchen
+///
+/// # Returns
+/// Aktuelle PipeWire-Node-ID oder None wenn das Device fehlt
+pub fn find_output_device(device_name: &str) -> Result<Option<u32>, String> {
+    Ok(list_audio_devices()?
+        .iter()
+        .find(|d| d.device_type == "output" && d.name == device_name)
+        .map(|d| d.id))
+}
+
+/// Loopback-Nodes eines einzelnen Bus stoppen (Bus gelöscht)
+pub fn destroy_bus_node(bus_id: &str) -> Result<(), String> {
+    validate_bus_id(bus_id)?;
+    // Leerzeichen nach dem Namen: inoX-Bus-A1 trifft nicht inoX-Bus-A10
+    for node in ["Sum", "Bus"] {
+        let _ = std::process::Command::new("pkill")
+            .arg("-f")
+            .arg(format!("pw-loopback -n inoX-{}-{} ", node, bus_id))
+            .output();
+    }
+
+    info!("Virtual Bus Node gestoppt: inoX-Bus-{}", bus_id);
+    Ok(())
+}
+
 /// Virtual Bus Nodes stoppen
 pub fn destroy_virtual_bus_nodes() -> Result<(), String> {
     info!("Stoppe Virtual Bus Nodes...");
 
-    // Alle pw-loopback Prozesse mit "inoX-Bus" im Namen beenden
+    // Alle pw-loopback Prozesse mit "inoX-Bus" oder "inoX-Sum" im Namen beenden
     let _ = std::process::Command::new("pkill")
         .arg("-f")
-        .arg("pw-loopback.*inoX-Bus")
+        .arg("pw-loopback.*inoX-(Bus|Sum)")
         .output();
 
     info!("Virtual Bus Nodes gestoppt");
@@ -721,23 +1219,19 @@ pub fn destroy_virtual_bus_nodes() -> Result<(), String> {
 
 /// Mapping: Bus-ID → PipeWire Port-Name
 ///
-/// Phase 2b: Nutzt virtuelle Bus-Nodes
+/// Phase 2b: Links enden im Summen-Node des Busses (Eingang der Mix-Engine).
+/// Ob der Bus existiert, prüft die Bus-Registry — hier nur, ob die ID einen
+/// gültigen Port-Namen ergibt.
 fn map_bus_to_port(bus_id: &str) -> Result<String, String> {
-    // Virtual Bus Nodes werden beim Start erstellt
-    let port = match bus_id {
-        "A1" => "inoX-Bus-A1:input_FL",
-        "A2" => "inoX-Bus-A2:input_FL",
-        "B1" => "inoX-Bus-B1:input_FL",
-        "B2" => "inoX-Bus-B2:input_FL",
-        _ => return Err(format!("Ungültige Bus-ID: {}", bus_id)),
-    };
-
-    Ok(port.to_string())
+    // Virtual Bus Nodes werden beim Start bzw. beim Anlegen erstellt
+    validate_bus_id(bus_id)?;
+    Ok(format!("inoX-Sum-{}:input_FL", bus_id))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::audio::bus::BusType;
 
     #[test]
     fn test_get_pipewire_info() {
@@ -819,20 +1313,50 @@ mod tests {
     fn test_map_bus_to_port_valid() {
         let result = map_bus_to_port("A1");
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "inoX-Bus-A1:input_FL");
+        assert_eq!(result.unwrap(), "inoX-Sum-A1:input_FL");
 
         let result = map_bus_to_port("B2");
         assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "inoX-Bus-B2:input_FL");
+        assert_eq!(result.unwrap(), "inoX-Sum-B2:input_FL");
     }
 
     #[test]
     fn test_map_bus_to_port_invalid() {
-        let result = map_bus_to_port("X1");
+        let result = map_bus_to_port("A1:input_FL");
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Ungültige Bus-ID"));
     }
 
+    #[test]
+    fn test_bus_node_args_target() {
+        let mut bus = OutputBus::new("A2", "HEADSET", BusType::Physical);
+        assert_eq!(bus_node_args(&bus, 256, 48000).len(), 6);
+
+        bus.device_name = Some("alsa_output.usb-headset".to_string());
+        let args = bus_node_args(&bus, 256, 48000);
+        assert_eq!(
+            &args[6..],
+            &[
+                "-P".to_string(),
+                "target.object=alsa_output.usb-headset".to_string()
+            ]
+        );
+
+        // Virtuelle Busse ignorieren ein Device
+        let mut virt = OutputBus::new("B1", "STREAM", BusType::Virtual);
+        virt.device_name = Some("alsa_output.x".to_string());
+        assert_eq!(bus_node_args(&virt, 256, 48000).len(), 6);
+    }
+
+    #[test]
+    fn test_sum_node_args() {
+        let bus = OutputBus::new("B3", "STREAM", BusType::Virtual);
+        let args = sum_node_args(&bus, 256, 48000);
+        assert_eq!(args[1], "inoX-Sum-B3");
+        assert!(args.contains(&"media.class=Audio/Sink".to_string()));
+        assert_eq!(args.last().unwrap(), "node.autoconnect=false");
+    }
+
     #[test]
     #[ignore] // Benötigt laufendes PipeWire und existierende Ports
     fn test_create_audio_link_integration() {
@@ -889,10 +1413,123 @@ mod tests {
         assert_eq!(devices[1].device_type, "output");
     }
 
+    // --- Clock-/Latenz Tests ---
+
+    #[test]
+    fn test_validate_clock_request() {
+        assert!(validate_clock_request(256, 48000).is_ok());
+        assert!(validate_clock_request(64, 44100).is_ok());
+        assert!(validate_clock_request(100, 48000).is_err()); // keine Zweierpotenz
+        assert!(validate_clock_request(16, 48000).is_err()); // zu klein
+        assert!(validate_clock_request(256, 22050).is_err()); // Rate nicht unterstützt
+    }
+
+    #[test]
+    fn test_parse_clock_settings() {
+        let output = "Found \"settings\" metadata 31\n\
+            update: id:0 key:'log.level' value:'2' type:''\n\
+            update: id:0 key:'clock.rate' value:'48000' type:''\n\
+            update: id:0 key:'clock.quantum' value:'1024' type:''\n\
+            update: id:0 key:'clock.force-rate' value:'0' type:''\n\
+            update: id:0 key:'clock.force-quantum' value:'128' type:''\n";
+
+        let settings = parse_clock_settings(output);
+        assert_eq!(settings.rate, 48000);
+        assert_eq!(settings.quantum, 1024);
+        assert_eq!(settings.force_rate, 0);
+        assert_eq!(settings.force_quantum, 128);
+        assert_eq!(settings.effective_quantum(), 128);
+        assert_eq!(settings.effective_rate(), 48000);
+    }
+
+    #[test]
+    fn test_clock_release_plan_only_own_values() {
+        let current = ClockSettings {
+            rate: 48000,
+            quantum: 1024,
+            force_rate: 48000,
+            force_quantum: 256,
+        };
+
+        // Beide Werte noch von uns → vorherige Werte wiederherstellen
+        let plan = clock_release_plan(&current, (256, 48000), (0, 44100));
+        assert_eq!(
+            plan,
+            vec![("clock.force-rate", 44100), ("clock.force-quantum", 0)]
+        );
+
+        // Quantum extern geändert → nur die Rate freigeben
+        let plan = clock_release_plan(&current, (128, 48000), (0, 0));
+        assert_eq!(plan, vec![("clock.force-rate", 0)]);
+
+        // Beides extern geändert → nichts anfassen
+        let plan = clock_release_plan(&current, (128, 96000), (0, 0));
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_clock_settings_fallback() {
+        let settings = parse_clock_settings("");
+        assert_eq!(settings.effective_quantum(), DEFAULT_BUFFER_SIZE);
+        assert_eq!(settings.effective_rate(), DEFAULT_SAMPLE_RATE);
+    }
+
+    #[test]
+    fn test_parse_pw_top() {
+        let output = "S   ID  QUANT   RATE    WAIT    BUSY   W/Q   B/Q  ERR FORMAT           NAME\n\
+            R   30    256  48000  22.7us  12.5us  0.01  0.01    0    S32LE 2 48000 alsa_output.pci\n\
+            R   75      0      0   5.1us   3.0us  0.00  0.00    0    F32LE 2 48000  + inoX-Bus-A1\n\
+            S   80      0      0    ---     ---   ---   ---     0                   inoX-Bus-B2\n";
+
+        let clocks = parse_pw_top(output);
+        assert_eq!(clocks.get("alsa_output.pci"), Some(&(256, 48000)));
+        // Follower übernimmt Quantum des Treibers
+        assert_eq!(clocks.get("inoX-Bus-A1"), Some(&(256, 48000)));
+        assert_eq!(clocks.get("inoX-Bus-B2"), Some(&(0, 0)));
+    }
+
+    #[test]
+    fn test_path_latency() {
+        // Physischer Bus: 4 Quanten (Capture, Summe, Loopback, Wiedergabe)
+        // plus ein Engine-Block
+        let a1 = path_latency("A1", true, 256, 48000);
+        assert_eq!(a1.latency_samples, 1280);
+        assert!((a1.latency_ms - 26.667).abs() < 0.01);
+
+        // Virtueller Bus: 3 Quanten plus ein Engine-Block
+        let b1 = path_latency("B1", false, 128, 48000);
+        assert_eq!(b1.latency_samples, 640);
+    }
+
+    #[test]
+    fn test_build_latency_report() {
+        let settings = ClockSettings {
+            rate: 48000,
+            quantum: 1024,
+            force_rate: 48000,
+            force_quantum: 256,
+        };
+        let mut node_clocks = std::collections::HashMap::new();
+        node_clocks.insert("inoX-Bus-A1".to_string(), (512, 48000));
+
+        let buses = [
+            OutputBus::new("A1", "SPEAKERS", BusType::Physical),
+            OutputBus::new("B1", "STREAM", BusType::Virtual),
+        ];
+        let report = build_latency_report(128, 48000, &settings, &node_clocks, &buses);
+        assert_eq!(report.requested_quantum, 128);
+        assert_eq!(report.negotiated_quantum, 256);
+        assert_eq!(report.paths.len(), 2);
+        // A1 meldet eigenes Quantum, B1 fällt auf Graph-Wert zurück
+        assert_eq!(report.paths[0].quantum, 512);
+        assert_eq!(report.paths[1].quantum, 256);
+    }
+
     #[test]
     #[ignore] // Benötigt laufendes PipeWire und Berechtigungen
     fn test_create_virtual_bus_nodes_integration() {
-        let result = create_virtual_bus_nodes();
+        let buses = crate::audio::bus::BusManager::new().get_buses();
+        let result = create_virtual_bus_nodes(&buses, DEFAULT_BUFFER_SIZE, DEFAULT_SAMPLE_RATE);
         println!("create_virtual_bus_nodes result: {:?}", result);
     }
 
@@ -2,49 +2,406 @@
 //
 // Verwaltet die Kreuzmatrix welche Audio-Quellen auf welche Output-Busse geroutet werden
 // SPEC: 06-routing-matrix
-
+//
+// Busse können selbst Quellen sein (Submixe, z.B. Musik-Bus → B1 und A1).
+// Bus → Bus Routings bilden einen gerichteten Graphen ohne Zyklen: jede
+// Änderung die eine Rückkopplung erzeugen würde, wird abgelehnt. Die
+// Verarbeitungs-Reihenfolge der Busse ergibt sich aus einer topologischen
+// Sortierung (Quell-Busse vor ihren Ziel-Bussen).
+//
+// Jeder Kreuzpunkt hat einen eigenen Gain (dB). Mehrere Kreuzpunkte lassen
+// sich in einem Schritt setzen: erst wird der komplette Satz geprüft, dann
+// erst die Matrix geändert — entweder alle Änderungen oder keine.
+// Submixe laufen nur in der Mix-Engine (kein PipeWire-Link), dort wirkt auch
+// ihr Gain. App- und Strip-Quellen sind PipeWire-Links in den Summen-Node —
+// Links kennen keinen Pegel, ein Gain ungleich 0 dB wird dort abgelehnt.
+
+use crate::audio::bus::DEFAULT_BUSES;
 use crate::audio::pipewire;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 /// Routing-Eintrag (Source → Bus Verbindung)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RoutingEntry {
     /// Source-ID (z.B. "mic-1", "app-browser", "app-discord")
     pub source_id: String,
-    /// Bus-ID (A1, A2, B1, B2)
+    /// Bus-ID (z.B. A1, B3)
     pub bus_id: String,
     /// Verbindung aktiv
     pub active: bool,
+    /// Kreuzpunkt-Gain in dB (ältere Sessions: 0)
+    #[serde(default)]
+    pub gain_db: f32,
+}
+
+/// Minimaler Kreuzpunkt-Gain (dB)
+pub const CROSSPOINT_GAIN_MIN_DB: f32 = -60.0;
+/// Maximaler Kreuzpunkt-Gain (dB)
+pub const CROSSPOINT_GAIN_MAX_DB: f32 = 12.0;
+/// Version des Routing-Template-Formats
+pub const ROUTING_TEMPLATE_VERSION: u32 = 1;
+
+/// Routing-Template zum Austausch zwischen Rechnern (JSON)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutingTemplate {
+    /// Format-Version
+    pub version: u32,
+    /// Anzeigename (z.B. "Podcast 3 Gäste")
+    pub name: String,
+    /// Aktive Kreuzpunkte
+    pub entries: Vec<RoutingEntry>,
+}
+
+impl RoutingTemplate {
+    /// Template-JSON parsen
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let template: Self = serde_json::from_str(json)
+            .map_err(|e| format!("Ungültiges Routing-Template: {}", e))?;
+        if template.version > ROUTING_TEMPLATE_VERSION {
+            return Err(format!(
+                "Routing-Template Version {} wird nicht unterstützt (max {})",
+                template.version, ROUTING_TEMPLATE_VERSION
+            ));
+        }
+        Ok(template)
+    }
+
+    /// Template-JSON erzeugen
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Routing-Template-Serialisierung: {}", e))
+    }
+}
+
+/// Kreuzpunkt-Gain prüfen
+pub fn validate_crosspoint_gain(gain_db: f32) -> Result<(), String> {
+    if !gain_db.is_finite() || !(CROSSPOINT_GAIN_MIN_DB..=CROSSPOINT_GAIN_MAX_DB).contains(&gain_db)
+    {
+        return Err(format!(
+            "Kreuzpunkt-Gain außerhalb des Bereichs: {} dB (erlaubt: {} bis {} dB)",
+            gain_db, CROSSPOINT_GAIN_MIN_DB, CROSSPOINT_GAIN_MAX_DB
+        ));
+    }
+    Ok(())
 }
 
 /// Routing-Manager verwaltet die Audio-Routing Matrix
 pub struct RoutingManager {
-    /// Routing-Matrix: (source_id, bus_id) → active
-    matrix: HashMap<(String, String), bool>,
+    /// Routing-Matrix: (source_id, bus_id) → Kreuzpunkt-Gain (dB), nur aktive
+    matrix: HashMap<(String, String), f32>,
+    /// Bekannte Bus-IDs (Abbild der Bus-Registry)
+    bus_ids: Vec<String>,
 }
 
 impl RoutingManager {
-    /// Neuer Routing-Manager mit leerer Matrix
+    /// Neuer Routing-Manager mit leerer Matrix (kennt die Standard-Busse)
     pub fn new() -> Self {
         log::info!("RoutingManager::new() — Erstelle leere Routing-Matrix");
         Self {
             matrix: HashMap::new(),
+            bus_ids: DEFAULT_BUSES
+                .iter()
+                .map(|(id, _, _)| id.to_string())
+                .collect(),
+        }
+    }
+
+    /// Bekannte Busse aktualisieren (nach Anlegen/Löschen in der Bus-Registry)
+    ///
+    /// Routings auf (und von) entfernten Bussen werden getrennt und aus der
+    /// Matrix genommen.
+    pub fn set_bus_ids(&mut self, bus_ids: &[String]) {
+        let gone: Vec<String> = self
+            .bus_ids
+            .iter()
+            .filter(|id| !bus_ids.contains(id))
+            .cloned()
+            .collect();
+        let removed: Vec<(String, String)> = self
+            .matrix
+            .keys()
+            .filter(|(source_id, bus_id)| !bus_ids.contains(bus_id) || gone.contains(source_id))
+            .cloned()
+            .collect();
+        for (source_id, bus_id) in removed {
+            if let Err(e) = self.unlink(&source_id, &bus_id) {
+                log::warn!("Routing {} → {} nicht getrennt: {}", source_id, bus_id, e);
+            }
+            self.matrix.remove(&(source_id, bus_id));
+        }
+        self.bus_ids = bus_ids.to_vec();
+    }
+
+    /// Prüfen ob ein Bus in der Registry existiert
+    fn check_bus(&self, bus_id: &str) -> Result<(), String> {
+        if !self.bus_ids.iter().any(|id| id == bus_id) {
+            return Err(format!("Ungültige Bus-ID: {}", bus_id));
         }
+        Ok(())
+    }
+
+    /// Kreuzpunkt-Gain eines Eintrags prüfen (Bereich, nur Submixe mit Pegel)
+    fn check_gain(&self, entry: &RoutingEntry) -> Result<(), String> {
+        validate_crosspoint_gain(entry.gain_db)?;
+        if entry.active && entry.gain_db != 0.0 && !self.is_bus_source(&entry.source_id) {
+            return Err(format!(
+                "Kreuzpunkt {} → {}: Gain nur für Submixe (Bus → Bus) möglich",
+                entry.source_id, entry.bus_id
+            ));
+        }
+        Ok(())
+    }
+
+    /// Ist die Quelle ein Bus der Registry (Submix)?
+    pub fn is_bus_source(&self, source_id: &str) -> bool {
+        self.bus_ids.iter().any(|id| id == source_id)
+    }
+
+    /// PipeWire-Link anlegen (Strip/App → Summen-Node des Busses)
+    ///
+    /// Bus → Bus mischt die Mix-Engine, dafür gibt es keinen Link.
+    fn link(&self, source_id: &str, bus_id: &str) -> Result<(), String> {
+        if self.is_bus_source(source_id) {
+            return Ok(());
+        }
+        pipewire::create_audio_link(source_id, bus_id)
+    }
+
+    /// PipeWire-Link entfernen (Strip/App → Summen-Node des Busses)
+    fn unlink(&self, source_id: &str, bus_id: &str) -> Result<(), String> {
+        if self.is_bus_source(source_id) {
+            return Ok(());
+        }
+        pipewire::remove_audio_link(source_id, bus_id)
+    }
+
+    /// Alle aktiven Bus → Bus Routings (sortiert)
+    pub fn bus_routes(&self) -> Vec<(String, String)> {
+        let mut routes: Vec<(String, String)> = self
+            .matrix
+            .iter()
+            .filter(|((source_id, _), _)| self.is_bus_source(source_id))
+            .map(|(key, _)| key.clone())
+            .collect();
+        routes.sort();
+        routes
+    }
+
+    /// Alle aktiven Bus → Bus Routings mit Kreuzpunkt-Gain in dB (sortiert)
+    pub fn submix_gains(&self) -> Vec<(String, String, f32)> {
+        self.bus_routes()
+            .into_iter()
+            .map(|(source_id, bus_id)| {
+                let gain_db = self.gain_db(&source_id, &bus_id);
+                (source_id, bus_id, gain_db)
+            })
+            .collect()
+    }
+
+    /// Verarbeitungs-Reihenfolge der Busse (Quell-Busse vor Ziel-Bussen)
+    ///
+    /// Busse ohne Abhängigkeit behalten die Registry-Reihenfolge.
+    pub fn bus_order(&self) -> Vec<String> {
+        // Die Matrix ist nach jeder Änderung zyklenfrei
+        topological_order(&self.bus_ids, &self.bus_routes())
+            .unwrap_or_else(|_| self.bus_ids.clone())
+    }
+
+    /// Prüfen ob ein Bus → Bus Routing eine Rückkopplung erzeugen würde
+    ///
+    /// Sucht einen Weg vom Ziel-Bus zurück zum Quell-Bus und gibt ihn im
+    /// Fehlertext aus (z.B. "B1 → B2 → B1").
+    fn check_feedback(&self, source_id: &str, bus_id: &str) -> Result<(), String> {
+        if source_id == bus_id {
+            return Err(format!(
+                "Bus {} kann nicht auf sich selbst geroutet werden",
+                bus_id
+            ));
+        }
+        if let Some(path) = find_path(&self.bus_routes(), bus_id, source_id) {
+            return Err(format!(
+                "Routing {} → {} abgelehnt: Rückkopplung ({} → {})",
+                source_id,
+                bus_id,
+                source_id,
+                path.join(" → ")
+            ));
+        }
+        Ok(())
     }
 
     /// Routing-Matrix als Liste abrufen
     pub fn get_routing_matrix(&self) -> Vec<RoutingEntry> {
         self.matrix
             .iter()
-            .map(|((source_id, bus_id), &active)| RoutingEntry {
+            .map(|((source_id, bus_id), &gain_db)| RoutingEntry {
                 source_id: source_id.clone(),
                 bus_id: bus_id.clone(),
-                active,
+                active: true,
+                gain_db,
             })
             .collect()
     }
 
+    /// Aktuellen Zustand eines Kreuzpunkts abrufen (inaktiv: Gain 0 dB)
+    pub fn crosspoint(&self, source_id: &str, bus_id: &str) -> RoutingEntry {
+        let gain_db = self
+            .matrix
+            .get(&(source_id.to_string(), bus_id.to_string()))
+            .copied();
+        RoutingEntry {
+            source_id: source_id.to_string(),
+            bus_id: bus_id.to_string(),
+            active: gain_db.is_some(),
+            gain_db: gain_db.unwrap_or(0.0),
+        }
+    }
+
+    /// Kreuzpunkt-Gain abfragen (0 dB wenn nicht geroutet)
+    pub fn gain_db(&self, source_id: &str, bus_id: &str) -> f32 {
+        self.crosspoint(source_id, bus_id).gain_db
+    }
+
+    /// Mehrere Kreuzpunkte in einem Schritt setzen (alle oder keiner)
+    ///
+    /// Prüft zuerst den kompletten Satz (Busse, Source-IDs, Gain-Bereich und
+    /// Gain nur bei Submixen, doppelte Kreuzpunkte, Rückkopplungen im Ergebnis) — bei einem Fehler
+    /// bleibt die Matrix unverändert. PipeWire-Links werden danach wie bei
+    /// `restore` bestmöglich angelegt. Gibt den vorherigen Zustand der
+    /// geänderten Kreuzpunkte zurück (für Undo).
+    pub fn set_crosspoints(
+        &mut self,
+        entries: &[RoutingEntry],
+    ) -> Result<Vec<RoutingEntry>, String> {
+        let mut seen: HashSet<(&str, &str)> = HashSet::new();
+        for entry in entries {
+            self.check_bus(&entry.bus_id)?;
+            if entry.source_id.is_empty() {
+                return Err("Source-ID darf nicht leer sein".to_string());
+            }
+            if entry.active && entry.source_id == entry.bus_id {
+                return Err(format!(
+                    "Bus {} kann nicht auf sich selbst geroutet werden",
+                    entry.bus_id
+                ));
+            }
+            self.check_gain(entry)?;
+            if !seen.insert((entry.source_id.as_str(), entry.bus_id.as_str())) {
+                return Err(format!(
+                    "Kreuzpunkt {} → {} mehrfach angegeben",
+                    entry.source_id, entry.bus_id
+                ));
+            }
+        }
+
+        // Rückkopplung im Ergebnis prüfen (nicht Schritt für Schritt —
+        // ein Satz darf Routings gleichzeitig entfernen und umdrehen)
+        let mut bus_routes: Vec<(String, String)> = self
+            .bus_routes()
+            .into_iter()
+            .filter(|(source_id, bus_id)| !seen.contains(&(source_id.as_str(), bus_id.as_str())))
+            .collect();
+        bus_routes.extend(
+            entries
+                .iter()
+                .filter(|e| e.active && self.is_bus_source(&e.source_id))
+                .map(|e| (e.source_id.clone(), e.bus_id.clone())),
+        );
+        topological_order(&self.bus_ids, &bus_routes)?;
+
+        let mut previous = Vec::new();
+        for entry in entries {
+            let prev = self.crosspoint(&entry.source_id, &entry.bus_id);
+            if prev.active == entry.active && (!entry.active || prev.gain_db == entry.gain_db) {
+                continue;
+            }
+            let key = (entry.source_id.clone(), entry.bus_id.clone());
+            if entry.active {
+                if !prev.active {
+                    if let Err(e) = self.link(&entry.source_id, &entry.bus_id) {
+                        log::warn!(
+                            "Routing {} → {} nicht verbunden: {}",
+                            entry.source_id,
+                            entry.bus_id,
+                            e
+                        );
+                    }
+                }
+                self.matrix.insert(key, entry.gain_db);
+            } else {
+                if let Err(e) = self.unlink(&entry.source_id, &entry.bus_id) {
+                    log::warn!(
+                        "Routing {} → {} nicht getrennt: {}",
+                        entry.source_id,
+                        entry.bus_id,
+                        e
+                    );
+                }
+                self.matrix.remove(&key);
+            }
+            previous.push(prev);
+        }
+        log::info!("{} Kreuzpunkte gesetzt", previous.len());
+        Ok(previous)
+    }
+
+    /// Kreuzpunkt-Änderungen um die Matrix auf `entries` zu bringen
+    ///
+    /// Nicht enthaltene Routings werden deaktiviert (Template-Import).
+    pub fn changes_to(&self, entries: &[RoutingEntry]) -> Vec<RoutingEntry> {
+        let wanted: Vec<&RoutingEntry> = entries.iter().filter(|e| e.active).collect();
+        let mut changes: Vec<RoutingEntry> = self
+            .get_routing_matrix()
+            .into_iter()
+            .filter(|current| {
+                !wanted
+                    .iter()
+                    .any(|e| e.source_id == current.source_id && e.bus_id == current.bus_id)
+            })
+            .map(|current| RoutingEntry {
+                active: false,
+                gain_db: 0.0,
+                ..current
+            })
+            .collect();
+        changes.sort_by(|a, b| (&a.source_id, &a.bus_id).cmp(&(&b.source_id, &b.bus_id)));
+        for entry in wanted {
+            if self.crosspoint(&entry.source_id, &entry.bus_id) != *entry {
+                changes.push(entry.clone());
+            }
+        }
+        changes
+    }
+
+    /// Matrix in einem Schritt auf einen Szenen-Stand bringen (inkl. Gain)
+    ///
+    /// Wie `set_crosspoints(&changes_to(..))`; ältere Stände mit Gain auf
+    /// App-/Strip-Quellen laden wie bei `restore` mit 0 dB.
+    pub fn apply_scene(&mut self, entries: &[RoutingEntry]) -> Result<(), String> {
+        let entries: Vec<RoutingEntry> = entries
+            .iter()
+            .map(|entry| {
+                if entry.active && entry.gain_db != 0.0 && !self.is_bus_source(&entry.source_id) {
+                    log::warn!(
+                        "Kreuzpunkt {} → {}: Gain {} dB verworfen (nur Submixe)",
+                        entry.source_id,
+                        entry.bus_id,
+                        entry.gain_db
+                    );
+                    RoutingEntry {
+                        gain_db: 0.0,
+                        ..entry.clone()
+                    }
+                } else {
+                    entry.clone()
+                }
+            })
+            .collect();
+        self.set_crosspoints(&self.changes_to(&entries))?;
+        Ok(())
+    }
+
     /// Routing setzen (Verbindung aktivieren/deaktivieren)
     pub fn set_routing(
         &mut self,
@@ -52,28 +409,31 @@ impl RoutingManager {
         bus_id: &str,
         active: bool,
     ) -> Result<(), String> {
-        // Validierung: Bus-ID muss A1, A2, B1 oder B2 sein
-        if !["A1", "A2", "B1", "B2"].contains(&bus_id) {
-            return Err(format!("Ungültige Bus-ID: {}", bus_id));
-        }
+        // Validierung: Bus muss in der Registry existieren
+        self.check_bus(bus_id)?;
 
         // Validierung: Source-ID darf nicht leer sein
         if source_id.is_empty() {
             return Err("Source-ID darf nicht leer sein".to_string());
         }
 
+        // Validierung: Submix darf keine Rückkopplung bilden
+        if active && self.is_bus_source(source_id) {
+            self.check_feedback(source_id, bus_id)?;
+        }
+
         let key = (source_id.to_string(), bus_id.to_string());
 
         if active {
             // Phase 2: PipeWire Link erstellen
-            pipewire::create_audio_link(source_id, bus_id)?;
+            self.link(source_id, bus_id)?;
 
-            // Verbindung in Matrix aktivieren (nur bei Erfolg)
-            self.matrix.insert(key, true);
+            // Verbindung in Matrix aktivieren (nur bei Erfolg, Gain bleibt)
+            self.matrix.entry(key).or_insert(0.0);
             log::info!("Routing aktiviert: {} → {}", source_id, bus_id);
         } else {
             // Phase 2: PipeWire Link entfernen
-            pipewire::remove_audio_link(source_id, bus_id)?;
+            self.unlink(source_id, bus_id)?;
 
             // Verbindung aus Matrix deaktivieren (nur bei Erfolg)
             self.matrix.remove(&key);
@@ -83,18 +443,120 @@ impl RoutingManager {
         Ok(())
     }
 
+    /// Gespeicherte Routings wiederherstellen (Session/Scene)
+    ///
+    /// Übernimmt alle aktiven Einträge in die Matrix und legt die PipeWire-Links
+    /// bestmöglich an — fehlende Quellen (z.B. App nicht gestartet) bleiben
+    /// in der Matrix, damit das Routing beim nächsten Verbinden erhalten ist.
+    /// Ältere Stände mit Gain auf App-/Strip-Quellen laden mit 0 dB.
+    pub fn restore(&mut self, entries: &[RoutingEntry]) -> Result<(), String> {
+        for entry in entries {
+            self.check_bus(&entry.bus_id)?;
+            if entry.source_id.is_empty() {
+                return Err("Source-ID darf nicht leer sein".to_string());
+            }
+            if entry.active && entry.source_id == entry.bus_id {
+                return Err(format!(
+                    "Bus {} kann nicht auf sich selbst geroutet werden",
+                    entry.bus_id
+                ));
+            }
+            validate_crosspoint_gain(entry.gain_db)?;
+        }
+        let bus_routes: Vec<(String, String)> = entries
+            .iter()
+            .filter(|e| e.active && self.is_bus_source(&e.source_id))
+            .map(|e| (e.source_id.clone(), e.bus_id.clone()))
+            .collect();
+        topological_order(&self.bus_ids, &bus_routes)?;
+
+        self.matrix.clear();
+        for entry in entries.iter().filter(|e| e.active) {
+            if let Err(e) = self.link(&entry.source_id, &entry.bus_id) {
+                log::warn!(
+                    "Routing {} → {} nicht verbunden: {}",
+                    entry.source_id,
+                    entry.bus_id,
+                    e
+                );
+            }
+            let gain_db = if self.is_bus_source(&entry.source_id) {
+                entry.gain_db
+            } else {
+                if entry.gain_db != 0.0 {
+                    log::warn!(
+                        "Kreuzpunkt {} → {}: Gain {} dB verworfen (nur Submixe)",
+                        entry.source_id,
+                        entry.bus_id,
+                        entry.gain_db
+                    );
+                }
+                0.0
+            };
+            self.matrix
+                .insert((entry.source_id.clone(), entry.bus_id.clone()), gain_db);
+        }
+        log::info!(
+            "Routing-Matrix wiederhergestellt ({} Einträge)",
+            self.matrix.len()
+        );
+        Ok(())
+    }
+
+    /// Abgeleitetes Routing übernehmen (Mix-Minus)
+    ///
+    /// Wie `restore`: die Matrix folgt der Ableitung auch wenn der PipeWire-Link
+    /// (noch) nicht angelegt werden kann. Rückkopplungen werden abgelehnt.
+    pub fn apply_derived(&mut self, entry: &RoutingEntry) -> Result<(), String> {
+        let (source_id, bus_id) = (entry.source_id.as_str(), entry.bus_id.as_str());
+        self.check_bus(bus_id)?;
+        let key = (source_id.to_string(), bus_id.to_string());
+        if entry.active {
+            if !self.is_routed(source_id, bus_id) {
+                if self.is_bus_source(source_id) {
+                    self.check_feedback(source_id, bus_id)?;
+                }
+                if let Err(e) = self.link(source_id, bus_id) {
+                    log::warn!("Routing {} → {} nicht verbunden: {}", source_id, bus_id, e);
+                }
+            }
+            self.matrix.insert(key, entry.gain_db);
+        } else {
+            if let Err(e) = self.unlink(source_id, bus_id) {
+                log::warn!("Routing {} → {} nicht getrennt: {}", source_id, bus_id, e);
+            }
+            self.matrix.remove(&key);
+        }
+        Ok(())
+    }
+
+    /// PipeWire-Links eines Busses neu anlegen (nach Neustart des Bus-Nodes)
+    ///
+    /// Betrifft die Routings in den Bus (Submixe brauchen keinen Link).
+    /// Fehlende Quellen werden nur protokolliert, die Matrix bleibt unverändert.
+    pub fn relink_bus(&self, bus_id: &str) {
+        for (source_id, target) in self.matrix.keys() {
+            if target != bus_id && source_id != bus_id {
+                continue;
+            }
+            if let Err(e) = self.link(source_id, target) {
+                log::warn!("Routing {} → {} nicht verbunden: {}", source_id, target, e);
+            }
+        }
+    }
+
     /// Routing-Status abfragen (ist Source mit Bus verbunden?)
     pub fn is_routed(&self, source_id: &str, bus_id: &str) -> bool {
         let key = (source_id.to_string(), bus_id.to_string());
-        self.matrix.get(&key).copied().unwrap_or(false)
+        self.matrix.contains_key(&key)
     }
 
     /// Alle Routings für eine Source abrufen
     pub fn get_source_routing(&self, source_id: &str) -> Vec<String> {
         self.matrix
-            .iter()
-            .filter_map(|((src, bus), &active)| {
-                if src == source_id && active {
+            .keys()
+            .filter_map(|(src, bus)| {
+                if src == source_id {
                     Some(bus.clone())
                 } else {
                     None
@@ -121,6 +583,74 @@ impl Default for RoutingManager {
     }
 }
 
+/// Topologische Sortierung der Busse (Kahn)
+///
+/// Bei gleichem Rang entscheidet die Reihenfolge in `bus_ids`.
+/// Fehler wenn die Routings einen Zyklus enthalten.
+fn topological_order(
+    bus_ids: &[String],
+    routes: &[(String, String)],
+) -> Result<Vec<String>, String> {
+    let mut pending: HashMap<&str, usize> = bus_ids.iter().map(|id| (id.as_str(), 0)).collect();
+    for (_, to) in routes {
+        if let Some(count) = pending.get_mut(to.as_str()) {
+            *count += 1;
+        }
+    }
+
+    let mut order: Vec<String> = Vec::with_capacity(bus_ids.len());
+    let mut done: HashSet<&str> = HashSet::new();
+    while order.len() < bus_ids.len() {
+        let next = bus_ids
+            .iter()
+            .find(|id| !done.contains(id.as_str()) && pending[id.as_str()] == 0);
+        let next = match next {
+            Some(id) => id,
+            None => {
+                let mut stuck: Vec<&str> = bus_ids
+                    .iter()
+                    .map(|id| id.as_str())
+                    .filter(|id| !done.contains(id))
+                    .collect();
+                stuck.sort();
+                return Err(format!(
+                    "Bus-Routing enthält eine Rückkopplung ({})",
+                    stuck.join(", ")
+                ));
+            }
+        };
+        done.insert(next);
+        order.push(next.clone());
+        for (_, to) in routes.iter().filter(|(from, _)| from == next) {
+            if let Some(count) = pending.get_mut(to.as_str()) {
+                *count -= 1;
+            }
+        }
+    }
+    Ok(order)
+}
+
+/// Weg von `from` nach `to` über Bus → Bus Routings (Tiefensuche)
+fn find_path(routes: &[(String, String)], from: &str, to: &str) -> Option<Vec<String>> {
+    let mut stack: Vec<Vec<String>> = vec![vec![from.to_string()]];
+    let mut visited: HashSet<String> = HashSet::new();
+    while let Some(path) = stack.pop() {
+        let last = path.last().cloned().unwrap_or_default();
+        if last == to {
+            return Some(path);
+        }
+        if !visited.insert(last.clone()) {
+            continue;
+        }
+        for (_, next) in routes.iter().filter(|(src, _)| *src == last) {
+            let mut longer = path.clone();
+            longer.push(next.clone());
+            stack.push(longer);
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,4 +758,254 @@ mod tests {
         manager.clear();
         assert_eq!(manager.routing_count(), 0);
     }
+
+    #[test]
+    fn test_restore_invalid_bus_id() {
+        let mut manager = RoutingManager::new();
+        let entries = vec![RoutingEntry {
+            source_id: "mic-1".to_string(),
+            bus_id: "X1".to_string(),
+            active: true,
+            gain_db: 0.0,
+        }];
+        assert!(manager.restore(&entries).is_err());
+        assert_eq!(manager.routing_count(), 0);
+    }
+
+    #[test]
+    fn test_user_defined_bus() {
+        let mut manager = RoutingManager::new();
+        let ids = vec!["A1".to_string(), "B3".to_string()];
+        manager.set_bus_ids(&ids);
+        let entry = |bus_id: &str| RoutingEntry {
+            source_id: "mic-1".to_string(),
+            bus_id: bus_id.to_string(),
+            active: true,
+            gain_db: 0.0,
+        };
+        // restore() legt Links bestmöglich an — läuft auch ohne PipeWire
+        manager.restore(&[entry("B3")]).unwrap();
+        assert!(manager.is_routed("mic-1", "B3"));
+        assert!(manager.restore(&[entry("B1")]).is_err());
+        assert!(manager.set_routing("mic-1", "B1", true).is_err());
+
+        // Gelöschter Bus: Routing verschwindet aus der Matrix
+        manager.set_bus_ids(&ids[..1]);
+        assert!(!manager.is_routed("mic-1", "B3"));
+        assert_eq!(manager.routing_count(), 0);
+    }
+
+    fn route(source_id: &str, bus_id: &str) -> RoutingEntry {
+        RoutingEntry {
+            source_id: source_id.to_string(),
+            bus_id: bus_id.to_string(),
+            active: true,
+            gain_db: 0.0,
+        }
+    }
+
+    fn off(source_id: &str, bus_id: &str) -> RoutingEntry {
+        RoutingEntry {
+            active: false,
+            ..route(source_id, bus_id)
+        }
+    }
+
+    #[test]
+    fn test_bus_order_follows_submix() {
+        let mut manager = RoutingManager::new();
+        // B2 (VoIP) → B1 (Stream), B1 → A1
+        manager
+            .restore(&[route("B2", "B1"), route("B1", "A1"), route("mic-1", "B2")])
+            .unwrap();
+        assert_eq!(manager.bus_order(), vec!["A2", "B2", "B1", "A1"]);
+        assert_eq!(
+            manager.bus_routes(),
+            vec![
+                ("B1".to_string(), "A1".to_string()),
+                ("B2".to_string(), "B1".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_feedback_rejected() {
+        let mut manager = RoutingManager::new();
+        manager
+            .restore(&[route("B2", "B1"), route("B1", "A1")])
+            .unwrap();
+
+        // Prüfung greift vor dem PipeWire-Link — läuft ohne PipeWire
+        let err = manager.set_routing("A1", "B2", true).unwrap_err();
+        assert_eq!(
+            err,
+            "Routing A1 → B2 abgelehnt: Rückkopplung (A1 → B2 → B1 → A1)"
+        );
+        assert!(manager.set_routing("B1", "B1", true).is_err());
+        assert!(!manager.is_routed("A1", "B2"));
+
+        assert!(manager
+            .restore(&[route("B1", "B2"), route("B2", "B1")])
+            .unwrap_err()
+            .contains("Rückkopplung"));
+        // Abgelehnte Wiederherstellung lässt die Matrix unverändert
+        assert!(manager.is_routed("B2", "B1"));
+    }
+
+    #[test]
+    fn test_apply_derived_without_pipewire() {
+        let mut manager = RoutingManager::new();
+        manager.apply_derived(&route("app-spotify", "B2")).unwrap();
+        assert!(manager.is_routed("app-spotify", "B2"));
+        manager.restore(&[route("B2", "B1")]).unwrap();
+        assert!(manager.apply_derived(&route("B1", "B2")).is_err());
+        manager.apply_derived(&off("B2", "B1")).unwrap();
+        assert_eq!(manager.routing_count(), 0);
+    }
+
+    #[test]
+    fn test_set_crosspoints_with_gain() {
+        let mut manager = RoutingManager::new();
+        manager.restore(&[route("B2", "B1")]).unwrap();
+
+        let quiet = RoutingEntry {
+            gain_db: -6.0,
+            ..route("B2", "B1")
+        };
+        let previous = manager
+            .set_crosspoints(&[
+                quiet.clone(),
+                route("app-spotify", "B1"),
+                off("app-game", "A1"),
+            ])
+            .unwrap();
+        // Unveränderte Kreuzpunkte tauchen im Undo-Satz nicht auf
+        assert_eq!(previous, vec![route("B2", "B1"), off("app-spotify", "B1")]);
+        assert_eq!(manager.gain_db("B2", "B1"), -6.0);
+        assert_eq!(
+            manager.submix_gains(),
+            vec![("B2".to_string(), "B1".to_string(), -6.0)]
+        );
+
+        // Der Undo-Satz stellt den vorherigen Stand wieder her
+        manager.set_crosspoints(&previous).unwrap();
+        assert_eq!(manager.gain_db("B2", "B1"), 0.0);
+        assert!(!manager.is_routed("app-spotify", "B1"));
+    }
+
+    #[test]
+    fn test_gain_only_for_submixes() {
+        let mut manager = RoutingManager::new();
+        // PipeWire-Links (App/Strip → Summen-Node) kennen keinen Pegel
+        let quiet = RoutingEntry {
+            gain_db: -6.0,
+            ..route("app-spotify", "B1")
+        };
+        assert!(manager
+            .set_crosspoints(std::slice::from_ref(&quiet))
+            .unwrap_err()
+            .contains("nur für Submixe"));
+        assert_eq!(manager.routing_count(), 0);
+
+        // Ältere Sessions laden mit 0 dB
+        manager.restore(&[quiet]).unwrap();
+        assert!(manager.is_routed("app-spotify", "B1"));
+        assert_eq!(manager.gain_db("app-spotify", "B1"), 0.0);
+    }
+
+    #[test]
+    fn test_apply_scene_keeps_gain() {
+        let mut manager = RoutingManager::new();
+        manager.restore(&[route("B2", "B1")]).unwrap();
+        let before = manager.get_routing_matrix();
+
+        // Recall: Submix mit -6 dB, ältere Szene mit Gain auf einer App
+        let send = RoutingEntry {
+            gain_db: -6.0,
+            ..route("B2", "B1")
+        };
+        let app = RoutingEntry {
+            gain_db: -3.0,
+            ..route("app-spotify", "A1")
+        };
+        manager.apply_scene(&[send, app]).unwrap();
+        assert_eq!(manager.gain_db("B2", "B1"), -6.0);
+        assert!(manager.is_routed("app-spotify", "A1"));
+        assert_eq!(manager.gain_db("app-spotify", "A1"), 0.0);
+
+        // Undo: vorheriger Stand inkl. 0 dB auf dem Submix
+        manager.apply_scene(&before).unwrap();
+        assert_eq!(manager.get_routing_matrix(), before);
+        assert_eq!(manager.gain_db("B2", "B1"), 0.0);
+    }
+
+    #[test]
+    fn test_set_crosspoints_is_atomic() {
+        let mut manager = RoutingManager::new();
+        manager.restore(&[route("B2", "B1")]).unwrap();
+
+        let loud = RoutingEntry {
+            gain_db: 20.0,
+            ..route("app-spotify", "B1")
+        };
+        assert!(manager
+            .set_crosspoints(&[route("app-game", "A1"), loud])
+            .is_err());
+        assert!(manager
+            .set_crosspoints(&[route("app-game", "A1"), route("B1", "B2")])
+            .unwrap_err()
+            .contains("Rückkopplung"));
+        assert!(manager
+            .set_crosspoints(&[route("app-game", "A1"), off("app-game", "A1")])
+            .is_err());
+        assert_eq!(manager.routing_count(), 1);
+
+        // Umdrehen in einem Schritt ist erlaubt
+        manager
+            .set_crosspoints(&[off("B2", "B1"), route("B1", "B2")])
+            .unwrap();
+        assert!(manager.is_routed("B1", "B2"));
+        assert_eq!(manager.routing_count(), 1);
+    }
+
+    #[test]
+    fn test_template_roundtrip() {
+        let mut manager = RoutingManager::new();
+        manager
+            .restore(&[route("app-spotify", "B1"), route("B2", "B1")])
+            .unwrap();
+        let template = RoutingTemplate {
+            version: ROUTING_TEMPLATE_VERSION,
+            name: "Podcast".to_string(),
+            entries: vec![
+                route("app-spotify", "B1"),
+                RoutingEntry {
+                    gain_db: -3.0,
+                    ..route("B2", "A1")
+                },
+                route("app-discord", "B2"),
+            ],
+        };
+        let parsed = RoutingTemplate::from_json(&template.to_json().unwrap()).unwrap();
+        assert_eq!(parsed, template);
+
+        let changes = manager.changes_to(&parsed.entries);
+        assert_eq!(changes.len(), 3);
+        manager.set_crosspoints(&changes).unwrap();
+        assert!(manager.changes_to(&parsed.entries).is_empty());
+        assert!(!manager.is_routed("B2", "B1"));
+
+        let future = r#"{"version": 99, "name": "x", "entries": []}"#;
+        assert!(RoutingTemplate::from_json(future).is_err());
+    }
+
+    #[test]
+    fn test_deleted_bus_drops_submix() {
+        let mut manager = RoutingManager::new();
+        manager.restore(&[route("B2", "B1")]).unwrap();
+        let ids: Vec<String> = ["A1", "A2", "B1"].map(String::from).to_vec();
+        manager.set_bus_ids(&ids);
+        assert_eq!(manager.routing_count(), 0);
+        assert!(!manager.is_bus_source("B2"));
+    }
 }
@@ -0,0 +1,294 @@
+// Modul: audio/talk — Talk-Modi pro Strip (Push-to-Talk, Push-to-Mute, Toggle)
+//
+// Ein Talk-Gate öffnet bzw. schließt einen Strip abhängig vom Modus und den
+// Press/Release-Ereignissen (Hotkeys, MIDI, API, Controller). Nach dem
+// Loslassen hält ein Nachlauf (Release-Tail) den Zustand noch kurz, der
+// Übergang selbst wird über eine Fade-Rampe klickfrei geblendet.
+// Das Gate kann auf einzelne Busse beschränkt werden (z.B. PTT nur auf B2).
+// SPEC: 14-hotkeys (Push-to-Talk)
+use serde::{Deserialize, Serialize};
+
+/// Standard-Nachlauf nach dem Loslassen (ms)
+pub const DEFAULT_RELEASE_TAIL_MS: u32 = 150;
+/// Standard-Fade-Zeit (ms)
+pub const DEFAULT_FADE_MS: u32 = 10;
+/// Maximaler Nachlauf (ms)
+const MAX_RELEASE_TAIL_MS: u32 = 2000;
+/// Maximale Fade-Zeit (ms)
+const MAX_FADE_MS: u32 = 500;
+
+/// Talk-Modus eines Strips
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TalkMode {
+    /// Immer offen (kein Gate)
+    #[default]
+    AlwaysOn,
+    /// Offen solange gedrückt (+ Nachlauf)
+    PushToTalk,
+    /// Stumm solange gedrückt (+ Nachlauf)
+    PushToMute,
+    /// Jeder Druck schaltet um (Start: stumm)
+    Toggle,
+}
+
+/// Talk-Einstellungen eines Strips
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TalkSettings {
+    /// Talk-Modus
+    pub mode: TalkMode,
+    /// Nachlauf nach dem Loslassen (ms, 0-2000)
+    pub release_tail_ms: u32,
+    /// Fade-Zeit beim Öffnen/Schließen (ms, 0-500)
+    pub fade_ms: u32,
+    /// Betroffene Busse (leer = alle Busse)
+    pub bus_ids: Vec<String>,
+}
+
+impl Default for TalkSettings {
+    fn default() -> Self {
+        Self {
+            mode: TalkMode::AlwaysOn,
+            release_tail_ms: DEFAULT_RELEASE_TAIL_MS,
+            fade_ms: DEFAULT_FADE_MS,
+            bus_ids: Vec::new(),
+        }
+    }
+}
+
+impl TalkSettings {
+    /// Wertebereiche prüfen
+    pub fn validate(&self) -> Result<(), String> {
+        if self.release_tail_ms > MAX_RELEASE_TAIL_MS {
+            return Err(format!(
+                "Nachlauf außerhalb des Bereichs (0 bis {} ms)",
+                MAX_RELEASE_TAIL_MS
+            ));
+        }
+        if self.fade_ms > MAX_FADE_MS {
+            return Err(format!(
+                "Fade-Zeit außerhalb des Bereichs (0 bis {} ms)",
+                MAX_FADE_MS
+            ));
+        }
+        Ok(())
+    }
+
+    /// Prüfen ob das Gate auf einen Bus wirkt
+    pub fn applies_to(&self, bus_id: &str) -> bool {
+        self.bus_ids.is_empty() || self.bus_ids.iter().any(|b| b == bus_id)
+    }
+}
+
+/// Zustand eines Talk-Gates (für das Frontend)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TalkStatus {
+    /// Strip-ID
+    pub strip_id: String,
+    /// Talk-Modus
+    pub mode: TalkMode,
+    /// Taste gedrückt
+    pub pressed: bool,
+    /// Gate offen (Ziel-Zustand, Fade kann noch laufen)
+    pub open: bool,
+}
+
+/// Talk-Gate eines Strips (Laufzeit-Zustand)
+#[derive(Debug, Clone)]
+pub struct TalkGate {
+    /// Einstellungen
+    settings: TalkSettings,
+    /// Taste gedrückt (PTT/PTM)
+    pressed: bool,
+    /// Umschalt-Zustand (Toggle)
+    latched: bool,
+    /// Verbleibender Nachlauf in Frames
+    tail_frames: u64,
+    /// Aktueller Gate-Faktor (0.0 - 1.0)
+    gain: f32,
+    /// Rampe des letzten Blocks (Start, Ende)
+    ramp: (f32, f32),
+}
+
+impl TalkGate {
+    /// Neues Gate im Ruhezustand erstellen (ohne Fade)
+    pub fn new(settings: TalkSettings) -> Self {
+        let mut gate = Self {
+            settings,
+            pressed: false,
+            latched: false,
+            tail_frames: 0,
+            gain: 0.0,
+            ramp: (0.0, 0.0),
+        };
+        let gain = if gate.is_open() { 1.0 } else { 0.0 };
+        gate.gain = gain;
+        gate.ramp = (gain, gain);
+        gate
+    }
+
+    /// Einstellungen übernehmen (Moduswechsel setzt Tasten-Zustand zurück)
+    pub fn set_settings(&mut self, settings: TalkSettings) {
+        if settings.mode != self.settings.mode {
+            self.pressed = false;
+            self.latched = false;
+            self.tail_frames = 0;
+        }
+        self.settings = settings;
+    }
+
+    /// Einstellungen abfragen
+    pub fn settings(&self) -> &TalkSettings {
+        &self.settings
+    }
+
+    /// Taste gedrückt
+    pub fn press(&mut self) {
+        match self.settings.mode {
+            TalkMode::Toggle => self.latched = !self.latched,
+            _ => {
+                self.pressed = true;
+                self.tail_frames = 0;
+            }
+        }
+    }
+
+    /// Taste losgelassen (startet den Nachlauf)
+    pub fn release(&mut self, sample_rate: u32) {
+        if self.settings.mode != TalkMode::Toggle && self.pressed {
+            self.pressed = false;
+            self.tail_frames = ms_to_frames(self.settings.release_tail_ms, sample_rate);
+        }
+    }
+
+    /// Ziel-Zustand: Gate offen
+    pub fn is_open(&self) -> bool {
+        let engaged = self.pressed || self.tail_frames > 0;
+        match self.settings.mode {
+            TalkMode::AlwaysOn => true,
+            TalkMode::PushToTalk => engaged,
+            TalkMode::PushToMute => !engaged,
+            TalkMode::Toggle => self.latched,
+        }
+    }
+
+    /// Gate um einen Block weiterschalten (Fade-Rampe + Nachlauf)
+    pub fn advance(&mut self, frames: usize, sample_rate: u32) {
+        let target = if self.is_open() { 1.0 } else { 0.0 };
+        let fade_frames = ms_to_frames(self.settings.fade_ms, sample_rate);
+        // Ohne Fade springt das Gate direkt (auch am Block-Anfang)
+        let start = if fade_frames == 0 { target } else { self.gain };
+        let end = if fade_frames == 0 {
+            target
+        } else {
+            let step = frames as f32 / fade_frames as f32;
+            if target > start {
+                (start + step).min(target)
+            } else {
+                (start - step).max(target)
+            }
+        };
+
+        self.gain = end;
+        self.ramp = (start, end);
+        self.tail_frames = self.tail_frames.saturating_sub(frames as u64);
+    }
+
+    /// Rampe des zuletzt verarbeiteten Blocks (Start, Ende)
+    pub fn ramp(&self) -> (f32, f32) {
+        self.ramp
+    }
+
+    /// Zustand für das Frontend
+    pub fn status(&self, strip_id: &str) -> TalkStatus {
+        TalkStatus {
+            strip_id: strip_id.to_string(),
+            mode: self.settings.mode,
+            pressed: self.pressed,
+            open: self.is_open(),
+        }
+    }
+}
+
+/// Millisekunden in Frames umrechnen
+fn ms_to_frames(ms: u32, sample_rate: u32) -> u64 {
+    ms as u64 * sample_rate as u64 / 1000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: u32 = 48000;
+
+    fn gate(mode: TalkMode, tail_ms: u32, fade_ms: u32) -> TalkGate {
+        TalkGate::new(TalkSettings {
+            mode,
+            release_tail_ms: tail_ms,
+            fade_ms,
+            bus_ids: vec![],
+        })
+    }
+
+    #[test]
+    fn test_push_to_talk_with_tail() {
+        let mut gate = gate(TalkMode::PushToTalk, 10, 0);
+        assert!(!gate.is_open());
+        gate.press();
+        assert!(gate.is_open());
+        gate.release(RATE);
+        // 10ms Nachlauf = 480 Frames
+        assert!(gate.is_open());
+        gate.advance(256, RATE);
+        assert!(gate.is_open());
+        gate.advance(256, RATE);
+        assert!(!gate.is_open());
+    }
+
+    #[test]
+    fn test_push_to_mute() {
+        let mut gate = gate(TalkMode::PushToMute, 0, 0);
+        assert!(gate.is_open());
+        gate.press();
+        assert!(!gate.is_open());
+        gate.release(RATE);
+        assert!(gate.is_open());
+    }
+
+    #[test]
+    fn test_toggle_ignores_release() {
+        let mut gate = gate(TalkMode::Toggle, 100, 0);
+        assert!(!gate.is_open());
+        gate.press();
+        gate.release(RATE);
+        assert!(gate.is_open());
+        gate.press();
+        assert!(!gate.is_open());
+    }
+
+    #[test]
+    fn test_fade_ramp() {
+        // 10ms Fade = 480 Frames → zwei Blöcke à 240 Frames
+        let mut gate = gate(TalkMode::PushToTalk, 0, 10);
+        assert_eq!(gate.ramp(), (0.0, 0.0));
+        gate.press();
+        gate.advance(240, RATE);
+        assert_eq!(gate.ramp(), (0.0, 0.5));
+        gate.advance(240, RATE);
+        assert_eq!(gate.ramp(), (0.5, 1.0));
+        gate.advance(240, RATE);
+        assert_eq!(gate.ramp(), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_settings_validate_and_scope() {
+        let mut settings = TalkSettings::default();
+        assert!(settings.validate().is_ok());
+        assert!(settings.applies_to("A1"));
+        settings.bus_ids = vec!["B2".to_string()];
+        assert!(settings.applies_to("B2"));
+        assert!(!settings.applies_to("B1"));
+        settings.fade_ms = MAX_FADE_MS + 1;
+        assert!(settings.validate().is_err());
+    }
+}
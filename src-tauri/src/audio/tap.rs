@@ -0,0 +1,362 @@
+// Modul: audio/tap — Lock-freie SPSC-Ringpuffer und Consumer-Taps
+//
+// Der Echtzeit-Callback schreibt genau einmal in einen SPSC-Ring (ringbuf).
+// Ein Verteiler außerhalb des Echtzeit-Threads kopiert die Daten pro Quelle
+// in je einen eigenen Tap pro Consumer (Metering, Mix-Engine, STT, Recording).
+// Jeder Ring zählt verworfene Samples in einem eigenen Overflow-Zähler.
+use ringbuf::traits::{Consumer, Observer, Producer, Split};
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Kapazität des Capture-Rings pro Stream in Samples (≈ 85ms Stereo @ 48kHz)
+pub const CAPTURE_RING_SAMPLES: usize = 8192;
+/// Kapazität eines Consumer-Taps in Samples (≈ 340ms Stereo @ 48kHz)
+pub const TAP_RING_SAMPLES: usize = 32768;
+
+/// Consumer eines Audio-Taps
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TapKind {
+    /// VU-Meter
+    Metering,
+    /// In-Process Mix-Engine
+    MixEngine,
+    /// Speech-to-Text (Bleeper)
+    Stt,
+    /// Aufnahme
+    Recording,
+}
+
+/// Schreibseite eines Rings (genau ein Producer)
+pub struct RingWriter {
+    /// ringbuf-Producer
+    prod: HeapProd<f32>,
+    /// Anzahl verworfener Samples (Ring voll)
+    overflow: Arc<AtomicU64>,
+}
+
+impl RingWriter {
+    /// Samples schreiben — zählt alles was nicht mehr passt als Overflow
+    ///
+    /// Allokationsfrei und ohne Lock, darf im Echtzeit-Callback laufen.
+    #[inline]
+    pub fn write(&mut self, samples: &[f32]) -> usize {
+        let written = self.prod.push_slice(samples);
+        self.count_dropped(samples.len() - written);
+        written
+    }
+
+    /// Verworfene Samples im Overflow-Zähler vermerken
+    #[inline]
+    fn count_dropped(&self, dropped: usize) {
+        if dropped > 0 {
+            self.overflow.fetch_add(dropped as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Anzahl bisher verworfener Samples
+    pub fn overflow_count(&self) -> u64 {
+        self.overflow.load(Ordering::Relaxed)
+    }
+}
+
+/// Leseseite eines Rings (genau ein Consumer)
+pub struct RingReader {
+    /// ringbuf-Consumer
+    cons: HeapCons<f32>,
+    /// Overflow-Zähler (geteilt mit dem Writer)
+    overflow: Arc<AtomicU64>,
+}
+
+impl RingReader {
+    /// Bis zu `out.len()` Samples lesen
+    #[inline]
+    pub fn read(&mut self, out: &mut [f32]) -> usize {
+        self.cons.pop_slice(out)
+    }
+
+    /// Anzahl lesbarer Samples
+    pub fn available(&self) -> usize {
+        self.cons.occupied_len()
+    }
+
+    /// Anzahl bisher verworfener Samples
+    pub fn overflow_count(&self) -> u64 {
+        self.overflow.load(Ordering::Relaxed)
+    }
+
+    /// Prüfen ob die Schreibseite noch existiert
+    pub fn is_connected(&self) -> bool {
+        self.cons.write_is_held()
+    }
+}
+
+/// Neuen SPSC-Ring mit `capacity` Samples erstellen
+pub fn ring(capacity: usize) -> (RingWriter, RingReader) {
+    let (prod, cons) = HeapRb::<f32>::new(capacity).split();
+    let overflow = Arc::new(AtomicU64::new(0));
+    (
+        RingWriter {
+            prod,
+            overflow: Arc::clone(&overflow),
+        },
+        RingReader { cons, overflow },
+    )
+}
+
+/// Ein Consumer-Tap: eigener Ring für genau einen Consumer einer Quelle
+pub struct AudioTap {
+    /// Quelle (Strip- oder Bus-ID)
+    pub source_id: String,
+    /// Consumer-Typ
+    pub kind: TapKind,
+    /// Leseseite des Tap-Rings
+    reader: RingReader,
+    /// Sample-Rate der Quelle (geteilt mit dem Verteiler, 0 = unbekannt)
+    sample_rate: Arc<AtomicU32>,
+}
+
+impl AudioTap {
+    /// Bis zu `out.len()` Samples (Stereo interleaved) lesen
+    #[inline]
+    pub fn read(&mut self, out: &mut [f32]) -> usize {
+        self.reader.read(out)
+    }
+
+    /// Anzahl lesbarer Samples
+    pub fn available(&self) -> usize {
+        self.reader.available()
+    }
+
+    /// Anzahl im Tap verworfener Samples (Consumer zu langsam)
+    pub fn overflow_count(&self) -> u64 {
+        self.reader.overflow_count()
+    }
+
+    /// Sample-Rate der Quelle (None solange der Producer sie nicht gemeldet hat)
+    pub fn sample_rate(&self) -> Option<u32> {
+        match self.sample_rate.load(Ordering::Relaxed) {
+            0 => None,
+            rate => Some(rate),
+        }
+    }
+
+    /// Prüfen ob der Tap noch beliefert wird
+    ///
+    /// false nach `unsubscribe` oder wenn ein neuer Tap gleichen Typs ihn ersetzt hat.
+    pub fn is_connected(&self) -> bool {
+        self.reader.is_connected()
+    }
+}
+
+/// Overflow-Statistik eines Taps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TapStats {
+    /// Quelle (Strip- oder Bus-ID)
+    pub source_id: String,
+    /// Consumer-Typ
+    pub kind: TapKind,
+    /// Verworfene Samples seit Tap-Start
+    pub overflow_samples: u64,
+}
+
+/// Verteiler: eine Quelle → beliebig viele Consumer-Taps
+///
+/// Läuft nie im Echtzeit-Callback; der Callback schreibt nur in seinen
+/// eigenen Capture-Ring, der Verteiler liest diesen und füllt die Taps.
+#[derive(Default)]
+pub struct TapHub {
+    /// Schreibseiten der Taps pro Quelle
+    taps: HashMap<String, Vec<(TapKind, RingWriter)>>,
+    /// Sample-Rate pro Quelle (vom Producer gemeldet)
+    rates: HashMap<String, Arc<AtomicU32>>,
+}
+
+impl TapHub {
+    /// Neuen, leeren Verteiler erstellen
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Tap für einen Consumer anlegen (ersetzt einen bestehenden gleichen Typs)
+    pub fn subscribe(&mut self, source_id: &str, kind: TapKind) -> AudioTap {
+        let (writer, reader) = ring(TAP_RING_SAMPLES);
+        let writers = self.taps.entry(source_id.to_string()).or_default();
+        writers.retain(|(k, _)| *k != kind);
+        writers.push((kind, writer));
+
+        AudioTap {
+            source_id: source_id.to_string(),
+            kind,
+            reader,
+            sample_rate: self.rate_slot(source_id),
+        }
+    }
+
+    /// Sample-Rate einer Quelle melden (vom Producer beim Verteilen)
+    pub fn set_sample_rate(&mut self, source_id: &str, rate: u32) {
+        self.rate_slot(source_id).store(rate, Ordering::Relaxed);
+    }
+
+    /// Geteilten Rate-Slot einer Quelle holen (legt ihn bei Bedarf an)
+    fn rate_slot(&mut self, source_id: &str) -> Arc<AtomicU32> {
+        if let Some(slot) = self.rates.get(source_id) {
+            return Arc::clone(slot);
+        }
+        let slot = Arc::new(AtomicU32::new(0));
+        self.rates.insert(source_id.to_string(), Arc::clone(&slot));
+        slot
+    }
+
+    /// Tap eines Consumers entfernen
+    pub fn unsubscribe(&mut self, source_id: &str, kind: TapKind) {
+        if let Some(writers) = self.taps.get_mut(source_id) {
+            writers.retain(|(k, _)| *k != kind);
+            if writers.is_empty() {
+                self.taps.remove(source_id);
+            }
+        }
+    }
+
+    /// Prüfen ob eine Quelle mindestens einen Tap hat
+    pub fn has_taps(&self, source_id: &str) -> bool {
+        self.taps.contains_key(source_id)
+    }
+
+    /// Samples einer Quelle an alle ihre Taps verteilen
+    pub fn distribute(&mut self, source_id: &str, samples: &[f32]) {
+        if let Some(writers) = self.taps.get_mut(source_id) {
+            for (_, writer) in writers.iter_mut() {
+                writer.write(samples);
+            }
+        }
+    }
+
+    /// Overflow-Statistik aller Taps (sortiert nach Quelle)
+    pub fn stats(&self) -> Vec<TapStats> {
+        let mut stats: Vec<TapStats> = self
+            .taps
+            .iter()
+            .flat_map(|(source_id, writers)| {
+                writers.iter().map(move |(kind, writer)| TapStats {
+                    source_id: source_id.clone(),
+                    kind: *kind,
+                    overflow_samples: writer.overflow_count(),
+                })
+            })
+            .collect();
+        stats.sort_by(|a, b| a.source_id.cmp(&b.source_id));
+        stats
+    }
+}
+
+/// Thread-sicher geteilter Verteiler
+pub type SharedTapHub = Arc<Mutex<TapHub>>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_write_read() {
+        let (mut writer, mut reader) = ring(8);
+        assert_eq!(writer.write(&[1.0, 2.0, 3.0]), 3);
+        assert_eq!(reader.available(), 3);
+
+        let mut out = [0.0; 4];
+        assert_eq!(reader.read(&mut out), 3);
+        assert_eq!(&out[..3], &[1.0, 2.0, 3.0]);
+        assert_eq!(reader.overflow_count(), 0);
+    }
+
+    #[test]
+    fn test_ring_overflow_counter() {
+        let (mut writer, reader) = ring(4);
+        assert_eq!(writer.write(&[0.5; 6]), 4);
+        assert_eq!(writer.overflow_count(), 2);
+        // Reader sieht denselben Zähler
+        assert_eq!(reader.overflow_count(), 2);
+    }
+
+    #[test]
+    fn test_hub_distributes_to_all_taps() {
+        let mut hub = TapHub::new();
+        let mut meter = hub.subscribe("hw-mic", TapKind::Metering);
+        let mut rec = hub.subscribe("hw-mic", TapKind::Recording);
+
+        hub.distribute("hw-mic", &[0.1, 0.2]);
+        hub.distribute("andere-quelle", &[0.9, 0.9]);
+
+        let mut out = [0.0; 4];
+        assert_eq!(meter.read(&mut out), 2);
+        assert_eq!(rec.read(&mut out), 2);
+        assert_eq!(&out[..2], &[0.1, 0.2]);
+    }
+
+    #[test]
+    fn test_hub_taps_are_independent() {
+        let mut hub = TapHub::new();
+        let mut fast = hub.subscribe("B1", TapKind::Metering);
+        let slow = hub.subscribe("B1", TapKind::Stt);
+
+        // Nur der schnelle Consumer liest — der langsame läuft über
+        let chunk = vec![0.0; TAP_RING_SAMPLES / 2];
+        let mut out = vec![0.0; TAP_RING_SAMPLES];
+        for _ in 0..3 {
+            hub.distribute("B1", &chunk);
+            fast.read(&mut out);
+        }
+
+        assert_eq!(fast.overflow_count(), 0);
+        assert_eq!(slow.overflow_count(), (TAP_RING_SAMPLES / 2) as u64);
+    }
+
+    #[test]
+    fn test_hub_subscribe_replaces_same_kind() {
+        let mut hub = TapHub::new();
+        let _old = hub.subscribe("hw-mic", TapKind::Stt);
+        let mut new = hub.subscribe("hw-mic", TapKind::Stt);
+        assert_eq!(hub.stats().len(), 1);
+
+        hub.distribute("hw-mic", &[0.3; 2]);
+        assert_eq!(new.available(), 2);
+    }
+
+    #[test]
+    fn test_tap_disconnects_when_replaced() {
+        let mut hub = TapHub::new();
+        let old = hub.subscribe("hw-mic", TapKind::Stt);
+        assert!(old.is_connected());
+
+        let new = hub.subscribe("hw-mic", TapKind::Stt);
+        assert!(!old.is_connected());
+        assert!(new.is_connected());
+
+        hub.unsubscribe("hw-mic", TapKind::Stt);
+        assert!(!new.is_connected());
+    }
+
+    #[test]
+    fn test_tap_sees_source_sample_rate() {
+        let mut hub = TapHub::new();
+        let tap = hub.subscribe("hw-mic", TapKind::Stt);
+        assert_eq!(tap.sample_rate(), None);
+
+        // Auch nach dem Abonnieren gemeldete Raten kommen beim Tap an
+        hub.set_sample_rate("hw-mic", 44100);
+        assert_eq!(tap.sample_rate(), Some(44100));
+    }
+
+    #[test]
+    fn test_hub_unsubscribe() {
+        let mut hub = TapHub::new();
+        let _tap = hub.subscribe("hw-mic", TapKind::Recording);
+        assert!(hub.has_taps("hw-mic"));
+
+        hub.unsubscribe("hw-mic", TapKind::Recording);
+        assert!(!hub.has_taps("hw-mic"));
+        assert!(hub.stats().is_empty());
+    }
+}
@@ -0,0 +1,746 @@
+// Modul: automation — Parameter-Automation (Aufnahme und Wiedergabe)
+//
+// Fader-, Mute- und FX-Parameter-Bewegungen werden als Punkte mit
+// Frame-Zeitstempel (48 kHz, sample-genau) in Automation-Lanes eines Clips
+// aufgezeichnet. Die Wiedergabe folgt der Transport-Position (Timeline oder
+// Hotkey-Start) und erzeugt MixerCommands für die Manager.
+// Der Transport zählt im Sample-Takt der Mix-Engine: alle Zeitpunkte sind
+// Engine-Frames (`MixEngine::frames_processed`), die Wiedergabe läuft an den
+// Block-Grenzen der Engine und stoppt im Read-Modus am Clip-Ende.
+// Modi: Read (nur abspielen), Write (nur aufnehmen), Touch (aufnehmen solange
+// bewegt) und Latch (aufnehmen ab der ersten Bewegung bis Stopp).
+// SPEC: 10-presets-scenes (Automation)
+
+pub mod store;
+
+use crate::audio::mix_engine::MIX_SAMPLE_RATE;
+use crate::fx::FxModuleType;
+use crate::history::MixerCommand;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Touch-Modus: Zeit ohne Bewegung bis das Lesen wieder übernimmt (ms)
+pub const TOUCH_RELEASE_MS: u64 = 500;
+/// Minimale Wertänderung die bei der Wiedergabe angewendet wird
+const APPLY_EPSILON: f32 = 1e-4;
+
+/// Automation-Modus
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationMode {
+    /// Lanes abspielen, nichts aufnehmen
+    #[default]
+    Read,
+    /// Nur aufnehmen (Lanes werden nicht abgespielt)
+    Write,
+    /// Abspielen, aufnehmen solange ein Parameter bewegt wird
+    Touch,
+    /// Abspielen, ab der ersten Bewegung bis zum Stopp aufnehmen
+    Latch,
+}
+
+/// Automatisierbarer Parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AutomationTarget {
+    /// Strip-Lautstärke (dB)
+    StripVolume { strip_id: String },
+    /// Strip-Gain (dB)
+    StripGain { strip_id: String },
+    /// Strip-Mute (0/1)
+    StripMute { strip_id: String },
+    /// Bus-Lautstärke (dB)
+    BusVolume { bus_id: String },
+    /// Bus-Mute (0/1)
+    BusMute { bus_id: String },
+    /// FX-Parameter
+    FxParam {
+        module_type: FxModuleType,
+        param_name: String,
+    },
+    /// FX-Bypass (0/1)
+    FxBypass { module_type: FxModuleType },
+    /// Master-Lautstärke (dB)
+    MasterVolume,
+}
+
+impl AutomationTarget {
+    /// Ziel und Wert aus einem Mixer-Kommando ableiten (None = nicht automatisierbar)
+    pub fn from_command(command: &MixerCommand) -> Option<(Self, f32)> {
+        use MixerCommand as C;
+        let flag = |b: bool| if b { 1.0 } else { 0.0 };
+        match command {
+            C::StripVolume {
+                strip_id,
+                volume_db,
+            } => Some((
+                Self::StripVolume {
+                    strip_id: strip_id.clone(),
+                },
+                *volume_db,
+            )),
+            C::StripGain { strip_id, gain_db } => Some((
+                Self::StripGain {
+                    strip_id: strip_id.clone(),
+                },
+                *gain_db,
+            )),
+            C::StripMute { strip_id, muted } => Some((
+                Self::StripMute {
+                    strip_id: strip_id.clone(),
+                },
+                flag(*muted),
+            )),
+            C::BusVolume { bus_id, volume_db } => Some((
+                Self::BusVolume {
+                    bus_id: bus_id.clone(),
+                },
+                *volume_db,
+            )),
+            C::BusMute { bus_id, muted } => Some((
+                Self::BusMute {
+                    bus_id: bus_id.clone(),
+                },
+                flag(*muted),
+            )),
+            C::FxParam {
+                module_type,
+                param_name,
+                value,
+            } => Some((
+                Self::FxParam {
+                    module_type: *module_type,
+                    param_name: param_name.clone(),
+                },
+                *value,
+            )),
+            C::FxBypass {
+                module_type,
+                bypass,
+            } => Some((
+                Self::FxBypass {
+                    module_type: *module_type,
+                },
+                flag(*bypass),
+            )),
+            C::MasterVolume { volume_db } => Some((Self::MasterVolume, *volume_db)),
+            _ => None,
+        }
+    }
+
+    /// Mixer-Kommando für einen Lane-Wert erzeugen
+    pub fn to_command(&self, value: f32) -> MixerCommand {
+        use MixerCommand as C;
+        match self {
+            Self::StripVolume { strip_id } => C::StripVolume {
+                strip_id: strip_id.clone(),
+                volume_db: value,
+            },
+            Self::StripGain { strip_id } => C::StripGain {
+                strip_id: strip_id.clone(),
+                gain_db: value,
+            },
+            Self::StripMute { strip_id } => C::StripMute {
+                strip_id: strip_id.clone(),
+                muted: value >= 0.5,
+            },
+            Self::BusVolume { bus_id } => C::BusVolume {
+                bus_id: bus_id.clone(),
+                volume_db: value,
+            },
+            Self::BusMute { bus_id } => C::BusMute {
+                bus_id: bus_id.clone(),
+                muted: value >= 0.5,
+            },
+            Self::FxParam {
+                module_type,
+                param_name,
+            } => C::FxParam {
+                module_type: *module_type,
+                param_name: param_name.clone(),
+                value,
+            },
+            Self::FxBypass { module_type } => C::FxBypass {
+                module_type: *module_type,
+                bypass: value >= 0.5,
+            },
+            Self::MasterVolume => C::MasterVolume { volume_db: value },
+        }
+    }
+
+    /// Schalt-Parameter (Stufen statt Interpolation)
+    pub fn is_discrete(&self) -> bool {
+        matches!(
+            self,
+            Self::StripMute { .. } | Self::BusMute { .. } | Self::FxBypass { .. }
+        )
+    }
+
+    /// Eindeutiger Schlüssel der Lane
+    pub fn key(&self) -> String {
+        match self {
+            Self::StripVolume { strip_id } => format!("strip-volume:{}", strip_id),
+            Self::StripGain { strip_id } => format!("strip-gain:{}", strip_id),
+            Self::StripMute { strip_id } => format!("strip-mute:{}", strip_id),
+            Self::BusVolume { bus_id } => format!("bus-volume:{}", bus_id),
+            Self::BusMute { bus_id } => format!("bus-mute:{}", bus_id),
+            Self::FxParam {
+                module_type,
+                param_name,
+            } => format!("fx:{:?}:{}", module_type, param_name),
+            Self::FxBypass { module_type } => format!("fx-bypass:{:?}", module_type),
+            Self::MasterVolume => "master-volume".to_string(),
+        }
+    }
+}
+
+/// Automation-Punkt (Frame-Position bei 48 kHz)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutomationPoint {
+    /// Position in Frames ab Clip-Anfang
+    pub frame: u64,
+    /// Wert (dB, Parameter-Wert oder 0/1)
+    pub value: f32,
+}
+
+/// Automation-Lane eines Parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationLane {
+    /// Ziel-Parameter
+    pub target: AutomationTarget,
+    /// Punkte (sortiert nach Frame)
+    pub points: Vec<AutomationPoint>,
+}
+
+impl AutomationLane {
+    /// Leere Lane erstellen
+    pub fn new(target: AutomationTarget) -> Self {
+        Self {
+            target,
+            points: Vec::new(),
+        }
+    }
+
+    /// Wert an einer Position (vor dem ersten Punkt: None)
+    ///
+    /// Kontinuierliche Parameter werden linear interpoliert, Schalt-Parameter
+    /// halten den letzten Punkt.
+    pub fn value_at(&self, frame: u64) -> Option<f32> {
+        let next = self.points.partition_point(|p| p.frame <= frame);
+        let prev = self.points.get(next.checked_sub(1)?)?;
+        match self.points.get(next) {
+            Some(after) if !self.target.is_discrete() => {
+                let span = (after.frame - prev.frame) as f32;
+                let t = (frame - prev.frame) as f32 / span;
+                Some(prev.value + (after.value - prev.value) * t)
+            }
+            _ => Some(prev.value),
+        }
+    }
+
+    /// Punkt schreiben (ersetzt einen Punkt an derselben Position)
+    pub fn write(&mut self, frame: u64, value: f32) {
+        let idx = self.points.partition_point(|p| p.frame < frame);
+        match self.points.get_mut(idx) {
+            Some(point) if point.frame == frame => point.value = value,
+            _ => self.points.insert(idx, AutomationPoint { frame, value }),
+        }
+    }
+
+    /// Punkte im Bereich (from, to] entfernen (Überschreiben beim Aufnehmen)
+    pub fn erase(&mut self, from: u64, to: u64) {
+        self.points.retain(|p| p.frame <= from || p.frame > to);
+    }
+}
+
+/// Automation-Clip (mehrere Lanes)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationClip {
+    /// Clip-ID ("clip_<uuid>")
+    pub id: String,
+    /// Anzeigename
+    pub name: String,
+    /// Lanes
+    pub lanes: Vec<AutomationLane>,
+}
+
+impl AutomationClip {
+    /// Leeren Clip erstellen
+    pub fn new(id: &str, name: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            lanes: Vec::new(),
+        }
+    }
+
+    /// Länge in Frames (letzter Punkt aller Lanes)
+    pub fn length_frames(&self) -> u64 {
+        self.lanes
+            .iter()
+            .filter_map(|l| l.points.last())
+            .map(|p| p.frame)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Lane eines Ziels (wird bei Bedarf angelegt)
+    fn lane_mut(&mut self, target: &AutomationTarget) -> &mut AutomationLane {
+        let key = target.key();
+        let idx = match self.lanes.iter().position(|l| l.target.key() == key) {
+            Some(idx) => idx,
+            None => {
+                self.lanes.push(AutomationLane::new(target.clone()));
+                self.lanes.len() - 1
+            }
+        };
+        &mut self.lanes[idx]
+    }
+}
+
+/// Zustand der Automation (für das Frontend)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationStatus {
+    /// Geladener Clip
+    pub clip_id: Option<String>,
+    /// Modus
+    pub mode: AutomationMode,
+    /// Transport läuft
+    pub playing: bool,
+    /// Position in Frames
+    pub position_frames: u64,
+    /// Clip-Länge in Frames (letzter Punkt)
+    pub length_frames: u64,
+    /// Ungespeicherte Aufnahmen vorhanden
+    pub modified: bool,
+}
+
+/// Laufende Aufnahme einer Lane
+#[derive(Debug, Clone)]
+struct WritePass {
+    /// Frame der letzten Bewegung (zuletzt geschriebener Punkt)
+    last_write: u64,
+    /// Zuletzt geschriebener Wert
+    value: f32,
+}
+
+/// Automation-Engine: Transport, Aufnahme und Wiedergabe eines Clips
+#[derive(Debug, Default)]
+pub struct AutomationEngine {
+    /// Geladener Clip
+    clip: Option<AutomationClip>,
+    /// Modus
+    mode: AutomationMode,
+    /// Engine-Frame beim Start und Start-Position des laufenden Transports
+    transport: Option<(u64, u64)>,
+    /// Sample-Rate des Engine-Takts (Hz, 0 = MIX_SAMPLE_RATE)
+    engine_rate: u32,
+    /// Position beim letzten Stopp/Locate
+    position: u64,
+    /// Laufende Aufnahmen pro Lane-Schlüssel
+    writing: HashMap<String, WritePass>,
+    /// Zuletzt angewendete Werte pro Lane-Schlüssel
+    applied: HashMap<String, f32>,
+    /// Ungespeicherte Aufnahmen
+    modified: bool,
+    /// Transport am Clip-Ende angehalten (noch nicht abgeholt)
+    ended: bool,
+}
+
+impl AutomationEngine {
+    /// Neue Engine ohne Clip erstellen
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clip laden (stoppt den Transport, Position auf Anfang)
+    pub fn load(&mut self, clip: AutomationClip) {
+        self.clip = Some(clip);
+        self.transport = None;
+        self.position = 0;
+        self.writing.clear();
+        self.applied.clear();
+        self.modified = false;
+    }
+
+    /// Geladenen Clip abfragen
+    pub fn clip(&self) -> Option<&AutomationClip> {
+        self.clip.as_ref()
+    }
+
+    /// Geladenen Clip entfernen (z.B. nach dem Löschen)
+    pub fn unload(&mut self) {
+        self.clip = None;
+        self.transport = None;
+        self.position = 0;
+        self.writing.clear();
+        self.applied.clear();
+        self.modified = false;
+    }
+
+    /// Modus setzen (laufende Aufnahmen werden abgeschlossen)
+    pub fn set_mode(&mut self, mode: AutomationMode, now: u64) {
+        let frame = self.position(now);
+        self.finish_writes(frame);
+        self.mode = mode;
+    }
+
+    /// Transport starten
+    pub fn play(&mut self, now: u64) -> Result<(), String> {
+        if self.clip.is_none() {
+            return Err("Kein Automation-Clip geladen".to_string());
+        }
+        if self.transport.is_none() {
+            self.transport = Some((now, self.position));
+            self.applied.clear();
+            self.ended = false;
+        }
+        Ok(())
+    }
+
+    /// Transport stoppen — gibt den Clip zurück wenn er aufgenommen wurde
+    pub fn stop(&mut self, now: u64) -> Option<AutomationClip> {
+        let frame = self.position(now);
+        self.finish_writes(frame);
+        self.position = frame;
+        self.transport = None;
+        if self.modified {
+            self.modified = false;
+            return self.clip.clone();
+        }
+        None
+    }
+
+    /// Position setzen (Timeline)
+    pub fn locate(&mut self, frame: u64, now: u64) {
+        let current = self.position(now);
+        self.finish_writes(current);
+        self.position = frame;
+        if self.transport.is_some() {
+            self.transport = Some((now, frame));
+        }
+        self.applied.clear();
+    }
+
+    /// Sample-Rate des Engine-Takts setzen (Hz)
+    ///
+    /// Clip-Positionen bleiben in 48-kHz-Frames; ein laufender Transport
+    /// wird an der aktuellen Position neu verankert.
+    pub fn set_engine_rate(&mut self, sample_rate: u32, now: u64) {
+        if self.transport.is_some() {
+            let frame = self.position(now);
+            self.transport = Some((now, frame));
+        }
+        self.engine_rate = sample_rate;
+    }
+
+    /// Aktuelle Position in Frames (`now` = Engine-Frame)
+    pub fn position(&self, now: u64) -> u64 {
+        match self.transport {
+            Some((started, from)) => {
+                let rate = match self.engine_rate {
+                    0 => MIX_SAMPLE_RATE,
+                    rate => rate,
+                };
+                let elapsed = now.saturating_sub(started) as u128;
+                from + (elapsed * MIX_SAMPLE_RATE as u128 / rate as u128) as u64
+            }
+            None => self.position,
+        }
+    }
+
+    /// Wurde der Transport seit der letzten Abfrage am Clip-Ende angehalten?
+    pub fn take_ended(&mut self) -> bool {
+        std::mem::take(&mut self.ended)
+    }
+
+    /// Zustand für das Frontend
+    pub fn status(&self, now: u64) -> AutomationStatus {
+        AutomationStatus {
+            clip_id: self.clip.as_ref().map(|c| c.id.clone()),
+            mode: self.mode,
+            playing: self.transport.is_some(),
+            position_frames: self.position(now),
+            length_frames: self.clip.as_ref().map_or(0, |c| c.length_frames()),
+            modified: self.modified,
+        }
+    }
+
+    /// Parameter-Bewegung beobachten und je nach Modus aufnehmen
+    ///
+    /// Gibt true zurück wenn ein Punkt geschrieben wurde.
+    pub fn record(&mut self, command: &MixerCommand, now: u64) -> bool {
+        if self.mode == AutomationMode::Read || self.transport.is_none() {
+            return false;
+        }
+        let (target, value) = match AutomationTarget::from_command(command) {
+            Some(entry) => entry,
+            None => return false,
+        };
+        let frame = self.position(now);
+        let clip = match self.clip.as_mut() {
+            Some(clip) => clip,
+            None => return false,
+        };
+
+        let lane = clip.lane_mut(&target);
+        let pass = self
+            .writing
+            .entry(target.key())
+            .or_insert_with(|| WritePass {
+                last_write: frame,
+                value,
+            });
+        lane.erase(pass.last_write, frame);
+        lane.write(frame, value);
+        pass.last_write = frame;
+        pass.value = value;
+        self.applied.insert(target.key(), value);
+        self.modified = true;
+        true
+    }
+
+    /// Transport weiterschalten: Kommandos der abzuspielenden Lanes
+    ///
+    /// Liefert nur geänderte Werte. Lanes in Aufnahme werden nicht gelesen;
+    /// im Touch-Modus endet die Aufnahme nach `TOUCH_RELEASE_MS` ohne Bewegung.
+    /// Im Read-Modus hält der Transport am Clip-Ende an (siehe `take_ended`);
+    /// in den Aufnahme-Modi läuft er weiter, der Clip wächst mit.
+    pub fn tick(&mut self, now: u64) -> Vec<MixerCommand> {
+        if self.transport.is_none() {
+            return Vec::new();
+        }
+        let frame = self.position(now);
+
+        // Laufende Aufnahmen bis zur aktuellen Position fortschreiben
+        let release_frames = TOUCH_RELEASE_MS * MIX_SAMPLE_RATE as u64 / 1000;
+        let mut released = Vec::new();
+        if let Some(clip) = self.clip.as_mut() {
+            for lane in clip.lanes.iter_mut() {
+                if let Some(pass) = self.writing.get(&lane.target.key()) {
+                    lane.erase(pass.last_write, frame);
+                    if self.mode == AutomationMode::Touch
+                        && frame.saturating_sub(pass.last_write) >= release_frames
+                    {
+                        lane.write(frame, pass.value);
+                        released.push(lane.target.key());
+                    }
+                }
+            }
+        }
+        for key in released {
+            self.writing.remove(&key);
+        }
+
+        if self.mode == AutomationMode::Write {
+            return Vec::new();
+        }
+        let clip = match self.clip.as_ref() {
+            Some(clip) => clip,
+            None => return Vec::new(),
+        };
+        let length = clip.length_frames();
+
+        let mut commands = Vec::new();
+        for lane in &clip.lanes {
+            let key = lane.target.key();
+            if self.writing.contains_key(&key) {
+                continue;
+            }
+            if let Some(value) = lane.value_at(frame) {
+                let changed = self
+                    .applied
+                    .get(&key)
+                    .map_or(true, |prev| (prev - value).abs() > APPLY_EPSILON);
+                if changed {
+                    self.applied.insert(key, value);
+                    commands.push(lane.target.to_command(value));
+                }
+            }
+        }
+
+        if self.mode == AutomationMode::Read && frame >= length {
+            self.position = length;
+            self.transport = None;
+            self.ended = true;
+        }
+        commands
+    }
+
+    /// Laufende Aufnahmen mit einem Endpunkt abschließen
+    fn finish_writes(&mut self, frame: u64) {
+        if let Some(clip) = self.clip.as_mut() {
+            for (key, pass) in self.writing.drain() {
+                if let Some(lane) = clip.lanes.iter_mut().find(|l| l.target.key() == key) {
+                    lane.erase(pass.last_write, frame);
+                    lane.write(frame.max(pass.last_write), pass.value);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume(strip_id: &str, volume_db: f32) -> MixerCommand {
+        MixerCommand::StripVolume {
+            strip_id: strip_id.to_string(),
+            volume_db,
+        }
+    }
+
+    fn lane(points: &[(u64, f32)]) -> AutomationLane {
+        let mut lane = AutomationLane::new(AutomationTarget::MasterVolume);
+        for (frame, value) in points {
+            lane.write(*frame, *value);
+        }
+        lane
+    }
+
+    /// Engine-Frames für eine Zeitspanne (48 kHz)
+    fn ms(ms: u64) -> u64 {
+        ms * MIX_SAMPLE_RATE as u64 / 1000
+    }
+
+    #[test]
+    fn test_lane_value_at() {
+        let lane = lane(&[(100, -20.0), (200, 0.0)]);
+        assert_eq!(lane.value_at(50), None);
+        assert_eq!(lane.value_at(100), Some(-20.0));
+        assert_eq!(lane.value_at(150), Some(-10.0));
+        assert_eq!(lane.value_at(500), Some(0.0));
+
+        let mut mute = AutomationLane::new(AutomationTarget::BusMute {
+            bus_id: "A1".to_string(),
+        });
+        mute.write(0, 0.0);
+        mute.write(100, 1.0);
+        assert_eq!(mute.value_at(99), Some(0.0));
+        assert_eq!(mute.value_at(100), Some(1.0));
+    }
+
+    #[test]
+    fn test_command_roundtrip() {
+        let command = MixerCommand::BusMute {
+            bus_id: "B1".to_string(),
+            muted: true,
+        };
+        let (target, value) = AutomationTarget::from_command(&command).unwrap();
+        assert!(target.is_discrete());
+        assert_eq!(target.to_command(value), command);
+        assert!(
+            AutomationTarget::from_command(&MixerCommand::MasterDim { active: true }).is_none()
+        );
+    }
+
+    #[test]
+    fn test_write_then_read() {
+        let mut engine = AutomationEngine::new();
+        engine.load(AutomationClip::new("clip_1", "Intro"));
+        engine.set_mode(AutomationMode::Write, 0);
+
+        let start = 1_000;
+        engine.play(start).unwrap();
+        assert!(engine.record(&volume("hw-mic", -30.0), start));
+        assert!(engine.record(&volume("hw-mic", -10.0), start + ms(100)));
+        // Write-Modus spielt nichts ab
+        assert!(engine.tick(start + ms(150)).is_empty());
+        let clip = engine.stop(start + ms(200)).unwrap();
+        assert_eq!(clip.lanes.len(), 1);
+        // 100 ms = 4800 Frames
+        assert_eq!(clip.lanes[0].points[1].frame, 4800);
+
+        engine.set_mode(AutomationMode::Read, 0);
+        engine.locate(0, 0);
+        let start = 50_000;
+        engine.play(start).unwrap();
+        assert_eq!(engine.tick(start), vec![volume("hw-mic", -30.0)]);
+        // Unveränderter Wert wird nicht erneut gesendet
+        assert!(engine.tick(start).is_empty());
+        // Read-Modus nimmt nichts auf
+        assert!(!engine.record(&volume("hw-mic", 0.0), start));
+    }
+
+    #[test]
+    fn test_touch_releases_after_timeout() {
+        let mut clip = AutomationClip::new("clip_1", "Touch");
+        let mut lane = AutomationLane::new(AutomationTarget::StripVolume {
+            strip_id: "hw-mic".to_string(),
+        });
+        lane.write(0, -20.0);
+        clip.lanes.push(lane);
+
+        let mut engine = AutomationEngine::new();
+        engine.load(clip);
+        let start = 1_000;
+        engine.set_mode(AutomationMode::Touch, start);
+        engine.play(start).unwrap();
+        assert_eq!(engine.tick(start).len(), 1);
+
+        engine.record(&volume("hw-mic", -5.0), start + ms(10));
+        // Während der Berührung wird die Lane nicht gelesen
+        assert!(engine.tick(start + ms(100)).is_empty());
+        // Nach dem Loslassen übernimmt das Lesen wieder (gehaltener Wert)
+        engine.tick(start + ms(10 + TOUCH_RELEASE_MS));
+        assert!(engine.writing.is_empty());
+        let lane = &engine.clip().unwrap().lanes[0];
+        assert_eq!(lane.value_at(48_000), Some(-5.0));
+    }
+
+    #[test]
+    fn test_latch_holds_until_stop() {
+        let mut engine = AutomationEngine::new();
+        engine.load(AutomationClip::new("clip_1", "Latch"));
+        let start = 1_000;
+        engine.set_mode(AutomationMode::Latch, start);
+        engine.play(start).unwrap();
+        engine.record(&volume("hw-mic", -12.0), start);
+        engine.tick(start + ms(2 * TOUCH_RELEASE_MS));
+        assert_eq!(engine.writing.len(), 1);
+
+        let clip = engine.stop(start + ms(2000)).unwrap();
+        let lane = &clip.lanes[0];
+        assert_eq!(lane.points.len(), 2);
+        assert_eq!(lane.value_at(96_000), Some(-12.0));
+        assert!(!engine.status(start).playing);
+    }
+
+    #[test]
+    fn test_read_stops_at_clip_end() {
+        let mut clip = AutomationClip::new("clip_1", "Fade");
+        let mut lane = AutomationLane::new(AutomationTarget::StripVolume {
+            strip_id: "hw-mic".to_string(),
+        });
+        lane.write(0, -20.0);
+        lane.write(ms(100), 0.0);
+        clip.lanes.push(lane);
+
+        let mut engine = AutomationEngine::new();
+        engine.load(clip);
+        let start = 1_000;
+        engine.play(start).unwrap();
+        engine.tick(start + ms(50));
+        assert!(!engine.take_ended());
+
+        // Letzter Block über das Ende hinaus: Endwert anwenden, dann anhalten
+        assert_eq!(engine.tick(start + ms(150)), vec![volume("hw-mic", 0.0)]);
+        assert!(engine.take_ended());
+        assert!(!engine.take_ended());
+        let status = engine.status(start + ms(300));
+        assert!(!status.playing);
+        assert_eq!(status.position_frames, ms(100));
+    }
+
+    #[test]
+    fn test_position_follows_engine_rate() {
+        let mut engine = AutomationEngine::new();
+        engine.load(AutomationClip::new("clip_1", "Rate"));
+        engine.set_mode(AutomationMode::Write, 0);
+        engine.set_engine_rate(96_000, 0);
+        engine.play(0).unwrap();
+        // 9600 Engine-Frames bei 96 kHz = 100 ms = 4800 Clip-Frames
+        assert_eq!(engine.position(9_600), 4_800);
+    }
+}
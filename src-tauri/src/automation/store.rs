@@ -0,0 +1,269 @@
+// Modul: automation/store — Automation-Clips in SQLite speichern
+//
+// Jeder Clip liegt als JSON in der Tabelle `automation_clips`; die
+// Scene-Zuordnung steht in einer eigenen Spalte und bleibt beim Speichern
+// aufgenommener Lanes erhalten. Der Scene-Recall startet den zugeordneten Clip.
+
+use super::AutomationClip;
+use crate::config::database::Database;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// Clip-Übersicht (ohne Lanes)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutomationClipInfo {
+    pub id: String,
+    pub name: String,
+    pub scene_id: Option<String>,
+    pub created_at: i64,
+}
+
+/// Automation-Store für Speichern/Laden/Löschen von Clips
+pub struct AutomationStore {
+    db: Arc<Database>,
+}
+
+impl AutomationStore {
+    /// Neuen Store mit Datenbank-Referenz erstellen
+    pub fn new(db: Arc<Database>) -> Self {
+        Self { db }
+    }
+
+    /// Neuen, leeren Clip anlegen
+    pub fn create_clip(&self, name: &str) -> Result<AutomationClip, String> {
+        let clip = AutomationClip::new(&format!("clip_{}", uuid::Uuid::new_v4()), name);
+        self.save_clip(&clip)?;
+        Ok(clip)
+    }
+
+    /// Clip speichern (neu oder überschreiben)
+    pub fn save_clip(&self, clip: &AutomationClip) -> Result<(), String> {
+        let clip_json =
+            serde_json::to_string(clip).map_err(|e| format!("Clip-Serialisierung: {}", e))?;
+        let created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| format!("Zeit-Fehler: {}", e))?
+            .as_secs() as i64;
+
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+        conn.execute(
+            "INSERT INTO automation_clips (id, name, clip_json, created_at)
+             VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(id) DO UPDATE SET
+                name = excluded.name,
+                clip_json = excluded.clip_json",
+            params![clip.id, clip.name, clip_json, created_at],
+        )
+        .map_err(|e| format!("DB-Insert-Fehler: {}", e))?;
+
+        log::info!(
+            "Automation-Clip gespeichert: {} (ID: {})",
+            clip.name,
+            clip.id
+        );
+        Ok(())
+    }
+
+    /// Clip laden
+    pub fn load_clip(&self, id: &str) -> Result<AutomationClip, String> {
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+
+        let clip_json: String = conn
+            .query_row(
+                "SELECT clip_json FROM automation_clips WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Automation-Clip nicht gefunden: {}", e))?;
+
+        serde_json::from_str(&clip_json).map_err(|e| format!("Clip-Format ungültig: {}", e))
+    }
+
+    /// Clip löschen
+    pub fn delete_clip(&self, id: &str) -> Result<(), String> {
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+
+        let rows_affected = conn
+            .execute("DELETE FROM automation_clips WHERE id = ?1", params![id])
+            .map_err(|e| format!("DB-Delete-Fehler: {}", e))?;
+
+        if rows_affected == 0 {
+            return Err(format!("Automation-Clip nicht gefunden: {}", id));
+        }
+
+        log::info!("Automation-Clip gelöscht: {}", id);
+        Ok(())
+    }
+
+    /// Clip einer Scene zuordnen (None = Zuordnung lösen)
+    pub fn attach_clip(&self, id: &str, scene_id: Option<&str>) -> Result<(), String> {
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE automation_clips SET scene_id = ?1 WHERE id = ?2",
+                params![scene_id, id],
+            )
+            .map_err(|e| format!("DB-Update-Fehler: {}", e))?;
+
+        if rows_affected == 0 {
+            return Err(format!("Automation-Clip nicht gefunden: {}", id));
+        }
+
+        log::info!("Automation-Clip {} → Scene {:?}", id, scene_id);
+        Ok(())
+    }
+
+    /// Zuordnungen zu einer gelöschten Scene lösen
+    ///
+    /// # Returns
+    /// Anzahl der gelösten Clips
+    pub fn detach_scene(&self, scene_id: &str) -> Result<usize, String> {
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE automation_clips SET scene_id = NULL WHERE scene_id = ?1",
+                params![scene_id],
+            )
+            .map_err(|e| format!("DB-Update-Fehler: {}", e))?;
+
+        if rows_affected > 0 {
+            log::info!(
+                "{} Automation-Clip(s) von Scene {} gelöst",
+                rows_affected,
+                scene_id
+            );
+        }
+        Ok(rows_affected)
+    }
+
+    /// Alle Clips auflisten (ohne Lanes)
+    pub fn list_clips(&self) -> Result<Vec<AutomationClipInfo>, String> {
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, name, scene_id, created_at FROM automation_clips
+                 ORDER BY created_at DESC, name",
+            )
+            .map_err(|e| format!("DB-Prepare-Fehler: {}", e))?;
+
+        let clips = stmt
+            .query_map([], |row| {
+                Ok(AutomationClipInfo {
+                    id: row.get(0)?,
+                    name: row.get(1)?,
+                    scene_id: row.get(2)?,
+                    created_at: row.get(3)?,
+                })
+            })
+            .map_err(|e| format!("DB-Query-Fehler: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("DB-Collect-Fehler: {}", e))?;
+
+        Ok(clips)
+    }
+
+    /// Erster Clip einer Scene (für den Start beim Scene-Recall)
+    pub fn clip_for_scene(&self, scene_id: &str) -> Result<Option<AutomationClip>, String> {
+        let id = self
+            .list_clips()?
+            .into_iter()
+            .find(|c| c.scene_id.as_deref() == Some(scene_id))
+            .map(|c| c.id);
+        id.map(|id| self.load_clip(&id)).transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automation::{AutomationLane, AutomationTarget};
+
+    fn setup() -> AutomationStore {
+        let db = Arc::new(Database::open_in_memory().unwrap());
+        AutomationStore::new(db)
+    }
+
+    #[test]
+    fn test_save_and_load_clip() {
+        let store = setup();
+        let mut clip = store.create_clip("Intro").unwrap();
+        assert!(clip.id.starts_with("clip_"));
+
+        let mut lane = AutomationLane::new(AutomationTarget::MasterVolume);
+        lane.write(48_000, -6.0);
+        clip.lanes.push(lane);
+        store.save_clip(&clip).unwrap();
+
+        assert_eq!(store.load_clip(&clip.id).unwrap(), clip);
+        assert_eq!(store.list_clips().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_attach_to_scene() {
+        let store = setup();
+        let clip = store.create_clip("Outro").unwrap();
+        assert!(store.clip_for_scene("scene_1").unwrap().is_none());
+
+        store.attach_clip(&clip.id, Some("scene_1")).unwrap();
+        let attached = store.clip_for_scene("scene_1").unwrap().unwrap();
+        assert_eq!(attached.id, clip.id);
+
+        // Speichern aufgenommener Lanes behält die Zuordnung
+        store.save_clip(&attached).unwrap();
+        assert!(store.clip_for_scene("scene_1").unwrap().is_some());
+
+        store.attach_clip(&clip.id, None).unwrap();
+        assert!(store.clip_for_scene("scene_1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_detach_deleted_scene() {
+        let store = setup();
+        let clip = store.create_clip("Intro").unwrap();
+        let other = store.create_clip("Outro").unwrap();
+        store.attach_clip(&clip.id, Some("scene_1")).unwrap();
+        store.attach_clip(&other.id, Some("scene_2")).unwrap();
+
+        assert_eq!(store.detach_scene("scene_1").unwrap(), 1);
+        assert!(store.clip_for_scene("scene_1").unwrap().is_none());
+        assert!(store.clip_for_scene("scene_2").unwrap().is_some());
+        assert_eq!(store.detach_scene("scene_1").unwrap(), 0);
+    }
+
+    #[test]
+    fn test_delete_clip() {
+        let store = setup();
+        let clip = store.create_clip("Weg").unwrap();
+        store.delete_clip(&clip.id).unwrap();
+        assert!(store.load_clip(&clip.id).is_err());
+        assert!(store.delete_clip(&clip.id).is_err());
+    }
+}
@@ -59,7 +59,7 @@ impl Database {
         Ok(db)
     }
 
-    /// Standard-Tabellen erstellen (config, presets, scenes)
+    /// Standard-Tabellen erstellen (config, presets, scenes, session, automation)
     pub fn create_tables(&self) -> Result<(), Box<dyn std::error::Error>> {
         let conn = self
             .conn
@@ -104,6 +104,20 @@ impl Database {
                 language TEXT NOT NULL DEFAULT 'de'
             );
 
+            CREATE TABLE IF NOT EXISTS session (
+                id         INTEGER PRIMARY KEY NOT NULL CHECK (id = 1),
+                state_json TEXT NOT NULL,
+                updated_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+            );
+
+            CREATE TABLE IF NOT EXISTS automation_clips (
+                id         TEXT PRIMARY KEY NOT NULL,
+                name       TEXT NOT NULL,
+                scene_id   TEXT,
+                clip_json  TEXT NOT NULL,
+                created_at INTEGER NOT NULL DEFAULT (strftime('%s', 'now'))
+            );
+
             CREATE TABLE IF NOT EXISTS schema_version (
                 version INTEGER PRIMARY KEY NOT NULL
             );",
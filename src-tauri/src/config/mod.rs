@@ -3,6 +3,9 @@
 pub mod database;
 pub mod migration;
 pub mod presets;
+pub mod scene_recall;
+pub mod scene_transition;
+pub mod session;
 
 use database::Database;
 use log::info;
@@ -13,6 +16,8 @@ use std::sync::Arc;
 const DEFAULT_SAMPLE_RATE: u32 = 48000;
 /// Standard Buffer-Größe in Samples
 const DEFAULT_BUFFER_SIZE: u32 = 256;
+/// Standard-Quelle für STT/Bleeper (Stream-Bus)
+const DEFAULT_STT_SOURCE: &str = "B1";
 
 /// Anwendungs-Konfiguration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -116,6 +121,26 @@ impl ConfigManager {
         self.set("ui.language", &config.language)?;
         Ok(())
     }
+
+    /// STT-Quelle (Strip- oder Bus-ID) laden — None = Überwachung aus
+    ///
+    /// Ohne gespeicherten Wert gilt der Stream-Bus. Ob die ID existiert,
+    /// prüft der Aufrufer gegen Mixer und Bus-Registry.
+    pub fn load_stt_source(&self) -> Result<Option<String>, Box<dyn std::error::Error>> {
+        Ok(match self.get("stt.source_id")? {
+            Some(id) if id.is_empty() => None,
+            Some(id) => Some(id),
+            None => Some(DEFAULT_STT_SOURCE.to_string()),
+        })
+    }
+
+    /// STT-Quelle speichern (None = Überwachung aus)
+    pub fn save_stt_source(
+        &self,
+        source_id: Option<&str>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.set("stt.source_id", source_id.unwrap_or(""))
+    }
 }
 
 #[cfg(test)]
@@ -183,6 +208,18 @@ mod tests {
         assert_eq!(loaded.language, "en");
     }
 
+    #[test]
+    fn test_stt_source() {
+        let cm = setup();
+        assert_eq!(cm.load_stt_source().unwrap(), Some("B1".to_string()));
+
+        cm.save_stt_source(Some("hw-mic")).unwrap();
+        assert_eq!(cm.load_stt_source().unwrap(), Some("hw-mic".to_string()));
+
+        cm.save_stt_source(None).unwrap();
+        assert_eq!(cm.load_stt_source().unwrap(), None);
+    }
+
     #[test]
     fn test_get_set() {
         let cm = setup();
@@ -106,6 +106,29 @@ impl SceneManager {
         Ok(scene)
     }
 
+    /// Gespeicherten Inhalt einer Scene ersetzen (Name und Zeitstempel bleiben)
+    pub fn update_state(&self, id: &str, state_json: &str) -> Result<(), String> {
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+
+        let rows_affected = conn
+            .execute(
+                "UPDATE scenes SET state_json = ?1 WHERE id = ?2",
+                params![state_json, id],
+            )
+            .map_err(|e| format!("DB-Update-Fehler: {}", e))?;
+
+        if rows_affected == 0 {
+            return Err(format!("Scene nicht gefunden: {}", id));
+        }
+
+        log::info!("Scene aktualisiert: {}", id);
+        Ok(())
+    }
+
     /// Scene löschen
     pub fn delete_scene(&self, id: &str) -> Result<(), String> {
         let conn = self
@@ -254,6 +277,19 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_update_state() {
+        let manager = setup();
+
+        let scene_id = manager.save_scene("Update", "{}").unwrap();
+        manager.update_state(&scene_id, r#"{"id": 2}"#).unwrap();
+        let scene = manager.load_scene(&scene_id).unwrap();
+        assert_eq!(scene.name, "Update");
+        assert_eq!(scene.state_json, r#"{"id": 2}"#);
+
+        assert!(manager.update_state("nonexistent", "{}").is_err());
+    }
+
     #[test]
     fn test_load_nonexistent() {
         let manager = setup();
@@ -0,0 +1,435 @@
+// Modul: config/scene_recall — Teil-Recall von Szenen mit Scope-Masken ("Safes")
+//
+// Eine Szene speichert neben dem Mixer-Snapshot eine Scope-Maske: welche
+// Parameter-Bereiche (Lautstärken, Mutes, Routing, FX, Master, Makros) beim Recall
+// übernommen werden und welche Strips/Busse "safe" sind, also nie angefasst
+// werden. Der Recall berechnet aus aktuellem Stand, Szene und Maske den
+// Ziel-Stand — die Struktur (welche Strips/Busse existieren) bleibt dabei
+// unverändert.
+// SPEC: 10-presets-scenes
+
+use super::session::SessionState;
+use crate::audio::routing::RoutingEntry;
+use crate::fx::FX_CHAIN_STRIP;
+use serde::{Deserialize, Serialize};
+
+/// Scope-Maske für den Szenen-Recall
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SceneScope {
+    /// Lautstärken: Strip-Volume/Gain/Pan, Bus-Volume, Master-Volume
+    pub volumes: bool,
+    /// Mutes: Strip-Mute/Solo, Bus-Mute, aktive Mute-Gruppen
+    pub mutes: bool,
+    /// Routing: Strip → Bus Zuweisungen, Routing-Matrix und Mix-Minus-Busse
+    pub routing: bool,
+    /// FX-Chain (Module, Bypass, Parameter), Strip-FX an/aus, Bus-Inserts,
+    /// Ducking-Stärke und Voice-FX Dry/Wet — die FX-Chain nur wenn ihr Strip
+    /// (FX_CHAIN_STRIP) nicht safe ist
+    pub fx: bool,
+    /// Master-Sektion: Limiter, DIM, MONO, Talkback
+    pub master: bool,
+    /// Ausgangs-Delays der Busse (A/V-Sync)
+    pub delays: bool,
+    /// Makro-Reglerstellungen (nur die Stellung — die Ziel-Parameter kommen
+    /// aus den übrigen Bereichen, so bleiben Safes unangetastet)
+    pub macros: bool,
+    /// Strips die nie angefasst werden ("Safes")
+    pub safe_strips: Vec<String>,
+    /// Busse die nie angefasst werden ("Safes")
+    pub safe_buses: Vec<String>,
+}
+
+impl Default for SceneScope {
+    /// Voller Recall ohne Safes
+    fn default() -> Self {
+        Self {
+            volumes: true,
+            mutes: true,
+            routing: true,
+            fx: true,
+            master: true,
+            delays: true,
+            macros: true,
+            safe_strips: Vec::new(),
+            safe_buses: Vec::new(),
+        }
+    }
+}
+
+/// Effekt-Pegel außerhalb des Session-Formats (Ducking, Voice-FX)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SceneLevels {
+    /// Ducking-Stärke in dB
+    pub ducking_amount_db: Option<f32>,
+    /// Voice-FX Dry/Wet (0.0-1.0)
+    pub voice_fx_dry_wet: Option<f32>,
+}
+
+impl SceneLevels {
+    /// Ziel-Pegel berechnen (nur wenn der FX-Bereich abgerufen wird)
+    pub fn merge(&self, scene: &SceneLevels, scope: &SceneScope) -> SceneLevels {
+        if !scope.fx {
+            return self.clone();
+        }
+        SceneLevels {
+            ducking_amount_db: scene.ducking_amount_db.or(self.ducking_amount_db),
+            voice_fx_dry_wet: scene.voice_fx_dry_wet.or(self.voice_fx_dry_wet),
+        }
+    }
+}
+
+/// Gespeicherter Szenen-Inhalt: Mixer-Snapshot + Scope-Maske
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SceneSnapshot {
+    /// Mixer-Snapshot (gleiches Format wie die Session)
+    #[serde(flatten)]
+    pub state: SessionState,
+    /// Ducking- und Voice-FX-Pegel (ältere Szenen: keine)
+    #[serde(default)]
+    pub levels: SceneLevels,
+    /// Gespeicherte Scope-Maske (Standard: voller Recall)
+    #[serde(default)]
+    pub scope: SceneScope,
+}
+
+impl SceneSnapshot {
+    /// Szenen-JSON parsen (Fehler bei Szenen ohne Mixer-Snapshot)
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        serde_json::from_str(json)
+            .map_err(|e| format!("Scene enthält keinen Mixer-Snapshot: {}", e))
+    }
+
+    /// Szenen-JSON erzeugen
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string(self).map_err(|e| format!("Scene-Serialisierung: {}", e))
+    }
+}
+
+impl SceneScope {
+    /// Prüfen ob ein Strip beim Recall angefasst werden darf
+    pub fn covers_strip(&self, strip_id: &str) -> bool {
+        !self.safe_strips.iter().any(|s| s == strip_id)
+    }
+
+    /// Prüfen ob ein Bus beim Recall angefasst werden darf
+    pub fn covers_bus(&self, bus_id: &str) -> bool {
+        !self.safe_buses.iter().any(|b| b == bus_id)
+    }
+
+    /// Ziel-Stand berechnen: `current` mit den Werten aus `scene` überlagert
+    ///
+    /// Nur Strips, Busse und Mute-Gruppen die in beiden Ständen existieren
+    /// werden übernommen; Safes und abgewählte Bereiche bleiben unverändert.
+    pub fn merge(&self, current: &SessionState, scene: &SessionState) -> SessionState {
+        let mut target = current.clone();
+
+        for strip in target.strips.iter_mut() {
+            if !self.covers_strip(&strip.id) {
+                continue;
+            }
+            let src = match scene.strips.iter().find(|s| s.id == strip.id) {
+                Some(src) => src,
+                None => continue,
+            };
+            if self.volumes {
+                strip.volume_db = src.volume_db;
+                strip.gain_db = src.gain_db;
+                strip.pan = src.pan;
+            }
+            if self.mutes {
+                strip.muted = src.muted;
+                strip.solo = src.solo;
+            }
+            if self.routing {
+                // Zuweisungen zu Safe-Bussen behalten den aktuellen Stand
+                let mut routing: Vec<String> = src
+                    .bus_routing
+                    .iter()
+                    .filter(|b| self.covers_bus(b) && current.buses.iter().any(|x| &x.id == *b))
+                    .cloned()
+                    .collect();
+                for bus_id in &strip.bus_routing {
+                    if !self.covers_bus(bus_id) && !routing.contains(bus_id) {
+                        routing.push(bus_id.clone());
+                    }
+                }
+                strip.bus_routing = routing;
+            }
+            if self.fx {
+                strip.fx_enabled = src.fx_enabled;
+            }
+        }
+
+        for bus in target.buses.iter_mut() {
+            if !self.covers_bus(&bus.id) {
+                continue;
+            }
+            let src = match scene.buses.iter().find(|b| b.id == bus.id) {
+                Some(src) => src,
+                None => continue,
+            };
+            if self.volumes {
+                bus.volume_db = src.volume_db;
+            }
+            if self.mutes {
+                bus.muted = src.muted;
+            }
+            if self.fx {
+                bus.inserts = src.inserts.clone();
+            }
+            if self.delays {
+                bus.delay_ms = src.delay_ms;
+            }
+            if self.routing {
+                bus.mix_minus = src.mix_minus.clone();
+            }
+        }
+
+        if self.routing {
+            target.routing = self.merge_routing(current, scene);
+        }
+        // Die FX-Chain gehört dem Mikrofon-Strip — ein Safe schützt sie mit
+        if self.fx && self.covers_strip(FX_CHAIN_STRIP) {
+            target.fx = scene.fx.clone();
+        }
+        if self.volumes {
+            target.master.volume_db = scene.master.volume_db;
+        }
+        if self.master {
+            target.master.limiter_ceiling_db = scene.master.limiter_ceiling_db;
+            target.master.dim = scene.master.dim;
+            target.master.mono = scene.master.mono;
+            target.master.talkback = scene.master.talkback;
+            target.master.talkback_settings = scene.master.talkback_settings.clone();
+            target.master.talkback_buses = scene
+                .master
+                .talkback_buses
+                .iter()
+                .filter(|b| current.buses.iter().any(|x| &x.id == *b))
+                .cloned()
+                .collect();
+        }
+        if self.mutes {
+            for group in target.mute_groups.iter_mut() {
+                if let Some(src) = scene.mute_groups.iter().find(|g| g.id == group.id) {
+                    group.active = src.active;
+                }
+            }
+        }
+        if self.macros {
+            for m in target.macros.iter_mut() {
+                if let Some(src) = scene.macros.iter().find(|x| x.id == m.id) {
+                    m.value = src.value;
+                }
+            }
+        }
+
+        target
+    }
+
+    /// Routing-Matrix zusammenführen (Einträge mit Safe-Strip/-Bus bleiben)
+    fn merge_routing(&self, current: &SessionState, scene: &SessionState) -> Vec<RoutingEntry> {
+        let covered =
+            |e: &RoutingEntry| self.covers_strip(&e.source_id) && self.covers_bus(&e.bus_id);
+        let known_bus = |e: &RoutingEntry| current.buses.iter().any(|b| b.id == e.bus_id);
+
+        let mut routing: Vec<RoutingEntry> = current
+            .routing
+            .iter()
+            .filter(|e| !covered(e))
+            .cloned()
+            .collect();
+        routing.extend(
+            scene
+                .routing
+                .iter()
+                .filter(|e| covered(e) && known_bus(e))
+                .cloned(),
+        );
+        routing.sort_by(|a, b| (&a.source_id, &a.bus_id).cmp(&(&b.source_id, &b.bus_id)));
+        routing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::bus::BusManager;
+    use crate::audio::master::MasterManager;
+    use crate::audio::mixer::MixerState;
+    use crate::fx::FxChain;
+
+    fn snapshot(mixer: &MixerState, routing: Vec<RoutingEntry>) -> SessionState {
+        SessionState::new(
+            mixer.get_strips(),
+            BusManager::new().get_buses(),
+            routing,
+            FxChain::new().get_all_modules(),
+            MasterManager::new().get_state(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    fn entry(source_id: &str, bus_id: &str) -> RoutingEntry {
+        RoutingEntry {
+            source_id: source_id.to_string(),
+            bus_id: bus_id.to_string(),
+            active: true,
+            gain_db: 0.0,
+        }
+    }
+
+    /// Aktueller Stand und eine Szene mit geänderten Mic- und Virtual-Werten
+    fn setup() -> (SessionState, SessionState) {
+        let current = snapshot(&MixerState::new(), vec![entry("hw-mic", "A1")]);
+
+        let mut mixer = MixerState::new();
+        mixer.set_volume("hw-mic", -12.0).unwrap();
+        mixer.set_mute("hw-mic", true).unwrap();
+        mixer.set_volume("virt-1", -6.0).unwrap();
+        mixer.set_bus_routing("virt-1", "B2", true).unwrap();
+        let scene = snapshot(&mixer, vec![entry("virt-1", "B2")]);
+        (current, scene)
+    }
+
+    fn strip<'a>(state: &'a SessionState, id: &str) -> &'a crate::audio::mixer::InputStrip {
+        state.strips.iter().find(|s| s.id == id).unwrap()
+    }
+
+    #[test]
+    fn test_full_recall() {
+        let (current, scene) = setup();
+        let target = SceneScope::default().merge(&current, &scene);
+        assert_eq!(strip(&target, "hw-mic").volume_db, -12.0);
+        assert!(strip(&target, "hw-mic").muted);
+        assert_eq!(target.routing.len(), 1);
+        assert_eq!(target.routing[0].source_id, "virt-1");
+    }
+
+    #[test]
+    fn test_volumes_only() {
+        let (current, scene) = setup();
+        let scope = SceneScope {
+            mutes: false,
+            routing: false,
+            ..SceneScope::default()
+        };
+        let target = scope.merge(&current, &scene);
+        assert_eq!(strip(&target, "hw-mic").volume_db, -12.0);
+        assert!(!strip(&target, "hw-mic").muted);
+        assert_eq!(strip(&target, "virt-1").bus_routing, vec!["B1"]);
+        assert_eq!(target.routing[0].source_id, "hw-mic");
+    }
+
+    #[test]
+    fn test_safe_strip_untouched() {
+        let (current, scene) = setup();
+        let scope = SceneScope {
+            safe_strips: vec!["hw-mic".to_string()],
+            ..SceneScope::default()
+        };
+        let target = scope.merge(&current, &scene);
+        assert_eq!(strip(&target, "hw-mic"), strip(&current, "hw-mic"));
+        assert_eq!(strip(&target, "virt-1").volume_db, -6.0);
+        // Routing des Safe-Strips bleibt, Virtual-Routing kommt aus der Szene
+        assert_eq!(target.routing.len(), 2);
+    }
+
+    #[test]
+    fn test_safe_mic_keeps_fx_chain() {
+        let (current, mut scene) = setup();
+        scene.fx[0].enabled = !scene.fx[0].enabled;
+        let scope = SceneScope {
+            safe_strips: vec!["hw-mic".to_string()],
+            ..SceneScope::default()
+        };
+        let target = scope.merge(&current, &scene);
+        assert_eq!(target.fx, current.fx);
+
+        // Ohne Safe kommt die Chain aus der Szene
+        let target = SceneScope::default().merge(&current, &scene);
+        assert_eq!(target.fx, scene.fx);
+    }
+
+    #[test]
+    fn test_safe_bus_keeps_assignment() {
+        let (current, scene) = setup();
+        let scope = SceneScope {
+            safe_buses: vec!["B1".to_string()],
+            ..SceneScope::default()
+        };
+        let target = scope.merge(&current, &scene);
+        // B1 bleibt zugewiesen (Safe), B2 kommt aus der Szene
+        assert_eq!(strip(&target, "virt-1").bus_routing, vec!["B2", "B1"]);
+    }
+
+    #[test]
+    fn test_bus_delay_scope() {
+        let (current, mut scene) = setup();
+        scene.buses.iter_mut().for_each(|b| b.delay_ms = 120.0);
+        let delay = |state: &SessionState, id: &str| {
+            state.buses.iter().find(|b| b.id == id).unwrap().delay_ms
+        };
+
+        let scope = SceneScope {
+            safe_buses: vec!["B1".to_string()],
+            ..SceneScope::default()
+        };
+        let target = scope.merge(&current, &scene);
+        assert_eq!(delay(&target, "A1"), 120.0);
+        assert_eq!(delay(&target, "B1"), 0.0);
+
+        let scope = SceneScope {
+            delays: false,
+            ..SceneScope::default()
+        };
+        assert_eq!(delay(&scope.merge(&current, &scene), "A1"), 0.0);
+    }
+
+    #[test]
+    fn test_levels_follow_fx_scope() {
+        let current = SceneLevels {
+            ducking_amount_db: Some(-6.0),
+            voice_fx_dry_wet: Some(0.5),
+        };
+        let scene = SceneLevels {
+            ducking_amount_db: Some(-20.0),
+            voice_fx_dry_wet: None,
+        };
+        let target = current.merge(&scene, &SceneScope::default());
+        assert_eq!(target.ducking_amount_db, Some(-20.0));
+        assert_eq!(target.voice_fx_dry_wet, Some(0.5));
+
+        let scope = SceneScope {
+            fx: false,
+            ..SceneScope::default()
+        };
+        assert_eq!(current.merge(&scene, &scope), current);
+    }
+
+    #[test]
+    fn test_snapshot_json_roundtrip() {
+        let (_, scene) = setup();
+        let snapshot = SceneSnapshot {
+            state: scene,
+            levels: SceneLevels {
+                ducking_amount_db: Some(-12.0),
+                voice_fx_dry_wet: None,
+            },
+            scope: SceneScope {
+                fx: false,
+                ..SceneScope::default()
+            },
+        };
+        let parsed = SceneSnapshot::from_json(&snapshot.to_json().unwrap()).unwrap();
+        assert!(!parsed.scope.fx);
+        assert_eq!(parsed.levels.ducking_amount_db, Some(-12.0));
+        assert_eq!(parsed.state.strips.len(), 5);
+
+        // Alte Szenen ohne Mixer-Snapshot werden abgelehnt
+        assert!(SceneSnapshot::from_json(r#"{"mixer": {"volume": 0.8}}"#).is_err());
+    }
+}
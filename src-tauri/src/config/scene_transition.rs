@@ -0,0 +1,585 @@
+// Modul: config/scene_transition — Zeitgesteuerte Szenen-Überblendung
+//
+// Blendet alle kontinuierlichen Parameter (Strip-/Bus-/Master-Lautstärke,
+// Gain, Pan, Submix-Sends, Limiter-Ceiling, Ducking-Stärke, Voice-FX Dry/Wet)
+// über eine wählbare Kurve vom aktuellen Stand zum Ziel-Stand der Szene. Pro
+// Takt werden nur die Werte geschrieben, die sich tatsächlich bewegen; Safes
+// bleiben außen vor. Diskrete Parameter (Mutes, Solo, Routing, FX-Chain,
+// DIM/MONO/Talkback, Mute-Gruppen) schalten genau einmal am einstellbaren
+// Umschaltpunkt — ein Send, der erst in der Szene entsteht oder dort entfällt,
+// schaltet also dort mit seinem Ziel-Gain.
+// SPEC: 10-presets-scenes (Crossfade)
+
+use super::scene_recall::{SceneLevels, SceneScope};
+use super::session::SessionState;
+use crate::audio::bus::{db_to_linear, linear_to_db};
+use crate::audio::routing::RoutingEntry;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Maximale Überblendzeit (ms)
+const MAX_TRANSITION_MS: u32 = 60_000;
+/// Standard-Umschaltpunkt für diskrete Parameter (Mitte der Blende)
+const DEFAULT_SWITCH_AT: f32 = 0.5;
+
+/// Überblendkurve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionCurve {
+    /// Linear
+    #[default]
+    Linear,
+    /// Equal-Power (Viertel-Sinus, schneller Anstieg)
+    EqualPower,
+    /// S-Kurve (weicher Anfang und weiches Ende)
+    SCurve,
+}
+
+impl TransitionCurve {
+    /// Gewicht des Ziel-Werts bei Fortschritt `t` (0.0 - 1.0)
+    pub fn weight(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => t,
+            Self::EqualPower => (t * std::f32::consts::FRAC_PI_2).sin(),
+            Self::SCurve => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// Einstellungen einer Überblendung
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TransitionSettings {
+    /// Dauer in ms (0 = sofort)
+    pub duration_ms: u32,
+    /// Überblendkurve
+    pub curve: TransitionCurve,
+    /// Umschaltpunkt für diskrete Parameter (0.0 = Anfang, 1.0 = Ende)
+    pub switch_at: f32,
+}
+
+impl Default for TransitionSettings {
+    fn default() -> Self {
+        Self {
+            duration_ms: 0,
+            curve: TransitionCurve::Linear,
+            switch_at: DEFAULT_SWITCH_AT,
+        }
+    }
+}
+
+impl TransitionSettings {
+    /// Wertebereiche prüfen
+    pub fn validate(&self) -> Result<(), String> {
+        if self.duration_ms > MAX_TRANSITION_MS {
+            return Err(format!(
+                "Überblendzeit außerhalb des Bereichs (0 bis {} ms)",
+                MAX_TRANSITION_MS
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.switch_at) {
+            return Err(format!(
+                "Umschaltpunkt außerhalb: {} (0.0-1.0)",
+                self.switch_at
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Fortschritt einer Überblendung (Event-Payload für das Frontend)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TransitionProgress {
+    /// Name der Ziel-Szene
+    pub scene_name: String,
+    /// Fortschritt (0.0 - 1.0)
+    pub progress: f32,
+    /// Diskrete Parameter bereits umgeschaltet
+    pub switched: bool,
+    /// Überblendung abgeschlossen
+    pub done: bool,
+}
+
+/// Kontinuierliche Werte eines Strips in einem Takt
+#[derive(Debug, Clone, PartialEq)]
+pub struct StripFrame {
+    /// Strip-ID
+    pub id: String,
+    /// Lautstärke (dB)
+    pub volume_db: f32,
+    /// Eingangs-Gain (dB)
+    pub gain_db: f32,
+    /// Panorama (-1.0 bis 1.0)
+    pub pan: f32,
+}
+
+/// Kontinuierliche Werte eines Takts — nur Ziele, die sich bewegen
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TransitionFrame {
+    /// Strips mit sich ändernder Lautstärke, Gain oder Pan
+    pub strips: Vec<StripFrame>,
+    /// Busse mit sich ändernder Lautstärke (Bus-ID, dB)
+    pub buses: Vec<(String, f32)>,
+    /// Submix-Kreuzpunkte mit sich änderndem Gain (in Start und Ziel geroutet)
+    pub crosspoints: Vec<RoutingEntry>,
+    /// Master-Lautstärke (None = unverändert)
+    pub master_volume_db: Option<f32>,
+    /// Limiter-Ceiling (None = unverändert)
+    pub limiter_ceiling_db: Option<f32>,
+    /// Ducking-Stärke und Voice-FX Dry/Wet (None = unverändert)
+    pub levels: SceneLevels,
+}
+
+/// Laufende Szenen-Überblendung
+#[derive(Debug, Clone)]
+pub struct SceneTransition {
+    /// Name der Ziel-Szene
+    scene_name: String,
+    /// Stand beim Start
+    from: SessionState,
+    /// Ziel-Stand (bereits mit der Scope-Maske zusammengeführt)
+    to: SessionState,
+    /// Effekt-Pegel beim Start
+    from_levels: SceneLevels,
+    /// Ziel-Pegel
+    to_levels: SceneLevels,
+    /// Scope-Maske des Recalls (Safes werden nie geschrieben)
+    scope: SceneScope,
+    /// Einstellungen
+    settings: TransitionSettings,
+    /// Startzeitpunkt
+    started: Instant,
+    /// Diskrete Parameter bereits umgeschaltet
+    switched: bool,
+}
+
+impl SceneTransition {
+    /// Überblendung starten
+    pub fn new(
+        scene_name: &str,
+        from: (SessionState, SceneLevels),
+        to: (SessionState, SceneLevels),
+        scope: SceneScope,
+        settings: TransitionSettings,
+        now: Instant,
+    ) -> Self {
+        Self {
+            scene_name: scene_name.to_string(),
+            from: from.0,
+            to: to.0,
+            from_levels: from.1,
+            to_levels: to.1,
+            scope,
+            settings,
+            started: now,
+            switched: false,
+        }
+    }
+
+    /// Name der Ziel-Szene
+    pub fn scene_name(&self) -> &str {
+        &self.scene_name
+    }
+
+    /// Stand beim Start (Inverse für das Journal)
+    pub fn origin(&self) -> (&SessionState, &SceneLevels) {
+        (&self.from, &self.from_levels)
+    }
+
+    /// Ziel-Stand
+    pub fn target(&self) -> (&SessionState, &SceneLevels) {
+        (&self.to, &self.to_levels)
+    }
+
+    /// Scope-Maske des Recalls
+    pub fn scope(&self) -> &SceneScope {
+        &self.scope
+    }
+
+    /// Fortschritt zum Zeitpunkt `now` (0.0 - 1.0)
+    pub fn progress(&self, now: Instant) -> f32 {
+        if self.settings.duration_ms == 0 {
+            return 1.0;
+        }
+        let elapsed = now.saturating_duration_since(self.started).as_secs_f32() * 1000.0;
+        (elapsed / self.settings.duration_ms as f32).min(1.0)
+    }
+
+    /// Fortschritts-Event zum Zeitpunkt `now`
+    pub fn status(&self, now: Instant) -> TransitionProgress {
+        let progress = self.progress(now);
+        TransitionProgress {
+            scene_name: self.scene_name.clone(),
+            progress,
+            switched: progress >= self.settings.switch_at,
+            done: progress >= 1.0,
+        }
+    }
+
+    /// Kontinuierliche Werte bei Fortschritt `progress` berechnen
+    ///
+    /// Enthält nur Strips/Busse, die nicht safe sind und deren Werte sich
+    /// zwischen Start und Ziel unterscheiden. Lautstärken werden im linearen
+    /// Amplituden-Bereich überblendet, alle anderen Werte direkt.
+    pub fn frame(&self, progress: f32) -> TransitionFrame {
+        let w = self.settings.curve.weight(progress);
+        let mut frame = TransitionFrame::default();
+
+        for to in &self.to.strips {
+            if !self.scope.covers_strip(&to.id) {
+                continue;
+            }
+            let from = match self.from.strips.iter().find(|s| s.id == to.id) {
+                Some(from) => from,
+                None => continue,
+            };
+            if from.volume_db == to.volume_db && from.gain_db == to.gain_db && from.pan == to.pan {
+                continue;
+            }
+            frame.strips.push(StripFrame {
+                id: to.id.clone(),
+                volume_db: lerp_db(from.volume_db, to.volume_db, w),
+                gain_db: lerp_db(from.gain_db, to.gain_db, w),
+                pan: lerp(from.pan, to.pan, w),
+            });
+        }
+        for to in &self.to.buses {
+            if !self.scope.covers_bus(&to.id) {
+                continue;
+            }
+            if let Some(from) = self.from.buses.iter().find(|b| b.id == to.id) {
+                if from.volume_db != to.volume_db {
+                    frame
+                        .buses
+                        .push((to.id.clone(), lerp_db(from.volume_db, to.volume_db, w)));
+                }
+            }
+        }
+
+        // Gain gibt es nur auf Submixen (Quelle ist ein Bus)
+        let submixes = self
+            .to
+            .routing
+            .iter()
+            .filter(|e| e.active && self.to.buses.iter().any(|b| b.id == e.source_id));
+        for to in submixes {
+            if !self.scope.covers_strip(&to.source_id) || !self.scope.covers_bus(&to.bus_id) {
+                continue;
+            }
+            let from = self
+                .from
+                .routing
+                .iter()
+                .find(|e| e.active && e.source_id == to.source_id && e.bus_id == to.bus_id);
+            if let Some(from) = from {
+                if from.gain_db != to.gain_db {
+                    frame.crosspoints.push(RoutingEntry {
+                        gain_db: lerp_db(from.gain_db, to.gain_db, w),
+                        ..to.clone()
+                    });
+                }
+            }
+        }
+
+        let (from, to) = (&self.from.master, &self.to.master);
+        if from.volume_db != to.volume_db {
+            frame.master_volume_db = Some(lerp_db(from.volume_db, to.volume_db, w));
+        }
+        if from.limiter_ceiling_db != to.limiter_ceiling_db {
+            frame.limiter_ceiling_db =
+                Some(lerp(from.limiter_ceiling_db, to.limiter_ceiling_db, w));
+        }
+
+        frame.levels = SceneLevels {
+            ducking_amount_db: lerp_changed(
+                self.from_levels.ducking_amount_db,
+                self.to_levels.ducking_amount_db,
+                w,
+            ),
+            voice_fx_dry_wet: lerp_changed(
+                self.from_levels.voice_fx_dry_wet,
+                self.to_levels.voice_fx_dry_wet,
+                w,
+            ),
+        };
+        frame
+    }
+
+    /// Diskreten Ziel-Stand genau einmal am Umschaltpunkt liefern
+    ///
+    /// Der Stand enthält die kontinuierlichen Werte bei `progress`, damit das
+    /// Umschalten keinen Sprung in der laufenden Blende erzeugt. Vor dem
+    /// Umschaltpunkt und nach dem ersten Aufruf danach: None.
+    pub fn take_switch(&mut self, progress: f32) -> Option<SessionState> {
+        if self.switched || progress < self.settings.switch_at {
+            return None;
+        }
+        self.switched = true;
+
+        let frame = self.frame(progress);
+        let mut state = self.to.clone();
+        for strip in state.strips.iter_mut() {
+            if let Some(f) = frame.strips.iter().find(|f| f.id == strip.id) {
+                strip.volume_db = f.volume_db;
+                strip.gain_db = f.gain_db;
+                strip.pan = f.pan;
+            }
+        }
+        for bus in state.buses.iter_mut() {
+            if let Some((_, volume_db)) = frame.buses.iter().find(|(id, _)| *id == bus.id) {
+                bus.volume_db = *volume_db;
+            }
+        }
+        for entry in state.routing.iter_mut() {
+            if let Some(f) = frame
+                .crosspoints
+                .iter()
+                .find(|f| f.source_id == entry.source_id && f.bus_id == entry.bus_id)
+            {
+                entry.gain_db = f.gain_db;
+            }
+        }
+        if let Some(volume_db) = frame.master_volume_db {
+            state.master.volume_db = volume_db;
+        }
+        if let Some(ceiling_db) = frame.limiter_ceiling_db {
+            state.master.limiter_ceiling_db = ceiling_db;
+        }
+        Some(state)
+    }
+}
+
+/// Linear zwischen zwei Werten interpolieren
+fn lerp(from: f32, to: f32, w: f32) -> f32 {
+    from + (to - from) * w
+}
+
+/// dB-Werte im linearen Amplituden-Bereich interpolieren
+fn lerp_db(from: f32, to: f32, w: f32) -> f32 {
+    let linear = lerp(db_to_linear(from), db_to_linear(to), w);
+    linear_to_db(linear).clamp(from.min(to), from.max(to))
+}
+
+/// Optionale Werte interpolieren — None wenn sich nichts bewegt
+fn lerp_changed(from: Option<f32>, to: Option<f32>, w: f32) -> Option<f32> {
+    match (from, to) {
+        (Some(from), Some(to)) if from != to => Some(lerp(from, to, w)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::bus::BusManager;
+    use crate::audio::master::MasterManager;
+    use crate::audio::mixer::MixerState;
+    use crate::fx::FxChain;
+    use std::time::Duration;
+
+    fn state(mixer: &MixerState) -> SessionState {
+        SessionState::new(
+            mixer.get_strips(),
+            BusManager::new().get_buses(),
+            Vec::new(),
+            FxChain::new().get_all_modules(),
+            MasterManager::new().get_state(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    fn transition(curve: TransitionCurve, switch_at: f32) -> SceneTransition {
+        transition_with_scope(curve, switch_at, SceneScope::default())
+    }
+
+    fn transition_with_scope(
+        curve: TransitionCurve,
+        switch_at: f32,
+        scope: SceneScope,
+    ) -> SceneTransition {
+        let mut from = MixerState::new();
+        from.set_volume("hw-mic", -40.0).unwrap();
+        let mut to = MixerState::new();
+        to.set_volume("hw-mic", 0.0).unwrap();
+        to.set_pan("hw-mic", 1.0).unwrap();
+        to.set_mute("hw-mic", true).unwrap();
+        to.set_volume("hw-headset", -10.0).unwrap();
+
+        SceneTransition::new(
+            "Test",
+            (
+                state(&from),
+                SceneLevels {
+                    ducking_amount_db: Some(-20.0),
+                    voice_fx_dry_wet: Some(0.0),
+                },
+            ),
+            (
+                state(&to),
+                SceneLevels {
+                    ducking_amount_db: Some(0.0),
+                    voice_fx_dry_wet: None,
+                },
+            ),
+            scope,
+            TransitionSettings {
+                duration_ms: 1000,
+                curve,
+                switch_at,
+            },
+            Instant::now(),
+        )
+    }
+
+    fn mic(state: &SessionState) -> &crate::audio::mixer::InputStrip {
+        state.strips.iter().find(|s| s.id == "hw-mic").unwrap()
+    }
+
+    fn mic_frame(frame: &TransitionFrame) -> &StripFrame {
+        frame.strips.iter().find(|s| s.id == "hw-mic").unwrap()
+    }
+
+    #[test]
+    fn test_curves() {
+        for curve in [
+            TransitionCurve::Linear,
+            TransitionCurve::EqualPower,
+            TransitionCurve::SCurve,
+        ] {
+            assert_eq!(curve.weight(0.0), 0.0);
+            assert!((curve.weight(1.0) - 1.0).abs() < 1e-6);
+        }
+        assert_eq!(TransitionCurve::Linear.weight(0.5), 0.5);
+        assert_eq!(TransitionCurve::SCurve.weight(0.5), 0.5);
+        assert!(
+            (TransitionCurve::EqualPower.weight(0.5) - std::f32::consts::FRAC_1_SQRT_2).abs()
+                < 1e-3
+        );
+        assert!(TransitionCurve::SCurve.weight(0.1) < TransitionCurve::Linear.weight(0.1));
+    }
+
+    #[test]
+    fn test_frame_interpolates_continuous() {
+        let t = transition(TransitionCurve::Linear, 0.5);
+        let frame = t.frame(0.5);
+        // Amplitude 0.01 → 1.0, Mitte 0.505 ≈ -5.9 dB
+        assert!((mic_frame(&frame).volume_db + 5.93).abs() < 0.05);
+        assert_eq!(mic_frame(&frame).pan, 0.5);
+        assert_eq!(frame.levels.ducking_amount_db, Some(-10.0));
+        // Voice-FX bewegt sich nicht → wird nicht geschrieben
+        assert_eq!(frame.levels.voice_fx_dry_wet, None);
+
+        let end = t.frame(1.0);
+        assert_eq!(mic_frame(&end).volume_db, 0.0);
+    }
+
+    #[test]
+    fn test_frame_only_moving_targets() {
+        let t = transition(TransitionCurve::Linear, 0.5);
+        let frame = t.frame(0.5);
+        let ids: Vec<&str> = frame.strips.iter().map(|s| s.id.as_str()).collect();
+        assert_eq!(ids, vec!["hw-mic", "hw-headset"]);
+        assert!(frame.buses.is_empty());
+        assert!(frame.crosspoints.is_empty());
+        assert_eq!(frame.master_volume_db, None);
+        assert_eq!(frame.limiter_ceiling_db, None);
+    }
+
+    #[test]
+    fn test_frame_skips_safe_strips() {
+        let scope = SceneScope {
+            safe_strips: vec!["hw-headset".to_string()],
+            ..SceneScope::default()
+        };
+        let t = transition_with_scope(TransitionCurve::Linear, 0.5, scope);
+        let frame = t.frame(0.5);
+        assert_eq!(frame.strips.len(), 1);
+        assert_eq!(frame.strips[0].id, "hw-mic");
+    }
+
+    #[test]
+    fn test_frame_interpolates_crosspoint_gain() {
+        let send = |gain_db: f32| RoutingEntry {
+            source_id: "B2".to_string(),
+            bus_id: "B1".to_string(),
+            active: true,
+            gain_db,
+        };
+        let mixer = MixerState::new();
+        let mut from = state(&mixer);
+        from.routing = vec![send(0.0)];
+        let mut to = state(&mixer);
+        to.routing = vec![send(-6.0)];
+        let mut t = SceneTransition::new(
+            "Test",
+            (from, SceneLevels::default()),
+            (to, SceneLevels::default()),
+            SceneScope::default(),
+            TransitionSettings {
+                duration_ms: 1000,
+                curve: TransitionCurve::Linear,
+                switch_at: 0.5,
+            },
+            Instant::now(),
+        );
+
+        // Amplitude 1.0 → 0.501, Mitte 0.75 ≈ -2.5 dB
+        let frame = t.frame(0.5);
+        assert_eq!(frame.crosspoints.len(), 1);
+        assert!((frame.crosspoints[0].gain_db + 2.5).abs() < 0.05);
+        assert_eq!(t.frame(1.0).crosspoints[0].gain_db, -6.0);
+
+        // Der Umschaltpunkt setzt den Send nicht auf den Ziel-Gain
+        let switched = t.take_switch(0.5).unwrap();
+        assert_eq!(switched.routing[0].gain_db, frame.crosspoints[0].gain_db);
+    }
+
+    #[test]
+    fn test_discrete_switch_point() {
+        let mut t = transition(TransitionCurve::SCurve, 0.25);
+        assert!(t.take_switch(0.2).is_none());
+        let switched = t.take_switch(0.25).unwrap();
+        assert!(mic(&switched).muted);
+        // Kontinuierliche Werte stehen auf dem aktuellen Blend-Punkt
+        assert_eq!(
+            mic(&switched).volume_db,
+            mic_frame(&t.frame(0.25)).volume_db
+        );
+        // Genau einmal
+        assert!(t.take_switch(0.5).is_none());
+        assert!(t.take_switch(1.0).is_none());
+
+        let mut t = transition(TransitionCurve::Linear, 1.0);
+        assert!(t.take_switch(0.99).is_none());
+        assert!(mic(&t.take_switch(1.0).unwrap()).muted);
+    }
+
+    #[test]
+    fn test_progress_and_status() {
+        let t = transition(TransitionCurve::Linear, 0.5);
+        let start = t.started;
+        assert_eq!(t.progress(start), 0.0);
+        let status = t.status(start + Duration::from_millis(600));
+        assert!((status.progress - 0.6).abs() < 1e-3);
+        assert!(status.switched);
+        assert!(!status.done);
+        assert!(t.status(start + Duration::from_secs(2)).done);
+    }
+
+    #[test]
+    fn test_settings_validate() {
+        assert!(TransitionSettings::default().validate().is_ok());
+        let settings = TransitionSettings {
+            switch_at: 1.5,
+            ..TransitionSettings::default()
+        };
+        assert!(settings.validate().is_err());
+        let settings = TransitionSettings {
+            duration_ms: MAX_TRANSITION_MS + 1,
+            ..TransitionSettings::default()
+        };
+        assert!(settings.validate().is_err());
+    }
+}
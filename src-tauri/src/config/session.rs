@@ -0,0 +1,434 @@
+// Modul: config/session — Automatische Session-Persistenz (Live-State ↔ SQLite)
+//
+// Der komplette Live-State (Strips, Busse, Routing, FX, Master, Mute-Gruppen) wird als
+// Snapshot beobachtet und nach einer Ruhephase (Debounce) gespeichert.
+// Beim Start wird die letzte Session wiederhergestellt — schlägt die
+// Validierung fehl, startet der Mixer mit Defaults.
+
+use super::database::Database;
+use crate::audio::bus::{BusManager, OutputBus};
+use crate::audio::master::{MasterManager, MasterState};
+use crate::audio::mixer::{InputStrip, MixerState};
+use crate::audio::mute_groups::{MuteGroup, MuteGroupManager};
+use crate::audio::routing::{RoutingEntry, RoutingManager};
+use crate::fx::{FxChain, FxModuleInfo};
+use crate::macros::{Macro, MacroManager, MacroTarget};
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Aktuelle Version des Session-Formats
+pub const SESSION_VERSION: u32 = 1;
+/// Ruhephase nach der letzten Änderung bis zum Speichern (ms)
+pub const SESSION_DEBOUNCE_MS: u64 = 1500;
+
+/// Kompletter Live-State des Mixers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionState {
+    /// Format-Version (für spätere Migrationen)
+    pub version: u32,
+    /// Input-Strips (sortiert nach `order`)
+    pub strips: Vec<InputStrip>,
+    /// Output-Busse (sortiert nach ID)
+    pub buses: Vec<OutputBus>,
+    /// Aktive Routings (sortiert nach Source/Bus)
+    pub routing: Vec<RoutingEntry>,
+    /// FX-Module (Bypass + Parameter)
+    pub fx: Vec<FxModuleInfo>,
+    /// Master-Sektion
+    pub master: MasterState,
+    /// Mute-Gruppen inkl. Aktiv-Status
+    #[serde(default)]
+    pub mute_groups: Vec<MuteGroup>,
+    /// Makros inkl. Reglerstellung
+    #[serde(default)]
+    pub macros: Vec<Macro>,
+}
+
+/// Aus einer Session wiederhergestellte Manager
+pub struct RestoredSession {
+    /// Input-Strips
+    pub mixer: MixerState,
+    /// Output-Busse
+    pub buses: BusManager,
+    /// Routing-Matrix
+    pub routing: RoutingManager,
+    /// FX-Chain
+    pub fx_chain: FxChain,
+    /// Master-Sektion
+    pub master: MasterManager,
+    /// Mute-Gruppen
+    pub mute_groups: MuteGroupManager,
+    /// Makros
+    pub macros: MacroManager,
+}
+
+impl SessionState {
+    /// Snapshot aus den Teil-States erstellen (Routing wird sortiert)
+    pub fn new(
+        strips: Vec<InputStrip>,
+        buses: Vec<OutputBus>,
+        mut routing: Vec<RoutingEntry>,
+        fx: Vec<FxModuleInfo>,
+        master: MasterState,
+        mute_groups: Vec<MuteGroup>,
+        macros: Vec<Macro>,
+    ) -> Self {
+        routing.sort_by(|a, b| (&a.source_id, &a.bus_id).cmp(&(&b.source_id, &b.bus_id)));
+        Self {
+            version: SESSION_VERSION,
+            strips,
+            buses,
+            routing,
+            fx,
+            master,
+            mute_groups,
+            macros,
+        }
+    }
+
+    /// Querverweise prüfen (Version, Bus-IDs in Strips, Routing, Talkback-Busse
+    /// und -Quelle, Mute-Gruppen und Makro-Ziele)
+    pub fn validate(&self) -> Result<(), String> {
+        if self.version != SESSION_VERSION {
+            return Err(format!(
+                "Unbekannte Session-Version: {} (erwartet: {})",
+                self.version, SESSION_VERSION
+            ));
+        }
+
+        let known_bus = |id: &str| self.buses.iter().any(|b| b.id == id);
+
+        for strip in &self.strips {
+            if let Some(bus_id) = strip.bus_routing.iter().find(|id| !known_bus(id)) {
+                return Err(format!("Strip '{}': unbekannter Bus {}", strip.id, bus_id));
+            }
+        }
+        if let Some(entry) = self.routing.iter().find(|e| !known_bus(&e.bus_id)) {
+            return Err(format!("Routing: unbekannter Bus {}", entry.bus_id));
+        }
+        if let Some(bus_id) = self.master.talkback_buses.iter().find(|id| !known_bus(id)) {
+            return Err(format!("Talkback: unbekannter Bus {}", bus_id));
+        }
+        let talkback_source = &self.master.talkback_settings.source_strip;
+        if !self.strips.iter().any(|s| &s.id == talkback_source) {
+            return Err(format!("Talkback: unbekannte Quelle {}", talkback_source));
+        }
+        for group in &self.mute_groups {
+            if let Some(bus_id) = group.bus_ids.iter().find(|id| !known_bus(id)) {
+                return Err(format!(
+                    "Mute-Gruppe '{}': unbekannter Bus {}",
+                    group.name, bus_id
+                ));
+            }
+        }
+        for m in &self.macros {
+            for mapping in &m.mappings {
+                match &mapping.target {
+                    MacroTarget::StripVolume { strip_id }
+                        if !self.strips.iter().any(|s| &s.id == strip_id) =>
+                    {
+                        return Err(format!(
+                            "Makro '{}': unbekannter Strip {}",
+                            m.name, strip_id
+                        ));
+                    }
+                    MacroTarget::BusSend { bus_id } if !known_bus(bus_id) => {
+                        return Err(format!("Makro '{}': unbekannter Bus {}", m.name, bus_id));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Session validieren und die Manager daraus aufbauen
+    pub fn restore(self) -> Result<RestoredSession, String> {
+        self.validate()?;
+
+        let mut mixer = MixerState::from_strips(self.strips)?;
+        let buses = BusManager::from_buses(self.buses)?;
+        let bus_ids = buses.bus_ids();
+        mixer.set_bus_ids(&bus_ids);
+        let master = MasterManager::from_state(self.master, &bus_ids)?;
+        let mute_groups = MuteGroupManager::from_groups(self.mute_groups)?;
+        let macros = MacroManager::from_macros(self.macros)?;
+        let mut fx_chain = FxChain::new();
+        fx_chain.apply_modules(&self.fx)?;
+        // Routing zuletzt: legt PipeWire-Links an
+        let mut routing = RoutingManager::new();
+        routing.set_bus_ids(&bus_ids);
+        routing.restore(&self.routing)?;
+
+        Ok(RestoredSession {
+            mixer,
+            buses,
+            routing,
+            fx_chain,
+            master,
+            mute_groups,
+            macros,
+        })
+    }
+}
+
+/// Session-Store: speichert den Live-State entprellt in SQLite
+pub struct SessionStore {
+    /// Datenbank-Referenz
+    db: Arc<Database>,
+    /// Zuletzt beobachteter Snapshot (JSON)
+    last_seen: Option<String>,
+    /// Zeitpunkt der letzten beobachteten Änderung
+    changed_at: Option<Instant>,
+    /// Zuletzt gespeicherter Snapshot (JSON)
+    last_saved: Option<String>,
+}
+
+impl SessionStore {
+    /// Neuen Session-Store mit Datenbank-Referenz erstellen
+    pub fn new(db: Arc<Database>) -> Self {
+        Self {
+            db,
+            last_seen: None,
+            changed_at: None,
+            last_saved: None,
+        }
+    }
+
+    /// Gespeicherte Session laden (None wenn noch keine existiert)
+    pub fn load(&self) -> Result<Option<SessionState>, String> {
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+
+        let result = conn.query_row("SELECT state_json FROM session WHERE id = 1", [], |row| {
+            row.get::<_, String>(0)
+        });
+
+        match result {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| format!("Session ungültig: {}", e)),
+            Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+            Err(e) => Err(format!("DB-Query-Fehler: {}", e)),
+        }
+    }
+
+    /// Bekannten Stand setzen ohne zu speichern (nach dem Wiederherstellen)
+    pub fn prime(&mut self, snapshot: &SessionState) -> Result<(), String> {
+        let json = to_json(snapshot)?;
+        self.last_seen = Some(json.clone());
+        self.last_saved = Some(json);
+        self.changed_at = None;
+        Ok(())
+    }
+
+    /// Snapshot beobachten und nach der Ruhephase speichern
+    ///
+    /// # Returns
+    /// true wenn gespeichert wurde
+    pub fn observe(&mut self, snapshot: &SessionState, now: Instant) -> Result<bool, String> {
+        let json = to_json(snapshot)?;
+        if self.last_seen.as_ref() != Some(&json) {
+            self.last_seen = Some(json);
+            self.changed_at = Some(now);
+            return Ok(false);
+        }
+
+        let settled = self
+            .changed_at
+            .map(|t| now.duration_since(t) >= Duration::from_millis(SESSION_DEBOUNCE_MS))
+            .unwrap_or(false);
+        if settled && self.last_saved != self.last_seen {
+            self.write(&json)?;
+            self.last_saved = Some(json);
+            self.changed_at = None;
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Snapshot sofort speichern falls er vom gespeicherten abweicht (z.B. beim Beenden)
+    pub fn flush(&mut self, snapshot: &SessionState) -> Result<(), String> {
+        let json = to_json(snapshot)?;
+        if self.last_saved.as_ref() != Some(&json) {
+            self.write(&json)?;
+            self.last_seen = Some(json.clone());
+            self.last_saved = Some(json);
+            self.changed_at = None;
+        }
+        Ok(())
+    }
+
+    /// Session-JSON in die Datenbank schreiben (genau eine Zeile)
+    fn write(&self, json: &str) -> Result<(), String> {
+        let conn = self
+            .db
+            .conn
+            .lock()
+            .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
+
+        let updated_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| format!("Zeit-Fehler: {}", e))?
+            .as_secs() as i64;
+
+        conn.execute(
+            "INSERT OR REPLACE INTO session (id, state_json, updated_at) VALUES (1, ?1, ?2)",
+            params![json, updated_at],
+        )
+        .map_err(|e| format!("DB-Insert-Fehler: {}", e))?;
+
+        log::debug!("Session gespeichert ({} Bytes)", json.len());
+        Ok(())
+    }
+}
+
+/// Snapshot serialisieren
+fn to_json(snapshot: &SessionState) -> Result<String, String> {
+    serde_json::to_string(snapshot).map_err(|e| format!("Session-Serialisierung: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> SessionStore {
+        let db = Arc::new(Database::open_in_memory().unwrap());
+        SessionStore::new(db)
+    }
+
+    fn snapshot(mixer: &MixerState, master: &MasterManager) -> SessionState {
+        SessionState::new(
+            mixer.get_strips(),
+            BusManager::new().get_buses(),
+            RoutingManager::new().get_routing_matrix(),
+            FxChain::new().get_all_modules(),
+            master.get_state(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    fn default_snapshot() -> SessionState {
+        snapshot(&MixerState::new(), &MasterManager::new())
+    }
+
+    #[test]
+    fn test_load_empty() {
+        let store = setup();
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_observe_debounces() {
+        let mut store = setup();
+        let t0 = Instant::now();
+        let mut snapshot = default_snapshot();
+
+        // Erste Beobachtung startet die Ruhephase
+        assert!(!store.observe(&snapshot, t0).unwrap());
+        // Weitere Änderung innerhalb der Ruhephase verschiebt das Speichern
+        snapshot.strips[0].volume_db = -10.0;
+        let t1 = t0 + Duration::from_millis(SESSION_DEBOUNCE_MS / 2);
+        assert!(!store.observe(&snapshot, t1).unwrap());
+        assert!(!store
+            .observe(&snapshot, t0 + Duration::from_millis(SESSION_DEBOUNCE_MS))
+            .unwrap());
+        assert!(store.load().unwrap().is_none());
+
+        // Nach der Ruhephase wird genau einmal gespeichert
+        let t2 = t1 + Duration::from_millis(SESSION_DEBOUNCE_MS);
+        assert!(store.observe(&snapshot, t2).unwrap());
+        assert!(!store
+            .observe(&snapshot, t2 + Duration::from_secs(5))
+            .unwrap());
+
+        let loaded = store.load().unwrap().unwrap();
+        assert_eq!(loaded.strips[0].volume_db, -10.0);
+    }
+
+    #[test]
+    fn test_prime_skips_unchanged() {
+        let mut store = setup();
+        let snapshot = default_snapshot();
+        store.prime(&snapshot).unwrap();
+
+        let later = Instant::now() + Duration::from_secs(10);
+        assert!(!store.observe(&snapshot, later).unwrap());
+        assert!(store.load().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_flush_and_restore() {
+        let mut store = setup();
+        let mut mixer = MixerState::new();
+        mixer.set_mute("hw-mic", true).unwrap();
+        let mut master = MasterManager::new();
+        master.set_mono(true).unwrap();
+        let snapshot = snapshot(&mixer, &master);
+        store.flush(&snapshot).unwrap();
+
+        let restored = store.load().unwrap().unwrap().restore().unwrap();
+        assert!(restored.mixer.get_strip("hw-mic").unwrap().muted);
+        assert!(restored.master.get_state().mono);
+    }
+
+    #[test]
+    fn test_restore_rejects_unknown_bus() {
+        let mut snapshot = default_snapshot();
+        snapshot.strips[0].bus_routing.push("Z9".to_string());
+        assert!(snapshot.restore().is_err());
+
+        let mut snapshot = default_snapshot();
+        snapshot.version = SESSION_VERSION + 1;
+        assert!(snapshot.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_talkback_source() {
+        let mut snapshot = default_snapshot();
+        assert!(snapshot.validate().is_ok());
+        snapshot.master.talkback_settings.source_strip = "virt-gone".to_string();
+        assert_eq!(
+            snapshot.validate().unwrap_err(),
+            "Talkback: unbekannte Quelle virt-gone"
+        );
+    }
+
+    #[test]
+    fn test_restore_user_bus() {
+        let mut buses = BusManager::new();
+        let bus = buses
+            .create_bus("MONITOR", crate::audio::bus::BusType::Physical, 2)
+            .unwrap();
+        let mut snapshot = default_snapshot();
+        snapshot.buses = buses.get_buses();
+        snapshot.master.talkback_buses = vec![bus.id.clone()];
+        snapshot.routing = vec![RoutingEntry {
+            source_id: "hw-mic".to_string(),
+            bus_id: bus.id.clone(),
+            active: true,
+            gain_db: 0.0,
+        }];
+
+        let restored = snapshot.restore().unwrap();
+        assert!(restored.buses.get_bus(&bus.id).is_some());
+        assert_eq!(
+            restored.master.get_state().talkback_buses,
+            vec![bus.id.clone()]
+        );
+        assert!(restored.routing.is_routed("hw-mic", &bus.id));
+    }
+
+    #[test]
+    fn test_load_corrupt_json() {
+        let store = setup();
+        store.write("{kaputt").unwrap();
+        assert!(store.load().is_err());
+    }
+}
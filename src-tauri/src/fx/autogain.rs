@@ -109,6 +109,26 @@ impl AutoGainModule {
         Ok(())
     }
 
+    /// Target-Level abfragen (in dB)
+    pub fn get_target_level(&self) -> f32 {
+        self.target_level_db
+    }
+
+    /// RMS-Fenster abfragen (in ms)
+    pub fn get_window(&self) -> f32 {
+        self.window_ms
+    }
+
+    /// Attack-Zeit abfragen (in ms)
+    pub fn get_attack(&self) -> f32 {
+        self.attack_ms
+    }
+
+    /// Release-Zeit abfragen (in ms)
+    pub fn get_release(&self) -> f32 {
+        self.release_ms
+    }
+
     /// Koeffizienten neu berechnen (nach Parameter-Änderung)
     fn update_coefficients(&mut self) {
         // Attack/Release Koeffizienten (exponentielles Smoothing)
@@ -0,0 +1,255 @@
+// Modul: fx/bus_chain — Insert-Chain für Output-Busse
+//
+// Bearbeitung nach der Summierung eines Busses (z.B. Stream-Bus B1):
+// EQ → Glue-Compressor → Loudness (Auto-Gain) → Limiter
+// Der Limiter steht zuletzt, damit die Loudness-Anhebung das Ceiling nicht
+// überschreitet. Neue Busse starten mit allen Modulen im Bypass — ältere
+// Sessions/Scenes klingen nach dem Laden unverändert.
+// Nutzt dieselben Module und dieselbe Info-Struktur wie die Strip-FxChain.
+
+use super::autogain::AutoGainModule;
+use super::compressor::CompressorModule;
+use super::eq::EqModule;
+use super::limiter::LimiterModule;
+use super::{AudioProcessor, FxModuleInfo, FxModuleType, SAMPLE_RATE};
+
+/// Module der Bus-Chain in Verarbeitungs-Reihenfolge
+pub const BUS_FX_MODULES: [FxModuleType; 4] = [
+    FxModuleType::Eq,
+    FxModuleType::Compressor,
+    FxModuleType::AutoGain,
+    FxModuleType::Limiter,
+];
+
+/// Glue-Compressor: sanfte Ratio für die Summe
+const GLUE_RATIO: f32 = 2.0;
+
+/// Insert-Chain eines Output-Busses
+pub struct BusFxChain {
+    eq: EqModule,
+    glue: CompressorModule,
+    loudness: AutoGainModule,
+    limiter: LimiterModule,
+}
+
+impl BusFxChain {
+    /// Neue Bus-Chain (alle Module im Bypass)
+    pub fn new() -> Self {
+        Self::with_sample_rate(SAMPLE_RATE)
+    }
+
+    /// Neue Bus-Chain in der Rate der Mix-Engine (alle Module im Bypass)
+    pub fn with_sample_rate(sample_rate: f32) -> Self {
+        let mut chain = Self {
+            eq: EqModule::new(sample_rate),
+            glue: CompressorModule::new(sample_rate),
+            loudness: AutoGainModule::new(sample_rate),
+            limiter: LimiterModule::new(sample_rate),
+        };
+        // Bereich ist gültig, Fehler nicht möglich
+        let _ = chain.glue.set_ratio(GLUE_RATIO);
+        for module_type in BUS_FX_MODULES {
+            let _ = chain.set_bypass(module_type, true);
+        }
+        chain
+    }
+
+    /// Standard-Zustand als Info-Liste (für neue Busse und ältere Sessions)
+    pub fn default_modules() -> Vec<FxModuleInfo> {
+        Self::new().get_all_modules()
+    }
+
+    /// Audio durch die Chain verarbeiten
+    pub fn process(&mut self, buffer_l: &mut [f32], buffer_r: &mut [f32]) {
+        self.eq.process(buffer_l, buffer_r);
+        self.glue.process(buffer_l, buffer_r);
+        self.loudness.process(buffer_l, buffer_r);
+        self.limiter.process(buffer_l, buffer_r);
+    }
+
+    /// Mindestens ein Modul aktiv (sonst kann die Verarbeitung entfallen)
+    pub fn is_active(&self) -> bool {
+        !(self.eq.is_bypassed()
+            && self.glue.is_bypassed()
+            && self.loudness.is_bypassed()
+            && self.limiter.is_bypassed())
+    }
+
+    /// Modul-Info für Frontend (None für Module ohne Bus-Variante)
+    pub fn get_module_info(&self, module_type: FxModuleType) -> Option<FxModuleInfo> {
+        match module_type {
+            FxModuleType::Eq => {
+                let [low, mid, high] = self.eq.get_gains();
+                Some(FxModuleInfo {
+                    module_type,
+                    enabled: !self.eq.is_bypassed(),
+                    params: vec![
+                        ("low_gain".to_string(), low),
+                        ("mid_gain".to_string(), mid),
+                        ("high_gain".to_string(), high),
+                    ],
+                })
+            }
+            FxModuleType::Compressor => Some(FxModuleInfo {
+                module_type,
+                enabled: !self.glue.is_bypassed(),
+                params: vec![
+                    ("threshold".to_string(), self.glue.get_threshold()),
+                    ("ratio".to_string(), self.glue.get_ratio()),
+                ],
+            }),
+            FxModuleType::AutoGain => Some(FxModuleInfo {
+                module_type,
+                enabled: !self.loudness.is_bypassed(),
+                params: vec![
+                    ("target_level".to_string(), self.loudness.get_target_level()),
+                    ("window".to_string(), self.loudness.get_window()),
+                    ("attack".to_string(), self.loudness.get_attack()),
+                    ("release".to_string(), self.loudness.get_release()),
+                ],
+            }),
+            FxModuleType::Limiter => Some(FxModuleInfo {
+                module_type,
+                enabled: !self.limiter.is_bypassed(),
+                params: vec![
+                    ("ceiling".to_string(), self.limiter.get_ceiling()),
+                    ("release".to_string(), self.limiter.get_release()),
+                ],
+            }),
+            _ => None,
+        }
+    }
+
+    /// Alle Module als Info-Liste (Verarbeitungs-Reihenfolge)
+    pub fn get_all_modules(&self) -> Vec<FxModuleInfo> {
+        BUS_FX_MODULES
+            .iter()
+            .filter_map(|&t| self.get_module_info(t))
+            .collect()
+    }
+
+    /// Parameter setzen
+    pub fn set_param(
+        &mut self,
+        module_type: FxModuleType,
+        param_name: &str,
+        value: f32,
+    ) -> Result<(), String> {
+        match (module_type, param_name) {
+            (FxModuleType::Eq, "low_gain") => self.eq.set_low_gain(value),
+            (FxModuleType::Eq, "mid_gain") => self.eq.set_mid_gain(value),
+            (FxModuleType::Eq, "high_gain") => self.eq.set_high_gain(value),
+            (FxModuleType::Compressor, "threshold") => self.glue.set_threshold(value),
+            (FxModuleType::Compressor, "ratio") => self.glue.set_ratio(value),
+            (FxModuleType::AutoGain, "target_level") => self.loudness.set_target_level(value),
+            (FxModuleType::AutoGain, "window") => self.loudness.set_window(value),
+            (FxModuleType::AutoGain, "attack") => self.loudness.set_attack(value),
+            (FxModuleType::AutoGain, "release") => self.loudness.set_release(value),
+            (FxModuleType::Limiter, "ceiling") => self.limiter.set_ceiling(value),
+            (FxModuleType::Limiter, "release") => self.limiter.set_release(value),
+            (t, _) if !BUS_FX_MODULES.contains(&t) => {
+                Err(format!("{} ist kein Bus-Insert", t.name()))
+            }
+            _ => Err(format!("Unbekannter Parameter: {}", param_name)),
+        }
+    }
+
+    /// Bypass setzen
+    pub fn set_bypass(&mut self, module_type: FxModuleType, bypass: bool) -> Result<(), String> {
+        match module_type {
+            FxModuleType::Eq => self.eq.set_bypass(bypass),
+            FxModuleType::Compressor => self.glue.set_bypass(bypass),
+            FxModuleType::AutoGain => self.loudness.set_bypass(bypass),
+            FxModuleType::Limiter => self.limiter.set_bypass(bypass),
+            t => return Err(format!("{} ist kein Bus-Insert", t.name())),
+        }
+        Ok(())
+    }
+
+    /// Gespeicherte Modul-Zustände übernehmen (Session/Scene)
+    pub fn apply_modules(&mut self, modules: &[FxModuleInfo]) -> Result<(), String> {
+        for module in modules {
+            for (name, value) in &module.params {
+                if !value.is_finite() {
+                    return Err(format!(
+                        "{}: Parameter '{}' ungültig",
+                        module.module_type.name(),
+                        name
+                    ));
+                }
+                self.set_param(module.module_type, name, *value)?;
+            }
+            self.set_bypass(module.module_type, !module.enabled)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for BusFxChain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_chain_bypassed() {
+        let mut chain = BusFxChain::new();
+        assert!(!chain.is_active());
+        assert!(chain.get_all_modules().iter().all(|m| !m.enabled));
+
+        let mut l = vec![0.9; 256];
+        let mut r = vec![0.9; 256];
+        chain.process(&mut l, &mut r);
+        assert!(l.iter().chain(r.iter()).all(|&s| s == 0.9));
+    }
+
+    #[test]
+    fn test_set_param_roundtrip() {
+        let mut chain = BusFxChain::new();
+        chain.set_param(FxModuleType::Eq, "high_gain", 3.0).unwrap();
+        chain
+            .set_param(FxModuleType::Compressor, "ratio", 3.0)
+            .unwrap();
+        chain
+            .set_param(FxModuleType::Limiter, "ceiling", -1.0)
+            .unwrap();
+        chain.set_bypass(FxModuleType::Limiter, false).unwrap();
+        assert!(chain.is_active());
+
+        let mut restored = BusFxChain::new();
+        restored.apply_modules(&chain.get_all_modules()).unwrap();
+        assert_eq!(restored.get_all_modules(), chain.get_all_modules());
+    }
+
+    #[test]
+    fn test_rejects_strip_only_modules() {
+        let mut chain = BusFxChain::new();
+        assert!(chain
+            .set_param(FxModuleType::Gate, "threshold", -40.0)
+            .is_err());
+        assert!(chain.set_bypass(FxModuleType::Hpf, false).is_err());
+        assert!(chain.set_param(FxModuleType::Eq, "freq", 100.0).is_err());
+        assert!(chain
+            .set_param(FxModuleType::Limiter, "ceiling", 3.0)
+            .is_err());
+    }
+
+    #[test]
+    fn test_limiter_holds_ceiling() {
+        let mut chain = BusFxChain::new();
+        chain
+            .set_param(FxModuleType::Limiter, "ceiling", -6.0)
+            .unwrap();
+        chain.set_bypass(FxModuleType::Limiter, false).unwrap();
+
+        let mut l = vec![1.0; 4800];
+        let mut r = vec![1.0; 4800];
+        chain.process(&mut l, &mut r);
+        let ceiling = 10.0_f32.powf(-6.0 / 20.0);
+        assert!(l[1000..].iter().all(|s| s.abs() <= ceiling + 1e-3));
+    }
+}
@@ -42,6 +42,22 @@ impl CompressorModule {
         Ok(())
     }
 
+    pub fn get_threshold(&self) -> f32 {
+        self.threshold_db
+    }
+
+    pub fn set_ratio(&mut self, ratio: f32) -> Result<(), String> {
+        if !(1.0..=20.0).contains(&ratio) {
+            return Err(format!("Ratio außerhalb: {}:1", ratio));
+        }
+        self.ratio = ratio;
+        Ok(())
+    }
+
+    pub fn get_ratio(&self) -> f32 {
+        self.ratio
+    }
+
     fn update_parameters(&mut self) {
         self.threshold_linear = 10.0_f32.powf(self.threshold_db / 20.0);
         self.attack_coeff = (-1.0 / (self.attack_ms * self.sample_rate / 1000.0)).exp();
@@ -183,6 +183,26 @@ impl EqModule {
         self.high.update_coefficients();
         Ok(())
     }
+
+    /// Gains der drei Bänder in dB (Low, Mid, High)
+    pub fn get_gains(&self) -> [f32; 3] {
+        [self.low.gain_db, self.mid.gain_db, self.high.gain_db]
+    }
+
+    /// Low-Gain setzen (Frequenz und Q bleiben)
+    pub fn set_low_gain(&mut self, gain_db: f32) -> Result<(), String> {
+        self.set_low(self.low.freq_hz, gain_db, self.low.q)
+    }
+
+    /// Mid-Gain setzen (Frequenz und Q bleiben)
+    pub fn set_mid_gain(&mut self, gain_db: f32) -> Result<(), String> {
+        self.set_mid(self.mid.freq_hz, gain_db, self.mid.q)
+    }
+
+    /// High-Gain setzen (Frequenz und Q bleiben)
+    pub fn set_high_gain(&mut self, gain_db: f32) -> Result<(), String> {
+        self.set_high(self.high.freq_hz, gain_db, self.high.q)
+    }
 }
 
 impl AudioProcessor for EqModule {
@@ -76,6 +76,16 @@ impl LimiterModule {
         Ok(())
     }
 
+    /// Ceiling abfragen (in dB)
+    pub fn get_ceiling(&self) -> f32 {
+        self.ceiling_db
+    }
+
+    /// Release abfragen (in ms)
+    pub fn get_release(&self) -> f32 {
+        self.release_ms
+    }
+
     /// Parameter neu berechnen
     fn update_parameters(&mut self) {
         // Ceiling linear
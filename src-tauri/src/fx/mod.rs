@@ -6,8 +6,9 @@
 // Phase 1: HPF + Gate implementiert
 // Phase 2: Weitere 6 Module (TODO)
 
-// Sub-Module (alle 8 Module)
+// Sub-Module (alle 8 Module + Bus-Insert-Chain)
 pub mod autogain;
+pub mod bus_chain;
 pub mod compressor;
 pub mod deesser;
 pub mod denoise;
@@ -21,6 +22,8 @@ use serde::{Deserialize, Serialize};
 // Audio-Konstanten
 pub const SAMPLE_RATE: f32 = 48000.0;
 pub const BUFFER_SIZE: usize = 256;
+/// Strip, den die globale FX-Chain bearbeitet (Mikrofon)
+pub const FX_CHAIN_STRIP: &str = "hw-mic";
 
 /// Audio-Prozessor Trait (Stereo L+R)
 /// Alle FX-Module implementieren dieses Interface
@@ -98,7 +101,7 @@ impl FxModuleType {
 }
 
 /// FX-Modul Info (für Frontend)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct FxModuleInfo {
     /// Modul-Typ
     pub module_type: FxModuleType,
@@ -314,6 +317,26 @@ impl FxChain {
         }
     }
 
+    /// Gespeicherte Modul-Zustände übernehmen (Session/Scene)
+    ///
+    /// Setzt Bypass und alle Parameter aus `get_all_modules()`-Snapshots.
+    pub fn apply_modules(&mut self, modules: &[FxModuleInfo]) -> Result<(), String> {
+        for module in modules {
+            for (name, value) in &module.params {
+                if !value.is_finite() {
+                    return Err(format!(
+                        "{}: Parameter '{}' ungültig",
+                        module.module_type.name(),
+                        name
+                    ));
+                }
+                self.set_param(module.module_type, name, *value)?;
+            }
+            self.set_bypass(module.module_type, !module.enabled)?;
+        }
+        Ok(())
+    }
+
     /// Chain zurücksetzen (alle Module)
     pub fn reset(&mut self) {
         self.hpf.reset();
@@ -404,4 +427,23 @@ mod tests {
         assert_eq!(modules[2].module_type, FxModuleType::Gate);
         assert_eq!(modules[7].module_type, FxModuleType::AutoGain);
     }
+
+    #[test]
+    fn test_apply_modules_roundtrip() {
+        let mut chain = FxChain::new();
+        chain.set_param(FxModuleType::Hpf, "freq", 120.0).unwrap();
+        chain.set_bypass(FxModuleType::Gate, true).unwrap();
+        let snapshot = chain.get_all_modules();
+
+        let mut restored = FxChain::new();
+        restored.apply_modules(&snapshot).unwrap();
+        let hpf = restored.get_module_info(FxModuleType::Hpf).unwrap();
+        assert_eq!(hpf.params[0].1, 120.0);
+        assert!(
+            !restored
+                .get_module_info(FxModuleType::Gate)
+                .unwrap()
+                .enabled
+        );
+    }
 }
@@ -0,0 +1,641 @@
+// Modul: history — Undo/Redo-Journal für alle Mixer-Änderungen
+//
+// Jede zustandsändernde Aktion wird als MixerCommand ausgeführt; das Journal
+// speichert dazu das inverse Kommando. Fortlaufende Fader-Bewegungen auf
+// demselben Ziel werden zu einem Schritt zusammengefasst.
+// SPEC: 22-shortcuts (Ctrl+Z / Ctrl+Y)
+
+use crate::audio::bus::{BusType, OutputBus};
+use crate::audio::master::TalkbackSettings;
+use crate::audio::mix_minus::MixMinus;
+use crate::audio::mixer::InputStrip;
+use crate::audio::routing::RoutingEntry;
+use crate::audio::talk::TalkSettings;
+use crate::config::scene_recall::SceneLevels;
+use crate::config::session::SessionState;
+use crate::fx::FxModuleType;
+use crate::streamer::voice_fx::VoiceFxPreset;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Maximale Anzahl an Undo-Schritten
+pub const HISTORY_MAX_DEPTH: usize = 100;
+/// Zeitfenster in dem Änderungen am selben Ziel zusammengefasst werden (ms)
+pub const COALESCE_WINDOW_MS: u64 = 500;
+
+/// Eine zustandsändernde Mixer-Aktion (auch als Inverse im Journal)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MixerCommand {
+    /// Strip-Lautstärke (dB)
+    StripVolume { strip_id: String, volume_db: f32 },
+    /// Strip-Gain (dB)
+    StripGain { strip_id: String, gain_db: f32 },
+    /// Strip-Stummschaltung
+    StripMute { strip_id: String, muted: bool },
+    /// Strip-Solo
+    StripSolo { strip_id: String, solo: bool },
+    /// Strip → Bus Zuweisung
+    StripBus {
+        strip_id: String,
+        bus_id: String,
+        active: bool,
+    },
+    /// Strip (wieder) einfügen — Redo von "hinzufügen", Undo von "entfernen"
+    InsertStrip { strip: InputStrip },
+    /// Strip entfernen
+    RemoveStrip { strip_id: String },
+    /// Capture-Device binden (None = lösen)
+    StripDevice {
+        strip_id: String,
+        device_name: Option<String>,
+    },
+    /// PipeWire-Node binden (None = lösen)
+    StripPwDevice {
+        strip_id: String,
+        device_id: Option<u32>,
+    },
+    /// Strip umbenennen
+    StripLabel { strip_id: String, label: String },
+    /// Strip-Icon
+    StripIcon { strip_id: String, icon: String },
+    /// Strip-Farbe (None = Standardfarbe)
+    StripColor {
+        strip_id: String,
+        color: Option<String>,
+    },
+    /// Strip-Reihenfolge (alle Strip-IDs in neuer Reihenfolge)
+    StripOrder { strip_ids: Vec<String> },
+    /// Talk-Modus eines Strips
+    StripTalk {
+        strip_id: String,
+        talk: TalkSettings,
+    },
+    /// Aktive Mute-Gruppen (genau diese Gruppen aktiv)
+    MuteGroups { active_groups: Vec<String> },
+    /// Bus-Lautstärke (dB)
+    BusVolume { bus_id: String, volume_db: f32 },
+    /// Bus-Stummschaltung
+    BusMute { bus_id: String, muted: bool },
+    /// Ausgangs-Delay eines Busses (ms)
+    BusDelay { bus_id: String, delay_ms: f32 },
+    /// Mix-Minus eines Busses (None = manuelles Routing)
+    BusMixMinus {
+        bus_id: String,
+        mix_minus: Option<MixMinus>,
+    },
+    /// Ausgabe-Device eines physischen Busses binden (None = Standard-Ausgabe)
+    BusDevice {
+        bus_id: String,
+        device_name: Option<String>,
+    },
+    /// Neuen Bus anlegen (ID wird vergeben)
+    CreateBus {
+        name: String,
+        bus_type: BusType,
+        channels: u16,
+    },
+    /// Bus umbenennen
+    RenameBus { bus_id: String, name: String },
+    /// Bus (wieder) anlegen — Undo von "Bus löschen" inkl. der dabei
+    /// getrennten Strip-Zuweisungen, Kreuzpunkte und Talkback-Ziele
+    InsertBus {
+        bus: OutputBus,
+        strips: Vec<String>,
+        routing: Vec<RoutingEntry>,
+        talkback: bool,
+    },
+    /// Bus löschen (abgelehnt solange ihn noch etwas nutzt)
+    RemoveBus { bus_id: String },
+    /// Routing Source → Bus
+    Routing {
+        source_id: String,
+        bus_id: String,
+        active: bool,
+    },
+    /// Mehrere Kreuzpunkte der Routing-Matrix in einem Schritt (inkl. Gain)
+    Crosspoints { entries: Vec<RoutingEntry> },
+    /// FX-Parameter
+    FxParam {
+        module_type: FxModuleType,
+        param_name: String,
+        value: f32,
+    },
+    /// FX-Bypass
+    FxBypass {
+        module_type: FxModuleType,
+        bypass: bool,
+    },
+    /// Insert-FX-Parameter eines Busses
+    BusFxParam {
+        bus_id: String,
+        module_type: FxModuleType,
+        param_name: String,
+        value: f32,
+    },
+    /// Insert-FX-Bypass eines Busses
+    BusFxBypass {
+        bus_id: String,
+        module_type: FxModuleType,
+        bypass: bool,
+    },
+    /// Master-Lautstärke (dB)
+    MasterVolume { volume_db: f32 },
+    /// Master-Limiter Ceiling (dB)
+    MasterLimiter { ceiling_db: f32 },
+    /// DIM
+    MasterDim { active: bool },
+    /// Mono-Check
+    MasterMono { active: bool },
+    /// Talkback inkl. Ziel-Busse
+    MasterTalkback {
+        active: bool,
+        target_buses: Vec<String>,
+    },
+    /// Talkback-Modus, Quelle, Auto-DIM und Auto-Release
+    MasterTalkbackSettings { settings: TalkbackSettings },
+    /// Ducking an/aus
+    DuckingEnabled { enabled: bool },
+    /// Ducking-Stärke (dB)
+    DuckingAmount { amount_db: f32 },
+    /// Ducking-Attack (ms)
+    DuckingAttack { attack_ms: f32 },
+    /// Ducking-Release (ms)
+    DuckingRelease { release_ms: f32 },
+    /// Ducking-Schwellwert (dB)
+    DuckingThreshold { threshold_db: f32 },
+    /// Voice-FX Preset
+    VoiceFxPreset { preset: VoiceFxPreset },
+    /// Voice-FX an/aus
+    VoiceFxEnabled { enabled: bool },
+    /// Voice-FX Dry/Wet (0.0-1.0)
+    VoiceFxDryWet { dry_wet: f32 },
+    /// Makro-Regler (0.0-1.0) inkl. aller zugeordneten Ziele
+    ///
+    /// `targets` setzt die Ziele auf feste Werte statt sie aus der
+    /// Reglerstellung zu berechnen (Undo: vorherige Ziel-Werte).
+    MacroValue {
+        macro_id: String,
+        value: f32,
+        #[serde(default)]
+        targets: Option<Vec<MixerCommand>>,
+    },
+    /// Mixer-Stand einer Szene übernehmen (Parameter bestehender Strips/Busse)
+    SceneState {
+        scene_name: String,
+        state: Box<SessionState>,
+        #[serde(default)]
+        levels: SceneLevels,
+    },
+}
+
+impl MixerCommand {
+    /// Schlüssel für das Zusammenfassen kontinuierlicher Änderungen
+    ///
+    /// Nur stufenlose Regler (Fader, Drehregler) haben einen Schlüssel —
+    /// Schalter und strukturelle Änderungen sind immer eigene Schritte.
+    pub fn coalesce_key(&self) -> Option<String> {
+        match self {
+            Self::StripVolume { strip_id, .. } => Some(format!("strip-volume:{}", strip_id)),
+            Self::StripGain { strip_id, .. } => Some(format!("strip-gain:{}", strip_id)),
+            Self::BusVolume { bus_id, .. } => Some(format!("bus-volume:{}", bus_id)),
+            Self::BusDelay { bus_id, .. } => Some(format!("bus-delay:{}", bus_id)),
+            // Gain-Ziehen an einem einzelnen Kreuzpunkt
+            Self::Crosspoints { entries } if entries.len() == 1 => Some(format!(
+                "crosspoint:{}:{}",
+                entries[0].source_id, entries[0].bus_id
+            )),
+            Self::FxParam {
+                module_type,
+                param_name,
+                ..
+            } => Some(format!("fx:{:?}:{}", module_type, param_name)),
+            Self::BusFxParam {
+                bus_id,
+                module_type,
+                param_name,
+                ..
+            } => Some(format!(
+                "bus-fx:{}:{:?}:{}",
+                bus_id, module_type, param_name
+            )),
+            Self::MasterVolume { .. } => Some("master-volume".to_string()),
+            Self::MasterLimiter { .. } => Some("master-limiter".to_string()),
+            Self::DuckingAmount { .. } => Some("ducking-amount".to_string()),
+            Self::DuckingAttack { .. } => Some("ducking-attack".to_string()),
+            Self::DuckingRelease { .. } => Some("ducking-release".to_string()),
+            Self::DuckingThreshold { .. } => Some("ducking-threshold".to_string()),
+            Self::VoiceFxDryWet { .. } => Some("voice-fx-drywet".to_string()),
+            Self::MacroValue { macro_id, .. } => Some(format!("macro:{}", macro_id)),
+            _ => None,
+        }
+    }
+
+    /// Kurzbeschreibung für die History-Anzeige
+    pub fn label(&self) -> String {
+        match self {
+            Self::StripVolume {
+                strip_id,
+                volume_db,
+            } => format!("{}: Lautstärke {:.1} dB", strip_id, volume_db),
+            Self::StripGain { strip_id, gain_db } => {
+                format!("{}: Gain {:.1} dB", strip_id, gain_db)
+            }
+            Self::StripMute { strip_id, muted } => format!("{}: Mute {}", strip_id, on_off(*muted)),
+            Self::StripSolo { strip_id, solo } => format!("{}: Solo {}", strip_id, on_off(*solo)),
+            Self::StripBus {
+                strip_id,
+                bus_id,
+                active,
+            } => format!("{} → {} {}", strip_id, bus_id, on_off(*active)),
+            Self::InsertStrip { strip } => format!("Strip {} hinzugefügt", strip.id),
+            Self::RemoveStrip { strip_id } => format!("Strip {} entfernt", strip_id),
+            Self::StripDevice {
+                strip_id,
+                device_name,
+            } => match device_name {
+                Some(name) => format!("{}: Device {}", strip_id, name),
+                None => format!("{}: Device gelöst", strip_id),
+            },
+            Self::StripPwDevice {
+                strip_id,
+                device_id,
+            } => match device_id {
+                Some(id) => format!("{}: PipeWire-Node {}", strip_id, id),
+                None => format!("{}: PipeWire-Node gelöst", strip_id),
+            },
+            Self::StripLabel { strip_id, label } => format!("{}: Name '{}'", strip_id, label),
+            Self::StripIcon { strip_id, icon } => format!("{}: Icon {}", strip_id, icon),
+            Self::StripColor { strip_id, color } => match color {
+                Some(color) => format!("{}: Farbe {}", strip_id, color),
+                None => format!("{}: Standardfarbe", strip_id),
+            },
+            Self::StripOrder { .. } => "Strips neu angeordnet".to_string(),
+            Self::StripTalk { strip_id, talk } => {
+                format!("{}: Talk-Modus {:?}", strip_id, talk.mode)
+            }
+            Self::MuteGroups { active_groups } if active_groups.is_empty() => {
+                "Mute-Gruppen gelöst".to_string()
+            }
+            Self::MuteGroups { active_groups } => {
+                format!("Mute-Gruppen: {}", active_groups.join(", "))
+            }
+            Self::BusVolume { bus_id, volume_db } => {
+                format!("Bus {}: Lautstärke {:.1} dB", bus_id, volume_db)
+            }
+            Self::BusMute { bus_id, muted } => format!("Bus {}: Mute {}", bus_id, on_off(*muted)),
+            Self::BusDelay { bus_id, delay_ms } => {
+                format!("Bus {}: Delay {:.0} ms", bus_id, delay_ms)
+            }
+            Self::BusMixMinus { bus_id, mix_minus } => match mix_minus {
+                Some(mm) => format!(
+                    "Bus {}: Mix-Minus von {} ohne {}",
+                    bus_id,
+                    mm.reference_bus,
+                    mm.excluded.join(", ")
+                ),
+                None => format!("Bus {}: Mix-Minus aus", bus_id),
+            },
+            Self::BusDevice {
+                bus_id,
+                device_name,
+            } => match device_name {
+                Some(name) => format!("Bus {}: Device {}", bus_id, name),
+                None => format!("Bus {}: Standard-Ausgabe", bus_id),
+            },
+            Self::CreateBus { name, .. } => format!("Bus {} angelegt", name),
+            Self::RenameBus { bus_id, name } => format!("Bus {}: Name '{}'", bus_id, name),
+            Self::InsertBus { bus, .. } => format!("Bus {} ({}) angelegt", bus.id, bus.name),
+            Self::RemoveBus { bus_id } => format!("Bus {} gelöscht", bus_id),
+            Self::Routing {
+                source_id,
+                bus_id,
+                active,
+            } => format!("Routing {} → {} {}", source_id, bus_id, on_off(*active)),
+            Self::Crosspoints { entries } => match entries.as_slice() {
+                [entry] if entry.active => format!(
+                    "Routing {} → {}: {:.1} dB",
+                    entry.source_id, entry.bus_id, entry.gain_db
+                ),
+                [entry] => format!("Routing {} → {} AUS", entry.source_id, entry.bus_id),
+                _ => format!("Routing: {} Kreuzpunkte", entries.len()),
+            },
+            Self::FxParam {
+                module_type,
+                param_name,
+                value,
+            } => format!("{} {}: {:.2}", module_type.name(), param_name, value),
+            Self::FxBypass {
+                module_type,
+                bypass,
+            } => format!("{} Bypass {}", module_type.name(), on_off(*bypass)),
+            Self::BusFxParam {
+                bus_id,
+                module_type,
+                param_name,
+                value,
+            } => format!(
+                "Bus {}: {} {}: {:.2}",
+                bus_id,
+                module_type.name(),
+                param_name,
+                value
+            ),
+            Self::BusFxBypass {
+                bus_id,
+                module_type,
+                bypass,
+            } => format!(
+                "Bus {}: {} Bypass {}",
+                bus_id,
+                module_type.name(),
+                on_off(*bypass)
+            ),
+            Self::MasterVolume { volume_db } => format!("Master: {:.1} dB", volume_db),
+            Self::MasterLimiter { ceiling_db } => format!("Master-Limiter: {:.1} dB", ceiling_db),
+            Self::MasterDim { active } => format!("DIM {}", on_off(*active)),
+            Self::MasterMono { active } => format!("MONO {}", on_off(*active)),
+            Self::MasterTalkback { active, .. } => format!("Talkback {}", on_off(*active)),
+            Self::MasterTalkbackSettings { settings } => format!(
+                "Talkback: {:?}, Quelle {}, DIM {:.0} dB",
+                settings.mode, settings.source_strip, settings.dim_db
+            ),
+            Self::DuckingEnabled { enabled } => format!("Ducking {}", on_off(*enabled)),
+            Self::DuckingAmount { amount_db } => format!("Ducking: {:.1} dB", amount_db),
+            Self::DuckingAttack { attack_ms } => format!("Ducking-Attack: {:.0} ms", attack_ms),
+            Self::DuckingRelease { release_ms } => {
+                format!("Ducking-Release: {:.0} ms", release_ms)
+            }
+            Self::DuckingThreshold { threshold_db } => {
+                format!("Ducking-Threshold: {:.1} dB", threshold_db)
+            }
+            Self::VoiceFxPreset { preset } => format!("Voice-FX: {}", preset.name()),
+            Self::VoiceFxEnabled { enabled } => format!("Voice-FX {}", on_off(*enabled)),
+            Self::VoiceFxDryWet { dry_wet } => format!("Voice-FX Dry/Wet: {:.0}%", dry_wet * 100.0),
+            Self::MacroValue {
+                macro_id, value, ..
+            } => {
+                format!("Makro {}: {:.0}%", macro_id, value * 100.0)
+            }
+            Self::SceneState { scene_name, .. } => format!("Szene: {}", scene_name),
+        }
+    }
+}
+
+/// "AN"/"AUS" für Labels
+fn on_off(active: bool) -> &'static str {
+    if active {
+        "AN"
+    } else {
+        "AUS"
+    }
+}
+
+/// Ein Schritt im Journal
+#[derive(Debug, Clone)]
+struct HistoryEntry {
+    /// Ausgeführtes Kommando (für Redo)
+    apply: MixerCommand,
+    /// Inverses Kommando (für Undo)
+    inverse: MixerCommand,
+    /// Zeitpunkt der letzten Änderung (für das Zusammenfassen)
+    updated_at: Instant,
+}
+
+/// History-Übersicht für das Frontend
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryInfo {
+    /// Undo-Schritte (neuester zuerst)
+    pub undo: Vec<String>,
+    /// Redo-Schritte (nächster zuerst)
+    pub redo: Vec<String>,
+    /// Maximale Tiefe
+    pub max_depth: usize,
+}
+
+/// Undo/Redo-Journal mit begrenzter Tiefe
+pub struct CommandHistory {
+    /// Undo-Stapel (ältester vorne)
+    undo: VecDeque<HistoryEntry>,
+    /// Redo-Stapel (nächster hinten)
+    redo: Vec<HistoryEntry>,
+    /// Maximale Anzahl an Undo-Schritten
+    max_depth: usize,
+}
+
+impl CommandHistory {
+    /// Leeres Journal mit Standard-Tiefe erstellen
+    pub fn new() -> Self {
+        Self::with_depth(HISTORY_MAX_DEPTH)
+    }
+
+    /// Leeres Journal mit eigener Tiefe erstellen
+    pub fn with_depth(max_depth: usize) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            max_depth: max_depth.max(1),
+        }
+    }
+
+    /// Ausgeführtes Kommando samt Inverse aufzeichnen
+    ///
+    /// Folgt es innerhalb von `COALESCE_WINDOW_MS` einem Kommando mit gleichem
+    /// Schlüssel, wird der letzte Schritt erweitert statt ein neuer angelegt —
+    /// die Inverse bleibt die des ersten Schritts (Wert vor der Fader-Bewegung).
+    pub fn record(&mut self, apply: MixerCommand, inverse: MixerCommand, now: Instant) {
+        self.redo.clear();
+
+        if let (Some(last), Some(key)) = (self.undo.back_mut(), apply.coalesce_key()) {
+            let window = Duration::from_millis(COALESCE_WINDOW_MS);
+            if last.apply.coalesce_key().as_ref() == Some(&key)
+                && now.duration_since(last.updated_at) <= window
+            {
+                last.apply = apply;
+                last.updated_at = now;
+                return;
+            }
+        }
+
+        self.undo.push_back(HistoryEntry {
+            apply,
+            inverse,
+            updated_at: now,
+        });
+        while self.undo.len() > self.max_depth {
+            self.undo.pop_front();
+        }
+    }
+
+    /// Letzten Schritt rückgängig machen
+    ///
+    /// `run` führt das inverse Kommando aus; schlägt es fehl, bleibt der
+    /// Schritt im Journal.
+    ///
+    /// # Returns
+    /// Das ausgeführte inverse Kommando (None wenn nichts rückgängig zu machen ist)
+    pub fn undo<F>(&mut self, run: F) -> Result<Option<MixerCommand>, String>
+    where
+        F: FnOnce(&MixerCommand) -> Result<(), String>,
+    {
+        let entry = match self.undo.pop_back() {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        if let Err(e) = run(&entry.inverse) {
+            self.undo.push_back(entry);
+            return Err(e);
+        }
+        let inverse = entry.inverse.clone();
+        self.redo.push(entry);
+        Ok(Some(inverse))
+    }
+
+    /// Zuletzt rückgängig gemachten Schritt wiederholen
+    pub fn redo<F>(&mut self, run: F) -> Result<Option<MixerCommand>, String>
+    where
+        F: FnOnce(&MixerCommand) -> Result<(), String>,
+    {
+        let mut entry = match self.redo.pop() {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        if let Err(e) = run(&entry.apply) {
+            self.redo.push(entry);
+            return Err(e);
+        }
+        let apply = entry.apply.clone();
+        // Nach Redo nicht mit der nächsten Fader-Bewegung verschmelzen
+        entry.updated_at = Instant::now() - Duration::from_millis(COALESCE_WINDOW_MS + 1);
+        self.undo.push_back(entry);
+        Ok(Some(apply))
+    }
+
+    /// Übersicht für das Frontend
+    pub fn info(&self) -> HistoryInfo {
+        HistoryInfo {
+            undo: self.undo.iter().rev().map(|e| e.apply.label()).collect(),
+            redo: self.redo.iter().rev().map(|e| e.apply.label()).collect(),
+            max_depth: self.max_depth,
+        }
+    }
+}
+
+impl Default for CommandHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volume(strip_id: &str, volume_db: f32) -> MixerCommand {
+        MixerCommand::StripVolume {
+            strip_id: strip_id.to_string(),
+            volume_db,
+        }
+    }
+
+    fn mute(muted: bool) -> MixerCommand {
+        MixerCommand::StripMute {
+            strip_id: "hw-mic".to_string(),
+            muted,
+        }
+    }
+
+    #[test]
+    fn test_undo_redo_roundtrip() {
+        let mut history = CommandHistory::new();
+        history.record(mute(true), mute(false), Instant::now());
+
+        let mut applied = Vec::new();
+        let undone = history
+            .undo(|cmd| {
+                applied.push(cmd.clone());
+                Ok(())
+            })
+            .unwrap();
+        assert_eq!(undone, Some(mute(false)));
+        assert_eq!(history.info().redo.len(), 1);
+
+        let redone = history.redo(|_| Ok(())).unwrap();
+        assert_eq!(redone, Some(mute(true)));
+        assert_eq!(history.info().undo.len(), 1);
+        assert!(history.info().redo.is_empty());
+    }
+
+    #[test]
+    fn test_fader_drag_coalesced() {
+        let mut history = CommandHistory::new();
+        let t0 = Instant::now();
+        history.record(volume("hw-mic", -1.0), volume("hw-mic", 0.0), t0);
+        history.record(
+            volume("hw-mic", -2.0),
+            volume("hw-mic", -1.0),
+            t0 + Duration::from_millis(100),
+        );
+        history.record(
+            volume("hw-mic", -3.0),
+            volume("hw-mic", -2.0),
+            t0 + Duration::from_millis(200),
+        );
+        assert_eq!(history.info().undo.len(), 1);
+
+        // Undo springt auf den Wert vor der Fader-Bewegung zurück
+        let undone = history.undo(|_| Ok(())).unwrap();
+        assert_eq!(undone, Some(volume("hw-mic", 0.0)));
+    }
+
+    #[test]
+    fn test_no_coalesce_across_targets_or_pause() {
+        let mut history = CommandHistory::new();
+        let t0 = Instant::now();
+        history.record(volume("hw-mic", -1.0), volume("hw-mic", 0.0), t0);
+        history.record(volume("virt-1", -1.0), volume("virt-1", 0.0), t0);
+        let later = t0 + Duration::from_millis(COALESCE_WINDOW_MS + 1);
+        history.record(volume("virt-1", -2.0), volume("virt-1", -1.0), later);
+        assert_eq!(history.info().undo.len(), 3);
+
+        // Schalter werden nie zusammengefasst
+        history.record(mute(true), mute(false), later);
+        history.record(mute(false), mute(true), later);
+        assert_eq!(history.info().undo.len(), 5);
+    }
+
+    #[test]
+    fn test_bounded_depth() {
+        let mut history = CommandHistory::with_depth(3);
+        for i in 0..5 {
+            history.record(mute(i % 2 == 0), mute(i % 2 != 0), Instant::now());
+        }
+        assert_eq!(history.info().undo.len(), 3);
+    }
+
+    #[test]
+    fn test_new_command_clears_redo() {
+        let mut history = CommandHistory::new();
+        history.record(mute(true), mute(false), Instant::now());
+        history.undo(|_| Ok(())).unwrap();
+        history.record(mute(true), mute(false), Instant::now());
+        assert!(history.info().redo.is_empty());
+    }
+
+    #[test]
+    fn test_failed_undo_keeps_entry() {
+        let mut history = CommandHistory::new();
+        history.record(mute(true), mute(false), Instant::now());
+        assert!(history.undo(|_| Err("Strip fehlt".to_string())).is_err());
+        assert_eq!(history.info().undo.len(), 1);
+        assert_eq!(history.undo(|_| Ok(())).unwrap(), Some(mute(false)));
+        assert_eq!(history.undo(|_| Ok(())).unwrap(), None);
+    }
+
+    #[test]
+    fn test_command_serialize() {
+        let json = serde_json::to_string(&mute(true)).unwrap();
+        assert!(json.contains("\"type\":\"strip_mute\""));
+    }
+}
@@ -0,0 +1,474 @@
+// Modul: macros — Makro-Regler (ein Regler steuert mehrere Parameter)
+//
+// Ein Makro ist ein benannter Regler von 0.0 bis 1.0 (z.B. "Hype"). Jede
+// Zuordnung bildet den Regler mit eigenem Bereich und eigener Kurve auf ein
+// Ziel ab: Strip-Lautstärke, Bus-Send, FX-Parameter, Voice-FX Dry/Wet oder
+// Ducking-Stärke. Bereiche dürfen fallen (min > max), so senkt derselbe
+// Regler z.B. das Ducking während die Musik lauter wird.
+// Die Ziel-Werte werden als MixerCommands erzeugt und von den Managern
+// übernommen — UI, API, MIDI und Szenen nutzen denselben Weg.
+use crate::fx::FxModuleType;
+use crate::history::MixerCommand;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Maximale Anzahl an Makros
+const MAX_MACROS: usize = 16;
+/// Maximale Anzahl an Zuordnungen pro Makro
+const MAX_MAPPINGS: usize = 16;
+/// Maximale Länge eines Makro-Namens (Zeichen)
+const MAX_NAME_LEN: usize = 32;
+
+/// Ziel einer Makro-Zuordnung
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MacroTarget {
+    /// Strip-Lautstärke (dB)
+    StripVolume { strip_id: String },
+    /// Bus-Send: Lautstärke des Output-Busses (dB)
+    BusSend { bus_id: String },
+    /// FX-Parameter der FxChain
+    FxParam {
+        module_type: FxModuleType,
+        param_name: String,
+    },
+    /// Voice-FX Dry/Wet (0.0-1.0)
+    VoiceFxDryWet,
+    /// Ducking-Stärke (dB, -30 bis 0)
+    DuckingAmount,
+}
+
+impl MacroTarget {
+    /// Mixer-Kommando für einen Ziel-Wert erzeugen
+    pub fn to_command(&self, value: f32) -> MixerCommand {
+        match self {
+            Self::StripVolume { strip_id } => MixerCommand::StripVolume {
+                strip_id: strip_id.clone(),
+                volume_db: value,
+            },
+            Self::BusSend { bus_id } => MixerCommand::BusVolume {
+                bus_id: bus_id.clone(),
+                volume_db: value,
+            },
+            Self::FxParam {
+                module_type,
+                param_name,
+            } => MixerCommand::FxParam {
+                module_type: *module_type,
+                param_name: param_name.clone(),
+                value,
+            },
+            Self::VoiceFxDryWet => MixerCommand::VoiceFxDryWet { dry_wet: value },
+            Self::DuckingAmount => MixerCommand::DuckingAmount { amount_db: value },
+        }
+    }
+}
+
+/// Regelkurve einer Zuordnung
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroCurve {
+    /// Linear
+    #[default]
+    Linear,
+    /// Exponentiell (langsamer Anfang, x²)
+    Exponential,
+    /// Logarithmisch (schneller Anfang, √x)
+    Logarithmic,
+    /// S-Kurve (weicher Anfang und weiches Ende)
+    SCurve,
+}
+
+impl MacroCurve {
+    /// Reglerstellung (0.0 - 1.0) auf die Kurve abbilden
+    pub fn apply(&self, x: f32) -> f32 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => x,
+            Self::Exponential => x * x,
+            Self::Logarithmic => x.sqrt(),
+            Self::SCurve => x * x * (3.0 - 2.0 * x),
+        }
+    }
+}
+
+/// Zuordnung eines Makros auf ein Ziel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MacroMapping {
+    /// Ziel-Parameter
+    pub target: MacroTarget,
+    /// Ziel-Wert bei Regler 0.0
+    pub min: f32,
+    /// Ziel-Wert bei Regler 1.0
+    pub max: f32,
+    /// Regelkurve
+    #[serde(default)]
+    pub curve: MacroCurve,
+}
+
+impl MacroMapping {
+    /// Ziel-Wert für eine Reglerstellung
+    pub fn value_at(&self, x: f32) -> f32 {
+        self.min + (self.max - self.min) * self.curve.apply(x)
+    }
+
+    /// Bereich prüfen
+    fn validate(&self) -> Result<(), String> {
+        if !self.min.is_finite() || !self.max.is_finite() {
+            return Err("Makro-Bereich ungültig".to_string());
+        }
+        if self.target == MacroTarget::VoiceFxDryWet
+            && !((0.0..=1.0).contains(&self.min) && (0.0..=1.0).contains(&self.max))
+        {
+            return Err("Dry/Wet-Bereich außerhalb (0.0-1.0)".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Ein Makro-Regler
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Macro {
+    /// Eindeutige Makro-ID (z.B. "macro-1")
+    pub id: String,
+    /// Anzeige-Name
+    pub name: String,
+    /// Reglerstellung (0.0 - 1.0)
+    pub value: f32,
+    /// Zuordnungen
+    pub mappings: Vec<MacroMapping>,
+}
+
+impl Macro {
+    /// Mixer-Kommandos für die aktuelle Reglerstellung
+    pub fn commands(&self) -> Vec<MixerCommand> {
+        self.mappings
+            .iter()
+            .map(|m| m.target.to_command(m.value_at(self.value)))
+            .collect()
+    }
+}
+
+/// Verwaltet alle Makros
+#[derive(Debug)]
+pub struct MacroManager {
+    /// Alle Makros (Key: Makro-ID)
+    macros: HashMap<String, Macro>,
+    /// Zähler für die nächste Makro-ID
+    next_id: u32,
+}
+
+impl MacroManager {
+    /// Neuen Manager ohne Makros erstellen
+    pub fn new() -> Self {
+        Self {
+            macros: HashMap::new(),
+            next_id: 1,
+        }
+    }
+
+    /// Makros aus gespeichertem State wiederherstellen (Session)
+    pub fn from_macros(macros: Vec<Macro>) -> Result<Self, String> {
+        if macros.len() > MAX_MACROS {
+            return Err(format!(
+                "Zu viele Makros: {} (max {})",
+                macros.len(),
+                MAX_MACROS
+            ));
+        }
+
+        let mut map = HashMap::new();
+        for mut m in macros {
+            if m.id.is_empty() {
+                return Err("Makro-ID darf nicht leer sein".to_string());
+            }
+            m.name = validate_name(&m.name)?;
+            validate_mappings(&m.mappings)?;
+            m.value = validate_value(m.value)?;
+            if map.contains_key(&m.id) {
+                return Err(format!("Makro-ID doppelt: {}", m.id));
+            }
+            map.insert(m.id.clone(), m);
+        }
+
+        let next_id = map
+            .keys()
+            .filter_map(|id| id.strip_prefix("macro-")?.parse::<u32>().ok())
+            .max()
+            .map_or(1, |max| max + 1);
+
+        Ok(Self {
+            macros: map,
+            next_id,
+        })
+    }
+
+    /// Alle Makros als sortierte Liste (nach Name)
+    pub fn get_macros(&self) -> Vec<Macro> {
+        let mut macros: Vec<Macro> = self.macros.values().cloned().collect();
+        macros.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.id.cmp(&b.id)));
+        macros
+    }
+
+    /// Einzelnes Makro abfragen
+    pub fn get_macro(&self, macro_id: &str) -> Option<&Macro> {
+        self.macros.get(macro_id)
+    }
+
+    /// Neues Makro anlegen (Regler auf 0.0)
+    pub fn create_macro(
+        &mut self,
+        name: &str,
+        mappings: Vec<MacroMapping>,
+    ) -> Result<Macro, String> {
+        if self.macros.len() >= MAX_MACROS {
+            return Err(format!(
+                "Maximale Anzahl von {} Makros erreicht",
+                MAX_MACROS
+            ));
+        }
+        validate_mappings(&mappings)?;
+
+        let m = Macro {
+            id: format!("macro-{}", self.next_id),
+            name: validate_name(name)?,
+            value: 0.0,
+            mappings,
+        };
+        self.next_id += 1;
+        self.macros.insert(m.id.clone(), m.clone());
+
+        info!("Makro '{}' angelegt ({})", m.name, m.id);
+        Ok(m)
+    }
+
+    /// Makro ändern (Name und Zuordnungen — Reglerstellung bleibt)
+    pub fn update_macro(
+        &mut self,
+        macro_id: &str,
+        name: &str,
+        mappings: Vec<MacroMapping>,
+    ) -> Result<(), String> {
+        let name = validate_name(name)?;
+        validate_mappings(&mappings)?;
+        let m = self
+            .macros
+            .get_mut(macro_id)
+            .ok_or_else(|| format!("Makro '{}' nicht gefunden", macro_id))?;
+
+        m.name = name;
+        m.mappings = mappings;
+        Ok(())
+    }
+
+    /// Makro löschen
+    pub fn delete_macro(&mut self, macro_id: &str) -> Result<(), String> {
+        self.macros
+            .remove(macro_id)
+            .ok_or_else(|| format!("Makro '{}' nicht gefunden", macro_id))?;
+        info!("Makro {} gelöscht", macro_id);
+        Ok(())
+    }
+
+    /// Reglerstellung setzen
+    ///
+    /// # Returns
+    /// Mixer-Kommandos für alle Ziele des Makros
+    pub fn set_value(&mut self, macro_id: &str, value: f32) -> Result<Vec<MixerCommand>, String> {
+        let value = validate_value(value)?;
+        let m = self
+            .macros
+            .get_mut(macro_id)
+            .ok_or_else(|| format!("Makro '{}' nicht gefunden", macro_id))?;
+        m.value = value;
+        Ok(m.commands())
+    }
+
+    /// Namen der Makros mit einem Ziel auf diesem Strip (sortiert)
+    pub fn macros_using_strip(&self, strip_id: &str) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .macros
+            .values()
+            .filter(|m| {
+                m.mappings.iter().any(|mapping| {
+                    matches!(&mapping.target, MacroTarget::StripVolume { strip_id: id } if id == strip_id)
+                })
+            })
+            .map(|m| m.name.clone())
+            .collect();
+        names.sort();
+        names
+    }
+
+    /// Reglerstellungen übernehmen ohne die Ziele anzusteuern (Szenen)
+    ///
+    /// Unbekannte Makros werden übersprungen.
+    pub fn restore_values(&mut self, macros: &[Macro]) {
+        for src in macros {
+            if let Some(m) = self.macros.get_mut(&src.id) {
+                m.value = src.value.clamp(0.0, 1.0);
+            }
+        }
+    }
+}
+
+impl Default for MacroManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// MIDI-CC-Wert (0-127) in eine Reglerstellung umrechnen
+pub fn value_from_midi(cc_value: u8) -> f32 {
+    cc_value.min(127) as f32 / 127.0
+}
+
+/// Makro-Namen prüfen und bereinigt zurückgeben
+fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LEN {
+        return Err(format!(
+            "Ungültiger Makro-Name (1 bis {} Zeichen erlaubt)",
+            MAX_NAME_LEN
+        ));
+    }
+    Ok(name.to_string())
+}
+
+/// Zuordnungen prüfen (Anzahl und Bereiche)
+fn validate_mappings(mappings: &[MacroMapping]) -> Result<(), String> {
+    if mappings.len() > MAX_MAPPINGS {
+        return Err(format!(
+            "Zu viele Zuordnungen: {} (max {})",
+            mappings.len(),
+            MAX_MAPPINGS
+        ));
+    }
+    mappings.iter().try_for_each(MacroMapping::validate)
+}
+
+/// Reglerstellung prüfen
+fn validate_value(value: f32) -> Result<f32, String> {
+    if !(0.0..=1.0).contains(&value) {
+        return Err(format!("Makro-Wert außerhalb: {} (0.0-1.0)", value));
+    }
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hype() -> Vec<MacroMapping> {
+        vec![
+            MacroMapping {
+                target: MacroTarget::StripVolume {
+                    strip_id: "virt-1".to_string(),
+                },
+                min: -20.0,
+                max: 0.0,
+                curve: MacroCurve::Linear,
+            },
+            MacroMapping {
+                target: MacroTarget::VoiceFxDryWet,
+                min: 0.0,
+                max: 1.0,
+                curve: MacroCurve::Exponential,
+            },
+            MacroMapping {
+                target: MacroTarget::DuckingAmount,
+                min: -20.0,
+                max: 0.0,
+                curve: MacroCurve::Logarithmic,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_curves() {
+        for curve in [
+            MacroCurve::Linear,
+            MacroCurve::Exponential,
+            MacroCurve::Logarithmic,
+            MacroCurve::SCurve,
+        ] {
+            assert_eq!(curve.apply(0.0), 0.0);
+            assert_eq!(curve.apply(1.0), 1.0);
+        }
+        assert_eq!(MacroCurve::Exponential.apply(0.5), 0.25);
+        assert!(MacroCurve::Logarithmic.apply(0.25) > 0.25);
+    }
+
+    #[test]
+    fn test_set_value_commands() {
+        let mut manager = MacroManager::new();
+        let m = manager.create_macro(" Hype ", hype()).unwrap();
+        assert_eq!(m.id, "macro-1");
+        assert_eq!(m.name, "Hype");
+
+        let commands = manager.set_value("macro-1", 0.25).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                MixerCommand::StripVolume {
+                    strip_id: "virt-1".to_string(),
+                    volume_db: -15.0,
+                },
+                MixerCommand::VoiceFxDryWet { dry_wet: 0.0625 },
+                MixerCommand::DuckingAmount { amount_db: -10.0 },
+            ]
+        );
+        assert!(manager.set_value("macro-1", 1.5).is_err());
+        assert!(manager.set_value("macro-9", 0.5).is_err());
+    }
+
+    #[test]
+    fn test_macros_using_strip() {
+        let mut manager = MacroManager::new();
+        manager.create_macro("Hype", hype()).unwrap();
+        assert_eq!(manager.macros_using_strip("virt-1"), vec!["Hype"]);
+        assert!(manager.macros_using_strip("virt-2").is_empty());
+    }
+
+    #[test]
+    fn test_inverted_range() {
+        let mapping = MacroMapping {
+            target: MacroTarget::BusSend {
+                bus_id: "B1".to_string(),
+            },
+            min: 0.0,
+            max: -30.0,
+            curve: MacroCurve::Linear,
+        };
+        assert_eq!(mapping.value_at(0.0), 0.0);
+        assert_eq!(mapping.value_at(1.0), -30.0);
+    }
+
+    #[test]
+    fn test_validation_and_restore() {
+        let mut manager = MacroManager::new();
+        let mut mappings = hype();
+        mappings[1].max = 2.0;
+        assert!(manager.create_macro("Hype", mappings).is_err());
+        assert!(manager.create_macro("", hype()).is_err());
+
+        manager.create_macro("Hype", hype()).unwrap();
+        let mut saved = manager.get_macros();
+        saved[0].value = 0.8;
+        manager.restore_values(&saved);
+        assert_eq!(manager.get_macro("macro-1").unwrap().value, 0.8);
+
+        let restored = MacroManager::from_macros(manager.get_macros()).unwrap();
+        let m = restored.get_macro("macro-1").unwrap();
+        assert_eq!(m.value, 0.8);
+        assert_eq!(m.mappings.len(), 3);
+    }
+
+    #[test]
+    fn test_value_from_midi() {
+        assert_eq!(value_from_midi(0), 0.0);
+        assert_eq!(value_from_midi(127), 1.0);
+        assert_eq!(value_from_midi(200), 1.0);
+    }
+}
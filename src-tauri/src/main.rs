@@ -3,16 +3,25 @@
 
 mod api;
 mod audio;
+mod automation;
 mod calibrate;
 mod config;
 mod fx;
+mod history;
+mod macros;
 mod recording;
 mod streamer;
 mod stt;
 mod updater;
 
+use automation::store::{AutomationClipInfo, AutomationStore};
+use automation::{AutomationEngine, AutomationMode, AutomationStatus};
 use calibrate::{CalibrateEngine, CalibrationResult};
 use config::presets::{Scene, SceneInfo, SceneManager};
+use config::scene_recall::{SceneLevels, SceneScope, SceneSnapshot};
+use config::scene_transition::{SceneTransition, TransitionFrame, TransitionSettings};
+use history::{CommandHistory, HistoryInfo, MixerCommand};
+use macros::{Macro, MacroManager, MacroMapping, MacroTarget};
 use recording::{ActiveRecording, RecordingEngine, RecordingFormat, RecordingInfo};
 use streamer::bleeper::{BleepMode, BleeperEngine};
 use streamer::ducking::{DuckingEngine, DuckingParams};
@@ -21,21 +30,43 @@ use streamer::voice_fx::{VoiceFxManager, VoiceFxPreset, VoiceFxState};
 use stt::{ProfanityCategory, ProfanityWord, SttEngineType, SttManager};
 use updater::{check_for_updates, install_update};
 
-use audio::bus::{BusManager, OutputBus};
-use audio::master::{MasterManager, MasterState};
+use audio::bus::{BusManager, BusType, OutputBus};
+use audio::bus_tap::BusTapSource;
+use audio::master::{MasterManager, MasterState, TalkbackSettings};
+use audio::cpal_capture::{CaptureStatus, CpalCaptureManager};
+use audio::generator::{GeneratorManager, GeneratorSettings, GeneratorStatus};
 use audio::metering_service::MeteringService;
-use audio::mixer::{InputStrip, MixerState};
+use audio::mix_engine::MixEngine;
+use audio::mix_minus::{self, MixMinus};
+use audio::tap::TapKind;
+use audio::mixer::{validate_label, InputStrip, MixerState, StripType};
+use audio::mute_groups::{MuteGroup, MuteGroupManager};
+use audio::talk::{TalkSettings, TalkStatus};
 use audio::pipewire::{self as pw, AudioDevice};
-use audio::routing::{RoutingEntry, RoutingManager};
+use audio::routing::{RoutingEntry, RoutingManager, RoutingTemplate, ROUTING_TEMPLATE_VERSION};
 use config::database::Database;
+use config::session::{SessionState, SessionStore};
 use config::ConfigManager;
 use fx::{FxChain, FxModuleInfo, FxModuleType};
 use log::{error, info, warn};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tauri::{Emitter, Manager};
 
 /// Dateiname der SQLite-Datenbank
 const DB_FILENAME: &str = "inox-mix.db";
+/// Intervall des Tap-Consumers für Recording und STT (ms)
+const TAP_CONSUMER_INTERVAL_MS: u64 = 20;
+/// Intervall in dem der Live-State auf Änderungen geprüft wird (ms)
+const SESSION_POLL_INTERVAL_MS: u64 = 250;
+/// Takt der Szenen-Überblendung (ms)
+const SCENE_TRANSITION_TICK_MS: u64 = 20;
+/// Takt der Automation-Spiegelung in die Manager (ms)
+const AUTOMATION_TICK_MS: u64 = 10;
+/// Intervall der Talkback-Auto-Release-Prüfung (ms)
+const TALKBACK_POLL_INTERVAL_MS: u64 = 100;
+/// Intervall der Generator-Auto-Stopp-Prüfung (ms)
+const GENERATOR_POLL_INTERVAL_MS: u64 = 250;
 
 /// Globaler App-State der über Tauri verwaltet wird
 struct AppState {
@@ -51,8 +82,14 @@ struct AppState {
     routing: Mutex<RoutingManager>,
     /// Master-Sektion (Master Volume, Limiter, DIM, MONO, Talkback)
     master: Mutex<MasterManager>,
+    /// Mute-Gruppen (überlagern den Strip-Mute)
+    mute_groups: Mutex<MuteGroupManager>,
+    /// Makros (ein Regler steuert mehrere Parameter)
+    macros: Mutex<MacroManager>,
     /// Recording-Engine für Audio-Aufnahmen
     recording: Mutex<RecordingEngine>,
+    /// Stopp-Flag des laufenden Tap-Consumer-Threads
+    tap_consumer: Mutex<Option<Arc<AtomicBool>>>,
     /// Scene-Manager für Szenen-Verwaltung
     scenes: SceneManager,
     /// Soundboard-Manager für Sound-Playback
@@ -67,8 +104,24 @@ struct AppState {
     calibrate: Mutex<CalibrateEngine>,
     /// Metering-Service für Echtzeit-VU-Meter
     metering: Mutex<MeteringService>,
+    /// Von der App angeforderte PipeWire-Clock (wird beim Beenden freigegeben)
+    clock: Mutex<pw::ClockClaim>,
+    /// Signal-Generator für Line-Checks
+    generator: Mutex<GeneratorManager>,
+    /// In-Process Mix-Engine (Strips → Busse)
+    mix_engine: Arc<Mutex<MixEngine>>,
     /// STT-Manager für Speech-to-Text (VOSK + Whisper)
     stt: Mutex<SttManager>,
+    /// Session-Store (automatische Persistenz des Live-States)
+    session: Mutex<SessionStore>,
+    /// Undo/Redo-Journal aller Mixer-Änderungen
+    history: Mutex<CommandHistory>,
+    /// Laufende Szenen-Überblendung
+    scene_transition: Mutex<Option<SceneTransition>>,
+    /// Automation-Clips in SQLite
+    automation_clips: AutomationStore,
+    /// App-Handle für Events aus den Sync-Helfern
+    app_handle: tauri::AppHandle,
 }
 
 // --- Tauri Commands ---
@@ -113,6 +166,96 @@ fn set_config(key: String, value: String, state: tauri::State<'_, AppState>) ->
         .map_err(|e| format!("Config-Fehler: {}", e))
 }
 
+/// Audio-Clock setzen: fordert Sample-Rate/Buffer-Größe bei PipeWire an und speichert sie
+///
+/// Gespeichert wird erst, wenn PipeWire die Anforderung angenommen hat. Die
+/// Bus-Nodes starten danach in der neuen Clock neu. Der zurückgegebene Report
+/// enthält die tatsächlich ausgehandelten Werte.
+#[tauri::command]
+fn set_audio_config(
+    sample_rate: u32,
+    buffer_size: u32,
+    state: tauri::State<'_, AppState>,
+) -> Result<pw::LatencyReport, String> {
+    pw::validate_clock_request(buffer_size, sample_rate)?;
+
+    let mut config = state
+        .config_manager
+        .load_app_config()
+        .map_err(|e| format!("Config-Fehler: {}", e))?;
+    let previous = (config.buffer_size, config.sample_rate);
+
+    {
+        let mut clock = state
+            .clock
+            .lock()
+            .map_err(|e| format!("Clock-Lock-Fehler: {}", e))?;
+        clock.apply(buffer_size, sample_rate)?;
+
+        config.sample_rate = sample_rate;
+        config.buffer_size = buffer_size;
+        if let Err(e) = state.config_manager.save_app_config(&config) {
+            // Gespeicherte und angeforderte Clock nicht auseinanderlaufen lassen
+            if let Err(e) = clock.apply(previous.0, previous.1) {
+                warn!(
+                    "Vorherige PipeWire-Clock konnte nicht wiederhergestellt werden: {}",
+                    e
+                );
+            }
+            return Err(format!("Config-Fehler: {}", e));
+        }
+    }
+
+    // Die Loopback-Nodes der Busse tragen Quantum und Rate als Latenz-Vorgabe
+    // und laufen sonst in der alten Clock weiter — neu starten, die Links
+    // stellt der Routing-Manager wieder her
+    if (buffer_size, sample_rate) != previous {
+        let buses = state
+            .buses
+            .lock()
+            .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?
+            .get_buses();
+        for bus in &buses {
+            restart_bus_output(&state, bus)?;
+        }
+    }
+
+    let report = get_latency_report(state.clone())?;
+    // Talk-Nachlauf, Fades und Delays in der tatsächlichen Graph-Rate rechnen
+    state
+        .mix_engine
+        .lock()
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?
+        .set_sample_rate(report.negotiated_rate);
+    if report.negotiated_quantum != buffer_size || report.negotiated_rate != sample_rate {
+        warn!(
+            "PipeWire hat {} Samples @ {} Hz ausgehandelt (angefordert: {} @ {})",
+            report.negotiated_quantum, report.negotiated_rate, buffer_size, sample_rate
+        );
+    }
+    Ok(report)
+}
+
+/// Latenz-Report: angeforderte vs. ausgehandelte Clock und Latenz pro Bus-Pfad
+#[tauri::command]
+fn get_latency_report(state: tauri::State<'_, AppState>) -> Result<pw::LatencyReport, String> {
+    let config = state
+        .config_manager
+        .load_app_config()
+        .map_err(|e| format!("Config-Fehler: {}", e))?;
+    let buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?
+        .get_buses();
+
+    Ok(pw::get_latency_report(
+        config.buffer_size,
+        config.sample_rate,
+        &buses,
+    ))
+}
+
 /// Gesamte Config als JSON exportieren
 #[tauri::command]
 fn export_config(state: tauri::State<'_, AppState>) -> Result<String, String> {
@@ -162,11 +305,13 @@ fn set_strip_volume(
     volume_db: f32,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut mixer = state
-        .mixer
-        .lock()
-        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
-    mixer.set_volume(&strip_id, volume_db)
+    execute(
+        &state,
+        MixerCommand::StripVolume {
+            strip_id,
+            volume_db,
+        },
+    )
 }
 
 /// Gain eines Strips setzen (in dB)
@@ -176,11 +321,7 @@ fn set_strip_gain(
     gain_db: f32,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut mixer = state
-        .mixer
-        .lock()
-        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
-    mixer.set_gain(&strip_id, gain_db)
+    execute(&state, MixerCommand::StripGain { strip_id, gain_db })
 }
 
 /// Stummschaltung eines Strips setzen
@@ -190,11 +331,7 @@ fn set_strip_mute(
     muted: bool,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut mixer = state
-        .mixer
-        .lock()
-        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
-    mixer.set_mute(&strip_id, muted)
+    execute(&state, MixerCommand::StripMute { strip_id, muted })
 }
 
 /// Solo-Modus eines Strips setzen
@@ -204,11 +341,7 @@ fn set_strip_solo(
     solo: bool,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut mixer = state
-        .mixer
-        .lock()
-        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
-    mixer.set_solo(&strip_id, solo)
+    execute(&state, MixerCommand::StripSolo { strip_id, solo })
 }
 
 /// Bus-Routing eines Strips ändern (Bus hinzufügen/entfernen)
@@ -219,274 +352,1600 @@ fn set_strip_bus(
     active: bool,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut mixer = state
-        .mixer
-        .lock()
-        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
-    mixer.set_bus_routing(&strip_id, &bus_id, active)
+    execute(
+        &state,
+        MixerCommand::StripBus {
+            strip_id,
+            bus_id,
+            active,
+        },
+    )
 }
 
-/// Neuen Virtual-Strip hinzufügen
+/// Neuen Virtual-Strip hinzufügen (ohne Namen: "VIRTUAL n")
 #[tauri::command]
-fn add_virtual_strip(state: tauri::State<'_, AppState>) -> Result<InputStrip, String> {
-    let mut mixer = state
-        .mixer
-        .lock()
-        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
-    mixer.add_virtual_strip()
+fn add_virtual_strip(
+    label: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<InputStrip, String> {
+    // Namen vorab prüfen, damit bei ungültiger Eingabe kein Strip entsteht
+    let label = label.as_deref().map(validate_label).transpose()?;
+    let strip = {
+        let mut mixer = state
+            .mixer
+            .lock()
+            .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+        let mut strip = mixer.add_virtual_strip()?;
+        if let Some(label) = label {
+            mixer.set_label(&strip.id, &label)?;
+            strip.label = label;
+        }
+        sync_mix_strips(&state, &mixer)?;
+        strip
+    };
+    // Neuer Strip routet auf den Standard-Bus — Mix-Minus-Busse nachziehen
+    sync_mix_minus(&state)?;
+    record_history(
+        &state,
+        MixerCommand::InsertStrip {
+            strip: strip.clone(),
+        },
+        MixerCommand::RemoveStrip {
+            strip_id: strip.id.clone(),
+        },
+    )?;
+    Ok(strip)
 }
 
 /// Virtual-Strip entfernen
 #[tauri::command]
 fn remove_virtual_strip(strip_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut mixer = state
-        .mixer
-        .lock()
-        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
-    mixer.remove_virtual_strip(&strip_id)
+    execute(&state, MixerCommand::RemoveStrip { strip_id })
 }
 
-// --- Bus Commands (Modul 04) ---
+/// Capture-Device an einen Strip binden (startet den Capture-Stream)
+#[tauri::command]
+fn bind_strip_device(
+    strip_id: String,
+    device_name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::StripDevice {
+            strip_id,
+            device_name: Some(device_name),
+        },
+    )
+}
 
-/// Alle Output-Busse als sortierte Liste abrufen
+/// Device-Bindung eines Strips lösen (stoppt den Capture-Stream)
 #[tauri::command]
-fn get_buses(state: tauri::State<'_, AppState>) -> Result<Vec<OutputBus>, String> {
-    let buses = state
-        .buses
-        .lock()
-        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
-    Ok(buses.get_buses())
+fn unbind_strip_device(strip_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::StripDevice {
+            strip_id,
+            device_name: None,
+        },
+    )
 }
 
-/// Lautstärke eines Bus setzen (in dB)
+/// Strip umbenennen
 #[tauri::command]
-fn set_bus_volume(
-    bus_id: String,
-    volume_db: f32,
+fn rename_strip(
+    strip_id: String,
+    label: String,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut buses = state
-        .buses
-        .lock()
-        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
-    buses.set_volume(&bus_id, volume_db)
+    execute(&state, MixerCommand::StripLabel { strip_id, label })
 }
 
-/// Stummschaltung eines Bus setzen
+/// Icon eines Strips setzen
 #[tauri::command]
-fn set_bus_mute(
-    bus_id: String,
-    muted: bool,
+fn set_strip_icon(
+    strip_id: String,
+    icon: String,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut buses = state
-        .buses
-        .lock()
-        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
-    buses.set_mute(&bus_id, muted)
+    execute(&state, MixerCommand::StripIcon { strip_id, icon })
 }
 
-// --- FX Commands (Modul 03 - Phase 1) ---
+/// Akzentfarbe eines Strips setzen ("#RRGGBB", null = Standardfarbe)
+#[tauri::command]
+fn set_strip_color(
+    strip_id: String,
+    color: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::StripColor { strip_id, color })
+}
 
-/// FX-Chain Module abrufen (Phase 1: Global, später pro strip_id)
+/// Strips neu anordnen (Liste aller Strip-IDs in neuer Reihenfolge)
 #[tauri::command]
-fn get_fx_chain(state: tauri::State<'_, AppState>) -> Result<Vec<FxModuleInfo>, String> {
-    let fx = state
-        .fx_chain
-        .lock()
-        .map_err(|e| format!("FX-Lock-Fehler: {}", e))?;
-    Ok(fx.get_all_modules())
+fn reorder_strips(strip_ids: Vec<String>, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::StripOrder { strip_ids })
 }
 
-/// FX-Parameter setzen
+/// PipeWire-Eingangs-Node an einen Strip binden
 #[tauri::command]
-fn set_fx_param(
-    module_type: FxModuleType,
-    param_name: String,
-    value: f32,
+fn bind_strip_pw_device(
+    strip_id: String,
+    device_id: u32,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut fx = state
-        .fx_chain
-        .lock()
-        .map_err(|e| format!("FX-Lock-Fehler: {}", e))?;
-    fx.set_param(module_type, &param_name, value)
+    let known = pw::list_audio_devices()?
+        .iter()
+        .any(|d| d.id == device_id && d.device_type == "input");
+    if !known {
+        return Err(format!("PipeWire-Eingang {} nicht gefunden", device_id));
+    }
+    execute(
+        &state,
+        MixerCommand::StripPwDevice {
+            strip_id,
+            device_id: Some(device_id),
+        },
+    )
 }
 
-/// FX-Bypass setzen
+/// PipeWire-Bindung eines Strips lösen
 #[tauri::command]
-fn set_fx_bypass(
-    module_type: FxModuleType,
-    bypass: bool,
+fn unbind_strip_pw_device(
+    strip_id: String,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut fx = state
-        .fx_chain
-        .lock()
-        .map_err(|e| format!("FX-Lock-Fehler: {}", e))?;
-    fx.set_bypass(module_type, bypass)
+    execute(
+        &state,
+        MixerCommand::StripPwDevice {
+            strip_id,
+            device_id: None,
+        },
+    )
 }
 
-// --- Routing Commands (Modul 06) ---
+/// Talk-Modus eines Strips setzen (Modus, Nachlauf, Fade, Bus-Auswahl)
+#[tauri::command]
+fn set_strip_talk_mode(
+    strip_id: String,
+    talk: TalkSettings,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    validate_bus_ids(&state, &talk.bus_ids)?;
+    execute(&state, MixerCommand::StripTalk { strip_id, talk })
+}
 
-/// Routing-Matrix abrufen
+/// Talk-Taste drücken (Hotkey, MIDI, API, Controller)
 #[tauri::command]
-fn get_routing_matrix(state: tauri::State<'_, AppState>) -> Result<Vec<RoutingEntry>, String> {
-    let routing = state
-        .routing
+fn talk_press(strip_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state
+        .mix_engine
         .lock()
-        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
-    Ok(routing.get_routing_matrix())
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?
+        .talk_press(&strip_id)
 }
 
-/// Routing setzen (Source → Bus Verbindung)
+/// Talk-Taste loslassen (Nachlauf und Fade laufen in der Mix-Engine)
 #[tauri::command]
-fn set_routing(
-    source_id: String,
-    bus_id: String,
-    active: bool,
-    state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
-    let mut routing = state
-        .routing
+fn talk_release(strip_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state
+        .mix_engine
         .lock()
-        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
-    routing.set_routing(&source_id, &bus_id, active)
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?
+        .talk_release(&strip_id)
 }
 
-// --- Master Commands (Modul 12) ---
-
-/// Master-State abrufen
+/// Zustand aller Talk-Gates abfragen
 #[tauri::command]
-fn get_master(state: tauri::State<'_, AppState>) -> Result<MasterState, String> {
-    let master = state
-        .master
+fn get_talk_status(state: tauri::State<'_, AppState>) -> Result<Vec<TalkStatus>, String> {
+    Ok(state
+        .mix_engine
         .lock()
-        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
-    Ok(master.get_state())
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?
+        .talk_status())
 }
 
-/// Master Volume setzen (in dB)
+/// Alle Capture-fähigen Input-Devices (CPAL) auflisten
 #[tauri::command]
-fn set_master_volume(volume_db: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut master = state
-        .master
-        .lock()
-        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
-    master.set_volume(volume_db)
+fn get_capture_devices() -> Result<Vec<String>, String> {
+    CpalCaptureManager::new()?.list_input_devices()
 }
 
-/// Master Limiter Ceiling setzen (in dB)
+/// Verbindungszustand aller gebundenen Capture-Devices abfragen
+/// (connected / reconnecting / missing — Änderungen kommen als "capture-state" Event)
 #[tauri::command]
-fn set_master_limiter(ceiling_db: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut master = state
-        .master
+fn get_capture_status(state: tauri::State<'_, AppState>) -> Result<Vec<CaptureStatus>, String> {
+    Ok(state
+        .metering
         .lock()
-        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
-    master.set_limiter(ceiling_db)
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?
+        .capture_status())
 }
 
-/// DIM-Funktion setzen
-#[tauri::command]
-fn set_dim(active: bool, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut master = state
-        .master
+/// Strip-Parameter an die Mix-Engine weitergeben (inkl. aktiver Mute-Gruppen)
+fn sync_mix_strips(state: &AppState, mixer: &MixerState) -> Result<(), String> {
+    let strips = state
+        .mute_groups
         .lock()
-        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
-    master.set_dim(active)
+        .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?
+        .apply(&mixer.get_strips());
+    state
+        .mix_engine
+        .lock()
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?
+        .update_strips(&strips);
+    Ok(())
 }
 
-/// Mono-Check setzen
-#[tauri::command]
-fn set_mono(active: bool, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut master = state
-        .master
+/// Bus-Parameter an die Mix-Engine weitergeben
+fn sync_mix_buses(state: &AppState, buses: &BusManager) -> Result<(), String> {
+    state
+        .mix_engine
         .lock()
-        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
-    master.set_mono(active)
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?
+        .update_buses(&buses.get_buses());
+    Ok(())
 }
 
-/// Talkback setzen
-#[tauri::command]
-fn set_talkback(
-    active: bool,
-    target_buses: Vec<String>,
-    state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
-    let mut master = state
-        .master
+/// Master-Sektion an die Mix-Engine weitergeben (Master-Stufe der Bus-Ausgänge)
+fn sync_mix_master(state: &AppState, master: &MasterManager) -> Result<(), String> {
+    state
+        .mix_engine
         .lock()
-        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
-    master.set_talkback(active, target_buses)
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?
+        .update_master(&master.get_state());
+    Ok(())
 }
 
-// --- Recording Commands (Modul 11) ---
+/// Node-ID eines gebundenen Ausgabe-Devices auflösen
+///
+/// Fehlt das Device (abgesteckt, PipeWire nicht erreichbar), bleibt die
+/// Bindung bestehen und der Bus spielt über die Standard-Ausgabe.
+fn resolve_output_device(bus_id: &str, device_name: &str) -> Option<u32> {
+    match pw::find_output_device(device_name) {
+        Ok(Some(id)) => Some(id),
+        Ok(None) => {
+            warn!(
+                "Ausgabe-Device '{}' für Bus {} nicht gefunden — nutze Standard-Ausgabe",
+                device_name, bus_id
+            );
+            None
+        }
+        Err(e) => {
+            warn!("Ausgabe-Device für Bus {} nicht aufgelöst: {}", bus_id, e);
+            None
+        }
+    }
+}
 
-/// Aufnahme starten
-#[tauri::command]
-fn start_recording(
-    source_id: String,
-    format: RecordingFormat,
-    state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
-    let mut recording = state
-        .recording
+/// Bus-Node mit neuem Ausgabe-Ziel neu starten und Routings wieder verbinden
+fn restart_bus_output(state: &AppState, bus: &OutputBus) -> Result<(), String> {
+    let config = state
+        .config_manager
+        .load_app_config()
+        .map_err(|e| format!("Config-Fehler: {}", e))?;
+    if let Err(e) = pw::restart_bus_node(bus, config.buffer_size, config.sample_rate) {
+        warn!("Bus-Node für {} nicht neu gestartet: {}", bus.id, e);
+    }
+    state
+        .routing
         .lock()
-        .map_err(|e| format!("Recording-Lock-Fehler: {}", e))?;
-    recording.start(&source_id, format)
+        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?
+        .relink_bus(&bus.id);
+    Ok(())
 }
 
-/// Aufnahme stoppen
-#[tauri::command]
-fn stop_recording(
-    source_id: String,
-    state: tauri::State<'_, AppState>,
-) -> Result<RecordingInfo, String> {
-    let mut recording = state
-        .recording
+/// Submix-Routings (Bus → Bus) an die Mix-Engine weitergeben
+///
+/// Jede Änderung der Routing-Matrix läuft hier durch (Commands, Undo/Redo,
+/// Szenen, Mix-Minus, Bus-Löschung) — daher meldet diese Stelle die Matrix
+/// als "routing-changed" Event ans Frontend.
+fn sync_bus_routes(state: &AppState, routing: &RoutingManager) -> Result<(), String> {
+    state
+        .mix_engine
         .lock()
-        .map_err(|e| format!("Recording-Lock-Fehler: {}", e))?;
-    recording.stop(&source_id)
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?
+        .update_bus_routes(&routing.bus_order(), &routing.submix_gains());
+    let _ = state
+        .app_handle
+        .emit("routing-changed", routing.get_routing_matrix());
+    Ok(())
 }
 
-/// Alle aktiven Aufnahmen abrufen
-#[tauri::command]
-fn get_recording_status(state: tauri::State<'_, AppState>) -> Result<Vec<ActiveRecording>, String> {
-    let recording = state
-        .recording
+/// Capture-Streams an die aktuellen Strip-Bindungen anpassen
+fn sync_capture_bindings(state: &AppState, mixer: &MixerState) -> Result<(), String> {
+    state
+        .metering
         .lock()
-        .map_err(|e| format!("Recording-Lock-Fehler: {}", e))?;
-    Ok(recording.get_active_recordings())
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?
+        .set_capture_bindings(mixer.capture_bindings());
+    Ok(())
 }
 
-// --- Scene Commands (Modul 10) ---
+// --- Mute-Gruppen Commands (Modul 02) ---
 
-/// Scene speichern (kompletter Mixer-State Snapshot)
+/// Alle Mute-Gruppen abrufen
 #[tauri::command]
-fn save_scene(
-    name: String,
-    state_json: String,
-    state: tauri::State<'_, AppState>,
-) -> Result<String, String> {
-    state.scenes.save_scene(&name, &state_json)
+fn get_mute_groups(state: tauri::State<'_, AppState>) -> Result<Vec<MuteGroup>, String> {
+    Ok(state
+        .mute_groups
+        .lock()
+        .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?
+        .get_groups())
 }
 
-/// Scene laden
+/// Mute-Gruppe anlegen (bus_ids leer = Strips auf allen Bussen stumm)
 #[tauri::command]
-fn load_scene(id: String, state: tauri::State<'_, AppState>) -> Result<Scene, String> {
-    state.scenes.load_scene(&id)
+fn create_mute_group(
+    name: String,
+    strip_ids: Vec<String>,
+    bus_ids: Vec<String>,
+    exclusive: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<MuteGroup, String> {
+    validate_mute_group_refs(&state, &strip_ids, &bus_ids)?;
+    state
+        .mute_groups
+        .lock()
+        .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?
+        .create_group(&name, strip_ids, bus_ids, exclusive)
 }
 
-/// Scene löschen
+/// Mute-Gruppe ändern (Aktiv-Status bleibt erhalten)
 #[tauri::command]
-fn delete_scene(id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    state.scenes.delete_scene(&id)
+fn update_mute_group(
+    group_id: String,
+    name: String,
+    strip_ids: Vec<String>,
+    bus_ids: Vec<String>,
+    exclusive: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    validate_mute_group_refs(&state, &strip_ids, &bus_ids)?;
+    state
+        .mute_groups
+        .lock()
+        .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?
+        .update_group(&group_id, &name, strip_ids, bus_ids, exclusive)?;
+    resync_mix_strips(&state)
+}
+
+/// Mute-Gruppe löschen
+#[tauri::command]
+fn delete_mute_group(group_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state
+        .mute_groups
+        .lock()
+        .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?
+        .delete_group(&group_id)?;
+    resync_mix_strips(&state)
+}
+
+/// Mute-Gruppe auslösen (active = null → umschalten)
+///
+/// Ein einzelner Aufruf für Hotkeys und Controller; exklusive Gruppen lösen
+/// dabei die anderen exklusiven Gruppen im selben Schritt.
+///
+/// # Returns
+/// Neuer Aktiv-Status der Gruppe
+#[tauri::command]
+fn trigger_mute_group(
+    group_id: String,
+    active: Option<bool>,
+    state: tauri::State<'_, AppState>,
+) -> Result<bool, String> {
+    let (prev, next, now_active) = {
+        let mut groups = state
+            .mute_groups
+            .lock()
+            .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?;
+        let prev = groups.active_groups();
+        let now_active = match active {
+            Some(active) => {
+                groups.set_active(&group_id, active)?;
+                active
+            }
+            None => groups.toggle(&group_id)?,
+        };
+        (prev, groups.active_groups(), now_active)
+    };
+    resync_mix_strips(&state)?;
+
+    if prev != next {
+        record_history(
+            &state,
+            MixerCommand::MuteGroups {
+                active_groups: next,
+            },
+            MixerCommand::MuteGroups {
+                active_groups: prev,
+            },
+        )?;
+    }
+    Ok(now_active)
+}
+
+/// Strip- und Bus-IDs einer Mute-Gruppe prüfen
+fn validate_mute_group_refs(
+    state: &AppState,
+    strip_ids: &[String],
+    bus_ids: &[String],
+) -> Result<(), String> {
+    {
+        let mixer = state
+            .mixer
+            .lock()
+            .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+        if let Some(id) = strip_ids.iter().find(|id| mixer.get_strip(id).is_none()) {
+            return Err(format!("Strip '{}' nicht gefunden", id));
+        }
+    }
+    validate_bus_ids(state, bus_ids)
+}
+
+/// Prüfen ob alle Bus-IDs existieren
+fn validate_bus_ids(state: &AppState, bus_ids: &[String]) -> Result<(), String> {
+    let buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+    if let Some(id) = bus_ids.iter().find(|id| buses.get_bus(id).is_none()) {
+        return Err(format!("Bus '{}' nicht gefunden", id));
+    }
+    Ok(())
+}
+
+/// Mix-Engine nach einer Mute-Gruppen-Änderung neu versorgen
+fn resync_mix_strips(state: &AppState) -> Result<(), String> {
+    let mixer = state
+        .mixer
+        .lock()
+        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+    sync_mix_strips(state, &mixer)
+}
+
+// --- Makro Commands (Modul 02) ---
+
+/// Alle Makros abrufen
+#[tauri::command]
+fn get_macros(state: tauri::State<'_, AppState>) -> Result<Vec<Macro>, String> {
+    Ok(state
+        .macros
+        .lock()
+        .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+        .get_macros())
+}
+
+/// Makro anlegen (Regler startet bei 0.0, Ziele werden nicht angefasst)
+#[tauri::command]
+fn create_macro(
+    name: String,
+    mappings: Vec<MacroMapping>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Macro, String> {
+    validate_macro_refs(&state, &mappings)?;
+    state
+        .macros
+        .lock()
+        .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+        .create_macro(&name, mappings)
+}
+
+/// Makro ändern (Reglerstellung bleibt erhalten)
+#[tauri::command]
+fn update_macro(
+    macro_id: String,
+    name: String,
+    mappings: Vec<MacroMapping>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    validate_macro_refs(&state, &mappings)?;
+    state
+        .macros
+        .lock()
+        .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+        .update_macro(&macro_id, &name, mappings)
+}
+
+/// Makro löschen (Ziel-Parameter behalten ihren Wert)
+#[tauri::command]
+fn delete_macro(macro_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state
+        .macros
+        .lock()
+        .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+        .delete_macro(&macro_id)
+}
+
+/// Makro-Regler setzen (0.0-1.0) und alle Ziele nachführen
+///
+/// Gemeinsamer Einstieg für UI, API und MIDI-Controller
+/// (CC-Werte über `macros::value_from_midi` umrechnen).
+#[tauri::command]
+fn set_macro_value(
+    macro_id: String,
+    value: f32,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::MacroValue {
+            macro_id,
+            value,
+            targets: None,
+        },
+    )
+}
+
+/// Makro-Regler per MIDI-CC setzen (0-127)
+#[tauri::command]
+fn set_macro_midi(
+    macro_id: String,
+    cc_value: u8,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let value = macros::value_from_midi(cc_value);
+    execute(
+        &state,
+        MixerCommand::MacroValue {
+            macro_id,
+            value,
+            targets: None,
+        },
+    )
+}
+
+/// Prüfen ob alle Makro-Ziele existieren (Strips, Busse, FX-Parameter)
+fn validate_macro_refs(state: &AppState, mappings: &[MacroMapping]) -> Result<(), String> {
+    let mut strip_ids = Vec::new();
+    let mut bus_ids = Vec::new();
+    for mapping in mappings {
+        match &mapping.target {
+            MacroTarget::StripVolume { strip_id } => strip_ids.push(strip_id.clone()),
+            MacroTarget::BusSend { bus_id } => bus_ids.push(bus_id.clone()),
+            MacroTarget::FxParam {
+                module_type,
+                param_name,
+            } => {
+                let known = state
+                    .fx_chain
+                    .lock()
+                    .map_err(|e| format!("FX-Lock-Fehler: {}", e))?
+                    .get_module_info(*module_type)
+                    .is_some_and(|info| info.params.iter().any(|(name, _)| name == param_name));
+                if !known {
+                    return Err(format!(
+                        "FX-Parameter '{}' in {:?} nicht gefunden",
+                        param_name, module_type
+                    ));
+                }
+            }
+            MacroTarget::VoiceFxDryWet | MacroTarget::DuckingAmount => {}
+        }
+    }
+    validate_mute_group_refs(state, &strip_ids, &bus_ids)
+}
+
+// --- Bus Commands (Modul 04) ---
+
+/// Alle Output-Busse als sortierte Liste abrufen
+#[tauri::command]
+fn get_buses(state: tauri::State<'_, AppState>) -> Result<Vec<OutputBus>, String> {
+    let buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+    Ok(buses.get_buses())
+}
+
+/// Lautstärke eines Bus setzen (in dB)
+#[tauri::command]
+fn set_bus_volume(
+    bus_id: String,
+    volume_db: f32,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::BusVolume { bus_id, volume_db })
+}
+
+/// Stummschaltung eines Bus setzen
+#[tauri::command]
+fn set_bus_mute(
+    bus_id: String,
+    muted: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::BusMute { bus_id, muted })
+}
+
+/// Mix-Minus eines Bus setzen (None = Routing wieder manuell)
+///
+/// Der Bus übernimmt danach das Routing des Referenz-Busses ohne die
+/// ausgeschlossenen Quellen (z.B. B2 = B1 ohne "app-discord").
+#[tauri::command]
+fn set_bus_mix_minus(
+    bus_id: String,
+    mix_minus: Option<MixMinus>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::BusMixMinus { bus_id, mix_minus })
+}
+
+/// Ausgabe-Device an einen physischen Bus binden (PipeWire node.name)
+#[tauri::command]
+fn bind_bus_device(
+    bus_id: String,
+    device_name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::BusDevice {
+            bus_id,
+            device_name: Some(device_name),
+        },
+    )
+}
+
+/// Device-Bindung eines Bus lösen (Standard-Ausgabe)
+#[tauri::command]
+fn unbind_bus_device(bus_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::BusDevice {
+            bus_id,
+            device_name: None,
+        },
+    )
+}
+
+/// Gebundene Ausgabe-Devices neu auflösen (z.B. nach Anstecken eines Headsets)
+///
+/// Busse deren Device wieder da ist, werden neu gestartet und auf das Device
+/// gelegt; fehlende Devices bleiben auf der Standard-Ausgabe.
+#[tauri::command]
+fn refresh_bus_devices(state: tauri::State<'_, AppState>) -> Result<Vec<OutputBus>, String> {
+    let (changed, all) = {
+        let mut buses = state
+            .buses
+            .lock()
+            .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+        let mut changed = Vec::new();
+        for (bus_id, device_name) in buses.output_bindings() {
+            let device_id = resolve_output_device(&bus_id, &device_name);
+            if bus_snapshot(&buses, &bus_id)?.device_id != device_id {
+                buses.set_pw_device(&bus_id, device_id)?;
+                changed.push(bus_snapshot(&buses, &bus_id)?);
+            }
+        }
+        (changed, buses.get_buses())
+    };
+    for bus in changed.iter().filter(|b| b.device_id.is_some()) {
+        restart_bus_output(&state, bus)?;
+    }
+    Ok(all)
+}
+
+/// Ausgangs-Delay eines Bus setzen (0-1000 ms, z.B. Ton auf das Webcam-Bild)
+#[tauri::command]
+fn set_bus_delay(
+    bus_id: String,
+    delay_ms: f32,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::BusDelay { bus_id, delay_ms })
+}
+
+/// Herkunft der Bus-Monitor-Taps setzen ("engine" oder "pipewire")
+///
+/// "engine" ohne PipeWire (Taps direkt aus der Mix-Engine), "pipewire" wenn
+/// die Busse über ihre PipeWire-Nodes laufen (Mitschnitt der Monitor-Ports,
+/// die Mix-Engine spielt die Busse dann in die Bus-Nodes).
+#[tauri::command]
+fn set_bus_tap_source(
+    source: BusTapSource,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .metering
+        .lock()
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?
+        .set_bus_tap_source(source);
+    Ok(())
+}
+
+/// Herkunft der Bus-Monitor-Taps abfragen
+#[tauri::command]
+fn get_bus_tap_source(state: tauri::State<'_, AppState>) -> Result<BusTapSource, String> {
+    Ok(state
+        .metering
+        .lock()
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?
+        .bus_tap_source())
+}
+
+/// Neuen Bus anlegen (ID wird vergeben: A<n> physisch, B<n> virtuell)
+///
+/// Legt den PipeWire-Node "inoX-Bus-<ID>" mit der gewählten Kanalzahl an.
+#[tauri::command]
+fn create_bus(
+    name: String,
+    bus_type: BusType,
+    channels: u16,
+    state: tauri::State<'_, AppState>,
+) -> Result<OutputBus, String> {
+    // Die ID vergibt das Kommando — der neue Bus ist der bisher unbekannte
+    let known = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?
+        .bus_ids();
+    execute(
+        &state,
+        MixerCommand::CreateBus {
+            name,
+            bus_type,
+            channels,
+        },
+    )?;
+    let buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+    buses
+        .get_buses()
+        .into_iter()
+        .find(|bus| !known.contains(&bus.id))
+        .ok_or_else(|| "Neuer Bus nicht gefunden".to_string())
+}
+
+/// Bus umbenennen (ID, Routing und PipeWire-Node bleiben)
+#[tauri::command]
+fn rename_bus(
+    bus_id: String,
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::RenameBus { bus_id, name })
+}
+
+/// Bus löschen (Undo legt ihn samt getrennter Zuweisungen wieder an)
+///
+/// Strip-Zuweisungen, Routings und Talkback-Ziele auf den Bus fallen weg.
+/// Solange Mute-Gruppen, Makros, Talk-Einstellungen, STT oder
+/// Soundboard-Sounds den Bus nutzen, wird das Löschen abgelehnt.
+#[tauri::command]
+fn delete_bus(bus_id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::RemoveBus { bus_id })
+}
+
+/// Bus-Registry an Mixer, Routing, Master und Mix-Engine weitergeben
+fn sync_bus_registry(state: &AppState, buses: &BusManager) -> Result<(), String> {
+    let bus_ids = buses.bus_ids();
+    {
+        let mut mixer = state
+            .mixer
+            .lock()
+            .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+        mixer.set_bus_ids(&bus_ids);
+        sync_mix_strips(state, &mixer)?;
+    }
+    {
+        let mut master = state
+            .master
+            .lock()
+            .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
+        master.set_bus_ids(&bus_ids);
+        sync_mix_master(state, &master)?;
+    }
+    sync_mix_buses(state, buses)?;
+
+    let mut routing = state
+        .routing
+        .lock()
+        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
+    routing.set_bus_ids(&bus_ids);
+    sync_bus_routes(state, &routing)
+}
+
+/// Wer nutzt einen Bus noch? (Mix-Minus, Mute-Gruppen, Makros, Talk, STT, Soundboard)
+///
+/// Der Aufrufer hält den Bus-Lock bis zum Löschen.
+fn bus_users(state: &AppState, buses: &BusManager, bus_id: &str) -> Result<Vec<String>, String> {
+    let mut users = Vec::new();
+    for (minus_id, mm) in buses.mix_minus_buses() {
+        if mm.reference_bus == bus_id {
+            users.push(format!("Mix-Minus {}", minus_id));
+        }
+    }
+    for group in state
+        .mute_groups
+        .lock()
+        .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?
+        .get_groups()
+    {
+        if group.bus_ids.iter().any(|id| id == bus_id) {
+            users.push(format!("Mute-Gruppe '{}'", group.name));
+        }
+    }
+    for m in state
+        .macros
+        .lock()
+        .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+        .get_macros()
+    {
+        let uses_bus = m.mappings.iter().any(
+            |mapping| matches!(&mapping.target, MacroTarget::BusSend { bus_id: id } if id == bus_id),
+        );
+        if uses_bus {
+            users.push(format!("Makro '{}'", m.name));
+        }
+    }
+    for strip in state
+        .mixer
+        .lock()
+        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?
+        .get_strips()
+    {
+        if strip.talk.bus_ids.iter().any(|id| id == bus_id) {
+            users.push(format!("Talk '{}'", strip.label));
+        }
+    }
+    for sound in state
+        .soundboard
+        .lock()
+        .map_err(|e| format!("Soundboard-Lock-Fehler: {}", e))?
+        .get_sounds()?
+    {
+        if sound.bus_id == bus_id {
+            users.push(format!("Sound '{}'", sound.name));
+        }
+    }
+    let stt = state
+        .stt
+        .lock()
+        .map_err(|e| format!("STT-Lock-Fehler: {}", e))?;
+    if stt.source_id() == Some(bus_id) {
+        users.push("STT".to_string());
+    }
+    Ok(users)
+}
+
+// --- FX Commands (Modul 03 - Phase 1) ---
+
+/// FX-Chain Module abrufen (Phase 1: Global, später pro strip_id)
+#[tauri::command]
+fn get_fx_chain(state: tauri::State<'_, AppState>) -> Result<Vec<FxModuleInfo>, String> {
+    let fx = state
+        .fx_chain
+        .lock()
+        .map_err(|e| format!("FX-Lock-Fehler: {}", e))?;
+    Ok(fx.get_all_modules())
+}
+
+/// FX-Parameter setzen
+#[tauri::command]
+fn set_fx_param(
+    module_type: FxModuleType,
+    param_name: String,
+    value: f32,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::FxParam {
+            module_type,
+            param_name,
+            value,
+        },
+    )
+}
+
+/// FX-Bypass setzen
+#[tauri::command]
+fn set_fx_bypass(
+    module_type: FxModuleType,
+    bypass: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::FxBypass {
+            module_type,
+            bypass,
+        },
+    )
+}
+
+/// Insert-Chain eines Output-Busses abrufen
+#[tauri::command]
+fn get_bus_fx_chain(
+    bus_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<FxModuleInfo>, String> {
+    let buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+    Ok(bus_snapshot(&buses, &bus_id)?.inserts)
+}
+
+/// Insert-Parameter eines Output-Busses setzen
+#[tauri::command]
+fn set_bus_fx_param(
+    bus_id: String,
+    module_type: FxModuleType,
+    param_name: String,
+    value: f32,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::BusFxParam {
+            bus_id,
+            module_type,
+            param_name,
+            value,
+        },
+    )
+}
+
+/// Insert-Bypass eines Output-Busses setzen
+#[tauri::command]
+fn set_bus_fx_bypass(
+    bus_id: String,
+    module_type: FxModuleType,
+    bypass: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::BusFxBypass {
+            bus_id,
+            module_type,
+            bypass,
+        },
+    )
+}
+
+// --- Routing Commands (Modul 06) ---
+
+/// Routing-Matrix abrufen
+#[tauri::command]
+fn get_routing_matrix(state: tauri::State<'_, AppState>) -> Result<Vec<RoutingEntry>, String> {
+    let routing = state
+        .routing
+        .lock()
+        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
+    Ok(routing.get_routing_matrix())
+}
+
+/// Routing setzen (Source → Bus Verbindung)
+///
+/// Als Source ist auch eine Bus-ID erlaubt (Submix) — Rückkopplungen werden
+/// abgelehnt.
+#[tauri::command]
+fn set_routing(
+    source_id: String,
+    bus_id: String,
+    active: bool,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::Routing {
+            source_id,
+            bus_id,
+            active,
+        },
+    )
+}
+
+/// Mehrere Kreuzpunkte in einem Schritt setzen (aktiv + Gain)
+///
+/// Alle oder keiner: ein ungültiger Eintrag lehnt den ganzen Satz ab.
+/// Ein Undo-Schritt für den ganzen Satz (z.B. Drag über die Matrix).
+#[tauri::command]
+fn set_crosspoints(
+    entries: Vec<RoutingEntry>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::Crosspoints { entries })
+}
+
+/// Aktuelle Routing-Matrix als Template-JSON exportieren
+#[tauri::command]
+fn export_routing_template(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let mut entries = state
+        .routing
+        .lock()
+        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?
+        .get_routing_matrix();
+    entries.sort_by(|a, b| (&a.source_id, &a.bus_id).cmp(&(&b.source_id, &b.bus_id)));
+    RoutingTemplate {
+        version: ROUTING_TEMPLATE_VERSION,
+        name,
+        entries,
+    }
+    .to_json()
+}
+
+/// Routing-Template importieren (ersetzt die Routing-Matrix, ein Undo-Schritt)
+///
+/// Mix-Minus-Busse bleiben außen vor — ihr Routing wird danach aus dem
+/// neuen Stand abgeleitet. Unbekannte Busse lehnen den Import ab.
+#[tauri::command]
+fn import_routing_template(
+    template_json: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let template = RoutingTemplate::from_json(&template_json)?;
+    let mix_minus: Vec<String> = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?
+        .mix_minus_buses()
+        .into_iter()
+        .map(|(bus_id, _)| bus_id)
+        .collect();
+    let mut entries = template.entries;
+    entries.retain(|e| !mix_minus.contains(&e.bus_id));
+    let mut changes = state
+        .routing
+        .lock()
+        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?
+        .changes_to(&entries);
+    changes.retain(|e| !mix_minus.contains(&e.bus_id));
+
+    info!(
+        "Routing-Template '{}' importiert ({} Änderungen)",
+        template.name,
+        changes.len()
+    );
+    if !changes.is_empty() {
+        execute(&state, MixerCommand::Crosspoints { entries: changes })?;
+    }
+    Ok(())
+}
+
+// --- Master Commands (Modul 12) ---
+
+/// Master-State abrufen
+#[tauri::command]
+fn get_master(state: tauri::State<'_, AppState>) -> Result<MasterState, String> {
+    let master = state
+        .master
+        .lock()
+        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
+    Ok(master.get_state())
+}
+
+/// Master Volume setzen (in dB)
+#[tauri::command]
+fn set_master_volume(volume_db: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::MasterVolume { volume_db })
+}
+
+/// Master Limiter Ceiling setzen (in dB)
+#[tauri::command]
+fn set_master_limiter(ceiling_db: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::MasterLimiter { ceiling_db })
+}
+
+/// DIM-Funktion setzen
+#[tauri::command]
+fn set_dim(active: bool, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::MasterDim { active })
+}
+
+/// Mono-Check setzen
+#[tauri::command]
+fn set_mono(active: bool, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::MasterMono { active })
+}
+
+/// Talkback setzen
+#[tauri::command]
+fn set_talkback(
+    active: bool,
+    target_buses: Vec<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(
+        &state,
+        MixerCommand::MasterTalkback {
+            active,
+            target_buses,
+        },
+    )
+}
+
+/// Talkback-Einstellungen setzen (Latch/Momentary, Quelle, Auto-DIM, Auto-Release)
+#[tauri::command]
+fn set_talkback_settings(
+    settings: TalkbackSettings,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::MasterTalkbackSettings { settings })
+}
+
+/// Talkback-Taste drücken (Latch: umschalten, Momentary: einschalten)
+///
+/// Wie die Talk-Tasten der Strips nicht im Undo-Journal.
+#[tauri::command]
+fn talkback_press(state: tauri::State<'_, AppState>) -> Result<MasterState, String> {
+    talkback_button(&state, MasterManager::talkback_on_press)
+}
+
+/// Talkback-Taste loslassen (nur Momentary schaltet aus)
+#[tauri::command]
+fn talkback_release(state: tauri::State<'_, AppState>) -> Result<MasterState, String> {
+    talkback_button(&state, MasterManager::talkback_on_release)
+}
+
+/// Talkback-Taste auswerten und den neuen Master-State zurückgeben
+fn talkback_button(
+    state: &AppState,
+    next: fn(&MasterManager) -> Option<bool>,
+) -> Result<MasterState, String> {
+    let (active, target_buses) = {
+        let master = state
+            .master
+            .lock()
+            .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
+        (next(&master), master.get_state().talkback_buses)
+    };
+    if let Some(active) = active {
+        apply_mixer_command(
+            state,
+            &MixerCommand::MasterTalkback {
+                active,
+                target_buses,
+            },
+        )?;
+    }
+    Ok(state
+        .master
+        .lock()
+        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?
+        .get_state())
+}
+
+// --- Signal-Generator Commands ---
+
+/// Signal-Generator auf einen Virtual-Strip starten
+///
+/// Ersetzt einen laufenden Generator; das Timeout beginnt neu. Wie Talkback
+/// nicht im Undo-Journal. Hörbar ist der Testton im PipeWire-Modus über den
+/// Bus-Ausgang der Mix-Engine.
+#[tauri::command]
+fn start_generator(
+    settings: GeneratorSettings,
+    state: tauri::State<'_, AppState>,
+) -> Result<GeneratorStatus, String> {
+    // Mixer gesperrt lassen — der Strip darf bis zum Start nicht verschwinden
+    let mixer = state
+        .mixer
+        .lock()
+        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+    if strip_snapshot(&mixer, &settings.target_strip)?.strip_type != StripType::Virtual {
+        return Err("Der Generator kann nur Virtual-Strips bespielen".to_string());
+    }
+    let mut generator = state
+        .generator
+        .lock()
+        .map_err(|e| format!("Generator-Lock-Fehler: {}", e))?;
+    generator.start(settings, std::time::Instant::now())?;
+    sync_generator(&state, &generator)?;
+    info!("Signal-Generator gestartet");
+    Ok(generator.status(std::time::Instant::now()))
+}
+
+/// Signal-Generator stoppen
+#[tauri::command]
+fn stop_generator(state: tauri::State<'_, AppState>) -> Result<GeneratorStatus, String> {
+    let mut generator = state
+        .generator
+        .lock()
+        .map_err(|e| format!("Generator-Lock-Fehler: {}", e))?;
+    if generator.stop() {
+        sync_generator(&state, &generator)?;
+        info!("Signal-Generator gestoppt");
+    }
+    Ok(generator.status(std::time::Instant::now()))
+}
+
+/// Zustand des Signal-Generators abrufen
+#[tauri::command]
+fn get_generator_status(state: tauri::State<'_, AppState>) -> Result<GeneratorStatus, String> {
+    Ok(state
+        .generator
+        .lock()
+        .map_err(|e| format!("Generator-Lock-Fehler: {}", e))?
+        .status(std::time::Instant::now()))
+}
+
+/// Laufenden Generator an den Capture-Thread weitergeben
+fn sync_generator(state: &AppState, generator: &GeneratorManager) -> Result<(), String> {
+    state
+        .metering
+        .lock()
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?
+        .set_generator(generator.active().cloned());
+    Ok(())
+}
+
+// --- Recording Commands (Modul 11) ---
+
+/// Aufnahme starten
+#[tauri::command]
+fn start_recording(
+    source_id: String,
+    format: RecordingFormat,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let mut recording = state
+        .recording
+        .lock()
+        .map_err(|e| format!("Recording-Lock-Fehler: {}", e))?;
+    recording.start(&source_id, format)?;
+
+    // Eigenen Recording-Tap der Quelle anschließen
+    let hub = state
+        .metering
+        .lock()
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?
+        .tap_hub();
+    let tap = hub
+        .lock()
+        .map_err(|e| format!("Tap-Lock-Fehler: {}", e))?
+        .subscribe(&source_id, TapKind::Recording);
+    recording.attach_tap(tap)?;
+
+    // Bus-Aufnahme im Bus-State vermerken
+    let mut buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+    if buses.get_bus(&source_id).is_some() {
+        buses.set_recording(&source_id, true)?;
+    }
+    Ok(())
+}
+
+/// Aufnahme stoppen
+#[tauri::command]
+fn stop_recording(
+    source_id: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<RecordingInfo, String> {
+    let mut recording = state
+        .recording
+        .lock()
+        .map_err(|e| format!("Recording-Lock-Fehler: {}", e))?;
+
+    let hub = state
+        .metering
+        .lock()
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?
+        .tap_hub();
+    hub.lock()
+        .map_err(|e| format!("Tap-Lock-Fehler: {}", e))?
+        .unsubscribe(&source_id, TapKind::Recording);
+
+    let mut buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+    if buses.get_bus(&source_id).is_some() {
+        buses.set_recording(&source_id, false)?;
+    }
+
+    recording.stop(&source_id)
+}
+
+/// Alle aktiven Aufnahmen abrufen
+#[tauri::command]
+fn get_recording_status(state: tauri::State<'_, AppState>) -> Result<Vec<ActiveRecording>, String> {
+    let recording = state
+        .recording
+        .lock()
+        .map_err(|e| format!("Recording-Lock-Fehler: {}", e))?;
+    Ok(recording.get_active_recordings())
+}
+
+// --- Audio-Puffer Commands ---
+
+/// Overflow-Statistik der Capture-Ringe und aller Consumer-Taps
+#[tauri::command]
+fn get_audio_buffer_stats(state: tauri::State<'_, AppState>) -> Result<serde_json::Value, String> {
+    let metering = state
+        .metering
+        .lock()
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?;
+    let taps = metering
+        .tap_hub()
+        .lock()
+        .map_err(|e| format!("Tap-Lock-Fehler: {}", e))?
+        .stats();
+
+    Ok(serde_json::json!({
+        "capture": metering.capture_stats(),
+        "taps": taps,
+    }))
+}
+
+// --- Scene Commands (Modul 10) ---
+
+/// Scene speichern (kompletter Mixer-State Snapshot)
+#[tauri::command]
+fn save_scene(
+    name: String,
+    state_json: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    state.scenes.save_scene(&name, &state_json)
+}
+
+/// Scene laden
+#[tauri::command]
+fn load_scene(id: String, state: tauri::State<'_, AppState>) -> Result<Scene, String> {
+    state.scenes.load_scene(&id)
+}
+
+/// Scene löschen (zugeordnete Automation-Clips werden gelöst)
+#[tauri::command]
+fn delete_scene(id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state.scenes.delete_scene(&id)?;
+    state.automation_clips.detach_scene(&id).map(|_| ())
 }
 
 /// Alle Scenes auflisten
 #[tauri::command]
-fn get_scenes(state: tauri::State<'_, AppState>) -> Result<Vec<SceneInfo>, String> {
-    state.scenes.list_scenes()
+fn get_scenes(state: tauri::State<'_, AppState>) -> Result<Vec<SceneInfo>, String> {
+    state.scenes.list_scenes()
+}
+
+/// Aktuellen Mixer-Stand als Scene speichern (inkl. Scope-Maske)
+#[tauri::command]
+fn capture_scene(
+    name: String,
+    scope: Option<SceneScope>,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let snapshot = SceneSnapshot {
+        state: session_snapshot(&state)?,
+        levels: scene_levels(&state)?,
+        scope: scope.unwrap_or_default(),
+    };
+    state.scenes.save_scene(&name, &snapshot.to_json()?)
+}
+
+/// Gespeicherte Scope-Maske einer Scene ändern
+#[tauri::command]
+fn set_scene_scope(
+    id: String,
+    scope: SceneScope,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let scene = state.scenes.load_scene(&id)?;
+    let mut snapshot = SceneSnapshot::from_json(&scene.state_json)?;
+    snapshot.scope = scope;
+    state.scenes.update_state(&id, &snapshot.to_json()?)
+}
+
+/// Scene abrufen — nur die Bereiche der Scope-Maske, Safes bleiben unberührt
+///
+/// Ohne `scope` gilt die in der Scene gespeicherte Maske. Mit `transition`
+/// wird über die angegebene Zeit übergeblendet (Fortschritt als
+/// "scene-transition" Event), eine laufende Überblendung wird ersetzt.
+#[tauri::command]
+fn recall_scene(
+    id: String,
+    scope: Option<SceneScope>,
+    transition: Option<TransitionSettings>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let transition = transition.unwrap_or_default();
+    transition.validate()?;
+
+    let scene = state.scenes.load_scene(&id)?;
+    let snapshot = SceneSnapshot::from_json(&scene.state_json)?;
+    let scope = scope.unwrap_or(snapshot.scope);
+    let current = session_snapshot(&state)?;
+    let current_levels = scene_levels(&state)?;
+    let target = scope.merge(&current, &snapshot.state);
+    let target_levels = current_levels.merge(&snapshot.levels, &scope);
+
+    if transition.duration_ms == 0 {
+        state
+            .scene_transition
+            .lock()
+            .map_err(|e| format!("Transition-Lock-Fehler: {}", e))?
+            .take();
+        execute(
+            &state,
+            MixerCommand::SceneState {
+                scene_name: scene.name,
+                state: Box::new(target),
+                levels: target_levels,
+            },
+        )?;
+        return start_scene_automation(&state, &id);
+    }
+
+    *state
+        .scene_transition
+        .lock()
+        .map_err(|e| format!("Transition-Lock-Fehler: {}", e))? = Some(SceneTransition::new(
+        &scene.name,
+        (current, current_levels),
+        (target, target_levels),
+        scope,
+        transition,
+        std::time::Instant::now(),
+    ));
+    info!("Szenen-Überblendung gestartet: {}", scene.name);
+    start_scene_automation(&state, &id)
+}
+
+/// Laufende Szenen-Überblendung am aktuellen Punkt anhalten
+#[tauri::command]
+fn cancel_scene_transition(state: tauri::State<'_, AppState>) -> Result<bool, String> {
+    let transition = state
+        .scene_transition
+        .lock()
+        .map_err(|e| format!("Transition-Lock-Fehler: {}", e))?
+        .take();
+    match transition {
+        Some(transition) => {
+            finish_scene_transition(&state, &transition, session_snapshot(&state)?)?;
+            info!(
+                "Szenen-Überblendung abgebrochen: {}",
+                transition.scene_name()
+            );
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+// --- Automation Commands (Modul 10) ---
+
+/// Alle Automation-Clips auflisten
+#[tauri::command]
+fn get_automation_clips(
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<AutomationClipInfo>, String> {
+    state.automation_clips.list_clips()
+}
+
+/// Neuen, leeren Automation-Clip anlegen und laden
+#[tauri::command]
+fn create_automation_clip(
+    name: String,
+    state: tauri::State<'_, AppState>,
+) -> Result<String, String> {
+    let clip = state.automation_clips.create_clip(name.trim())?;
+    let id = clip.id.clone();
+    stop_automation(&state)?;
+    with_automation(&state, |automation, _| automation.load(clip))?;
+    Ok(id)
+}
+
+/// Automation-Clip laden (laufende Aufnahme wird vorher gespeichert)
+#[tauri::command]
+fn load_automation_clip(id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    let clip = state.automation_clips.load_clip(&id)?;
+    stop_automation(&state)?;
+    with_automation(&state, |automation, _| automation.load(clip))
+}
+
+/// Automation-Clip löschen
+#[tauri::command]
+fn delete_automation_clip(id: String, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    state.automation_clips.delete_clip(&id)?;
+    with_automation(&state, |automation, _| {
+        if automation.clip().map(|c| c.id.as_str()) == Some(id.as_str()) {
+            automation.unload();
+        }
+    })
+}
+
+/// Automation-Clip einer Scene zuordnen (startet beim Scene-Recall)
+#[tauri::command]
+fn attach_automation_clip(
+    id: String,
+    scene_id: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(scene_id) = &scene_id {
+        state.scenes.load_scene(scene_id)?;
+    }
+    state.automation_clips.attach_clip(&id, scene_id.as_deref())
+}
+
+/// Automation-Modus setzen (read/write/touch/latch)
+#[tauri::command]
+fn set_automation_mode(
+    mode: AutomationMode,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    with_automation(&state, |automation, now| automation.set_mode(mode, now))
+}
+
+/// Automation-Transport starten (optional mit Clip, z.B. per Hotkey)
+#[tauri::command]
+fn automation_play(
+    clip_id: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(clip_id) = clip_id {
+        let clip = state.automation_clips.load_clip(&clip_id)?;
+        stop_automation(&state)?;
+        with_automation(&state, |automation, _| automation.load(clip))?;
+    }
+    with_automation(&state, |automation, now| automation.play(now))?
+}
+
+/// Automation-Transport stoppen (Aufnahmen werden gespeichert)
+#[tauri::command]
+fn automation_stop(state: tauri::State<'_, AppState>) -> Result<(), String> {
+    stop_automation(&state)
+}
+
+/// Automation-Position setzen (Timeline, in Frames bei 48 kHz)
+#[tauri::command]
+fn automation_locate(frame: u64, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    with_automation(&state, |automation, now| automation.locate(frame, now))
+}
+
+/// Automation-Zustand abfragen
+#[tauri::command]
+fn get_automation_status(state: tauri::State<'_, AppState>) -> Result<AutomationStatus, String> {
+    with_automation(&state, |automation, now| automation.status(now))
+}
+
+/// Automation stoppen und aufgenommene Lanes speichern
+fn stop_automation(state: &AppState) -> Result<(), String> {
+    let recorded = with_automation(state, |automation, now| automation.stop(now))?;
+    match recorded {
+        Some(clip) => state.automation_clips.save_clip(&clip),
+        None => Ok(()),
+    }
+}
+
+/// Der Scene zugeordneten Automation-Clip von vorne starten
+fn start_scene_automation(state: &AppState, scene_id: &str) -> Result<(), String> {
+    let clip = match state.automation_clips.clip_for_scene(scene_id)? {
+        Some(clip) => clip,
+        None => return Ok(()),
+    };
+    stop_automation(state)?;
+    info!("Automation-Clip '{}' für Scene gestartet", clip.name);
+    with_automation(state, |automation, now| {
+        automation.load(clip);
+        automation.play(now)
+    })?
+}
+
+/// Automation-Transport der Mix-Engine bearbeiten
+///
+/// `now` ist die Engine-Position (verarbeitete Frames) — Transport und
+/// Aufnahme laufen im Sample-Takt der Engine.
+fn with_automation<T>(
+    state: &AppState,
+    f: impl FnOnce(&mut AutomationEngine, u64) -> T,
+) -> Result<T, String> {
+    let mut mix = state
+        .mix_engine
+        .lock()
+        .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?;
+    let now = mix.frames_processed();
+    Ok(f(mix.automation_mut(), now))
 }
 
 // --- Soundboard Commands (Modul 13) ---
@@ -520,11 +1979,27 @@ fn add_sound(
     bus_id: Option<String>,
     state: tauri::State<'_, AppState>,
 ) -> Result<String, String> {
+    // Ohne Angabe: erster virtueller Bus (Stream), sonst der erste Bus
+    let bus_id = {
+        let buses = state
+            .buses
+            .lock()
+            .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+        match bus_id {
+            Some(id) if buses.get_bus(&id).is_some() => id,
+            Some(id) => return Err(format!("Bus '{}' nicht gefunden", id)),
+            None => buses
+                .first_of_type(BusType::Virtual)
+                .or_else(|| buses.get_buses().into_iter().next())
+                .map(|b| b.id)
+                .ok_or_else(|| "Keine Busse vorhanden".to_string())?,
+        }
+    };
     let soundboard = state
         .soundboard
         .lock()
         .map_err(|e| format!("Soundboard-Lock-Fehler: {}", e))?;
-    soundboard.add_sound(&name, &file_path, hotkey, bus_id)
+    soundboard.add_sound(&name, &file_path, hotkey, &bus_id)
 }
 
 /// Sound entfernen
@@ -590,103 +2065,1240 @@ fn set_voice_fx_preset(
     preset: VoiceFxPreset,
     state: tauri::State<'_, AppState>,
 ) -> Result<(), String> {
-    let mut voice_fx = state
-        .voice_fx
-        .lock()
-        .map_err(|e| format!("VoiceFX-Lock-Fehler: {}", e))?;
-    voice_fx.set_preset(preset);
-    Ok(())
+    execute(&state, MixerCommand::VoiceFxPreset { preset })
 }
 
 /// Voice FX aktivieren/deaktivieren
 #[tauri::command]
 fn set_voice_fx_enabled(enabled: bool, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut voice_fx = state
+    execute(&state, MixerCommand::VoiceFxEnabled { enabled })
+}
+
+/// Voice FX Dry/Wet Mix setzen (0.0-1.0)
+#[tauri::command]
+fn set_voice_fx_drywet(dry_wet: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::VoiceFxDryWet { dry_wet })
+}
+
+// --- Ducking Commands (Modul 07) ---
+
+/// Ducking State abrufen
+#[tauri::command]
+fn get_ducking_state(state: tauri::State<'_, AppState>) -> Result<DuckingParams, String> {
+    let ducking = state
+        .ducking
+        .lock()
+        .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?;
+    Ok(ducking.params.clone())
+}
+
+/// Ducking aktivieren/deaktivieren
+#[tauri::command]
+fn set_ducking_enabled(enabled: bool, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::DuckingEnabled { enabled })
+}
+
+/// Ducking Amount setzen (in dB, -30 bis 0)
+#[tauri::command]
+fn set_ducking_amount(amount_db: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::DuckingAmount { amount_db })
+}
+
+/// Ducking Attack setzen (in ms, 10-500)
+#[tauri::command]
+fn set_ducking_attack(attack_ms: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::DuckingAttack { attack_ms })
+}
+
+/// Ducking Release setzen (in ms, 50-2000)
+#[tauri::command]
+fn set_ducking_release(release_ms: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
+    execute(&state, MixerCommand::DuckingRelease { release_ms })
+}
+
+/// Ducking Threshold setzen (in dB, -50 bis 0)
+#[tauri::command]
+fn set_ducking_threshold(
+    threshold_db: f32,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    execute(&state, MixerCommand::DuckingThreshold { threshold_db })
+}
+
+// --- History Commands (Undo/Redo) ---
+
+/// Letzte Mixer-Änderung rückgängig machen
+///
+/// Gibt das ausgeführte inverse Kommando zurück (None wenn das Journal leer ist),
+/// damit das Frontend den betroffenen Bereich neu laden kann.
+#[tauri::command]
+fn undo(state: tauri::State<'_, AppState>) -> Result<Option<MixerCommand>, String> {
+    let mut history = state
+        .history
+        .lock()
+        .map_err(|e| format!("History-Lock-Fehler: {}", e))?;
+    history.undo(|command| apply_mixer_command(&state, command).map(|_| ()))
+}
+
+/// Zuletzt rückgängig gemachte Änderung wiederholen
+#[tauri::command]
+fn redo(state: tauri::State<'_, AppState>) -> Result<Option<MixerCommand>, String> {
+    let mut history = state
+        .history
+        .lock()
+        .map_err(|e| format!("History-Lock-Fehler: {}", e))?;
+    history.redo(|command| apply_mixer_command(&state, command).map(|_| ()))
+}
+
+/// Undo/Redo-Stapel für die Anzeige abrufen
+#[tauri::command]
+fn get_history(state: tauri::State<'_, AppState>) -> Result<HistoryInfo, String> {
+    Ok(state
+        .history
+        .lock()
+        .map_err(|e| format!("History-Lock-Fehler: {}", e))?
+        .info())
+}
+
+/// Mixer-Kommando ausführen und im Journal aufzeichnen
+///
+/// Änderungen ohne Wirkung (Inverse = Kommando) landen nicht im Journal.
+fn execute(state: &AppState, command: MixerCommand) -> Result<(), String> {
+    let inverse = apply_mixer_command(state, &command)?;
+    with_automation(state, |automation, now| automation.record(&command, now))?;
+    match inverse {
+        Some(inverse) if inverse != command => record_history(state, command, inverse),
+        _ => Ok(()),
+    }
+}
+
+/// Bereits ausgeführtes Kommando samt Inverse im Journal aufzeichnen
+fn record_history(
+    state: &AppState,
+    command: MixerCommand,
+    inverse: MixerCommand,
+) -> Result<(), String> {
+    state
+        .history
+        .lock()
+        .map_err(|e| format!("History-Lock-Fehler: {}", e))?
+        .record(command, inverse, std::time::Instant::now());
+    Ok(())
+}
+
+/// Mixer-Kommando auf die Manager anwenden
+///
+/// Liest vorher den aktuellen Wert und gibt das inverse Kommando zurück —
+/// None wenn sich der vorherige Wert nicht ermitteln lässt (nicht undo-fähig).
+/// Danach werden die Mix-Minus-Busse neu abgeleitet (nicht im Journal — sie
+/// folgen bei Undo/Redo automatisch).
+/// Alle Manager-Locks sind bei Rückkehr wieder freigegeben.
+fn apply_mixer_command(
+    state: &AppState,
+    command: &MixerCommand,
+) -> Result<Option<MixerCommand>, String> {
+    if let MixerCommand::StripBus { bus_id, .. } | MixerCommand::Routing { bus_id, .. } = command {
+        reject_mix_minus_target(state, bus_id)?;
+    }
+    if let MixerCommand::Crosspoints { entries } = command {
+        for entry in entries {
+            reject_mix_minus_target(state, &entry.bus_id)?;
+        }
+    }
+    let inverse = apply_mixer_change(state, command)?;
+    sync_mix_minus(state)?;
+    Ok(inverse)
+}
+
+/// Manuelles Routing auf einen Mix-Minus-Bus ablehnen
+fn reject_mix_minus_target(state: &AppState, bus_id: &str) -> Result<(), String> {
+    let buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+    if buses.get_bus(bus_id).is_some_and(|b| b.mix_minus.is_some()) {
+        return Err(format!(
+            "Bus {} ist ein Mix-Minus-Bus — sein Routing wird automatisch abgeleitet",
+            bus_id
+        ));
+    }
+    Ok(())
+}
+
+/// Mix-Minus-Busse aus dem Routing ihrer Referenz-Busse neu ableiten
+///
+/// Hält Strip-Routing und Routing-Matrix (Apps, Submixe) aktuell, wenn
+/// Quellen hinzukommen, wegfallen oder umgeroutet werden.
+fn sync_mix_minus(state: &AppState) -> Result<(), String> {
+    let mix_minus = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?
+        .mix_minus_buses();
+    if mix_minus.is_empty() {
+        return Ok(());
+    }
+
+    {
+        let mut mixer = state
+            .mixer
+            .lock()
+            .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+        let changes = mix_minus::strip_changes(&mixer.get_strips(), &mix_minus);
+        for (strip_id, bus_id, active) in &changes {
+            mixer.set_bus_routing(strip_id, bus_id, *active)?;
+        }
+        if !changes.is_empty() {
+            sync_mix_strips(state, &mixer)?;
+        }
+    }
+
+    let mut routing = state
+        .routing
+        .lock()
+        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
+    let changes = mix_minus::matrix_changes(&routing.get_routing_matrix(), &mix_minus);
+    for entry in &changes {
+        if let Err(e) = routing.apply_derived(entry) {
+            warn!("Mix-Minus {}: {}", entry.bus_id, e);
+        }
+    }
+    if !changes.is_empty() {
+        sync_bus_routes(state, &routing)?;
+    }
+    Ok(())
+}
+
+/// Mixer-Kommando ohne Mix-Minus-Ableitung anwenden (siehe `apply_mixer_command`)
+fn apply_mixer_change(
+    state: &AppState,
+    command: &MixerCommand,
+) -> Result<Option<MixerCommand>, String> {
+    use MixerCommand as C;
+
+    match command {
+        C::StripVolume { .. }
+        | C::StripGain { .. }
+        | C::StripMute { .. }
+        | C::StripSolo { .. }
+        | C::StripBus { .. }
+        | C::InsertStrip { .. }
+        | C::RemoveStrip { .. }
+        | C::StripDevice { .. }
+        | C::StripPwDevice { .. }
+        | C::StripLabel { .. }
+        | C::StripIcon { .. }
+        | C::StripColor { .. }
+        | C::StripOrder { .. }
+        | C::StripTalk { .. } => apply_strip_command(state, command),
+
+        C::MuteGroups { active_groups } => {
+            let prev = {
+                let mut groups = state
+                    .mute_groups
+                    .lock()
+                    .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?;
+                let prev = groups.active_groups();
+                groups.set_active_groups(active_groups)?;
+                prev
+            };
+            resync_mix_strips(state)?;
+            Ok(Some(C::MuteGroups {
+                active_groups: prev,
+            }))
+        }
+
+        C::BusVolume { bus_id, volume_db } => {
+            let mut buses = state
+                .buses
+                .lock()
+                .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+            let prev = bus_snapshot(&buses, bus_id)?.volume_db;
+            buses.set_volume(bus_id, *volume_db)?;
+            sync_mix_buses(state, &buses)?;
+            Ok(Some(C::BusVolume {
+                bus_id: bus_id.clone(),
+                volume_db: prev,
+            }))
+        }
+        C::BusMute { bus_id, muted } => {
+            let mut buses = state
+                .buses
+                .lock()
+                .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+            let prev = bus_snapshot(&buses, bus_id)?.muted;
+            buses.set_mute(bus_id, *muted)?;
+            sync_mix_buses(state, &buses)?;
+            Ok(Some(C::BusMute {
+                bus_id: bus_id.clone(),
+                muted: prev,
+            }))
+        }
+        C::BusDevice {
+            bus_id,
+            device_name,
+        } => {
+            let bus = {
+                let mut buses = state
+                    .buses
+                    .lock()
+                    .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+                let prev = bus_snapshot(&buses, bus_id)?.device_name;
+                if prev == *device_name {
+                    return Ok(None);
+                }
+                match device_name {
+                    Some(name) => {
+                        buses.bind_output_device(bus_id, name)?;
+                        buses.set_pw_device(bus_id, resolve_output_device(bus_id, name))?;
+                    }
+                    None => buses.unbind_output_device(bus_id)?,
+                }
+                (bus_snapshot(&buses, bus_id)?, prev)
+            };
+            let (bus, prev) = bus;
+            restart_bus_output(state, &bus)?;
+            Ok(Some(C::BusDevice {
+                bus_id: bus_id.clone(),
+                device_name: prev,
+            }))
+        }
+        C::BusMixMinus { bus_id, mix_minus } => {
+            let mut buses = state
+                .buses
+                .lock()
+                .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+            let prev = bus_snapshot(&buses, bus_id)?.mix_minus;
+            buses.set_mix_minus(bus_id, mix_minus.clone())?;
+            Ok(Some(C::BusMixMinus {
+                bus_id: bus_id.clone(),
+                mix_minus: prev,
+            }))
+        }
+        C::BusDelay { bus_id, delay_ms } => {
+            let mut buses = state
+                .buses
+                .lock()
+                .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+            let prev = bus_snapshot(&buses, bus_id)?.delay_ms;
+            buses.set_delay(bus_id, *delay_ms)?;
+            sync_mix_buses(state, &buses)?;
+            Ok(Some(C::BusDelay {
+                bus_id: bus_id.clone(),
+                delay_ms: prev,
+            }))
+        }
+        C::CreateBus {
+            name,
+            bus_type,
+            channels,
+        } => {
+            let bus = {
+                let mut buses = state
+                    .buses
+                    .lock()
+                    .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+                let bus = buses.create_bus(name, *bus_type, *channels)?;
+                sync_bus_registry(state, &buses)?;
+                bus
+            };
+
+            let config = state
+                .config_manager
+                .load_app_config()
+                .map_err(|e| format!("Config-Fehler: {}", e))?;
+            if let Err(e) = pw::create_bus_node(&bus, config.buffer_size, config.sample_rate) {
+                warn!("Bus-Node für {} nicht erstellt: {}", bus.id, e);
+            }
+            Ok(Some(C::RemoveBus { bus_id: bus.id }))
+        }
+        C::RenameBus { bus_id, name } => {
+            let mut buses = state
+                .buses
+                .lock()
+                .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+            let prev = bus_snapshot(&buses, bus_id)?.name;
+            buses.rename_bus(bus_id, name)?;
+            Ok(Some(C::RenameBus {
+                bus_id: bus_id.clone(),
+                name: prev,
+            }))
+        }
+        C::RemoveBus { bus_id } => {
+            // Prüfen und Löschen unter demselben Bus-Lock
+            let mut buses = state
+                .buses
+                .lock()
+                .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+            let users = bus_users(state, &buses, bus_id)?;
+            if !users.is_empty() {
+                return Err(format!(
+                    "Bus '{}' wird noch verwendet: {}",
+                    bus_id,
+                    users.join(", ")
+                ));
+            }
+
+            // Was beim Löschen wegfällt, für Undo festhalten
+            let strips: Vec<String> = state
+                .mixer
+                .lock()
+                .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?
+                .get_strips()
+                .into_iter()
+                .filter(|strip| strip.bus_routing.contains(bus_id))
+                .map(|strip| strip.id)
+                .collect();
+            let routing: Vec<RoutingEntry> = state
+                .routing
+                .lock()
+                .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?
+                .get_routing_matrix()
+                .into_iter()
+                .filter(|e| e.active && (&e.bus_id == bus_id || &e.source_id == bus_id))
+                .collect();
+            let talkback = state
+                .master
+                .lock()
+                .map_err(|e| format!("Master-Lock-Fehler: {}", e))?
+                .get_state()
+                .talkback_buses
+                .contains(bus_id);
+
+            let bus = buses.delete_bus(bus_id)?;
+            sync_bus_registry(state, &buses)?;
+            drop(buses);
+
+            if let Err(e) = pw::destroy_bus_node(bus_id) {
+                warn!("Bus-Node für {} nicht gestoppt: {}", bus_id, e);
+            }
+            Ok(Some(C::InsertBus {
+                bus,
+                strips,
+                routing,
+                talkback,
+            }))
+        }
+        C::InsertBus {
+            bus,
+            strips,
+            routing,
+            talkback,
+        } => {
+            let bus = {
+                let mut buses = state
+                    .buses
+                    .lock()
+                    .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+                let bus = buses.restore_bus(bus.clone())?;
+                if let Some(name) = &bus.device_name {
+                    buses.set_pw_device(&bus.id, resolve_output_device(&bus.id, name))?;
+                }
+                sync_bus_registry(state, &buses)?;
+                bus_snapshot(&buses, &bus.id)?
+            };
+            restart_bus_output(state, &bus)?;
+
+            {
+                let mut mixer = state
+                    .mixer
+                    .lock()
+                    .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+                for strip_id in strips {
+                    if mixer.get_strip(strip_id).is_some() {
+                        mixer.set_bus_routing(strip_id, &bus.id, true)?;
+                    }
+                }
+                sync_mix_strips(state, &mixer)?;
+            }
+            if !routing.is_empty() {
+                let mut matrix = state
+                    .routing
+                    .lock()
+                    .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
+                matrix.set_crosspoints(routing)?;
+                sync_bus_routes(state, &matrix)?;
+            }
+            if *talkback {
+                let mut master = state
+                    .master
+                    .lock()
+                    .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
+                let current = master.get_state();
+                let mut targets = current.talkback_buses;
+                targets.push(bus.id.clone());
+                master.set_talkback(current.talkback, targets)?;
+                sync_mix_master(state, &master)?;
+            }
+            sync_mix_minus(state)?;
+            Ok(Some(C::RemoveBus {
+                bus_id: bus.id.clone(),
+            }))
+        }
+
+        C::Routing {
+            source_id,
+            bus_id,
+            active,
+        } => {
+            let mut routing = state
+                .routing
+                .lock()
+                .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
+            let prev = routing.crosspoint(source_id, bus_id);
+            routing.set_routing(source_id, bus_id, *active)?;
+            sync_bus_routes(state, &routing)?;
+            // Beim Trennen den Kreuzpunkt-Gain für Undo mitnehmen
+            if prev.active && !*active && prev.gain_db != 0.0 {
+                return Ok(Some(C::Crosspoints {
+                    entries: vec![prev],
+                }));
+            }
+            Ok(Some(C::Routing {
+                source_id: source_id.clone(),
+                bus_id: bus_id.clone(),
+                active: prev.active,
+            }))
+        }
+
+        C::Crosspoints { entries } => {
+            let mut routing = state
+                .routing
+                .lock()
+                .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
+            let previous = routing.set_crosspoints(entries)?;
+            if previous.is_empty() {
+                return Ok(None);
+            }
+            sync_bus_routes(state, &routing)?;
+            Ok(Some(C::Crosspoints { entries: previous }))
+        }
+
+        C::FxParam {
+            module_type,
+            param_name,
+            value,
+        } => {
+            let mut fx = state
+                .fx_chain
+                .lock()
+                .map_err(|e| format!("FX-Lock-Fehler: {}", e))?;
+            let prev = fx.get_module_info(*module_type).and_then(|info| {
+                info.params
+                    .iter()
+                    .find(|(name, _)| name == param_name)
+                    .map(|(_, v)| *v)
+            });
+            fx.set_param(*module_type, param_name, *value)?;
+            Ok(prev.map(|prev| C::FxParam {
+                module_type: *module_type,
+                param_name: param_name.clone(),
+                value: prev,
+            }))
+        }
+        C::FxBypass {
+            module_type,
+            bypass,
+        } => {
+            let mut fx = state
+                .fx_chain
+                .lock()
+                .map_err(|e| format!("FX-Lock-Fehler: {}", e))?;
+            let prev = fx.get_module_info(*module_type).map(|info| !info.enabled);
+            fx.set_bypass(*module_type, *bypass)?;
+            Ok(prev.map(|prev| C::FxBypass {
+                module_type: *module_type,
+                bypass: prev,
+            }))
+        }
+        C::BusFxParam {
+            bus_id,
+            module_type,
+            param_name,
+            value,
+        } => {
+            let mut buses = state
+                .buses
+                .lock()
+                .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+            let prev = bus_snapshot(&buses, bus_id)?
+                .inserts
+                .iter()
+                .find(|info| info.module_type == *module_type)
+                .and_then(|info| {
+                    info.params
+                        .iter()
+                        .find(|(name, _)| name == param_name)
+                        .map(|(_, v)| *v)
+                });
+            buses.set_insert_param(bus_id, *module_type, param_name, *value)?;
+            sync_mix_buses(state, &buses)?;
+            Ok(prev.map(|prev| C::BusFxParam {
+                bus_id: bus_id.clone(),
+                module_type: *module_type,
+                param_name: param_name.clone(),
+                value: prev,
+            }))
+        }
+        C::BusFxBypass {
+            bus_id,
+            module_type,
+            bypass,
+        } => {
+            let mut buses = state
+                .buses
+                .lock()
+                .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+            let prev = bus_snapshot(&buses, bus_id)?
+                .inserts
+                .iter()
+                .find(|info| info.module_type == *module_type)
+                .map(|info| !info.enabled);
+            buses.set_insert_bypass(bus_id, *module_type, *bypass)?;
+            sync_mix_buses(state, &buses)?;
+            Ok(prev.map(|prev| C::BusFxBypass {
+                bus_id: bus_id.clone(),
+                module_type: *module_type,
+                bypass: prev,
+            }))
+        }
+
+        C::MasterVolume { .. }
+        | C::MasterLimiter { .. }
+        | C::MasterDim { .. }
+        | C::MasterMono { .. }
+        | C::MasterTalkback { .. }
+        | C::MasterTalkbackSettings { .. } => {
+            if let C::MasterTalkbackSettings { settings } = command {
+                let mixer = state
+                    .mixer
+                    .lock()
+                    .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+                strip_snapshot(&mixer, &settings.source_strip)?;
+            }
+            let mut master = state
+                .master
+                .lock()
+                .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
+            let prev = master.get_state();
+            let inverse = match command {
+                C::MasterVolume { volume_db } => {
+                    master.set_volume(*volume_db)?;
+                    C::MasterVolume {
+                        volume_db: prev.volume_db,
+                    }
+                }
+                C::MasterLimiter { ceiling_db } => {
+                    master.set_limiter(*ceiling_db)?;
+                    C::MasterLimiter {
+                        ceiling_db: prev.limiter_ceiling_db,
+                    }
+                }
+                C::MasterDim { active } => {
+                    master.set_dim(*active)?;
+                    C::MasterDim { active: prev.dim }
+                }
+                C::MasterMono { active } => {
+                    master.set_mono(*active)?;
+                    C::MasterMono { active: prev.mono }
+                }
+                C::MasterTalkback {
+                    active,
+                    target_buses,
+                } => {
+                    master.set_talkback(*active, target_buses.clone())?;
+                    C::MasterTalkback {
+                        active: prev.talkback,
+                        target_buses: prev.talkback_buses,
+                    }
+                }
+                C::MasterTalkbackSettings { settings } => {
+                    master.set_talkback_settings(settings.clone())?;
+                    C::MasterTalkbackSettings {
+                        settings: prev.talkback_settings,
+                    }
+                }
+                other => return Err(format!("Kein Master-Befehl: {:?}", other)),
+            };
+            sync_mix_master(state, &master)?;
+            Ok(Some(inverse))
+        }
+
+        C::DuckingEnabled { .. }
+        | C::DuckingAmount { .. }
+        | C::DuckingAttack { .. }
+        | C::DuckingRelease { .. }
+        | C::DuckingThreshold { .. } => {
+            let mut ducking = state
+                .ducking
+                .lock()
+                .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?;
+            let prev = ducking.params.clone();
+            let inverse = match command {
+                C::DuckingEnabled { enabled } => {
+                    let prev_enabled = ducking.enabled;
+                    ducking.enabled = *enabled;
+                    C::DuckingEnabled {
+                        enabled: prev_enabled,
+                    }
+                }
+                C::DuckingAmount { amount_db } => {
+                    ducking.set_amount(*amount_db);
+                    C::DuckingAmount {
+                        amount_db: prev.amount_db,
+                    }
+                }
+                C::DuckingAttack { attack_ms } => {
+                    ducking.set_attack(*attack_ms);
+                    C::DuckingAttack {
+                        attack_ms: prev.attack_ms,
+                    }
+                }
+                C::DuckingRelease { release_ms } => {
+                    ducking.set_release(*release_ms);
+                    C::DuckingRelease {
+                        release_ms: prev.release_ms,
+                    }
+                }
+                C::DuckingThreshold { threshold_db } => {
+                    ducking.set_threshold(*threshold_db);
+                    C::DuckingThreshold {
+                        threshold_db: prev.threshold_db,
+                    }
+                }
+                other => return Err(format!("Kein Ducking-Befehl: {:?}", other)),
+            };
+            Ok(Some(inverse))
+        }
+
+        C::VoiceFxPreset { .. } | C::VoiceFxEnabled { .. } | C::VoiceFxDryWet { .. } => {
+            let mut voice_fx = state
+                .voice_fx
+                .lock()
+                .map_err(|e| format!("VoiceFX-Lock-Fehler: {}", e))?;
+            let prev = voice_fx.get_state().clone();
+            let inverse = match command {
+                C::VoiceFxPreset { preset } => {
+                    voice_fx.set_preset(*preset);
+                    C::VoiceFxPreset {
+                        preset: prev.preset,
+                    }
+                }
+                C::VoiceFxEnabled { enabled } => {
+                    voice_fx.set_enabled(*enabled);
+                    C::VoiceFxEnabled {
+                        enabled: prev.enabled,
+                    }
+                }
+                C::VoiceFxDryWet { dry_wet } => {
+                    voice_fx.set_dry_wet(*dry_wet)?;
+                    C::VoiceFxDryWet {
+                        dry_wet: prev.dry_wet,
+                    }
+                }
+                other => return Err(format!("Kein Voice-FX-Befehl: {:?}", other)),
+            };
+            Ok(Some(inverse))
+        }
+
+        C::MacroValue {
+            macro_id,
+            value,
+            targets,
+        } => {
+            let (prev, commands) = {
+                let mut macros = state
+                    .macros
+                    .lock()
+                    .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?;
+                let prev = macros
+                    .get_macro(macro_id)
+                    .map(|m| m.value)
+                    .ok_or_else(|| format!("Makro '{}' nicht gefunden", macro_id))?;
+                let commands = macros.set_value(macro_id, *value)?;
+                (prev, targets.clone().unwrap_or(commands))
+            };
+            // Ziele ohne eigenen Journal-Eintrag setzen — das Undo des Makros
+            // stellt die vorherigen Ziel-Werte wieder her
+            let mut restore = Vec::new();
+            for target in &commands {
+                match apply_mixer_command(state, target) {
+                    Ok(inverse) => restore.extend(inverse),
+                    Err(e) => {
+                        rollback_macro(state, macro_id, prev, &restore)?;
+                        return Err(format!(
+                            "Makro {}: Ziel '{}' fehlgeschlagen: {}",
+                            macro_id,
+                            target.label(),
+                            e
+                        ));
+                    }
+                }
+            }
+            restore.reverse();
+            Ok(Some(C::MacroValue {
+                macro_id: macro_id.clone(),
+                value: prev,
+                targets: Some(restore),
+            }))
+        }
+
+        C::SceneState {
+            scene_name,
+            state: target,
+            levels,
+        } => {
+            let prev = session_snapshot(state)?;
+            let prev_levels = scene_levels(state)?;
+            apply_scene_state(state, &SceneScope::default().merge(&prev, target))?;
+            apply_scene_levels(state, levels)?;
+            Ok(Some(C::SceneState {
+                scene_name: scene_name.clone(),
+                state: Box::new(prev),
+                levels: prev_levels,
+            }))
+        }
+    }
+}
+
+/// Teilweise gesetztes Makro zurücknehmen (Ziele in umgekehrter Reihenfolge)
+fn rollback_macro(
+    state: &AppState,
+    macro_id: &str,
+    value: f32,
+    applied: &[MixerCommand],
+) -> Result<(), String> {
+    for inverse in applied.iter().rev() {
+        apply_mixer_command(state, inverse)?;
+    }
+    state
+        .macros
+        .lock()
+        .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+        .set_value(macro_id, value)?;
+    Ok(())
+}
+
+/// Aktuelle Effekt-Pegel (Ducking-Stärke, Voice-FX Dry/Wet)
+fn scene_levels(state: &AppState) -> Result<SceneLevels, String> {
+    let ducking_amount_db = state
+        .ducking
+        .lock()
+        .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?
+        .params
+        .amount_db;
+    let voice_fx_dry_wet = state
         .voice_fx
         .lock()
-        .map_err(|e| format!("VoiceFX-Lock-Fehler: {}", e))?;
-    voice_fx.set_enabled(enabled);
+        .map_err(|e| format!("VoiceFX-Lock-Fehler: {}", e))?
+        .get_state()
+        .dry_wet;
+    Ok(SceneLevels {
+        ducking_amount_db: Some(ducking_amount_db),
+        voice_fx_dry_wet: Some(voice_fx_dry_wet),
+    })
+}
+
+/// Effekt-Pegel einer Scene setzen (fehlende Werte bleiben unverändert)
+fn apply_scene_levels(state: &AppState, levels: &SceneLevels) -> Result<(), String> {
+    if let Some(amount_db) = levels.ducking_amount_db {
+        state
+            .ducking
+            .lock()
+            .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?
+            .set_amount(amount_db);
+    }
+    if let Some(dry_wet) = levels.voice_fx_dry_wet {
+        state
+            .voice_fx
+            .lock()
+            .map_err(|e| format!("VoiceFX-Lock-Fehler: {}", e))?
+            .set_dry_wet(dry_wet)?;
+    }
     Ok(())
 }
 
-/// Voice FX Dry/Wet Mix setzen (0.0-1.0)
-#[tauri::command]
-fn set_voice_fx_drywet(dry_wet: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut voice_fx = state
-        .voice_fx
-        .lock()
-        .map_err(|e| format!("VoiceFX-Lock-Fehler: {}", e))?;
-    voice_fx.set_dry_wet(dry_wet)
+/// Kontinuierliche Werte eines Überblend-Takts anwenden
+///
+/// Setzt nur Lautstärke, Gain, Pan, Limiter-Ceiling und Effekt-Pegel der
+/// sich bewegenden Ziele — FX, Inserts, Delays, Routing und Talkback bleiben
+/// unberührt.
+fn apply_transition_frame(state: &AppState, frame: &TransitionFrame) -> Result<(), String> {
+    if !frame.strips.is_empty() {
+        let mut mixer = state
+            .mixer
+            .lock()
+            .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+        for strip in &frame.strips {
+            mixer.set_volume(&strip.id, strip.volume_db)?;
+            mixer.set_gain(&strip.id, strip.gain_db)?;
+            mixer.set_pan(&strip.id, strip.pan)?;
+        }
+        sync_mix_strips(state, &mixer)?;
+    }
+
+    if !frame.buses.is_empty() {
+        let mut buses = state
+            .buses
+            .lock()
+            .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+        let mut engine = state
+            .mix_engine
+            .lock()
+            .map_err(|e| format!("Engine-Lock-Fehler: {}", e))?;
+        for (bus_id, volume_db) in &frame.buses {
+            buses.set_volume(bus_id, *volume_db)?;
+            engine.set_bus_volume(bus_id, *volume_db);
+        }
+    }
+
+    if !frame.crosspoints.is_empty() {
+        let mut routing = state
+            .routing
+            .lock()
+            .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
+        routing.set_crosspoints(&frame.crosspoints)?;
+        sync_bus_routes(state, &routing)?;
+    }
+
+    if frame.master_volume_db.is_some() || frame.limiter_ceiling_db.is_some() {
+        let mut master = state
+            .master
+            .lock()
+            .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
+        if let Some(volume_db) = frame.master_volume_db {
+            master.set_volume(volume_db)?;
+        }
+        if let Some(ceiling_db) = frame.limiter_ceiling_db {
+            master.set_limiter(ceiling_db)?;
+        }
+        sync_mix_master(state, &master)?;
+    }
+
+    apply_scene_levels(state, &frame.levels)
 }
 
-// --- Ducking Commands (Modul 07) ---
+/// Beendete oder abgebrochene Überblendung als ein Schritt ins Journal
+fn finish_scene_transition(
+    state: &AppState,
+    transition: &SceneTransition,
+    reached: SessionState,
+) -> Result<(), String> {
+    let (origin, origin_levels) = transition.origin();
+    record_history(
+        state,
+        MixerCommand::SceneState {
+            scene_name: transition.scene_name().to_string(),
+            state: Box::new(reached),
+            levels: scene_levels(state)?,
+        },
+        MixerCommand::SceneState {
+            scene_name: transition.scene_name().to_string(),
+            state: Box::new(origin.clone()),
+            levels: origin_levels.clone(),
+        },
+    )
+}
 
-/// Ducking State abrufen
-#[tauri::command]
-fn get_ducking_state(state: tauri::State<'_, AppState>) -> Result<DuckingParams, String> {
-    let ducking = state
-        .ducking
+/// Ziel-Stand eines Szenen-Recalls auf die Manager anwenden
+///
+/// Erwartet einen mit `SceneScope::merge` berechneten Stand: nur Parameter
+/// bestehender Strips/Busse werden gesetzt, die Struktur bleibt unverändert.
+/// Die Manager werden nacheinander gesperrt; danach werden die Mix-Minus-Busse
+/// aus dem neuen Routing abgeleitet (auch beim Umschalten einer Überblendung).
+fn apply_scene_state(state: &AppState, target: &SessionState) -> Result<(), String> {
+    {
+        let mut mixer = state
+            .mixer
+            .lock()
+            .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+        for strip in &target.strips {
+            let current = strip_snapshot(&mixer, &strip.id)?;
+            mixer.set_volume(&strip.id, strip.volume_db)?;
+            mixer.set_gain(&strip.id, strip.gain_db)?;
+            mixer.set_pan(&strip.id, strip.pan)?;
+            mixer.set_mute(&strip.id, strip.muted)?;
+            mixer.set_solo(&strip.id, strip.solo)?;
+            mixer.set_fx_enabled(&strip.id, strip.fx_enabled)?;
+            for bus_id in &current.bus_routing {
+                if !strip.bus_routing.contains(bus_id) {
+                    mixer.set_bus_routing(&strip.id, bus_id, false)?;
+                }
+            }
+            for bus_id in &strip.bus_routing {
+                mixer.set_bus_routing(&strip.id, bus_id, true)?;
+            }
+        }
+        state
+            .mute_groups
+            .lock()
+            .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?
+            .set_active_groups(
+                &target
+                    .mute_groups
+                    .iter()
+                    .filter(|g| g.active)
+                    .map(|g| g.id.clone())
+                    .collect::<Vec<_>>(),
+            )?;
+        sync_mix_strips(state, &mixer)?;
+    }
+
+    {
+        let mut buses = state
+            .buses
+            .lock()
+            .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?;
+        // Erst lösen, dann setzen — Szenen dürfen Referenz und Mix-Minus tauschen
+        for bus in &target.buses {
+            buses.set_mix_minus(&bus.id, None)?;
+        }
+        for bus in &target.buses {
+            buses.set_mix_minus(&bus.id, bus.mix_minus.clone())?;
+            buses.set_volume(&bus.id, bus.volume_db)?;
+            buses.set_mute(&bus.id, bus.muted)?;
+            buses.set_inserts(&bus.id, &bus.inserts)?;
+            buses.set_delay(&bus.id, bus.delay_ms)?;
+        }
+        sync_mix_buses(state, &buses)?;
+    }
+
+    {
+        let mut routing = state
+            .routing
+            .lock()
+            .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?;
+        routing.apply_scene(&target.routing)?;
+        sync_bus_routes(state, &routing)?;
+    }
+    sync_mix_minus(state)?;
+
+    state
+        .fx_chain
         .lock()
-        .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?;
-    Ok(ducking.params.clone())
-}
+        .map_err(|e| format!("FX-Lock-Fehler: {}", e))?
+        .apply_modules(&target.fx)?;
 
-/// Ducking aktivieren/deaktivieren
-#[tauri::command]
-fn set_ducking_enabled(enabled: bool, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut ducking = state
-        .ducking
+    // Nur Reglerstellungen — die Ziel-Parameter stehen bereits im Ziel-Stand
+    state
+        .macros
         .lock()
-        .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?;
-    ducking.enabled = enabled;
-    Ok(())
-}
+        .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+        .restore_values(&target.macros);
 
-/// Ducking Amount setzen (in dB, -30 bis 0)
-#[tauri::command]
-fn set_ducking_amount(amount_db: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut ducking = state
-        .ducking
+    let mut master = state
+        .master
         .lock()
-        .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?;
-    ducking.set_amount(amount_db);
-    Ok(())
+        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?;
+    master.set_volume(target.master.volume_db)?;
+    master.set_limiter(target.master.limiter_ceiling_db)?;
+    master.set_dim(target.master.dim)?;
+    master.set_mono(target.master.mono)?;
+    master.set_talkback_settings(target.master.talkback_settings.clone())?;
+    master.set_talkback(target.master.talkback, target.master.talkback_buses.clone())?;
+    sync_mix_master(state, &master)
 }
 
-/// Ducking Attack setzen (in ms, 10-500)
-#[tauri::command]
-fn set_ducking_attack(attack_ms: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut ducking = state
-        .ducking
+/// Strip-Kommandos anwenden (Mix-Engine und Capture-Bindungen werden nachgezogen)
+fn apply_strip_command(
+    state: &AppState,
+    command: &MixerCommand,
+) -> Result<Option<MixerCommand>, String> {
+    use MixerCommand as C;
+
+    let mut mixer = state
+        .mixer
         .lock()
-        .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?;
-    ducking.set_attack(attack_ms);
-    Ok(())
+        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?;
+
+    let inverse = match command {
+        C::StripVolume {
+            strip_id,
+            volume_db,
+        } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.volume_db;
+            mixer.set_volume(strip_id, *volume_db)?;
+            C::StripVolume {
+                strip_id: strip_id.clone(),
+                volume_db: prev,
+            }
+        }
+        C::StripGain { strip_id, gain_db } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.gain_db;
+            mixer.set_gain(strip_id, *gain_db)?;
+            C::StripGain {
+                strip_id: strip_id.clone(),
+                gain_db: prev,
+            }
+        }
+        C::StripMute { strip_id, muted } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.muted;
+            mixer.set_mute(strip_id, *muted)?;
+            C::StripMute {
+                strip_id: strip_id.clone(),
+                muted: prev,
+            }
+        }
+        C::StripSolo { strip_id, solo } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.solo;
+            mixer.set_solo(strip_id, *solo)?;
+            C::StripSolo {
+                strip_id: strip_id.clone(),
+                solo: prev,
+            }
+        }
+        C::StripBus {
+            strip_id,
+            bus_id,
+            active,
+        } => {
+            let prev = strip_snapshot(&mixer, strip_id)?
+                .bus_routing
+                .contains(bus_id);
+            mixer.set_bus_routing(strip_id, bus_id, *active)?;
+            C::StripBus {
+                strip_id: strip_id.clone(),
+                bus_id: bus_id.clone(),
+                active: prev,
+            }
+        }
+        C::InsertStrip { strip } => {
+            mixer.restore_strip(strip.clone())?;
+            sync_capture_bindings(state, &mixer)?;
+            C::RemoveStrip {
+                strip_id: strip.id.clone(),
+            }
+        }
+        C::RemoveStrip { strip_id } => {
+            let prev = strip_snapshot(&mixer, strip_id)?;
+            // Makro-Ziele auf den Strip würden die Session ungültig machen
+            let users = state
+                .macros
+                .lock()
+                .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+                .macros_using_strip(strip_id);
+            if !users.is_empty() {
+                return Err(format!(
+                    "Strip '{}' wird von Makro(s) verwendet: {}",
+                    strip_id,
+                    users.join(", ")
+                ));
+            }
+            // Talkback-Quelle darf nicht ins Leere zeigen
+            let talkback_source = state
+                .master
+                .lock()
+                .map_err(|e| format!("Master-Lock-Fehler: {}", e))?
+                .get_state()
+                .talkback_settings
+                .source_strip;
+            if talkback_source == *strip_id {
+                return Err(format!(
+                    "Strip '{}' ist die Talkback-Quelle — erst eine andere Quelle wählen",
+                    strip_id
+                ));
+            }
+            // Sonst speist der Capture-Thread weiter in eine tote Strip-ID
+            let generator_target = state
+                .generator
+                .lock()
+                .map_err(|e| format!("Generator-Lock-Fehler: {}", e))?
+                .active()
+                .map(|s| s.target_strip.clone());
+            if generator_target.as_deref() == Some(strip_id.as_str()) {
+                return Err(format!(
+                    "Strip '{}' wird vom Signal-Generator bespielt — erst den Generator stoppen",
+                    strip_id
+                ));
+            }
+            mixer.remove_virtual_strip(strip_id)?;
+            sync_capture_bindings(state, &mixer)?;
+            C::InsertStrip { strip: prev }
+        }
+        C::StripDevice {
+            strip_id,
+            device_name,
+        } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.device_name;
+            match device_name {
+                Some(name) => mixer.bind_device(strip_id, name)?,
+                None => mixer.unbind_device(strip_id)?,
+            }
+            sync_capture_bindings(state, &mixer)?;
+            C::StripDevice {
+                strip_id: strip_id.clone(),
+                device_name: prev,
+            }
+        }
+        C::StripPwDevice {
+            strip_id,
+            device_id,
+        } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.device_id;
+            mixer.set_pw_device(strip_id, *device_id)?;
+            C::StripPwDevice {
+                strip_id: strip_id.clone(),
+                device_id: prev,
+            }
+        }
+        C::StripLabel { strip_id, label } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.label;
+            mixer.set_label(strip_id, label)?;
+            C::StripLabel {
+                strip_id: strip_id.clone(),
+                label: prev,
+            }
+        }
+        C::StripIcon { strip_id, icon } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.icon;
+            mixer.set_icon(strip_id, icon)?;
+            C::StripIcon {
+                strip_id: strip_id.clone(),
+                icon: prev,
+            }
+        }
+        C::StripColor { strip_id, color } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.color;
+            mixer.set_color(strip_id, color.as_deref())?;
+            C::StripColor {
+                strip_id: strip_id.clone(),
+                color: prev,
+            }
+        }
+        C::StripOrder { strip_ids } => {
+            let prev = mixer.strip_order();
+            mixer.reorder(strip_ids)?;
+            C::StripOrder { strip_ids: prev }
+        }
+        C::StripTalk { strip_id, talk } => {
+            let prev = strip_snapshot(&mixer, strip_id)?.talk;
+            mixer.set_talk(strip_id, talk.clone())?;
+            C::StripTalk {
+                strip_id: strip_id.clone(),
+                talk: prev,
+            }
+        }
+        _ => return Err("Kein Strip-Kommando".to_string()),
+    };
+
+    sync_mix_strips(state, &mixer)?;
+    Ok(Some(inverse))
 }
 
-/// Ducking Release setzen (in ms, 50-2000)
-#[tauri::command]
-fn set_ducking_release(release_ms: f32, state: tauri::State<'_, AppState>) -> Result<(), String> {
-    let mut ducking = state
-        .ducking
-        .lock()
-        .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?;
-    ducking.set_release(release_ms);
-    Ok(())
+/// Aktuellen Stand eines Strips kopieren (für das inverse Kommando)
+fn strip_snapshot(mixer: &MixerState, strip_id: &str) -> Result<InputStrip, String> {
+    mixer
+        .get_strip(strip_id)
+        .cloned()
+        .ok_or_else(|| format!("Strip '{}' nicht gefunden", strip_id))
 }
 
-/// Ducking Threshold setzen (in dB, -50 bis 0)
-#[tauri::command]
-fn set_ducking_threshold(
-    threshold_db: f32,
-    state: tauri::State<'_, AppState>,
-) -> Result<(), String> {
-    let mut ducking = state
-        .ducking
-        .lock()
-        .map_err(|e| format!("Ducking-Lock-Fehler: {}", e))?;
-    ducking.set_threshold(threshold_db);
-    Ok(())
+/// Aktuellen Stand eines Bus kopieren (für das inverse Kommando)
+fn bus_snapshot(buses: &BusManager, bus_id: &str) -> Result<OutputBus, String> {
+    buses
+        .get_bus(bus_id)
+        .cloned()
+        .ok_or_else(|| format!("Bus '{}' nicht gefunden", bus_id))
 }
 
 // --- Bleeper Commands (Modul 09) ---
@@ -848,9 +3460,59 @@ fn get_stt_status(state: tauri::State<'_, AppState>) -> Result<serde_json::Value
         },
         "vosk_available": stt.is_vosk_available(),
         "whisper_available": stt.is_whisper_available(),
+        "source_id": stt.source_id(),
     }))
 }
 
+/// Quelle (Strip oder Bus) für STT setzen — None beendet die Überwachung
+///
+/// Die Quelle wird gespeichert und beim nächsten Start wieder überwacht.
+#[tauri::command]
+fn set_stt_source(
+    source_id: Option<String>,
+    state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    if let Some(id) = &source_id {
+        let known_strip = state
+            .mixer
+            .lock()
+            .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?
+            .get_strip(id)
+            .is_some();
+        let known_bus = state
+            .buses
+            .lock()
+            .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?
+            .get_bus(id)
+            .is_some();
+        if !known_strip && !known_bus {
+            return Err(format!("Unbekannte STT-Quelle: {}", id));
+        }
+    }
+    state
+        .config_manager
+        .save_stt_source(source_id.as_deref())
+        .map_err(|e| format!("Config-Fehler: {}", e))?;
+
+    let mut stt = state
+        .stt
+        .lock()
+        .map_err(|e| format!("STT-Lock-Fehler: {}", e))?;
+    let hub = state
+        .metering
+        .lock()
+        .map_err(|e| format!("Metering-Lock-Fehler: {}", e))?
+        .tap_hub();
+    let mut hub = hub.lock().map_err(|e| format!("Tap-Lock-Fehler: {}", e))?;
+
+    if let Some(previous) = stt.source_id() {
+        hub.unsubscribe(previous, TapKind::Stt);
+    }
+    let tap = source_id.map(|id| hub.subscribe(&id, TapKind::Stt));
+    stt.set_tap(tap);
+    Ok(())
+}
+
 // --- Calibrate Commands (Modul 24) ---
 
 /// Quick Calibrate durchführen
@@ -870,6 +3532,309 @@ async fn run_calibration(
     calibrate.run_calibration(&mock_samples)
 }
 
+/// Hintergrund-Thread: leert die Recording- und STT-Taps außerhalb des Audio-Threads
+///
+/// Ein erneuter Aufruf beendet den vorherigen Thread über dessen Stopp-Flag.
+fn spawn_tap_consumer(app_handle: tauri::AppHandle) {
+    let stop = Arc::new(AtomicBool::new(false));
+    if let Ok(mut current) = app_handle.state::<AppState>().tap_consumer.lock() {
+        if let Some(previous) = current.replace(Arc::clone(&stop)) {
+            previous.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let result = std::thread::Builder::new()
+        .name("tap-consumer".to_string())
+        .spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                let state = app_handle.state::<AppState>();
+
+                if let Ok(mut recording) = state.recording.lock() {
+                    if let Err(e) = recording.pump() {
+                        warn!("Recording-Tap konnte nicht geschrieben werden: {}", e);
+                    }
+                }
+
+                if let Ok(mut stt) = state.stt.lock() {
+                    if let Some(words) = stt.pump() {
+                        let profanity = stt.check_profanity(&words);
+                        let _ = app_handle.emit(
+                            "stt-words",
+                            serde_json::json!({ "words": words, "profanity": profanity }),
+                        );
+                    }
+                }
+
+                std::thread::sleep(std::time::Duration::from_millis(TAP_CONSUMER_INTERVAL_MS));
+            }
+        });
+
+    if let Err(e) = result {
+        error!("Tap-Consumer-Thread konnte nicht gestartet werden: {}", e);
+    }
+}
+
+/// Snapshot des kompletten Live-States (für Session-Persistenz)
+///
+/// Sperrt die Manager nacheinander, nie gleichzeitig.
+fn session_snapshot(state: &AppState) -> Result<SessionState, String> {
+    let strips = state
+        .mixer
+        .lock()
+        .map_err(|e| format!("Mixer-Lock-Fehler: {}", e))?
+        .get_strips();
+    let buses = state
+        .buses
+        .lock()
+        .map_err(|e| format!("Bus-Lock-Fehler: {}", e))?
+        .get_buses();
+    let routing = state
+        .routing
+        .lock()
+        .map_err(|e| format!("Routing-Lock-Fehler: {}", e))?
+        .get_routing_matrix();
+    let fx = state
+        .fx_chain
+        .lock()
+        .map_err(|e| format!("FX-Lock-Fehler: {}", e))?
+        .get_all_modules();
+    let master = state
+        .master
+        .lock()
+        .map_err(|e| format!("Master-Lock-Fehler: {}", e))?
+        .get_state();
+    let mute_groups = state
+        .mute_groups
+        .lock()
+        .map_err(|e| format!("MuteGroup-Lock-Fehler: {}", e))?
+        .get_groups();
+    let macros = state
+        .macros
+        .lock()
+        .map_err(|e| format!("Macro-Lock-Fehler: {}", e))?
+        .get_macros();
+    Ok(SessionState::new(
+        strips,
+        buses,
+        routing,
+        fx,
+        master,
+        mute_groups,
+        macros,
+    ))
+}
+
+/// Hintergrund-Thread: laufende Szenen-Überblendung weiterschalten
+///
+/// Setzt pro Takt den Zwischen-Stand und meldet den Fortschritt als
+/// "scene-transition" Event. Am Ende landet die Überblendung als ein
+/// Schritt im Undo-Journal.
+fn spawn_scene_transitions(app_handle: tauri::AppHandle) {
+    let result = std::thread::Builder::new()
+        .name("scene-transition".to_string())
+        .spawn(move || loop {
+            let state = app_handle.state::<AppState>();
+
+            if let Err(e) = step_scene_transition(&app_handle, &state) {
+                warn!("Szenen-Überblendung fehlgeschlagen: {}", e);
+                if let Ok(mut transition) = state.scene_transition.lock() {
+                    transition.take();
+                }
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(SCENE_TRANSITION_TICK_MS));
+        });
+
+    if let Err(e) = result {
+        error!("Transition-Thread konnte nicht gestartet werden: {}", e);
+    }
+}
+
+/// Einen Takt der Szenen-Überblendung ausführen
+///
+/// Schreibt pro Takt nur die sich bewegenden kontinuierlichen Werte; der
+/// diskrete Ziel-Stand wird genau einmal am Umschaltpunkt angewendet.
+fn step_scene_transition(app_handle: &tauri::AppHandle, state: &AppState) -> Result<(), String> {
+    let step = {
+        let mut guard = state
+            .scene_transition
+            .lock()
+            .map_err(|e| format!("Transition-Lock-Fehler: {}", e))?;
+        let step = guard.as_mut().map(|transition| {
+            let status = transition.status(std::time::Instant::now());
+            let switch = transition
+                .take_switch(status.progress)
+                .map(|target| (target, transition.scope().clone()));
+            (transition.frame(status.progress), switch, status)
+        });
+        match step {
+            Some((frame, switch, status)) => {
+                let finished = if status.done { guard.take() } else { None };
+                Some((frame, switch, status, finished))
+            }
+            None => None,
+        }
+    };
+    let (frame, switch, status, finished) = match step {
+        Some(step) => step,
+        None => return Ok(()),
+    };
+
+    if let Some((target, scope)) = switch {
+        // Safes und abgewählte Bereiche behalten den aktuellen Stand
+        let current = session_snapshot(state)?;
+        apply_scene_state(state, &scope.merge(&current, &target))?;
+    }
+    apply_transition_frame(state, &frame)?;
+    if let Some(transition) = finished {
+        finish_scene_transition(state, &transition, transition.target().0.clone())?;
+        info!(
+            "Szenen-Überblendung abgeschlossen: {}",
+            transition.scene_name()
+        );
+    }
+    let _ = app_handle.emit("scene-transition", status);
+    Ok(())
+}
+
+/// Hintergrund-Thread: Automation in die Manager spiegeln
+///
+/// Die Mix-Engine wendet die Lane-Werte blockgenau selbst an; der Thread
+/// übernimmt die abgeholten Kommandos in die Manager (ohne Journal), meldet
+/// den Transport als "automation-status" Event und beim Anhalten am
+/// Clip-Ende zusätzlich "automation-ended".
+fn spawn_automation(app_handle: tauri::AppHandle) {
+    let result = std::thread::Builder::new()
+        .name("automation".to_string())
+        .spawn(move || loop {
+            let state = app_handle.state::<AppState>();
+
+            let step = state.mix_engine.lock().ok().map(|mut mix| {
+                let now = mix.frames_processed();
+                let commands = mix.take_automation_commands();
+                let automation = mix.automation_mut();
+                (commands, automation.take_ended(), automation.status(now))
+            });
+            if let Some((commands, ended, status)) = step {
+                for command in &commands {
+                    if let Err(e) = apply_mixer_command(&state, command) {
+                        warn!("Automation '{}' fehlgeschlagen: {}", command.label(), e);
+                    }
+                }
+                if ended {
+                    if let Err(e) = stop_automation(&state) {
+                        warn!("Automation-Clip nicht gespeichert: {}", e);
+                    }
+                    info!("Automation am Clip-Ende angehalten");
+                    let _ = app_handle.emit("automation-ended", &status);
+                }
+                if status.playing || ended {
+                    let _ = app_handle.emit("automation-status", status);
+                }
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(AUTOMATION_TICK_MS));
+        });
+
+    if let Err(e) = result {
+        error!("Automation-Thread konnte nicht gestartet werden: {}", e);
+    }
+}
+
+/// Hintergrund-Thread: Talkback nach Ablauf der Auto-Release-Zeit lösen
+///
+/// Schaltet wie die Taste ohne Journal-Eintrag und meldet den neuen
+/// Master-State als "talkback-released" Event.
+fn spawn_talkback_release(app_handle: tauri::AppHandle) {
+    let result = std::thread::Builder::new()
+        .name("talkback-release".to_string())
+        .spawn(move || loop {
+            let state = app_handle.state::<AppState>();
+
+            let expired = state.master.lock().ok().and_then(|master| {
+                master
+                    .talkback_expired(std::time::Instant::now())
+                    .then(|| master.get_state().talkback_buses)
+            });
+            if let Some(target_buses) = expired {
+                let command = MixerCommand::MasterTalkback {
+                    active: false,
+                    target_buses,
+                };
+                match apply_mixer_command(&state, &command) {
+                    Ok(_) => {
+                        info!("Talkback automatisch gelöst");
+                        if let Ok(master) = state.master.lock() {
+                            let _ = app_handle.emit("talkback-released", master.get_state());
+                        }
+                    }
+                    Err(e) => warn!("Talkback konnte nicht gelöst werden: {}", e),
+                }
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(TALKBACK_POLL_INTERVAL_MS));
+        });
+
+    if let Err(e) = result {
+        error!("Talkback-Thread konnte nicht gestartet werden: {}", e);
+    }
+}
+
+/// Hintergrund-Thread: Signal-Generator nach Ablauf des Timeouts stoppen
+fn spawn_generator_stop(app_handle: tauri::AppHandle) {
+    let result = std::thread::Builder::new()
+        .name("generator-stop".to_string())
+        .spawn(move || loop {
+            let state = app_handle.state::<AppState>();
+
+            if let Ok(mut generator) = state.generator.lock() {
+                if generator.expired(std::time::Instant::now()) {
+                    generator.stop();
+                    match sync_generator(&state, &generator) {
+                        Ok(()) => {
+                            info!("Signal-Generator automatisch gestoppt");
+                            let status = generator.status(std::time::Instant::now());
+                            let _ = app_handle.emit("generator-stopped", status);
+                        }
+                        Err(e) => warn!("Signal-Generator konnte nicht gestoppt werden: {}", e),
+                    }
+                }
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(GENERATOR_POLL_INTERVAL_MS));
+        });
+
+    if let Err(e) = result {
+        error!("Generator-Thread konnte nicht gestartet werden: {}", e);
+    }
+}
+
+/// Hintergrund-Thread: Live-State beobachten und entprellt speichern
+fn spawn_session_persistence(app_handle: tauri::AppHandle) {
+    let result = std::thread::Builder::new()
+        .name("session-persist".to_string())
+        .spawn(move || loop {
+            let state = app_handle.state::<AppState>();
+
+            let result = session_snapshot(&state).and_then(|snapshot| {
+                state
+                    .session
+                    .lock()
+                    .map_err(|e| format!("Session-Lock-Fehler: {}", e))?
+                    .observe(&snapshot, std::time::Instant::now())
+            });
+            if let Err(e) = result {
+                warn!("Session konnte nicht gespeichert werden: {}", e);
+            }
+
+            std::thread::sleep(std::time::Duration::from_millis(SESSION_POLL_INTERVAL_MS));
+        });
+
+    if let Err(e) = result {
+        error!("Session-Thread konnte nicht gestartet werden: {}", e);
+    }
+}
+
 /// Datenbank-Pfad ermitteln (im Tauri App-Data Verzeichnis)
 fn get_db_path(app: &tauri::App) -> Result<String, Box<dyn std::error::Error>> {
     let app_data = app
@@ -906,14 +3871,22 @@ fn main() {
 
             info!("Datenbank und Config initialisiert");
 
+            let app_config = config_manager.load_app_config().unwrap_or_else(|e| {
+                warn!("AppConfig konnte nicht geladen werden, nutze Defaults: {}", e);
+                config::AppConfig::default()
+            });
+
             // 3. PipeWire-Verfügbarkeit prüfen
-            match pw::check_pipewire_available() {
+            // (Virtual Bus Nodes folgen nach dem Wiederherstellen der Bus-Registry)
+            let mut clock = pw::ClockClaim::new();
+            let pipewire_available = match pw::check_pipewire_available() {
                 Ok(()) => {
                     info!("PipeWire verfügbar");
-                    // Virtual Bus Nodes erstellen
-                    if let Err(e) = pw::create_virtual_bus_nodes() {
-                        warn!("Virtual Bus Nodes konnten nicht erstellt werden: {}", e);
+                    // Quantum + Sample-Rate aus der AppConfig beim Graphen anfordern
+                    if let Err(e) = clock.apply(app_config.buffer_size, app_config.sample_rate) {
+                        warn!("PipeWire-Clock konnte nicht gesetzt werden: {}", e);
                     }
+                    true
                 }
                 Err(msg) => {
                     warn!("PipeWire-Warnung: {}", msg);
@@ -921,28 +3894,106 @@ fn main() {
                     if let Some(window) = app.get_webview_window("main") {
                         let _ = window.emit("pipewire-warning", msg);
                     }
+                    false
                 }
-            }
+            };
 
-            // 4. Mixer-State erstellen
-            let mixer = MixerState::new();
-            info!("Mixer initialisiert mit {} Strips", mixer.strip_count());
+            // Die Mix-Engine rechnet in der ausgehandelten Graph-Rate — sie kann
+            // von der angeforderten abweichen (Clock nicht setzbar, Treiber)
+            let engine_rate = if pipewire_available {
+                let negotiated = pw::read_clock_settings().effective_rate();
+                if negotiated != app_config.sample_rate {
+                    warn!(
+                        "PipeWire läuft mit {} Hz (angefordert: {} Hz)",
+                        negotiated, app_config.sample_rate
+                    );
+                }
+                negotiated
+            } else {
+                app_config.sample_rate
+            };
 
-            // 5. Bus-Manager erstellen
-            let buses = BusManager::new();
-            info!("Bus-Manager initialisiert mit {} Bussen", buses.bus_count());
+            // 4.–8. Live-State aus der letzten Session wiederherstellen
+            // (Mixer, Busse, FX-Chain, Routing, Master, Mute-Gruppen, Makros) — sonst Defaults
+            let mut session = SessionStore::new(db.clone());
+            let saved = session.load().unwrap_or_else(|e| {
+                warn!("Session konnte nicht geladen werden, nutze Defaults: {}", e);
+                None
+            });
 
-            // 6. FX-Chain erstellen (Phase 1: Global)
-            let fx_chain = FxChain::new();
-            info!("FX-Chain initialisiert (Phase 1: HPF + Gate)");
+            // Virtual Bus Nodes für die Bus-Registry erstellen — vor dem
+            // Routing-Restore, die Links brauchen die Nodes
+            if pipewire_available {
+                let node_buses = saved
+                    .as_ref()
+                    .filter(|s| s.validate().is_ok())
+                    .map(|s| s.buses.clone())
+                    .unwrap_or_else(|| BusManager::new().get_buses());
+                if let Err(e) = pw::create_virtual_bus_nodes(
+                    &node_buses,
+                    app_config.buffer_size,
+                    app_config.sample_rate,
+                ) {
+                    warn!("Virtual Bus Nodes konnten nicht erstellt werden: {}", e);
+                }
+            }
 
-            // 7. Routing-Manager erstellen
-            let routing = RoutingManager::new();
-            info!("Routing-Manager initialisiert");
+            let restored = saved.and_then(|saved| match saved.restore() {
+                Ok(restored) => Some(restored),
+                Err(e) => {
+                    warn!("Gespeicherte Session ungültig, nutze Defaults: {}", e);
+                    None
+                }
+            });
+            let (mixer, buses, fx_chain, routing, master, mute_groups, macros) = match restored {
+                Some(r) => {
+                    info!("Letzte Session wiederhergestellt");
+                    (
+                        r.mixer,
+                        r.buses,
+                        r.fx_chain,
+                        r.routing,
+                        r.master,
+                        r.mute_groups,
+                        r.macros,
+                    )
+                }
+                None => (
+                    MixerState::new(),
+                    BusManager::new(),
+                    FxChain::new(),
+                    RoutingManager::new(),
+                    MasterManager::new(),
+                    MuteGroupManager::new(),
+                    MacroManager::new(),
+                ),
+            };
+            info!("Mixer initialisiert mit {} Strips", mixer.strip_count());
+            info!("Bus-Manager initialisiert mit {} Bussen", buses.bus_count());
+            // Gebundene Ausgabe-Devices auflösen (fehlende: Standard-Ausgabe)
+            let mut buses = buses;
+            if pipewire_available {
+                for (bus_id, device_name) in buses.output_bindings() {
+                    let device_id = resolve_output_device(&bus_id, &device_name);
+                    if let Err(e) = buses.set_pw_device(&bus_id, device_id) {
+                        warn!("{}", e);
+                    }
+                }
+            }
+            info!("FX-Chain, Routing und Master-Sektion initialisiert");
 
-            // 8. Master-Sektion erstellen
-            let master = MasterManager::new();
-            info!("Master-Sektion initialisiert");
+            // Wiederhergestellten Stand als gespeichert markieren
+            if let Err(e) = session.prime(&SessionState::new(
+                mixer.get_strips(),
+                buses.get_buses(),
+                routing.get_routing_matrix(),
+                fx_chain.get_all_modules(),
+                master.get_state(),
+                mute_groups.get_groups(),
+                macros.get_macros(),
+            )) {
+                warn!("Session-Store konnte nicht initialisiert werden: {}", e);
+            }
 
             // 9. Recording-Engine erstellen
             let recording = RecordingEngine::new();
@@ -950,6 +4001,7 @@ fn main() {
 
             // 10. Scene-Manager erstellen
             let scenes = SceneManager::new(db.clone());
+            let automation_clips = AutomationStore::new(db.clone());
             info!("Scene-Manager initialisiert");
 
             // 11. Soundboard-Manager erstellen
@@ -979,7 +4031,7 @@ fn main() {
                 .ok()
                 .flatten();
 
-            let stt = match SttManager::new(
+            let mut stt = match SttManager::new(
                 db.clone(),
                 vosk_model_path.as_deref(),
                 whisper_model_path.as_deref(),
@@ -1001,8 +4053,19 @@ fn main() {
             info!("Calibrate-Engine initialisiert");
 
             // 16. Metering-Service erstellen und starten
+            // Mix-Engine mit dem Start-State der Strips/Busse füttern
+            let mut mix_engine = MixEngine::new();
+            mix_engine.set_sample_rate(engine_rate);
+            mix_engine.update_strips(&mute_groups.apply(&mixer.get_strips()));
+            mix_engine.update_buses(&buses.get_buses());
+            mix_engine.update_bus_routes(&routing.bus_order(), &routing.submix_gains());
+            mix_engine.update_master(&master.get_state());
+            let mix_engine = Arc::new(Mutex::new(mix_engine));
+
             let app_handle = app.handle().clone();
-            let metering = MeteringService::start(app_handle);
+            let metering = MeteringService::start(app_handle, Arc::clone(&mix_engine));
+            metering.set_capture_bindings(mixer.capture_bindings());
+            metering.set_monitored_buses(buses.get_buses().into_iter().map(|b| b.id).collect());
             info!("Metering-Service initialisiert");
 
             // Strips für Metering registrieren
@@ -1011,6 +4074,21 @@ fn main() {
             metering.register_strip("virt-browser");
             metering.register_strip("virt-spotify");
 
+            // STT/Bleeper überwacht die gespeicherte Quelle (Standard: Stream-Bus B1)
+            match config_manager.load_stt_source() {
+                Ok(Some(source_id))
+                    if mixer.get_strip(&source_id).is_some()
+                        || buses.get_bus(&source_id).is_some() =>
+                {
+                    if let Ok(mut hub) = metering.tap_hub().lock() {
+                        stt.set_tap(Some(hub.subscribe(&source_id, TapKind::Stt)));
+                    }
+                }
+                Ok(Some(source_id)) => warn!("STT-Quelle {} existiert nicht mehr", source_id),
+                Ok(None) => {}
+                Err(e) => warn!("STT-Quelle nicht geladen: {}", e),
+            }
+
             // 17. App-State registrieren
             app.manage(AppState {
                 config_manager,
@@ -1019,7 +4097,10 @@ fn main() {
                 fx_chain: Mutex::new(fx_chain),
                 routing: Mutex::new(routing),
                 master: Mutex::new(master),
+                mute_groups: Mutex::new(mute_groups),
+                macros: Mutex::new(macros),
                 recording: Mutex::new(recording),
+                tap_consumer: Mutex::new(None),
                 scenes,
                 soundboard: Mutex::new(soundboard),
                 voice_fx: Mutex::new(voice_fx),
@@ -1028,8 +4109,34 @@ fn main() {
                 stt: Mutex::new(stt),
                 calibrate: Mutex::new(calibrate),
                 metering: Mutex::new(metering),
+                clock: Mutex::new(clock),
+                generator: Mutex::new(GeneratorManager::new()),
+                mix_engine,
+                session: Mutex::new(session),
+                history: Mutex::new(CommandHistory::new()),
+                scene_transition: Mutex::new(None),
+                automation_clips,
+                app_handle: app.handle().clone(),
             });
 
+            // 18. Tap-Consumer für Recording und STT starten
+            spawn_tap_consumer(app.handle().clone());
+
+            // 19. Session-Persistenz starten (speichert Änderungen entprellt)
+            spawn_session_persistence(app.handle().clone());
+
+            // 20. Szenen-Überblendung (Takt-Thread)
+            spawn_scene_transitions(app.handle().clone());
+
+            // 21. Automation in die Manager spiegeln (Takt-Thread)
+            spawn_automation(app.handle().clone());
+
+            // 22. Talkback-Auto-Release
+            spawn_talkback_release(app.handle().clone());
+
+            // 23. Generator-Auto-Stopp
+            spawn_generator_stop(app.handle().clone());
+
             info!("Setup abgeschlossen");
             Ok(())
         })
@@ -1038,6 +4145,8 @@ fn main() {
             get_audio_devices,
             get_config,
             set_config,
+            set_audio_config,
+            get_latency_report,
             export_config,
             import_config,
             get_strips,
@@ -1048,27 +4157,89 @@ fn main() {
             set_strip_bus,
             add_virtual_strip,
             remove_virtual_strip,
+            bind_strip_device,
+            unbind_strip_device,
+            get_capture_devices,
+            get_capture_status,
+            rename_strip,
+            set_strip_icon,
+            set_strip_color,
+            reorder_strips,
+            bind_strip_pw_device,
+            unbind_strip_pw_device,
+            set_strip_talk_mode,
+            talk_press,
+            talk_release,
+            get_talk_status,
+            get_mute_groups,
+            create_mute_group,
+            update_mute_group,
+            delete_mute_group,
+            trigger_mute_group,
+            get_macros,
+            create_macro,
+            update_macro,
+            delete_macro,
+            set_macro_value,
+            set_macro_midi,
             get_buses,
             set_bus_volume,
             set_bus_mute,
+            set_bus_delay,
+            set_bus_mix_minus,
+            bind_bus_device,
+            unbind_bus_device,
+            refresh_bus_devices,
+            create_bus,
+            rename_bus,
+            delete_bus,
+            set_bus_tap_source,
+            get_bus_tap_source,
             get_fx_chain,
             set_fx_param,
             set_fx_bypass,
+            get_bus_fx_chain,
+            set_bus_fx_param,
+            set_bus_fx_bypass,
             get_routing_matrix,
             set_routing,
+            set_crosspoints,
+            export_routing_template,
+            import_routing_template,
             get_master,
             set_master_volume,
             set_master_limiter,
             set_dim,
             set_mono,
             set_talkback,
+            set_talkback_settings,
+            talkback_press,
+            talkback_release,
+            start_generator,
+            stop_generator,
+            get_generator_status,
             start_recording,
             stop_recording,
             get_recording_status,
+            get_audio_buffer_stats,
             save_scene,
             load_scene,
             delete_scene,
             get_scenes,
+            capture_scene,
+            set_scene_scope,
+            recall_scene,
+            cancel_scene_transition,
+            get_automation_clips,
+            create_automation_clip,
+            load_automation_clip,
+            delete_automation_clip,
+            attach_automation_clip,
+            set_automation_mode,
+            automation_play,
+            automation_stop,
+            automation_locate,
+            get_automation_status,
             play_sound,
             stop_sound,
             add_sound,
@@ -1086,6 +4257,9 @@ fn main() {
             set_ducking_attack,
             set_ducking_release,
             set_ducking_threshold,
+            undo,
+            redo,
+            get_history,
             get_bleeper_state,
             set_bleeper_armed,
             set_bleeper_mode,
@@ -1096,10 +4270,42 @@ fn main() {
             remove_profanity_word,
             get_profanity_words,
             get_stt_status,
+            set_stt_source,
             run_calibration,
             check_for_updates,
             install_update,
         ])
-        .run(tauri::generate_context!())
-        .expect("Fehler beim Starten der Tauri-Anwendung");
+        .build(tauri::generate_context!())
+        .expect("Fehler beim Starten der Tauri-Anwendung")
+        .run(|app, event| {
+            if let tauri::RunEvent::Exit = event {
+                // Letzte Änderungen ohne Debounce sichern
+                let state = app.state::<AppState>();
+                let result = session_snapshot(&state).and_then(|snapshot| {
+                    state
+                        .session
+                        .lock()
+                        .map_err(|e| format!("Session-Lock-Fehler: {}", e))?
+                        .flush(&snapshot)
+                });
+                if let Err(e) = result {
+                    warn!("Session konnte beim Beenden nicht gespeichert werden: {}", e);
+                }
+
+                // Tap-Consumer beenden
+                if let Ok(Some(stop)) = state.tap_consumer.lock().map(|c| c.clone()) {
+                    stop.store(true, Ordering::Relaxed);
+                }
+
+                // Eigene Clock-Anforderung freigeben — sonst bleibt sie systemweit aktiv
+                let released = state
+                    .clock
+                    .lock()
+                    .map_err(|e| format!("Clock-Lock-Fehler: {}", e))
+                    .and_then(|mut clock| clock.release());
+                if let Err(e) = released {
+                    warn!("PipeWire-Clock konnte nicht freigegeben werden: {}", e);
+                }
+            }
+        });
 }
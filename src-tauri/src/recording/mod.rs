@@ -5,6 +5,7 @@
 
 pub mod encoder;
 
+use crate::audio::tap::AudioTap;
 use encoder::{AudioEncoder, WavEncoder};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -55,10 +56,17 @@ struct ActiveRecordingInternal {
     info: ActiveRecording,
 }
 
+/// Samples pro Lese-Durchgang aus einem Recording-Tap
+const PUMP_CHUNK_SAMPLES: usize = 4096;
+
 /// Aufnahme-Engine verwaltet aktive Aufnahmen
 pub struct RecordingEngine {
     /// Aktive Aufnahmen (Source-ID → Recording)
     active: HashMap<String, ActiveRecordingInternal>,
+    /// Recording-Taps der aufgenommenen Quellen (Source-ID → Tap)
+    taps: HashMap<String, AudioTap>,
+    /// Lese-Puffer für die Taps
+    scratch: Vec<f32>,
     /// Ausgabe-Verzeichnis (Standard: ~/Recordings/inoX-MIX)
     output_dir: PathBuf,
 }
@@ -78,6 +86,8 @@ impl RecordingEngine {
 
         Self {
             active: HashMap::new(),
+            taps: HashMap::new(),
+            scratch: vec![0.0; PUMP_CHUNK_SAMPLES],
             output_dir,
         }
     }
@@ -148,6 +158,11 @@ impl RecordingEngine {
 
     /// Aufnahme stoppen
     pub fn stop(&mut self, source_id: &str) -> Result<RecordingInfo, String> {
+        // Restliche Samples aus dem Tap noch schreiben
+        if let Some(mut tap) = self.taps.remove(source_id) {
+            self.drain_tap(source_id, &mut tap)?;
+        }
+
         // Aufnahme aus HashMap entfernen
         let mut recording = self
             .active
@@ -189,6 +204,48 @@ impl RecordingEngine {
         Ok(())
     }
 
+    /// Recording-Tap einer aktiven Aufnahme anschließen
+    ///
+    /// Die Samples werden per `pump()` außerhalb des Audio-Threads geschrieben.
+    pub fn attach_tap(&mut self, tap: AudioTap) -> Result<(), String> {
+        if !self.active.contains_key(&tap.source_id) {
+            return Err(format!("Keine aktive Aufnahme für '{}'", tap.source_id));
+        }
+        self.taps.insert(tap.source_id.clone(), tap);
+        Ok(())
+    }
+
+    /// Wartende Samples aller Recording-Taps in die Dateien schreiben
+    pub fn pump(&mut self) -> Result<(), String> {
+        let mut taps = std::mem::take(&mut self.taps);
+        let mut result = Ok(());
+        for (source_id, tap) in taps.iter_mut() {
+            if let Err(e) = self.drain_tap(source_id, tap) {
+                result = Err(e);
+            }
+        }
+        self.taps = taps;
+        result
+    }
+
+    /// Alle wartenden Samples eines Taps schreiben
+    fn drain_tap(&mut self, source_id: &str, tap: &mut AudioTap) -> Result<(), String> {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        let mut result = Ok(());
+        loop {
+            let read = tap.read(&mut scratch);
+            if read == 0 {
+                break;
+            }
+            if let Err(e) = self.write_samples(source_id, &scratch[..read]) {
+                result = Err(e);
+                break;
+            }
+        }
+        self.scratch = scratch;
+        result
+    }
+
     /// Alle aktiven Aufnahmen abrufen
     pub fn get_active_recordings(&self) -> Vec<ActiveRecording> {
         self.active.values().map(|r| r.info.clone()).collect()
@@ -245,6 +302,35 @@ mod tests {
         fs::remove_file(&path).ok();
     }
 
+    #[test]
+    fn test_recording_from_tap() {
+        use crate::audio::tap::{TapHub, TapKind};
+
+        let mut engine = RecordingEngine::new();
+        let mut hub = TapHub::new();
+        let source_id = "test_tap_bus";
+
+        // Tap ohne aktive Aufnahme wird abgelehnt
+        let tap = hub.subscribe(source_id, TapKind::Recording);
+        assert!(engine.attach_tap(tap).is_err());
+
+        engine.start(source_id, RecordingFormat::Wav).unwrap();
+        engine
+            .attach_tap(hub.subscribe(source_id, TapKind::Recording))
+            .unwrap();
+
+        hub.distribute(source_id, &vec![0.25_f32; 9600]);
+        engine.pump().unwrap();
+        assert_eq!(engine.get_active_recordings()[0].samples_written, 9600);
+
+        // Beim Stoppen wird der Rest aus dem Tap noch geschrieben
+        hub.distribute(source_id, &vec![0.25_f32; 400]);
+        let info = engine.stop(source_id).unwrap();
+        assert!((info.duration_secs - 0.104).abs() < 0.001);
+
+        fs::remove_file(&info.path).ok();
+    }
+
     #[test]
     fn test_multiple_recordings() {
         let mut engine = RecordingEngine::new();
@@ -47,13 +47,13 @@ impl SoundboardManager {
         }
     }
 
-    /// Sound hinzufügen
+    /// Sound hinzufügen (Ziel-Bus aus der Bus-Registry)
     pub fn add_sound(
         &self,
         name: &str,
         file_path: &str,
         hotkey: Option<String>,
-        bus_id: Option<String>,
+        bus_id: &str,
     ) -> Result<String, String> {
         // Prüfen ob Datei existiert
         if !PathBuf::from(file_path).exists() {
@@ -67,7 +67,6 @@ impl SoundboardManager {
             .map_err(|e| format!("DB-Lock-Fehler: {}", e))?;
 
         let id = format!("sound_{}", uuid::Uuid::new_v4());
-        let bus_id = bus_id.unwrap_or_else(|| "B1".to_string());
         let created_at = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .map_err(|e| format!("Zeit-Fehler: {}", e))?
@@ -255,7 +254,7 @@ mod tests {
     #[test]
     fn test_add_sound_nonexistent_file() {
         let manager = setup();
-        let result = manager.add_sound("Test", "/nonexistent/file.wav", None, None);
+        let result = manager.add_sound("Test", "/nonexistent/file.wav", None, "B1");
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("nicht gefunden"));
     }
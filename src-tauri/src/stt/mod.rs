@@ -6,6 +6,7 @@
 pub mod vosk;
 pub mod whisper;
 
+use crate::audio::tap::AudioTap;
 use crate::config::database::Database;
 use log::{info, warn};
 use rusqlite::params;
@@ -14,6 +15,13 @@ use std::sync::{Arc, Mutex};
 use vosk::VoskEngine;
 use whisper::WhisperEngine;
 
+/// Angenommene Sample-Rate, solange die Quelle keine gemeldet hat (Hz)
+const FALLBACK_SAMPLE_RATE: u32 = 48000;
+/// Sample-Rate der STT-Engines (Hz)
+const STT_SAMPLE_RATE: u32 = 16000;
+/// Maximal pro Durchgang gelesene Samples (100ms Stereo @ 48kHz)
+const PUMP_CHUNK_SAMPLES: usize = 9600;
+
 /// Erkanntes Wort mit Zeitstempel
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RecognizedWord {
@@ -109,6 +117,12 @@ pub struct SttManager {
     db: Arc<Database>,
     /// Gecachte Profanity-Wörter (für schnelles Matching)
     profanity_words: Arc<Mutex<Vec<ProfanityWord>>>,
+    /// STT-Tap der überwachten Quelle (Strip oder Bus)
+    tap: Option<AudioTap>,
+    /// Lese-Puffer für den Tap
+    scratch: Vec<f32>,
+    /// Downmix/Resampling der Tap-Samples auf Mono 16kHz
+    downmix: SttDownmix,
 }
 
 impl SttManager {
@@ -184,6 +198,9 @@ impl SttManager {
             active_engine,
             db,
             profanity_words: Arc::new(Mutex::new(profanity_words)),
+            tap: None,
+            scratch: vec![0.0; PUMP_CHUNK_SAMPLES],
+            downmix: SttDownmix::new(FALLBACK_SAMPLE_RATE),
         })
     }
 
@@ -246,6 +263,61 @@ impl SttManager {
         }
     }
 
+    /// STT-Tap setzen (None = keine Quelle überwachen)
+    pub fn set_tap(&mut self, tap: Option<AudioTap>) {
+        if let Some(ref t) = tap {
+            info!("STT überwacht Quelle: {}", t.source_id);
+        }
+        self.tap = tap;
+        self.downmix = SttDownmix::new(self.tap_sample_rate());
+    }
+
+    /// Aktuell überwachte Quelle
+    pub fn source_id(&self) -> Option<&str> {
+        self.tap.as_ref().map(|t| t.source_id.as_str())
+    }
+
+    /// Sample-Rate der überwachten Quelle (Fallback solange unbekannt)
+    fn tap_sample_rate(&self) -> u32 {
+        self.tap
+            .as_ref()
+            .and_then(|t| t.sample_rate())
+            .unwrap_or(FALLBACK_SAMPLE_RATE)
+    }
+
+    /// Wartende Samples des STT-Taps verarbeiten
+    ///
+    /// Liest Stereo in der Rate der Quelle aus dem Tap, mischt auf Mono @ 16kHz
+    /// herunter und übergibt an die aktive Engine. Ein nicht mehr belieferter
+    /// Tap (Quelle abgemeldet oder ersetzt) wird verworfen.
+    pub fn pump(&mut self) -> Option<Vec<String>> {
+        if !self.tap.as_ref()?.is_connected() {
+            let tap = self.tap.take()?;
+            warn!("STT-Tap von '{}' wird nicht mehr beliefert", tap.source_id);
+            return None;
+        }
+
+        let rate = self.tap_sample_rate();
+        if rate != self.downmix.source_rate {
+            self.downmix = SttDownmix::new(rate);
+        }
+
+        // Nur ganze Stereo-Frames lesen
+        let tap = self.tap.as_mut()?;
+        let available = tap.available().min(self.scratch.len());
+        let len = available - available % 2;
+        if len == 0 {
+            return None;
+        }
+        let read = tap.read(&mut self.scratch[..len]);
+
+        let mono = self.downmix.process(&self.scratch[..read]);
+        if mono.is_empty() {
+            return None;
+        }
+        self.process_audio(&mono)
+    }
+
     /// Erkannte Wörter gegen Profanity-Liste prüfen
     ///
     /// # Rückgabe
@@ -377,10 +449,83 @@ impl SttManager {
     }
 }
 
+/// Stereo in beliebiger Rate → Mono 16kHz
+///
+/// Mittelt alle Eingangs-Frames, die auf ein STT-Sample fallen. Das Verhältnis
+/// muss nicht ganzzahlig sein (44,1 kHz); Reste bleiben bis zum nächsten
+/// Aufruf erhalten, damit beim stückweisen Lesen nichts verloren geht.
+struct SttDownmix {
+    /// Sample-Rate der Quelle (Hz)
+    source_rate: u32,
+    /// Fortschritt im laufenden STT-Sample (in STT-Rate-Einheiten pro Frame)
+    phase: u32,
+    /// Summe der gesammelten Mono-Werte
+    sum: f32,
+    /// Anzahl der gesammelten Frames
+    count: u32,
+}
+
+impl SttDownmix {
+    /// Downmix für eine Quell-Sample-Rate erstellen
+    fn new(source_rate: u32) -> Self {
+        Self {
+            source_rate,
+            phase: 0,
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    /// Stereo-Samples (interleaved) verarbeiten, liefert fertige Mono-Samples
+    fn process(&mut self, stereo: &[f32]) -> Vec<f32> {
+        let frames = stereo.len() / 2;
+        let mut mono =
+            Vec::with_capacity(frames * STT_SAMPLE_RATE as usize / self.source_rate as usize + 1);
+        for frame in stereo.chunks_exact(2) {
+            self.sum += (frame[0] + frame[1]) / 2.0;
+            self.count += 1;
+            self.phase += STT_SAMPLE_RATE;
+            if self.phase >= self.source_rate {
+                mono.push(self.sum / self.count as f32);
+                self.phase -= self.source_rate;
+                self.sum = 0.0;
+                self.count = 0;
+            }
+        }
+        mono
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_downmix_for_stt() {
+        // 6 Frames Stereo @ 48kHz → 2 Samples Mono @ 16kHz
+        let stereo = [0.3, 0.1, 0.3, 0.1, 0.3, 0.1, -0.5, -0.5, -0.5, -0.5, -0.5, -0.5];
+        let mono = SttDownmix::new(48000).process(&stereo);
+        assert_eq!(mono.len(), 2);
+        assert!((mono[0] - 0.2).abs() < 0.001);
+        assert!((mono[1] + 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_downmix_follows_source_rate() {
+        // 1 Sekunde @ 44,1kHz in ungeraden Stücken → genau 1 Sekunde @ 16kHz
+        let mut downmix = SttDownmix::new(44100);
+        let stereo = vec![0.25; 44100 * 2];
+        let mut total = 0;
+        for chunk in stereo.chunks(2 * 1001) {
+            total += downmix.process(chunk).len();
+        }
+        assert_eq!(total, 16000);
+
+        // 96kHz: 6 Frames pro STT-Sample
+        let mono = SttDownmix::new(96000).process(&[0.5; 24]);
+        assert_eq!(mono.len(), 2);
+    }
+
     #[test]
     fn test_profanity_category() {
         assert_eq!(ProfanityCategory::Schimpf.as_str(), "schimpf");